@@ -0,0 +1,48 @@
+#import bevy_sprite::mesh2d_vertex_output::VertexOutput
+
+struct FlashMaterial {
+    tint: vec4<f32>,
+    flash_color: vec4<f32>,
+    // Atlas sub-rect in UV space: (min.x, min.y, max.x, max.y), x swapped when flipped
+    uv_rect: vec4<f32>,
+    outline_color: vec4<f32>,
+    flash_amount: f32,
+    // Outline thickness in texels, zero disables it
+    outline_width: f32,
+};
+
+@group(2) @binding(0) var<uniform> material: FlashMaterial;
+@group(2) @binding(1) var sprite_texture: texture_2d<f32>;
+@group(2) @binding(2) var sprite_sampler: sampler;
+
+@fragment
+fn fragment(mesh: VertexOutput) -> @location(0) vec4<f32> {
+    let uv = mix(material.uv_rect.xy, material.uv_rect.zw, mesh.uv);
+    let texel = textureSample(sprite_texture, sprite_sampler, uv);
+    let tinted = texel * material.tint;
+
+    if material.outline_width > 0.0 && texel.a < 0.5 {
+        // Transparent texel next to an opaque one: part of the outline.
+        // Explicit LOD since this branch isn't in uniform control flow.
+        let texel_size = material.outline_width / vec2<f32>(textureDimensions(sprite_texture));
+        let lo = min(material.uv_rect.xy, material.uv_rect.zw);
+        let hi = max(material.uv_rect.xy, material.uv_rect.zw);
+        let neighbors = max(
+            max(
+                textureSampleLevel(sprite_texture, sprite_sampler, clamp(uv + vec2(texel_size.x, 0.0), lo, hi), 0.0).a,
+                textureSampleLevel(sprite_texture, sprite_sampler, clamp(uv - vec2(texel_size.x, 0.0), lo, hi), 0.0).a,
+            ),
+            max(
+                textureSampleLevel(sprite_texture, sprite_sampler, clamp(uv + vec2(0.0, texel_size.y), lo, hi), 0.0).a,
+                textureSampleLevel(sprite_texture, sprite_sampler, clamp(uv - vec2(0.0, texel_size.y), lo, hi), 0.0).a,
+            ),
+        );
+        if neighbors >= 0.5 {
+            return material.outline_color;
+        }
+    }
+
+    // Flash replaces the color but keeps the sprite's silhouette
+    let flashed = mix(tinted.rgb, material.flash_color.rgb, material.flash_amount);
+    return vec4<f32>(flashed, tinted.a);
+}
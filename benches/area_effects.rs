@@ -0,0 +1,67 @@
+//! Per-frame cost of finding the enemies under every area effect circle.
+//! Run with `cargo bench --bench area_effects`.
+
+use bevy::prelude::*;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use survivors_prototype::spatial_grid::EnemySpatialGrid;
+
+// Roughly a late run: the horde spread around the player, a screenful of circles
+const ARENA_RADIUS: f32 = 900.0;
+const CIRCLE_RADIUS: f32 = 96.0;
+const CIRCLES: usize = 40;
+
+fn scatter(rng: &mut StdRng, count: usize) -> Vec<Vec2> {
+    (0..count)
+        .map(|_| Vec2::new(rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0)) * ARENA_RADIUS)
+        .collect()
+}
+
+fn circle_lookups(c: &mut Criterion) {
+    let mut group = c.benchmark_group("area_effect_lookups");
+
+    for enemies in [100, 500, 2000] {
+        let mut rng = StdRng::seed_from_u64(7);
+        let enemy_positions = scatter(&mut rng, enemies);
+        let circles = scatter(&mut rng, CIRCLES);
+
+        // What area_effect_system does each frame, rebuilding the grid included
+        group.bench_with_input(BenchmarkId::new("grid", enemies), &enemies, |b, _| {
+            let mut grid = EnemySpatialGrid::default();
+            b.iter(|| {
+                grid.clear();
+                for (i, position) in enemy_positions.iter().enumerate() {
+                    grid.insert(Entity::from_raw(i as u32), *position);
+                }
+                circles
+                    .iter()
+                    .map(|center| grid.query_radius(*center, CIRCLE_RADIUS).count())
+                    .sum::<usize>()
+            });
+        });
+
+        // Checking every enemy against every circle, for comparison
+        group.bench_with_input(BenchmarkId::new("all_pairs", enemies), &enemies, |b, _| {
+            b.iter(|| {
+                circles
+                    .iter()
+                    .map(|center| {
+                        enemy_positions
+                            .iter()
+                            .filter(|position| {
+                                black_box(*position).distance_squared(*center)
+                                    <= CIRCLE_RADIUS * CIRCLE_RADIUS
+                            })
+                            .count()
+                    })
+                    .sum::<usize>()
+            });
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, circle_lookups);
+criterion_main!(benches);
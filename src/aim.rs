@@ -0,0 +1,70 @@
+use crate::components::{Facing, Player};
+use crate::resources::GameState;
+use crate::systems::gameplay_movement_system;
+use crate::GameplaySets;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+/// Lets the mouse steer `Facing` instead of movement, so directional weapons can be
+/// aimed while walking the other way. Off unless started with `--mouse-aim`.
+pub struct AimPlugin;
+
+impl Plugin for AimPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(AimMode::from_args()).add_systems(
+            Update,
+            aim_at_cursor
+                .after(gameplay_movement_system)
+                .in_set(GameplaySets::Movement)
+                .run_if(|mode: Res<AimMode>| *mode == AimMode::Mouse)
+                .run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+/// What the player's facing follows
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AimMode {
+    #[default]
+    Movement, // Last direction moved in
+    Mouse, // Towards the cursor while it's over the window
+}
+
+impl AimMode {
+    // Opted into from the command line until there's a settings screen for it
+    pub fn from_args() -> Self {
+        if std::env::args().any(|arg| arg == "--mouse-aim") {
+            AimMode::Mouse
+        } else {
+            AimMode::default()
+        }
+    }
+}
+
+// A cursor this close to the player has no clear direction, facing stays put
+const AIM_DEAD_ZONE: f32 = 8.0;
+
+fn aim_at_cursor(
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+    mut player_query: Query<(&Transform, &mut Facing), With<Player>>,
+) {
+    let (Ok(window), Ok((camera, camera_transform))) =
+        (window_query.get_single(), camera_query.get_single())
+    else {
+        return;
+    };
+    let Some(cursor) = window
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor).ok())
+    else {
+        return;
+    };
+
+    for (transform, mut facing) in player_query.iter_mut() {
+        let offset = cursor - transform.translation.truncate();
+        if offset.length() > AIM_DEAD_ZONE {
+            facing.face(offset.normalize());
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use crate::resources::GameState;
+use crate::settings::GameSettings;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+pub struct AnnouncerPlugin;
+
+impl Plugin for AnnouncerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<DirectorMilestone>()
+            .init_resource::<AnnouncerCues>()
+            .add_systems(
+                Update,
+                (play_milestone_cues, fade_announcements)
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+/// Notable moments in a run, sent by whatever drives the pacing (spawning, bosses, run timer)
+#[derive(Event, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum DirectorMilestone {
+    SurgeStart,
+    BossSpawn,
+    FinalMinute,
+    WaveCleared, // Bracketed waves only
+}
+
+/// Everything that reacts to a milestone; any part can be left out
+#[derive(Debug, Clone, Default)]
+pub struct AnnouncementCue {
+    pub sound: Option<&'static str>, // Asset path
+    pub banner: Option<&'static str>,
+    pub subtitle: Option<&'static str>,
+}
+
+#[derive(Resource)]
+pub struct AnnouncerCues {
+    pub cues: HashMap<DirectorMilestone, AnnouncementCue>,
+    pub banner_duration: f32,
+}
+
+impl Default for AnnouncerCues {
+    fn default() -> Self {
+        // No announcer voice lines recorded yet, so only the text cues are filled in
+        let mut cues = HashMap::new();
+        cues.insert(
+            DirectorMilestone::SurgeStart,
+            AnnouncementCue {
+                sound: None,
+                banner: Some("The Horde Surges!"),
+                subtitle: Some("Enemies are converging from all sides"),
+            },
+        );
+        cues.insert(
+            DirectorMilestone::BossSpawn,
+            AnnouncementCue {
+                sound: None,
+                banner: Some("A Greater Foe Approaches"),
+                subtitle: Some("Something powerful has entered the field"),
+            },
+        );
+        cues.insert(
+            DirectorMilestone::FinalMinute,
+            AnnouncementCue {
+                sound: None,
+                banner: Some("Final Minute"),
+                subtitle: Some("Hold out a little longer"),
+            },
+        );
+        cues.insert(
+            DirectorMilestone::WaveCleared,
+            AnnouncementCue {
+                sound: None,
+                banner: Some("Wave Cleared"),
+                subtitle: Some("Catch your breath, the next wave is coming"),
+            },
+        );
+
+        Self {
+            cues,
+            banner_duration: 2.5,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Announcement {
+    pub timer: Timer,
+}
+
+fn play_milestone_cues(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    settings: Res<GameSettings>,
+    announcer_cues: Res<AnnouncerCues>,
+    mut milestones: EventReader<DirectorMilestone>,
+    existing: Query<Entity, With<Announcement>>,
+) {
+    for milestone in milestones.read() {
+        let Some(cue) = announcer_cues.cues.get(milestone) else {
+            warn!("No announcer cue mapped for {:?}", milestone);
+            continue;
+        };
+
+        info!("Director milestone: {:?}", milestone);
+
+        if let Some(sound) = cue.sound {
+            commands.spawn((
+                AudioPlayer::new(asset_server.load(sound)),
+                settings.sfx_playback(),
+            ));
+        }
+
+        if cue.banner.is_none() && cue.subtitle.is_none() {
+            continue;
+        }
+
+        // Newer announcements replace older ones instead of stacking
+        for entity in existing.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+
+        commands
+            .spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    top: Val::Percent(20.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(8.0),
+                    ..default()
+                },
+                GlobalZIndex(50),
+                Announcement {
+                    timer: Timer::from_seconds(announcer_cues.banner_duration, TimerMode::Once),
+                },
+            ))
+            .with_children(|parent| {
+                if let Some(banner) = cue.banner {
+                    parent.spawn((
+                        Text::new(banner),
+                        TextFont {
+                            font_size: 48.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(1.0, 0.8, 0.0)),
+                    ));
+                }
+
+                if let Some(subtitle) = cue.subtitle {
+                    parent.spawn((
+                        Text::new(subtitle),
+                        TextFont {
+                            font_size: 20.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                    ));
+                }
+            });
+    }
+}
+
+fn fade_announcements(
+    mut commands: Commands,
+    time: Res<Time<Virtual>>,
+    mut announcements: Query<(Entity, &mut Announcement, &Children)>,
+    mut text_colors: Query<&mut TextColor>,
+) {
+    for (entity, mut announcement, children) in announcements.iter_mut() {
+        announcement.timer.tick(time.delta());
+
+        if announcement.timer.finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        // Hold fully visible for the first half, then fade out
+        let alpha = ((1.0 - announcement.timer.fraction()) * 2.0).min(1.0);
+        for &child in children.iter() {
+            if let Ok(mut color) = text_colors.get_mut(child) {
+                color.0.set_alpha(alpha);
+            }
+        }
+    }
+}
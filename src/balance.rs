@@ -0,0 +1,191 @@
+use crate::definitions::{Definitions, WeaponDefinition};
+use crate::weapons::WeaponType;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+const BALANCE_OVERRIDE_PATH: &str = "balance_override.ron";
+
+/// Playtest tuning read from `balance_override.ron` in the working directory, applied
+/// over the loaded definitions so values can change without a new build. Only the
+/// values given are touched, e.g.
+/// `(enemies: {"imp": (speed: Some(70.0))}, weapons: {MagickCircle: (damage: Some(14))})`
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct BalanceOverride {
+    pub enemies: HashMap<String, EnemyOverride>, // By enemy definition id
+    pub weapons: HashMap<WeaponType, WeaponOverride>,
+    pub director: DirectorOverride, // For the active stage
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct EnemyOverride {
+    pub speed: Option<f32>,
+    pub health: Option<i32>,
+    pub experience_value: Option<u32>,
+    pub contact_damage: Option<i32>,
+    pub cost: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct WeaponOverride {
+    pub cooldown: Option<f32>,
+    pub damage: Option<i32>,
+    pub area: Option<f32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct DirectorOverride {
+    pub max_enemies: Option<u32>,
+    pub run_length: Option<f32>,
+    pub kill_goal: Option<u32>,
+}
+
+/// Sets `target` to the override if there is one, logging the change
+fn override_value<T: Copy + std::fmt::Debug>(
+    target: &mut T,
+    value: Option<T>,
+    label: &str,
+    applied: &mut usize,
+) {
+    if let Some(value) = value {
+        info!("Balance override: {} {:?} -> {:?}", label, target, value);
+        *target = value;
+        *applied += 1;
+    }
+}
+
+impl BalanceOverride {
+    /// `None` when there's no override file, which is the normal case
+    pub fn load() -> Option<Self> {
+        let contents = fs::read_to_string(BALANCE_OVERRIDE_PATH).ok()?;
+        match ron::from_str(&contents) {
+            Ok(overrides) => {
+                info!("Loaded balance overrides from {}", BALANCE_OVERRIDE_PATH);
+                Some(overrides)
+            }
+            Err(e) => {
+                warn!(
+                    "Failed to parse {}, ignoring it: {}",
+                    BALANCE_OVERRIDE_PATH, e
+                );
+                None
+            }
+        }
+    }
+
+    /// Writes every override into the definitions, returns how many values changed
+    pub fn apply(&self, definitions: &mut Definitions) -> usize {
+        let mut applied = 0;
+
+        for (id, enemy_override) in &self.enemies {
+            let Some(enemy) = definitions.enemies.get_mut(id) else {
+                warn!("Balance override for unknown enemy '{}', skipping it", id);
+                continue;
+            };
+            let label = |field: &str| format!("enemy '{}' {}", id, field);
+            override_value(
+                &mut enemy.speed,
+                enemy_override.speed,
+                &label("speed"),
+                &mut applied,
+            );
+            override_value(
+                &mut enemy.health,
+                enemy_override.health,
+                &label("health"),
+                &mut applied,
+            );
+            override_value(
+                &mut enemy.experience_value,
+                enemy_override.experience_value,
+                &label("experience_value"),
+                &mut applied,
+            );
+            override_value(
+                &mut enemy.contact_damage,
+                enemy_override.contact_damage,
+                &label("contact_damage"),
+                &mut applied,
+            );
+            override_value(
+                &mut enemy.cost,
+                enemy_override.cost,
+                &label("cost"),
+                &mut applied,
+            );
+        }
+
+        for (weapon_type, weapon_override) in &self.weapons {
+            // Weapons without a definition get one, so any weapon can be tuned
+            let id = definitions
+                .weapons
+                .iter()
+                .find(|(_, definition)| definition.weapon == *weapon_type)
+                .map_or_else(|| weapon_type.to_string(), |(id, _)| id.clone());
+            let definition = definitions.weapons.entry(id).or_insert(WeaponDefinition {
+                weapon: *weapon_type,
+                cooldown: None,
+                damage: None,
+                area: None,
+            });
+            let label = |field: &str| format!("weapon {} {}", weapon_type, field);
+            override_value(
+                &mut definition.cooldown,
+                weapon_override.cooldown.map(Some),
+                &label("cooldown"),
+                &mut applied,
+            );
+            override_value(
+                &mut definition.damage,
+                weapon_override.damage.map(Some),
+                &label("damage"),
+                &mut applied,
+            );
+            override_value(
+                &mut definition.area,
+                weapon_override.area.map(Some),
+                &label("area"),
+                &mut applied,
+            );
+        }
+
+        let active_stage = definitions.active_stage.clone();
+        match definitions.stages.get_mut(&active_stage) {
+            Some(stage) => {
+                let label = |field: &str| format!("stage '{}' {}", active_stage, field);
+                let director = &self.director;
+                override_value(
+                    &mut stage.max_enemies,
+                    director.max_enemies,
+                    &label("max_enemies"),
+                    &mut applied,
+                );
+                override_value(
+                    &mut stage.run_length,
+                    director.run_length.map(Some),
+                    &label("run_length"),
+                    &mut applied,
+                );
+                override_value(
+                    &mut stage.kill_goal,
+                    director.kill_goal.map(Some),
+                    &label("kill_goal"),
+                    &mut applied,
+                );
+            }
+            None if self.director != DirectorOverride::default() => warn!(
+                "Balance override can't reach unknown stage '{}'",
+                active_stage
+            ),
+            None => {}
+        }
+
+        info!("Applied {} balance overrides", applied);
+        applied
+    }
+}
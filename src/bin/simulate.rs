@@ -0,0 +1,307 @@
+//! Balance simulator: plays seeded headless runs with a simple bot and writes CSV summaries.
+//!
+//! cargo run --bin simulate -- --runs 20 --seed 0 --duration 600 --out simulation
+//!
+//! Seeds drive the bot's upgrade picks. Enemy spawns still roll the thread RNG,
+//! so compare averages over enough runs rather than single runs.
+
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use survivors_prototype::components::Player;
+use survivors_prototype::controls::{Action, KeyBindings};
+use survivors_prototype::gameplay_events::{GameplayEvent, GameplayEventsAppExt};
+use survivors_prototype::headless::headless_app;
+use survivors_prototype::menu::{MenuAction, MenuActionComponent, MenuItem};
+use survivors_prototype::resources::{GameRng, GameState};
+use survivors_prototype::spatial_grid::EnemySpatialGrid;
+use survivors_prototype::weapons::{SourceWeapon, WeaponMeta};
+
+const TIMESTEP: f32 = 1.0 / 60.0;
+// How close an enemy has to be before the bot starts running from it
+const THREAT_RANGE: f32 = 250.0;
+
+struct SimulationArgs {
+    runs: u32,
+    seed: u64,
+    duration: f32, // Runs that survive this long are cut off
+    out: PathBuf,
+}
+
+impl SimulationArgs {
+    fn from_args() -> Self {
+        let mut args = Self {
+            runs: 20,
+            seed: 0,
+            duration: 600.0,
+            out: PathBuf::from("simulation"),
+        };
+
+        let mut iter = std::env::args().skip(1);
+        while let Some(arg) = iter.next() {
+            let Some(value) = iter.next() else {
+                break;
+            };
+            match arg.as_str() {
+                "--runs" => args.runs = value.parse().expect("--runs takes a number"),
+                "--seed" => args.seed = value.parse().expect("--seed takes a number"),
+                "--duration" => args.duration = value.parse().expect("--duration takes seconds"),
+                "--out" => args.out = PathBuf::from(value),
+                _ => eprintln!("Ignoring unknown argument: {}", arg),
+            }
+        }
+
+        args
+    }
+}
+
+/// Decides what the simulated player presses each frame
+#[derive(Resource)]
+struct Bot {
+    rng: StdRng,
+}
+
+/// Everything a run reports once it's over
+#[derive(Resource, Default)]
+struct RunRecord {
+    died_at: Option<f32>,
+    won: bool,
+    kills: u32,
+    damage_by_weapon: HashMap<String, i64>,
+    level_times: Vec<(u32, f32)>,
+}
+
+struct RunSummary {
+    seed: u64,
+    survival_time: f32,
+    died: bool,
+    record: RunRecord,
+}
+
+fn main() {
+    let args = SimulationArgs::from_args();
+    let mut summaries = Vec::with_capacity(args.runs as usize);
+
+    for run in 0..args.runs {
+        let seed = args.seed + run as u64;
+        let summary = simulate_run(seed, args.duration);
+        println!(
+            "Run {} (seed {}): {} at {:.1}s, {} kills",
+            run,
+            seed,
+            if summary.died {
+                "died"
+            } else if summary.record.won {
+                "won"
+            } else {
+                "survived"
+            },
+            summary.survival_time,
+            summary.record.kills
+        );
+        summaries.push(summary);
+    }
+
+    if let Err(e) = write_csvs(&args.out, &summaries) {
+        eprintln!("Failed to write results to {}: {}", args.out.display(), e);
+        std::process::exit(1);
+    }
+
+    let runs = summaries.len().max(1) as f32;
+    let average_survival = summaries.iter().map(|s| s.survival_time).sum::<f32>() / runs;
+    println!(
+        "Average survival time over {} runs: {:.1}s",
+        summaries.len(),
+        average_survival
+    );
+    println!("Results written to {}", args.out.display());
+}
+
+fn simulate_run(seed: u64, duration: f32) -> RunSummary {
+    let mut app = headless_app(Duration::from_secs_f32(TIMESTEP));
+    app.insert_resource(Bot {
+        rng: StdRng::seed_from_u64(seed),
+    })
+    .insert_resource(GameRng::from_seed(seed))
+    .init_resource::<RunRecord>()
+    .add_systems(PreUpdate, bot_input.after(InputSystem))
+    .add_systems(Update, bot_start_run.run_if(in_state(GameState::MainMenu)))
+    .add_systems(OnEnter(GameState::GameOver), record_death)
+    .add_systems(OnEnter(GameState::Victory), record_victory)
+    .add_gameplay_observer(record_gameplay_events);
+
+    // Level-up menus stop game time, so cap real frames too in case the bot gets stuck on one
+    let max_updates = (duration / TIMESTEP) as u32 * 2;
+    for _ in 0..max_updates {
+        app.update();
+
+        let world = app.world();
+        let elapsed = world.resource::<Time<Virtual>>().elapsed_secs();
+        let record = world.resource::<RunRecord>();
+        if record.died_at.is_some() || record.won || elapsed >= duration {
+            break;
+        }
+    }
+
+    let world = app.world_mut();
+    let elapsed = world.resource::<Time<Virtual>>().elapsed_secs();
+    let record = world.remove_resource::<RunRecord>().unwrap_or_default();
+
+    RunSummary {
+        seed,
+        survival_time: record.died_at.unwrap_or(elapsed),
+        died: record.died_at.is_some(),
+        record,
+    }
+}
+
+fn bot_start_run(mut next_state: ResMut<NextState<GameState>>) {
+    next_state.set(GameState::Playing);
+}
+
+fn bot_input(
+    mut bot: ResMut<Bot>,
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    game_state: Res<State<GameState>>,
+    grid: Res<EnemySpatialGrid>,
+    player_query: Query<&Transform, With<Player>>,
+    mut menu_items: Query<(&mut MenuItem, &MenuActionComponent)>,
+) {
+    // The bot is the only one at the keyboard
+    keyboard.release_all();
+
+    match game_state.get() {
+        GameState::Playing => {
+            let Ok(transform) = player_query.get_single() else {
+                return;
+            };
+
+            // Run from the crowd, weighting closer enemies more
+            let position = transform.translation.truncate();
+            let flee = grid
+                .query_radius(position, THREAT_RANGE)
+                .map(|(_, enemy)| {
+                    let away = position - enemy;
+                    away.normalize_or_zero() / away.length().max(1.0)
+                })
+                .sum::<Vec2>()
+                .normalize_or_zero();
+
+            if flee.y > 0.38 {
+                keyboard.press(bindings.primary(Action::MoveUp));
+            } else if flee.y < -0.38 {
+                keyboard.press(bindings.primary(Action::MoveDown));
+            }
+            if flee.x > 0.38 {
+                keyboard.press(bindings.primary(Action::MoveRight));
+            } else if flee.x < -0.38 {
+                keyboard.press(bindings.primary(Action::MoveLeft));
+            }
+        }
+        GameState::LevelUp | GameState::Milestone => {
+            let choices = menu_items
+                .iter()
+                .filter(|(_, action)| matches!(action.action, MenuAction::SelectUpgrade(_)))
+                .count();
+            if choices == 0 {
+                return;
+            }
+
+            let pick = bot.rng.gen_range(0..choices);
+            for (i, (mut item, _)) in menu_items
+                .iter_mut()
+                .filter(|(_, action)| matches!(action.action, MenuAction::SelectUpgrade(_)))
+                .enumerate()
+            {
+                item.selected = i == pick;
+            }
+            keyboard.press(bindings.primary(Action::Confirm));
+        }
+        _ => {}
+    }
+}
+
+fn record_death(time: Res<Time<Virtual>>, mut record: ResMut<RunRecord>) {
+    record.died_at = Some(time.elapsed_secs());
+}
+
+fn record_victory(mut record: ResMut<RunRecord>) {
+    record.won = true;
+}
+
+fn record_gameplay_events(
+    time: Res<Time<Virtual>>,
+    mut events: EventReader<GameplayEvent>,
+    mut record: ResMut<RunRecord>,
+    source_query: Query<&SourceWeapon>,
+    weapon_query: Query<&WeaponMeta>,
+) {
+    for event in events.read() {
+        match event {
+            GameplayEvent::DamageDealt { amount, source, .. } => {
+                let weapon = source
+                    .and_then(|attack| source_query.get(attack).ok())
+                    .and_then(|source| weapon_query.get(source.0).ok())
+                    .map_or("Other".to_string(), |meta| meta.weapon_type.to_string());
+                *record.damage_by_weapon.entry(weapon).or_default() += *amount as i64;
+            }
+            GameplayEvent::Kill { .. } => record.kills += 1,
+            GameplayEvent::LevelUp { level } => {
+                let elapsed = time.elapsed_secs();
+                record.level_times.push((*level, elapsed));
+            }
+            _ => {}
+        }
+    }
+}
+
+fn write_csvs(out: &Path, summaries: &[RunSummary]) -> std::io::Result<()> {
+    fs::create_dir_all(out)?;
+
+    let mut runs = String::from("run,seed,survival_time,died,won,kills,final_level\n");
+    let mut damage = String::from("run,seed,weapon,damage,dps\n");
+    let mut levels = String::from("run,seed,level,time\n");
+
+    for (run, summary) in summaries.iter().enumerate() {
+        let record = &summary.record;
+        let final_level = record.level_times.last().map_or(1, |(level, _)| *level);
+        let _ = writeln!(
+            runs,
+            "{},{},{:.2},{},{},{},{}",
+            run,
+            summary.seed,
+            summary.survival_time,
+            summary.died,
+            record.won,
+            record.kills,
+            final_level
+        );
+
+        let mut weapons: Vec<_> = record.damage_by_weapon.iter().collect();
+        weapons.sort();
+        for (weapon, total) in weapons {
+            let dps = *total as f32 / summary.survival_time.max(TIMESTEP);
+            let _ = writeln!(
+                damage,
+                "{},{},{},{},{:.2}",
+                run, summary.seed, weapon, total, dps
+            );
+        }
+
+        for (level, time) in &record.level_times {
+            let _ = writeln!(levels, "{},{},{},{:.2}", run, summary.seed, level, time);
+        }
+    }
+
+    fs::write(out.join("runs.csv"), runs)?;
+    fs::write(out.join("weapon_damage.csv"), damage)?;
+    fs::write(out.join("level_curve.csv"), levels)?;
+    Ok(())
+}
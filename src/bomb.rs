@@ -0,0 +1,70 @@
+use crate::combat::DamageEvent;
+use crate::components::Enemy;
+use crate::death::MarkedForDeath;
+use crate::resources::GameState;
+use crate::status::{ApplyStatusEvent, StatusEffect};
+use crate::GameplaySets;
+use bevy::prelude::*;
+
+/// Screen clears, like the ones bomb pickups set off. Whatever was on screen takes a huge hit,
+/// everything further out panics and scatters for a moment.
+pub struct BombPlugin;
+
+impl Plugin for BombPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ScreenClearEvent>().add_systems(
+            Update,
+            clear_screen
+                .after(GameplaySets::Movement)
+                .before(GameplaySets::Combat)
+                .run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+/// Blasts everything in view. Sent by bombs, and by anything else that wipes the screen.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ScreenClearEvent {
+    pub damage: i32,
+    pub source: Option<Entity>,
+}
+
+pub const BOMB_DAMAGE: i32 = 500;
+// How long enemies that escaped the blast run off for
+const FEAR_DURATION: f32 = 1.5;
+
+fn clear_screen(
+    mut screen_clears: EventReader<ScreenClearEvent>,
+    camera_query: Query<(&Transform, &OrthographicProjection), With<Camera2d>>,
+    enemy_query: Query<(Entity, &Transform), (With<Enemy>, Without<MarkedForDeath>)>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut status_events: EventWriter<ApplyStatusEvent>,
+) {
+    let Ok((camera_transform, projection)) = camera_query.get_single() else {
+        return;
+    };
+    let view = Rect::from_center_half_size(
+        camera_transform.translation.truncate(),
+        projection.area.half_size(),
+    );
+
+    for clear in screen_clears.read() {
+        for (enemy, transform) in enemy_query.iter() {
+            if view.contains(transform.translation.truncate()) {
+                damage_events.send(DamageEvent {
+                    target: enemy,
+                    amount: clear.damage,
+                    source: clear.source,
+                });
+            } else {
+                status_events.send(ApplyStatusEvent {
+                    target: enemy,
+                    effect: StatusEffect::Fear {
+                        duration: FEAR_DURATION,
+                    },
+                    source: clear.source,
+                });
+            }
+        }
+    }
+}
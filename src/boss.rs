@@ -0,0 +1,331 @@
+use crate::components::{Enemy, Health, Player};
+use crate::death::MarkedForDeath;
+use crate::enemy_projectile::spawn_enemy_projectile;
+use crate::events::EntityDeathEvent;
+use crate::experience::spawn_experience_orb;
+use crate::game_time::PausableTimer;
+use crate::gameplay_events::GameplayEvent;
+use crate::player_handle::PlayerHandle;
+use crate::resources::{GameState, GameStats, GameTextures};
+use crate::set_piece::{PlaySetPiece, SetPieceStep};
+use crate::supply_drop::spawn_supply_crate;
+use crate::GameplaySets;
+use bevy::prelude::*;
+use std::f32::consts::TAU;
+
+/// Boss fights: phase changes as they lose health, their attacks, the health bar
+/// and what they leave behind. The director decides when bosses show up.
+pub struct BossPlugin;
+
+impl Plugin for BossPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                (advance_boss_phases, boss_attacks)
+                    .chain()
+                    .after(GameplaySets::Movement)
+                    .before(GameplaySets::Combat),
+                // Dying entities are still around until cleanup, so we can still look at them
+                reward_boss_kills
+                    .after(GameplaySets::Combat)
+                    .before(GameplaySets::Cleanup),
+                introduce_bosses.after(GameplaySets::Spawning),
+                sync_boss_health_bar.in_set(GameplaySets::UI),
+            )
+                .run_if(in_state(GameState::Playing)),
+        )
+        .add_systems(OnExit(GameState::Playing), cleanup_boss_health_bar);
+    }
+}
+
+/// Stages of a boss fight, moved through in order as the boss loses health
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BossPhase {
+    Hunt,    // Chases and fires the odd ring of bolts
+    Barrage, // Slows down to pepper the player with aimed spreads
+    Frenzy,  // Rushes in, rings and spreads together
+}
+
+impl BossPhase {
+    /// Phase a boss should be in at this share of its health
+    pub fn for_health(fraction: f32) -> Self {
+        if fraction > 0.66 {
+            BossPhase::Hunt
+        } else if fraction > 0.33 {
+            BossPhase::Barrage
+        } else {
+            BossPhase::Frenzy
+        }
+    }
+
+    fn attack_interval(self) -> f32 {
+        match self {
+            BossPhase::Hunt => 4.0,
+            BossPhase::Barrage => 1.5,
+            BossPhase::Frenzy => 2.0,
+        }
+    }
+
+    fn speed_multiplier(self) -> f32 {
+        match self {
+            BossPhase::Hunt => 1.0,
+            BossPhase::Barrage => 0.5,
+            BossPhase::Frenzy => 1.6,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Boss {
+    pub name: String,
+    pub phase: BossPhase,
+    pub attack_timer: PausableTimer,
+    pub base_speed: f32, // Each phase scales this
+}
+
+impl Boss {
+    pub fn new(name: String, base_speed: f32) -> Self {
+        Self {
+            name,
+            phase: BossPhase::Hunt,
+            attack_timer: PausableTimer::from_seconds(
+                BossPhase::Hunt.attack_interval(),
+                TimerMode::Repeating,
+            ),
+            base_speed,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct BossHealthBar;
+
+#[derive(Component)]
+pub struct BossHealthFill;
+
+#[derive(Component)]
+pub struct BossNameText;
+
+// Game speed while a boss makes its entrance
+const INTRO_TIME_SCALE: f32 = 0.2;
+
+const RING_BOLTS: usize = 10;
+const SPREAD_BOLTS: usize = 5;
+const SPREAD_ANGLE: f32 = 0.15; // Radians between bolts in a spread
+const BOSS_BOLT_SPEED: f32 = 180.0;
+const BOSS_BOLT_DAMAGE: i32 = 8;
+const REWARD_ORBS: u32 = 12;
+const REWARD_ORB_VALUE: u32 = 200;
+
+/// Slows the game and swings the camera over to a new boss with its name up
+fn introduce_bosses(
+    boss_query: Query<(Entity, &Boss), Added<Boss>>,
+    mut set_pieces: EventWriter<PlaySetPiece>,
+) {
+    for (entity, boss) in boss_query.iter() {
+        set_pieces.send(PlaySetPiece(vec![
+            SetPieceStep {
+                duration: 0.6,
+                time_scale: INTRO_TIME_SCALE,
+                focus: Some(entity),
+                caption: None,
+            },
+            SetPieceStep {
+                duration: 1.5,
+                time_scale: INTRO_TIME_SCALE,
+                focus: Some(entity),
+                caption: Some(boss.name.clone()),
+            },
+            // Back to the player before handing control back
+            SetPieceStep {
+                duration: 0.5,
+                time_scale: 0.5,
+                ..default()
+            },
+        ]));
+    }
+}
+
+fn advance_boss_phases(
+    mut boss_query: Query<(Entity, &mut Boss, &mut Enemy, &Health)>,
+    mut gameplay_events: EventWriter<GameplayEvent>,
+) {
+    for (entity, mut boss, mut enemy, health) in boss_query.iter_mut() {
+        let phase = BossPhase::for_health(health.current as f32 / health.maximum as f32);
+        if phase == boss.phase {
+            continue;
+        }
+
+        info!("{} enters {:?} phase", boss.name, phase);
+        boss.phase = phase;
+        boss.attack_timer =
+            PausableTimer::from_seconds(phase.attack_interval(), TimerMode::Repeating);
+        enemy.speed = boss.base_speed * phase.speed_multiplier();
+        gameplay_events.send(GameplayEvent::BossPhase {
+            boss: entity,
+            phase,
+        });
+    }
+}
+
+fn boss_attacks(
+    mut commands: Commands,
+    time: Res<Time<Virtual>>,
+    textures: Res<GameTextures>,
+    player_handle: Res<PlayerHandle>,
+    player_query: Query<&Transform, With<Player>>,
+    mut boss_query: Query<(&mut Boss, &Transform), (Without<MarkedForDeath>, Without<Player>)>,
+) {
+    let Some(player_transform) = player_handle.fetch(&player_query) else {
+        return;
+    };
+
+    for (mut boss, transform) in boss_query.iter_mut() {
+        if !boss.attack_timer.tick(&time).just_finished() {
+            continue;
+        }
+
+        let aim = (player_transform.translation - transform.translation)
+            .truncate()
+            .normalize_or(Vec2::X);
+        let ring = (0..RING_BOLTS).map(|i| Vec2::from_angle(TAU * i as f32 / RING_BOLTS as f32));
+        let spread = (0..SPREAD_BOLTS).map(|i| {
+            let offset = i as f32 - (SPREAD_BOLTS - 1) as f32 / 2.0;
+            Vec2::from_angle(offset * SPREAD_ANGLE).rotate(aim)
+        });
+
+        let directions: Vec<Vec2> = match boss.phase {
+            BossPhase::Hunt => ring.collect(),
+            BossPhase::Barrage => spread.collect(),
+            BossPhase::Frenzy => ring.chain(spread).collect(),
+        };
+
+        for direction in directions {
+            spawn_enemy_projectile(
+                &mut commands,
+                &textures,
+                transform.translation,
+                direction * BOSS_BOLT_SPEED,
+                BOSS_BOLT_DAMAGE,
+            );
+        }
+    }
+}
+
+/// A crate and a ring of big orbs, on top of the usual experience drop
+fn reward_boss_kills(
+    mut commands: Commands,
+    mut death_events: EventReader<EntityDeathEvent>,
+    boss_query: Query<&Boss>,
+    mut game_stats: ResMut<GameStats>,
+) {
+    for event in death_events.read() {
+        let Ok(boss) = boss_query.get(event.entity) else {
+            continue;
+        };
+
+        game_stats.bosses_killed += 1;
+        info!("{} defeated", boss.name);
+
+        spawn_supply_crate(&mut commands, event.position);
+        for i in 0..REWARD_ORBS {
+            let angle = TAU * i as f32 / REWARD_ORBS as f32;
+            spawn_experience_orb(
+                &mut commands,
+                event.position + Vec2::from_angle(angle) * 40.0,
+                REWARD_ORB_VALUE,
+            );
+        }
+    }
+}
+
+/// Shows a bar across the top of the screen while a boss is alive
+fn sync_boss_health_bar(
+    mut commands: Commands,
+    boss_query: Query<(&Boss, &Health), Without<MarkedForDeath>>,
+    bar_query: Query<Entity, With<BossHealthBar>>,
+    mut fill_query: Query<&mut Node, With<BossHealthFill>>,
+    mut name_query: Query<&mut Text, With<BossNameText>>,
+) {
+    // With more than one boss up, the bar follows the healthiest
+    let boss = boss_query.iter().max_by_key(|(_, health)| health.current);
+
+    let Some((boss, health)) = boss else {
+        for entity in bar_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    };
+
+    if bar_query.is_empty() {
+        spawn_boss_health_bar(&mut commands);
+        return;
+    }
+
+    let percent = (health.current as f32 / health.maximum as f32 * 100.0).clamp(0.0, 100.0);
+    if let Ok(mut node) = fill_query.get_single_mut() {
+        node.width = Val::Percent(percent);
+    }
+    if let Ok(mut text) = name_query.get_single_mut() {
+        if text.0 != boss.name {
+            text.0 = boss.name.clone();
+        }
+    }
+}
+
+fn spawn_boss_health_bar(commands: &mut Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(40.0),
+                left: Val::Percent(30.0),
+                top: Val::Px(56.0), // Below the run timer
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(4.0),
+                ..default()
+            },
+            BossHealthBar,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.5, 0.2)),
+                BossNameText,
+            ));
+            parent
+                .spawn((
+                    Node {
+                        width: Val::Percent(100.0),
+                        height: Val::Px(16.0),
+                        border: UiRect::all(Val::Px(2.0)),
+                        ..default()
+                    },
+                    BorderColor(Color::srgb(0.7, 0.7, 0.7)),
+                    BackgroundColor(Color::srgb(0.1, 0.1, 0.1)),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Node {
+                            width: Val::Percent(100.0),
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgb(0.6, 0.1, 0.5)),
+                        BossHealthFill,
+                    ));
+                });
+        });
+}
+
+fn cleanup_boss_health_bar(mut commands: Commands, bar_query: Query<Entity, With<BossHealthBar>>) {
+    for entity in bar_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
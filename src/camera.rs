@@ -0,0 +1,115 @@
+use crate::components::Player;
+use crate::definitions::StageBounds;
+use crate::resources::GameState;
+use bevy::prelude::*;
+
+/// Keeps the gameplay camera on the player, easing after them rather than sticking to them.
+/// In a bounded stage the camera stops at the walls instead of showing what's past them.
+pub struct CameraPlugin;
+
+impl Plugin for CameraPlugin {
+    fn build(&self, app: &mut App) {
+        // After movement has settled but before transforms are propagated for rendering
+        app.init_resource::<CameraFollow>().add_systems(
+            PostUpdate,
+            follow_player
+                .before(TransformSystem::TransformPropagate)
+                .run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+/// How the camera follows the player
+#[derive(Resource)]
+pub struct CameraFollow {
+    pub smoothing: f32,         // Catch-up rate per second, 0 sticks to the player
+    pub deadzone: Option<Vec2>, // Half size of a box the player can move around in freely
+    // Where the follow has got to. Set pieces and the kill cam move the camera on top of this,
+    // so it's kept apart from the camera's own transform.
+    position: Option<Vec2>,
+}
+
+impl Default for CameraFollow {
+    fn default() -> Self {
+        Self {
+            smoothing: 8.0,
+            deadzone: None,
+            position: None,
+        }
+    }
+}
+
+impl CameraFollow {
+    /// Where the camera heads for: the player, or with a deadzone only as far as it takes
+    /// to have the player back inside it
+    pub fn target(&self, current: Vec2, player: Vec2) -> Vec2 {
+        match self.deadzone {
+            Some(half_size) => player - (player - current).clamp(-half_size, half_size),
+            None => player,
+        }
+    }
+
+    /// Moves `current` towards `target` over `delta` seconds
+    pub fn approach(&self, current: Vec2, target: Vec2, delta: f32) -> Vec2 {
+        if self.smoothing <= 0.0 {
+            return target;
+        }
+        let blend = 1.0 - (-self.smoothing * delta).exp();
+        current.lerp(target, blend)
+    }
+
+    /// Jumps straight to the player next time instead of easing over, e.g. for a new run
+    pub fn snap(&mut self) {
+        self.position = None;
+    }
+}
+
+/// Eases the camera after the player, clamped so the view stays inside the stage bounds.
+/// An axis where the arena is smaller than the view stays centered on the arena.
+pub fn follow_player(
+    time: Res<Time>,
+    bounds: Option<Res<StageBounds>>,
+    mut follow: ResMut<CameraFollow>,
+    player_query: Query<&Transform, (With<Player>, Without<Camera2d>)>,
+    mut camera_query: Query<(&mut Transform, &OrthographicProjection), With<Camera2d>>,
+) {
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+    let player = player_transform.translation.truncate();
+
+    let position = match follow.position {
+        Some(current) => {
+            let target = follow.target(current, player);
+            follow.approach(current, target, time.delta_secs())
+        }
+        None => player,
+    };
+
+    for (mut camera_transform, projection) in camera_query.iter_mut() {
+        let mut position = position;
+        if let Some(bounds) = &bounds {
+            position = clamp_view(position, projection.area.half_size(), bounds.0);
+        }
+        follow.position = Some(position);
+
+        camera_transform.translation.x = position.x;
+        camera_transform.translation.y = position.y;
+    }
+}
+
+/// Hook for keeping the view inside an arena, `target` moved so a view of this size fits
+pub fn clamp_view(target: Vec2, half_view: Vec2, bounds: Rect) -> Vec2 {
+    let clamp_axis = |value: f32, half: f32, min: f32, max: f32| {
+        if max - min <= half * 2.0 {
+            (min + max) * 0.5
+        } else {
+            value.clamp(min + half, max - half)
+        }
+    };
+
+    Vec2::new(
+        clamp_axis(target.x, half_view.x, bounds.min.x, bounds.max.x),
+        clamp_axis(target.y, half_view.y, bounds.min.y, bounds.max.y),
+    )
+}
@@ -0,0 +1,114 @@
+use crate::components::{Character, Player};
+use crate::loadout::apply_loadout;
+use crate::menu::{
+    spawn_main_menu, spawn_menu_button, spawn_menu_container, MenuAction, MenuActionComponent,
+    MenuInput, MenuItem, MenuRoot, MenuType,
+};
+use crate::resources::GameState;
+use crate::weapons::{StartingWeapon, WeaponType};
+use bevy::prelude::*;
+use strum::IntoEnumIterator;
+
+/// Picks who the next run is played as and what they start with, from the main menu
+pub struct CharacterSelectPlugin;
+
+impl Plugin for CharacterSelectPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            handle_character_select.run_if(in_state(GameState::MainMenu)),
+        );
+    }
+}
+
+/// Every character and starting weapon a run can begin with, evolutions have to be earned
+pub fn character_choices() -> Vec<(Character, WeaponType)> {
+    Character::iter()
+        .flat_map(|character| {
+            WeaponType::iter()
+                .filter(|weapon_type| !weapon_type.is_evolution())
+                .map(move |weapon_type| (character, weapon_type))
+        })
+        .collect()
+}
+
+fn handle_character_select(
+    mut commands: Commands,
+    menu_input: MenuInput,
+    menu_items: Query<(&MenuItem, &MenuActionComponent, Ref<Interaction>)>,
+    menu_roots: Query<Entity, With<MenuRoot>>,
+    mut player_query: Query<(&mut Character, &mut StartingWeapon), With<Player>>,
+) {
+    let character_items = menu_items.iter().filter(|(_, action_component, _)| {
+        matches!(
+            action_component.action,
+            MenuAction::OpenCharacterSelect
+                | MenuAction::SelectCharacter(..)
+                | MenuAction::CloseCharacterSelect
+        )
+    });
+    let Some(action) = menu_input.confirmed(character_items) else {
+        return;
+    };
+
+    for entity in menu_roots.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    match action {
+        MenuAction::OpenCharacterSelect => {
+            let current = player_query
+                .get_single()
+                .ok()
+                .map(|(character, starting_weapon)| (*character, starting_weapon.0));
+            spawn_character_select(&mut commands, current);
+        }
+        MenuAction::SelectCharacter(character, weapon_type) => {
+            info!("Selected {} with {}", character, weapon_type);
+            apply_loadout(&mut player_query, *character, *weapon_type);
+            commands.run_system_cached(spawn_main_menu);
+        }
+        _ => commands.run_system_cached(spawn_main_menu),
+    }
+}
+
+fn spawn_character_select(commands: &mut Commands, current: Option<(Character, WeaponType)>) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+            MenuRoot {
+                menu_type: MenuType::CharacterSelect,
+            },
+        ))
+        .with_children(|parent| {
+            spawn_menu_container(parent, |parent| {
+                parent.spawn((
+                    Text::new("Character Select"),
+                    TextFont {
+                        font_size: 36.0,
+                        ..default()
+                    },
+                    TextColor(Color::WHITE),
+                ));
+
+                // The current pick has focus, so confirming straight away keeps it
+                for (character, weapon_type) in character_choices() {
+                    spawn_menu_button(
+                        parent,
+                        &format!("{} - {}", character, weapon_type),
+                        MenuAction::SelectCharacter(character, weapon_type),
+                        current == Some((character, weapon_type)),
+                    );
+                }
+                spawn_menu_button(parent, "Back", MenuAction::CloseCharacterSelect, false);
+            });
+        });
+}
@@ -0,0 +1,243 @@
+use crate::components::{CooldownReduction, DamageMultiplier, Enemy, EnemyKind, Player};
+use crate::death::MarkedForDeath;
+use crate::resources::{GameState, GameStats};
+use crate::save::SaveData;
+use crate::telemetry::{MasteryTier, WeaponStats};
+use crate::weapons::magick_circle::PatternType;
+use crate::weapons::weapon_upgrade::{WeaponUpgradeConfig, MAX_WEAPON_LEVEL};
+use crate::weapons::{WeaponMeta, WeaponType};
+use crate::GameplaySets;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use strum::IntoEnumIterator;
+
+pub struct CodexPlugin;
+
+impl Plugin for CodexPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AccountBonuses>()
+            .add_systems(
+                Update,
+                (
+                    record_enemy_defeats,
+                    record_maxed_weapons,
+                    record_pattern_unlocks,
+                    record_weapon_mastery.run_if(resource_changed::<WeaponStats>),
+                )
+                    .before(GameplaySets::Cleanup)
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(
+                Update,
+                (
+                    apply_account_bonuses,
+                    lock_patterns.run_if(resource_changed::<SaveData>),
+                ),
+            );
+    }
+}
+
+/// Everything the player has ever discovered, persisted in the save file
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(default)]
+pub struct Codex {
+    pub enemies_defeated: HashSet<EnemyKind>,
+    pub weapons_maxed: HashSet<WeaponType>,
+    pub patterns_unlocked: HashSet<PatternType>,
+    pub weapon_mastery: HashMap<WeaponType, MasteryTier>, // Best kill milestone in any run
+}
+
+impl Codex {
+    // Every Magick Circle starts out with Banishment
+    pub fn has_pattern(&self, pattern: PatternType) -> bool {
+        pattern == PatternType::Banishment || self.patterns_unlocked.contains(&pattern)
+    }
+}
+
+/// What it takes to unlock each Magick Circle pattern, checked during runs
+pub fn pattern_requirement(pattern: PatternType) -> &'static str {
+    match pattern {
+        PatternType::Banishment => "Unlocked from the start",
+        PatternType::Invocation => "Defeat 300 enemies in one run",
+        PatternType::Binding => "Survive for 5 minutes",
+        PatternType::Protection => "Defeat a boss",
+        PatternType::Manifestation => "Bring the Magick Circle to max level",
+    }
+}
+
+fn pattern_requirement_met(
+    pattern: PatternType,
+    codex: &Codex,
+    stats: &GameStats,
+    elapsed: f32,
+) -> bool {
+    match pattern {
+        PatternType::Banishment => true,
+        PatternType::Invocation => stats.enemies_killed >= 300,
+        PatternType::Binding => elapsed >= 300.0,
+        PatternType::Protection => stats.bosses_killed >= 1,
+        PatternType::Manifestation => codex.weapons_maxed.contains(&WeaponType::MagickCircle),
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum CodexSection {
+    Bestiary, // Defeat every enemy kind
+    Armory,   // Bring every weapon to max level once
+}
+
+impl std::fmt::Display for CodexSection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CodexSection::Bestiary => write!(f, "Bestiary"),
+            CodexSection::Armory => write!(f, "Armory"),
+        }
+    }
+}
+
+impl CodexSection {
+    pub const ALL: [CodexSection; 2] = [CodexSection::Bestiary, CodexSection::Armory];
+
+    pub fn is_complete(&self, codex: &Codex) -> bool {
+        match self {
+            CodexSection::Bestiary => {
+                EnemyKind::iter().all(|kind| codex.enemies_defeated.contains(&kind))
+            }
+            CodexSection::Armory => {
+                WeaponType::iter().all(|weapon| codex.weapons_maxed.contains(&weapon))
+            }
+        }
+    }
+}
+
+/// Permanent bonuses earned from completed codex sections, resolved when a run starts
+#[derive(Resource, Default, Debug)]
+pub struct AccountBonuses {
+    pub damage: f32,             // Added to DamageMultiplier.factor
+    pub cooldown_reduction: f32, // Added to CooldownReduction.percent
+}
+
+impl AccountBonuses {
+    pub fn resolve(codex: &Codex) -> Self {
+        let mut bonuses = Self::default();
+
+        for section in CodexSection::ALL {
+            if !section.is_complete(codex) {
+                continue;
+            }
+
+            match section {
+                CodexSection::Bestiary => bonuses.damage += 0.05,
+                CodexSection::Armory => bonuses.cooldown_reduction += 0.05,
+            }
+        }
+
+        bonuses
+    }
+}
+
+fn record_enemy_defeats(
+    mut save_data: ResMut<SaveData>,
+    defeated: Query<&Enemy, Added<MarkedForDeath>>,
+) {
+    for enemy in defeated.iter() {
+        // Check first so the save file is only touched on new discoveries
+        if save_data.codex.enemies_defeated.contains(&enemy.kind) {
+            continue;
+        }
+
+        info!("Codex: first {} defeated", enemy.kind);
+        save_data.codex.enemies_defeated.insert(enemy.kind);
+
+        if CodexSection::Bestiary.is_complete(&save_data.codex) {
+            info!("Codex section {} complete!", CodexSection::Bestiary);
+        }
+    }
+}
+
+fn record_maxed_weapons(
+    mut save_data: ResMut<SaveData>,
+    weapons: Query<&WeaponMeta, Changed<WeaponMeta>>,
+) {
+    for meta in weapons.iter() {
+        if meta.level < MAX_WEAPON_LEVEL
+            || save_data.codex.weapons_maxed.contains(&meta.weapon_type)
+        {
+            continue;
+        }
+
+        info!("Codex: {} reached max level", meta.weapon_type);
+        save_data.codex.weapons_maxed.insert(meta.weapon_type);
+
+        if CodexSection::Armory.is_complete(&save_data.codex) {
+            info!("Codex section {} complete!", CodexSection::Armory);
+        }
+    }
+}
+
+fn record_pattern_unlocks(
+    mut save_data: ResMut<SaveData>,
+    stats: Res<GameStats>,
+    time: Res<Time<Virtual>>,
+) {
+    // Read through the plain reference first so the save isn't flagged as changed every frame
+    let newly_met: Vec<PatternType> = PatternType::iter()
+        .filter(|pattern| {
+            !save_data.codex.has_pattern(*pattern)
+                && pattern_requirement_met(*pattern, &save_data.codex, &stats, time.elapsed_secs())
+        })
+        .collect();
+
+    for pattern in newly_met {
+        info!(
+            "Codex: {} pattern unlocked ({})",
+            pattern,
+            pattern_requirement(pattern)
+        );
+        save_data.codex.patterns_unlocked.insert(pattern);
+    }
+}
+
+fn record_weapon_mastery(mut save_data: ResMut<SaveData>, weapon_stats: Res<WeaponStats>) {
+    // Read through the plain reference first so the save isn't flagged as changed every kill
+    let improved: Vec<(WeaponType, MasteryTier)> = weapon_stats
+        .weapons
+        .iter()
+        .filter_map(|(weapon_type, tally)| Some((*weapon_type, tally.mastery()?)))
+        .filter(|(weapon_type, tier)| {
+            save_data
+                .codex
+                .weapon_mastery
+                .get(weapon_type)
+                .is_none_or(|best| tier > best)
+        })
+        .collect();
+
+    for (weapon_type, tier) in improved {
+        info!("Codex: {} reached {} mastery", weapon_type, tier);
+        save_data.codex.weapon_mastery.insert(weapon_type, tier);
+    }
+}
+
+// Upgrades offered from here on only add circles of patterns the codex has unlocked
+fn lock_patterns(save_data: Res<SaveData>, mut config: ResMut<WeaponUpgradeConfig>) {
+    config.locked_patterns = PatternType::iter()
+        .filter(|pattern| !save_data.codex.has_pattern(*pattern))
+        .collect();
+}
+
+// Bonus resolution step: runs once for every freshly spawned player
+fn apply_account_bonuses(
+    save_data: Res<SaveData>,
+    mut account_bonuses: ResMut<AccountBonuses>,
+    mut player_query: Query<(&mut DamageMultiplier, &mut CooldownReduction), Added<Player>>,
+) {
+    for (mut damage_multiplier, mut cooldown_reduction) in player_query.iter_mut() {
+        *account_bonuses = AccountBonuses::resolve(&save_data.codex);
+        info!("Applying account bonuses: {:?}", *account_bonuses);
+
+        damage_multiplier.factor += account_bonuses.damage;
+        cooldown_reduction.percent += account_bonuses.cooldown_reduction;
+    }
+}
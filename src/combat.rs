@@ -1,5 +1,7 @@
-use crate::components::Health;
-use crate::death::MarkedForDeath;
+use crate::components::{Armor, Health, Player};
+use crate::death::{KilledBy, MarkedForDeath};
+use crate::game_time::PausableTimer;
+use crate::materials::FlashEffect;
 use bevy::prelude::*;
 
 #[derive(Event)]
@@ -30,6 +32,29 @@ impl Default for DamageCooldown {
     }
 }
 
+/// Ignores all damage until the timer runs out, the sprite blinks meanwhile.
+/// Given to the player after a hit big enough that a follow-up could be unfair.
+#[derive(Component)]
+pub struct Invulnerable {
+    pub timer: PausableTimer,
+}
+
+impl Invulnerable {
+    pub fn new(duration: f32) -> Self {
+        Self {
+            timer: PausableTimer::from_seconds(duration, TimerMode::Once),
+        }
+    }
+}
+
+// Armor can't take a hit below this
+const MIN_DAMAGE: i32 = 1;
+// A single hit taking at least this share of maximum health grants i-frames
+const BIG_HIT_FRACTION: f32 = 0.15;
+const INVULNERABLE_DURATION: f32 = 1.0;
+// Sprite visibility toggles this many times per second while invulnerable
+const INVULNERABLE_BLINK_RATE: f32 = 12.0;
+
 impl Default for LastDamageTime {
     fn default() -> Self {
         Self {
@@ -43,15 +68,24 @@ pub fn handle_damage(
     time: Res<Time<Virtual>>,
     mut commands: Commands,
     mut damage_events: EventReader<DamageEvent>,
-    mut health_query: Query<&mut Health>,
+    mut health_query: Query<(&mut Health, Option<&Armor>, Has<Player>)>,
     mut cooldown_query: Query<&mut DamageCooldown>,
+    invulnerable_query: Query<(), With<Invulnerable>>,
 ) {
+    // Invulnerability granted this frame isn't inserted yet, later events need to see it too
+    let mut granted = Vec::new();
+
     for event in damage_events.read() {
         info!(
             "Processing damage event for {:?}, amount: {}",
             event.target, event.amount
         );
 
+        if invulnerable_query.contains(event.target) || granted.contains(&event.target) {
+            info!("{:?} is invulnerable, ignoring damage", event.target);
+            continue;
+        }
+
         let current_time = time.elapsed_secs();
 
         // Check for cooldown
@@ -80,23 +114,64 @@ pub fn handle_damage(
         }
 
         // Apply damage
-        if let Ok(mut health) = health_query.get_mut(event.target) {
+        if let Ok((mut health, armor, is_player)) = health_query.get_mut(event.target) {
+            let amount = match armor {
+                Some(armor) if event.amount > 0 => (event.amount - armor.0).max(MIN_DAMAGE),
+                _ => event.amount,
+            };
             let old_health = health.current;
-            health.current -= event.amount;
+            health.current -= amount;
             info!(
                 "Health changed from {} to {} for {:?}",
                 old_health, health.current, event.target
             );
 
+            commands.entity(event.target).try_insert(FlashEffect::hit());
+
+            if is_player && amount as f32 >= health.maximum as f32 * BIG_HIT_FRACTION {
+                info!("Big hit on {:?}, granting invulnerability", event.target);
+                commands
+                    .entity(event.target)
+                    .try_insert(Invulnerable::new(INVULNERABLE_DURATION));
+                granted.push(event.target);
+            }
+
             if health.current <= 0 {
                 info!(
                     "Marking {:?} for death at health {}",
                     event.target, health.current
                 );
                 commands.entity(event.target).insert(MarkedForDeath);
+                if is_player && old_health > 0 {
+                    commands.entity(event.target).insert(KilledBy(event.source));
+                }
             }
         } else {
             info!("No health component found for {:?}", event.target);
         }
     }
 }
+
+/// Counts down invulnerability and blinks the sprite until it's over
+pub fn tick_invulnerability(
+    mut commands: Commands,
+    time: Res<Time<Virtual>>,
+    mut query: Query<(Entity, &mut Invulnerable, &mut Visibility)>,
+) {
+    for (entity, mut invulnerable, mut visibility) in query.iter_mut() {
+        invulnerable.timer.tick(&time);
+
+        if invulnerable.timer.finished() {
+            *visibility = Visibility::Inherited;
+            commands.entity(entity).remove::<Invulnerable>();
+            continue;
+        }
+
+        let blink = (invulnerable.timer.elapsed_secs() * INVULNERABLE_BLINK_RATE) as u32;
+        *visibility = if blink.is_multiple_of(2) {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
@@ -1,4 +1,8 @@
+use crate::equipment::StatModifiers;
+use crate::types::EquipmentType;
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumIter;
 
 #[derive(Component)]
 pub struct Player {
@@ -7,10 +11,70 @@ pub struct Player {
     pub magnet_speed: f32,
 }
 
+/// Who the player is playing as
+#[derive(
+    Component, Debug, Clone, Copy, Eq, PartialEq, Default, EnumIter, Serialize, Deserialize,
+)]
+pub enum Character {
+    #[default]
+    Magus,
+}
+
+impl std::fmt::Display for Character {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Character::Magus => write!(f, "Magus"),
+        }
+    }
+}
+
 #[derive(Component)]
 pub struct Enemy {
+    pub kind: EnemyKind,
     pub speed: f32,
     pub experience_value: u32,
+    pub contact_damage: i32, // Per hit while touching the player
+    pub behavior: EnemyBehavior,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, EnumIter, Serialize, Deserialize)]
+pub enum EnemyKind {
+    Imp,
+    Wisp,
+    Swarmling,
+    Brute,
+    Hexer,
+}
+
+impl EnemyKind {
+    // Only two frames drawn so far, the newer kinds borrow them at a different size
+    pub fn sprite_index(&self) -> usize {
+        match self {
+            EnemyKind::Imp | EnemyKind::Swarmling => 0,
+            EnemyKind::Wisp | EnemyKind::Brute | EnemyKind::Hexer => 1,
+        }
+    }
+}
+
+impl std::fmt::Display for EnemyKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EnemyKind::Imp => write!(f, "Imp"),
+            EnemyKind::Wisp => write!(f, "Wisp"),
+            EnemyKind::Swarmling => write!(f, "Swarmling"),
+            EnemyKind::Brute => write!(f, "Brute"),
+            EnemyKind::Hexer => write!(f, "Hexer"),
+        }
+    }
+}
+
+/// How an enemy closes in on the player
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default, Serialize, Deserialize)]
+pub enum EnemyBehavior {
+    #[default]
+    Chase, // Straight at the player
+    Swarm,  // Weaves side to side on the way, so packs spread out
+    Ranged, // Hangs back at a distance and shoots at the player
 }
 
 #[derive(Component)]
@@ -19,6 +83,22 @@ pub struct Health {
     pub maximum: i32,
 }
 
+impl Health {
+    /// Heals without going over the maximum, returns how much was actually restored
+    pub fn heal(&mut self, amount: i32) -> i32 {
+        let new_health = (self.current + amount).min(self.maximum).max(self.current);
+        let healed = new_health - self.current;
+        self.current = new_health;
+        healed
+    }
+
+    /// Raises the maximum and heals the gained amount, so it isn't an empty bar to fill
+    pub fn raise_maximum(&mut self, amount: i32) {
+        self.maximum += amount;
+        self.heal(amount);
+    }
+}
+
 /// Player-specific components that affect weapons
 #[derive(Component)]
 pub struct CooldownReduction {
@@ -35,9 +115,91 @@ pub struct AreaMultiplier {
     pub factor: f32, // e.g., 1.2 for 120% area
 }
 
+/// Extra attacks every weapon spawns when it fires
+#[derive(Component, Default)]
+pub struct AmountBonus(pub u32);
+
+/// Flat damage taken off every hit, down to a floor of 1
+#[derive(Component, Default)]
+pub struct Armor(pub i32);
+
 #[derive(Component)]
 pub struct Luck(pub i32);
 
+#[derive(Component, Default)]
+pub struct Gold(pub u32);
+
+/// Passive items the player carries, one of each at its own level
+#[derive(Component, Default)]
+pub struct Equipment {
+    pub items: Vec<(EquipmentType, u32)>, // In the order they were picked up
+    pub applied: StatModifiers,           // Bonuses currently folded into the player's stats
+}
+
+impl Equipment {
+    pub fn has(&self, equipment_type: EquipmentType) -> bool {
+        self.level(equipment_type) > 0
+    }
+
+    // 0 when not carried
+    pub fn level(&self, equipment_type: EquipmentType) -> u32 {
+        self.items
+            .iter()
+            .find(|(item, _)| *item == equipment_type)
+            .map_or(0, |(_, level)| *level)
+    }
+
+    /// Picks up an item or levels up the one already carried, returns its new level
+    pub fn add(&mut self, equipment_type: EquipmentType) -> u32 {
+        match self
+            .items
+            .iter_mut()
+            .find(|(item, _)| *item == equipment_type)
+        {
+            Some((_, level)) => {
+                *level += 1;
+                *level
+            }
+            None => {
+                self.items.push((equipment_type, 1));
+                1
+            }
+        }
+    }
+}
+
+/// Last direction the player moved or aimed in, always normalized.
+/// Directional weapons and abilities read this rather than the raw input.
+#[derive(Component)]
+pub struct Facing {
+    pub direction: Vec2,
+    // Straight up or down keeps whichever side the sprite last faced
+    left: bool,
+}
+
+impl Facing {
+    pub fn new(direction: Vec2) -> Self {
+        let mut facing = Self {
+            direction,
+            left: false,
+        };
+        facing.face(direction);
+        facing
+    }
+
+    pub fn face(&mut self, direction: Vec2) {
+        self.direction = direction;
+        if direction.x != 0.0 {
+            self.left = direction.x < 0.0;
+        }
+    }
+
+    // Sprites are drawn facing right
+    pub fn is_left(&self) -> bool {
+        self.left
+    }
+}
+
 impl Default for CooldownReduction {
     fn default() -> Self {
         Self { percent: 0.0 }
@@ -56,8 +218,14 @@ impl Default for AreaMultiplier {
     }
 }
 
+impl Default for Facing {
+    fn default() -> Self {
+        Self::new(Vec2::X)
+    }
+}
+
 impl Default for Luck {
     fn default() -> Self {
         Self(20)
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,368 @@
+use crate::menu::{
+    spawn_menu_button, spawn_settings_menu, MenuAction, MenuActionComponent, MenuInput, MenuItem,
+    MenuRoot, MenuType,
+};
+use crate::resources::GameState;
+use bevy::ecs::system::SystemParam;
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+const KEY_BINDINGS_PATH: &str = "key_bindings.ron";
+
+/// Maps keyboard keys to game actions, so systems ask for an action instead of a key,
+/// and lets the controls page under Settings rebind them
+pub struct ControlsPlugin;
+
+impl Plugin for ControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(KeyBindings::load())
+            .init_resource::<Rebinding>()
+            // Before any Update system gets to see the key that was just bound
+            .add_systems(PreUpdate, capture_rebinding.after(InputSystem))
+            .add_systems(
+                Update,
+                (handle_controls_menu, update_controls_labels)
+                    .chain()
+                    .run_if(in_state(GameState::Settings)),
+            )
+            .add_systems(OnExit(GameState::Settings), cancel_rebinding);
+    }
+}
+
+/// Something the player does with the keyboard. Developer keys like the HUD preview
+/// aren't actions, they're not something a player needs to rebind.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, EnumIter, Serialize, Deserialize)]
+pub enum Action {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    Pause, // Also backs out of the settings screen
+    Interact,
+    WeaponStats,
+    QualityHint, // Takes up the lower quality preset when the game is running slowly
+    MenuUp,
+    MenuDown,
+    Confirm,
+}
+
+impl std::fmt::Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Action::MoveUp => write!(f, "Move Up"),
+            Action::MoveDown => write!(f, "Move Down"),
+            Action::MoveLeft => write!(f, "Move Left"),
+            Action::MoveRight => write!(f, "Move Right"),
+            Action::Pause => write!(f, "Pause"),
+            Action::Interact => write!(f, "Interact"),
+            Action::WeaponStats => write!(f, "Weapon Stats"),
+            Action::QualityHint => write!(f, "Lower Quality"),
+            Action::MenuUp => write!(f, "Menu Up"),
+            Action::MenuDown => write!(f, "Menu Down"),
+            Action::Confirm => write!(f, "Confirm"),
+        }
+    }
+}
+
+impl Action {
+    pub fn default_keys(&self) -> &'static [KeyCode] {
+        match self {
+            Action::MoveUp => &[KeyCode::KeyW],
+            Action::MoveDown => &[KeyCode::KeyS],
+            Action::MoveLeft => &[KeyCode::KeyA],
+            Action::MoveRight => &[KeyCode::KeyD],
+            Action::Pause => &[KeyCode::Escape],
+            Action::Interact => &[KeyCode::KeyE],
+            Action::WeaponStats => &[KeyCode::Tab],
+            Action::QualityHint => &[KeyCode::F10],
+            Action::MenuUp => &[KeyCode::ArrowUp, KeyCode::KeyW],
+            Action::MenuDown => &[KeyCode::ArrowDown, KeyCode::KeyS, KeyCode::Tab],
+            Action::Confirm => &[KeyCode::Enter, KeyCode::Space],
+        }
+    }
+
+    // Only read while a menu is open, so these can share keys with the gameplay actions
+    fn is_menu(&self) -> bool {
+        matches!(self, Action::MenuUp | Action::MenuDown | Action::Confirm)
+    }
+
+    /// Whether both actions are read at the same time, so they can't share a key
+    pub fn conflicts_with(&self, other: Action) -> bool {
+        *self != other
+            && (self.is_menu() == other.is_menu()
+                || *self == Action::Pause
+                || other == Action::Pause)
+    }
+}
+
+/// Short name for a key, e.g. `W` rather than `KeyW`
+pub fn key_name(key: KeyCode) -> String {
+    let name = format!("{:?}", key);
+    ["Key", "Digit", "Arrow"]
+        .into_iter()
+        .find_map(|prefix| name.strip_prefix(prefix))
+        .map_or_else(|| name.clone(), ToString::to_string)
+}
+
+/// The player's key for each rebound action, anything not rebound keeps its default keys
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct KeyBindings {
+    pub rebound: HashMap<Action, KeyCode>,
+}
+
+impl KeyBindings {
+    pub fn load() -> Self {
+        match fs::read_to_string(KEY_BINDINGS_PATH) {
+            Ok(contents) => ron::from_str(&contents).unwrap_or_else(|e| {
+                warn!(
+                    "Failed to parse {}, using default controls: {}",
+                    KEY_BINDINGS_PATH, e
+                );
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn save(&self) {
+        let contents = match ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default()) {
+            Ok(contents) => contents,
+            Err(e) => {
+                error!("Failed to serialize key bindings: {}", e);
+                return;
+            }
+        };
+
+        if let Err(e) = fs::write(KEY_BINDINGS_PATH, contents) {
+            error!("Failed to write {}: {}", KEY_BINDINGS_PATH, e);
+        }
+    }
+
+    /// Every key that triggers the action
+    pub fn keys(&self, action: Action) -> Vec<KeyCode> {
+        match self.rebound.get(&action) {
+            Some(key) => vec![*key],
+            None => action.default_keys().to_vec(),
+        }
+    }
+
+    /// The key shown for the action, and the one to press to trigger it
+    pub fn primary(&self, action: Action) -> KeyCode {
+        self.keys(action)[0]
+    }
+
+    /// Binds the action to the key. An action the key is taken from swaps over
+    /// to this action's old key, so no two conflicting actions share one.
+    pub fn bind(&mut self, action: Action, key: KeyCode) {
+        let previous = self.primary(action);
+        let displaced: Vec<Action> = Action::iter()
+            .filter(|other| action.conflicts_with(*other) && self.keys(*other).contains(&key))
+            .collect();
+        for other in displaced {
+            self.rebound.insert(other, previous);
+        }
+        self.rebound.insert(action, key);
+    }
+}
+
+/// Keyboard input read through the key bindings
+#[derive(SystemParam)]
+pub struct ActionInput<'w> {
+    keyboard: Res<'w, ButtonInput<KeyCode>>,
+    bindings: Res<'w, KeyBindings>,
+}
+
+impl ActionInput<'_> {
+    pub fn pressed(&self, action: Action) -> bool {
+        self.keyboard.any_pressed(self.bindings.keys(action))
+    }
+
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.keyboard.any_just_pressed(self.bindings.keys(action))
+    }
+
+    pub fn bindings(&self) -> &KeyBindings {
+        &self.bindings
+    }
+}
+
+/// The action waiting for its new key on the controls page
+#[derive(Resource, Default)]
+pub struct Rebinding(pub Option<Action>);
+
+/// What an action's button on the controls page reads
+pub fn controls_label(bindings: &KeyBindings, rebinding: &Rebinding, action: Action) -> String {
+    if rebinding.0 == Some(action) {
+        format!("{}: ...", action)
+    } else {
+        format!("{}: {}", action, key_name(bindings.primary(action)))
+    }
+}
+
+// The next key pressed becomes the binding, Escape leaves it as it was
+fn capture_rebinding(
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    mut rebinding: ResMut<Rebinding>,
+    mut bindings: ResMut<KeyBindings>,
+) {
+    let Some(action) = rebinding.0 else {
+        return;
+    };
+    let Some(key) = keyboard.get_just_pressed().next().copied() else {
+        return;
+    };
+
+    if key == KeyCode::Escape {
+        info!("Kept the key for {}", action);
+    } else {
+        info!("Bound {} to {}", action, key_name(key));
+        bindings.bind(action, key);
+        bindings.save();
+    }
+    keyboard.clear_just_pressed(key);
+    rebinding.0 = None;
+}
+
+fn cancel_rebinding(mut rebinding: ResMut<Rebinding>) {
+    rebinding.0 = None;
+}
+
+fn handle_controls_menu(
+    mut commands: Commands,
+    menu_input: MenuInput,
+    menu_items: Query<(&MenuItem, &MenuActionComponent, Ref<Interaction>)>,
+    menu_roots: Query<Entity, With<MenuRoot>>,
+    mut bindings: ResMut<KeyBindings>,
+    mut rebinding: ResMut<Rebinding>,
+) {
+    let controls_items = menu_items.iter().filter(|(_, action_component, _)| {
+        matches!(
+            action_component.action,
+            MenuAction::OpenControls
+                | MenuAction::RebindAction(_)
+                | MenuAction::ResetControls
+                | MenuAction::CloseControls
+        )
+    });
+    let Some(action) = menu_input.confirmed(controls_items) else {
+        return;
+    };
+
+    // Picked up by `capture_rebinding` with the next key
+    if let MenuAction::RebindAction(action) = action {
+        rebinding.0 = Some(*action);
+        return;
+    }
+
+    for entity in menu_roots.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    match action {
+        MenuAction::CloseControls => commands.run_system_cached(spawn_settings_menu),
+        MenuAction::ResetControls => {
+            info!("Reset controls to their defaults");
+            *bindings = KeyBindings::default();
+            bindings.save();
+            spawn_controls_menu(&mut commands, &bindings);
+        }
+        _ => spawn_controls_menu(&mut commands, &bindings),
+    }
+}
+
+fn update_controls_labels(
+    bindings: Res<KeyBindings>,
+    rebinding: Res<Rebinding>,
+    menu_items: Query<(&MenuActionComponent, &Children)>,
+    mut button_text: Query<&mut Text>,
+) {
+    if !bindings.is_changed() && !rebinding.is_changed() {
+        return;
+    }
+
+    for (action_component, children) in menu_items.iter() {
+        let MenuAction::RebindAction(action) = action_component.action else {
+            continue;
+        };
+        if let Some(mut text) = children
+            .first()
+            .and_then(|&child| button_text.get_mut(child).ok())
+        {
+            text.0 = controls_label(&bindings, &rebinding, action);
+        }
+    }
+}
+
+fn spawn_controls_menu(commands: &mut Commands, bindings: &KeyBindings) {
+    let rebinding = Rebinding::default();
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(20.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+            MenuRoot {
+                menu_type: MenuType::Controls,
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("Controls"),
+                TextFont {
+                    font_size: 36.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+            parent.spawn((
+                Text::new("Confirm an action, then press its new key. Escape keeps the old one."),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+            ));
+
+            // Too many actions for one column, every button shares the grid so focus runs in order
+            parent
+                .spawn((
+                    Node {
+                        flex_direction: FlexDirection::Row,
+                        flex_wrap: FlexWrap::Wrap,
+                        justify_content: JustifyContent::Center,
+                        max_width: Val::Px(680.0),
+                        row_gap: Val::Px(10.0),
+                        column_gap: Val::Px(10.0),
+                        padding: UiRect::all(Val::Px(20.0)),
+                        border: UiRect::all(Val::Px(2.0)),
+                        ..default()
+                    },
+                    BorderColor(Color::srgb(0.7, 0.7, 0.7)),
+                    BackgroundColor(Color::srgb(0.1, 0.1, 0.1)),
+                ))
+                .with_children(|parent| {
+                    for (index, action) in Action::iter().enumerate() {
+                        spawn_menu_button(
+                            parent,
+                            &controls_label(bindings, &rebinding, action),
+                            MenuAction::RebindAction(action),
+                            index == 0,
+                        );
+                    }
+                    spawn_menu_button(parent, "Reset", MenuAction::ResetControls, false);
+                    spawn_menu_button(parent, "Back", MenuAction::CloseControls, false);
+                });
+        });
+}
@@ -1,5 +1,9 @@
 use crate::components::{Enemy, Health, Player};
+use crate::difficulty::ExperienceScale;
+use crate::elite::Elite;
+use crate::enemy_pool::EnemyPool;
 use crate::events::EntityDeathEvent;
+use crate::kill_cam::KillCam;
 use crate::resources::{GameState, GameStats};
 use bevy::prelude::*;
 
@@ -9,30 +13,47 @@ pub struct MarkedForDeath;
 #[derive(Component)]
 pub struct MarkedForDespawn;
 
+/// What dealt the player's fatal hit, if it came from anything
+#[derive(Component)]
+pub struct KilledBy(pub Option<Entity>);
+
 pub fn death_system(
     mut commands: Commands,
     mut game_stats: ResMut<GameStats>,
-    player_query: Query<(Entity, &Health), With<Player>>,
-    marked_entities: Query<(Entity, Option<&Transform>, Option<&Enemy>), With<MarkedForDeath>>,
+    experience_scale: Res<ExperienceScale>,
+    player_query: Query<(Entity, &Health, &Transform, Option<&KilledBy>), With<Player>>,
+    marked_entities: Query<
+        (Entity, Option<&Transform>, Option<&Enemy>, Has<Elite>),
+        With<MarkedForDeath>,
+    >,
     mut death_events: EventWriter<EntityDeathEvent>,
     mut next_state: ResMut<NextState<GameState>>,
+    kill_cam: Option<ResMut<KillCam>>,
 ) {
     // Check player death first
-    if let Ok((entity, health)) = player_query.get_single() {
+    if let Ok((entity, health, transform, killed_by)) = player_query.get_single() {
         if health.current <= 0 {
             commands.entity(entity).insert(MarkedForDespawn);
             death_events.send(EntityDeathEvent {
                 entity,
                 position: Vec2::ZERO, // Player position if needed
                 exp_value: None,
+                elite: false,
             });
-            next_state.set(GameState::GameOver);
+            // The kill cam brings up the results screen once it's done
+            match kill_cam {
+                Some(mut kill_cam) => kill_cam.start(
+                    killed_by.and_then(|killed_by| killed_by.0),
+                    transform.translation.truncate(),
+                ),
+                None => next_state.set(GameState::GameOver),
+            }
             return;
         }
     }
 
     // Handle marked entities
-    for (entity, transform, enemy) in marked_entities.iter() {
+    for (entity, transform, enemy, elite) in marked_entities.iter() {
         if let Some(_enemy) = enemy {
             game_stats.enemies_killed += 1;
         }
@@ -41,7 +62,8 @@ pub fn death_system(
         death_events.send(EntityDeathEvent {
             entity,
             position: transform.map_or(Vec2::ZERO, |t| t.translation.truncate()),
-            exp_value: enemy.map(|e| e.experience_value * 66),
+            exp_value: enemy.map(|e| experience_scale.apply(e.experience_value * 66)),
+            elite,
         });
 
         // Mark for despawn after death processing
@@ -51,9 +73,16 @@ pub fn death_system(
 
 pub fn cleanup_marked_entities(
     mut commands: Commands,
-    query: Query<Entity, With<MarkedForDespawn>>,
+    time: Res<Time<Virtual>>,
+    mut enemy_pool: ResMut<EnemyPool>,
+    query: Query<(Entity, Has<Enemy>), With<MarkedForDespawn>>,
 ) {
-    for entity in query.iter() {
-        commands.entity(entity).despawn_recursive();
+    for (entity, enemy) in query.iter() {
+        // Dead enemies go back in the pool while there's room for them
+        if enemy && !enemy_pool.is_full() {
+            enemy_pool.release(&mut commands, entity, time.elapsed_secs());
+        } else {
+            commands.entity(entity).despawn_recursive();
+        }
     }
 }
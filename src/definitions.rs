@@ -0,0 +1,529 @@
+use crate::balance::BalanceOverride;
+use crate::components::{EnemyBehavior, EnemyKind};
+use crate::difficulty::DifficultyScaling;
+use crate::director::SpawnCaps;
+use crate::game_time::PausableTimer;
+use crate::mods::{self, MODS_ASSET_SOURCE};
+use crate::physics::ENEMY_RADIUS;
+use crate::resources::WaveConfig;
+use crate::waves::{BracketedWaves, DirectorStrategy, WaveDefinition, WaveDirector, WaveEnemy};
+use crate::weapons::{WeaponArea, WeaponCooldown, WeaponDamage, WeaponMeta, WeaponType};
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use bevy_rapier2d::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub struct DefinitionsPlugin;
+
+impl Plugin for DefinitionsPlugin {
+    fn build(&self, app: &mut App) {
+        let mut definitions = mods::load_definitions();
+        if let Some(overrides) = BalanceOverride::load() {
+            overrides.apply(&mut definitions);
+        }
+
+        app.insert_resource(definitions)
+            .add_systems(Startup, (load_definition_sprites, apply_active_stage))
+            .add_systems(Update, apply_weapon_definitions);
+    }
+}
+
+/// Stats for a kind of enemy the spawner can pick
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EnemyDefinition {
+    pub name: String,
+    pub kind: EnemyKind, // What it counts as for the codex, and its atlas frame if no sprite is given
+    pub speed: f32,
+    pub health: i32,
+    pub experience_value: u32,
+    #[serde(default = "EnemyDefinition::default_contact_damage")]
+    pub contact_damage: i32,
+    #[serde(default = "EnemyDefinition::default_radius")]
+    pub radius: f32, // The sprite and collider are scaled to match
+    #[serde(default)]
+    pub behavior: EnemyBehavior,
+    #[serde(default = "EnemyDefinition::default_cost")]
+    pub cost: u32, // Wave budget points spent on each one
+    #[serde(default)]
+    pub sprite: Option<String>, // Image path, relative to the mod's folder
+    #[serde(skip)]
+    pub sprite_handle: Option<Handle<Image>>,
+}
+
+impl EnemyDefinition {
+    fn default_contact_damage() -> i32 {
+        1
+    }
+
+    fn default_radius() -> f32 {
+        ENEMY_RADIUS
+    }
+
+    fn default_cost() -> u32 {
+        1
+    }
+}
+
+/// Base stat overrides for one of the built-in weapons
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WeaponDefinition {
+    pub weapon: WeaponType,
+    pub cooldown: Option<f32>,
+    pub damage: Option<i32>,
+    pub area: Option<f32>,
+}
+
+/// Spawner pacing, enemy mix and goals for a run.
+/// A stage without a run length or kill goal is endless, and one without bounds is open in every direction.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StageDefinition {
+    pub name: String,
+    pub max_enemies: u32,
+    pub waves: Vec<WaveDefinition>, // Played in order, the last one repeats
+    #[serde(default)]
+    pub run_length: Option<f32>, // Seconds
+    #[serde(default)]
+    pub kill_goal: Option<u32>,
+    #[serde(default)]
+    pub bosses: Vec<BossSpawn>,
+    #[serde(default)]
+    pub surges: Vec<SurgeEvent>,
+    #[serde(default)]
+    pub difficulty: DifficultyScaling,
+    #[serde(default)]
+    pub spawn_caps: SpawnCaps,
+    #[serde(default)]
+    pub bounds: Option<(f32, f32)>, // Arena width and height, walled in around the origin
+    #[serde(default)]
+    pub strategy: DirectorStrategy,
+}
+
+/// A tougher copy of an enemy that shows up at a set time
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BossSpawn {
+    pub time: f32,     // Seconds into the run
+    pub enemy: String, // Enemy definition id
+    #[serde(default = "BossSpawn::default_health_multiplier")]
+    pub health_multiplier: f32,
+}
+
+/// A crowd of fast, weak enemies that appears around the player all at once and closes in
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SurgeEvent {
+    pub time: f32,     // Seconds into the run
+    pub enemy: String, // Enemy definition id
+    pub count: u32,
+    #[serde(default)]
+    pub formation: SurgeFormation,
+    #[serde(default = "SurgeEvent::default_speed_multiplier")]
+    pub speed_multiplier: f32,
+}
+
+impl SurgeEvent {
+    fn default_speed_multiplier() -> f32 {
+        1.8
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SurgeFormation {
+    #[default]
+    Ring, // Surrounds the player
+    Wall, // A line sweeping in from one side
+}
+
+/// Walled-in area of a bounded stage, absent when the stage is open.
+/// The player is kept inside it and the camera never shows past it.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct StageBounds(pub Rect);
+
+/// One of the colliders walling in a bounded stage, they last for the whole session
+#[derive(Component)]
+pub struct StageWall;
+
+const WALL_THICKNESS: f32 = 32.0;
+const WALL_COLOR: Color = Color::srgb(0.25, 0.22, 0.3);
+
+impl BossSpawn {
+    fn default_health_multiplier() -> f32 {
+        10.0
+    }
+}
+
+/// Every enemy, weapon and stage definition known to the game, keyed by id.
+/// Built-ins are registered first and mods are merged on top.
+#[derive(Resource, Debug)]
+pub struct Definitions {
+    pub enemies: HashMap<String, EnemyDefinition>,
+    pub weapons: HashMap<String, WeaponDefinition>,
+    pub stages: HashMap<String, StageDefinition>,
+    pub active_stage: String,
+}
+
+impl Definitions {
+    pub fn builtin() -> Self {
+        let mut enemies = HashMap::new();
+        // A steady baseline, then fast chasers, swarms and tanks as the run goes on
+        enemies.insert(
+            "imp".to_string(),
+            EnemyDefinition {
+                name: "Imp".to_string(),
+                kind: EnemyKind::Imp,
+                speed: 100.0,
+                health: 20,
+                experience_value: 50,
+                contact_damage: 1,
+                radius: ENEMY_RADIUS,
+                behavior: EnemyBehavior::Chase,
+                cost: 2,
+                sprite: None,
+                sprite_handle: None,
+            },
+        );
+        enemies.insert(
+            "swarmling".to_string(),
+            EnemyDefinition {
+                name: "Swarmling".to_string(),
+                kind: EnemyKind::Swarmling,
+                speed: 120.0,
+                health: 8,
+                experience_value: 20,
+                contact_damage: 1,
+                radius: 8.0,
+                behavior: EnemyBehavior::Swarm,
+                cost: 1,
+                sprite: None,
+                sprite_handle: None,
+            },
+        );
+        enemies.insert(
+            "wisp".to_string(),
+            EnemyDefinition {
+                name: "Wisp".to_string(),
+                kind: EnemyKind::Wisp,
+                speed: 170.0,
+                health: 14,
+                experience_value: 60,
+                contact_damage: 1,
+                radius: 10.0,
+                behavior: EnemyBehavior::Chase,
+                cost: 3,
+                sprite: None,
+                sprite_handle: None,
+            },
+        );
+        enemies.insert(
+            "brute".to_string(),
+            EnemyDefinition {
+                name: "Brute".to_string(),
+                kind: EnemyKind::Brute,
+                speed: 60.0,
+                health: 90,
+                experience_value: 200,
+                contact_damage: 3,
+                radius: 20.0,
+                behavior: EnemyBehavior::Chase,
+                cost: 6,
+                sprite: None,
+                sprite_handle: None,
+            },
+        );
+        // Keeps its distance, so its contact damage is what each of its bolts deals
+        enemies.insert(
+            "hexer".to_string(),
+            EnemyDefinition {
+                name: "Hexer".to_string(),
+                kind: EnemyKind::Hexer,
+                speed: 90.0,
+                health: 16,
+                experience_value: 80,
+                contact_damage: 4,
+                radius: 11.0,
+                behavior: EnemyBehavior::Ranged,
+                cost: 4,
+                sprite: None,
+                sprite_handle: None,
+            },
+        );
+
+        let mut stages = HashMap::new();
+        stages.insert(
+            "default".to_string(),
+            StageDefinition {
+                name: "Default".to_string(),
+                max_enemies: 20,
+                waves: vec![
+                    wave(45.0, 45, &[("imp", 1.0)]),
+                    wave(45.0, 60, &[("imp", 3.0), ("swarmling", 4.0)]),
+                    wave(30.0, 70, &[("imp", 3.0), ("swarmling", 4.0), ("wisp", 2.0)]),
+                    wave(
+                        60.0,
+                        150,
+                        &[
+                            ("imp", 3.0),
+                            ("swarmling", 4.0),
+                            ("wisp", 2.0),
+                            ("hexer", 1.5),
+                        ],
+                    ),
+                    wave(
+                        120.0,
+                        320,
+                        &[
+                            ("imp", 3.0),
+                            ("swarmling", 4.0),
+                            ("wisp", 2.0),
+                            ("hexer", 1.5),
+                            ("brute", 1.0),
+                        ],
+                    ),
+                ],
+                run_length: Some(900.0),
+                kill_goal: None,
+                bosses: vec![
+                    BossSpawn {
+                        time: 300.0,
+                        enemy: "imp".to_string(),
+                        health_multiplier: 10.0,
+                    },
+                    BossSpawn {
+                        time: 600.0,
+                        enemy: "wisp".to_string(),
+                        health_multiplier: 20.0,
+                    },
+                ],
+                surges: vec![
+                    SurgeEvent {
+                        time: 150.0,
+                        enemy: "swarmling".to_string(),
+                        count: 24,
+                        formation: SurgeFormation::Ring,
+                        speed_multiplier: 1.8,
+                    },
+                    SurgeEvent {
+                        time: 450.0,
+                        enemy: "swarmling".to_string(),
+                        count: 20,
+                        formation: SurgeFormation::Wall,
+                        speed_multiplier: 2.0,
+                    },
+                    SurgeEvent {
+                        time: 750.0,
+                        enemy: "imp".to_string(),
+                        count: 36,
+                        formation: SurgeFormation::Ring,
+                        speed_multiplier: 1.8,
+                    },
+                ],
+                difficulty: DifficultyScaling::default(),
+                spawn_caps: SpawnCaps::default(),
+                bounds: None,
+                strategy: DirectorStrategy::Continuous,
+            },
+        );
+        // The same waves one at a time, without the timed events on top
+        let gauntlet = StageDefinition {
+            name: "Gauntlet".to_string(),
+            bosses: Vec::new(),
+            surges: Vec::new(),
+            strategy: DirectorStrategy::Bracketed(BracketedWaves {
+                intermission: 8.0,
+                gold_reward: 10,
+            }),
+            ..stages["default"].clone()
+        };
+        stages.insert("gauntlet".to_string(), gauntlet);
+
+        Self {
+            enemies,
+            // Weapons keep the stats they're spawned with unless something overrides them
+            weapons: HashMap::new(),
+            stages,
+            active_stage: Self::stage_from_args().unwrap_or_else(|| "default".to_string()),
+        }
+    }
+
+    // Like mutators, stages are picked from the command line until there's a menu for it
+    fn stage_from_args() -> Option<String> {
+        let mut args = std::env::args();
+        args.find(|arg| arg == "--stage")?;
+        args.next()
+    }
+
+    pub fn stage(&self) -> Option<&StageDefinition> {
+        self.stages.get(&self.active_stage)
+    }
+}
+
+fn wave(duration: f32, budget: u32, enemies: &[(&str, f32)]) -> WaveDefinition {
+    WaveDefinition {
+        duration,
+        budget,
+        enemies: enemies
+            .iter()
+            .map(|(enemy, weight)| WaveEnemy {
+                enemy: enemy.to_string(),
+                weight: *weight,
+            })
+            .collect(),
+    }
+}
+
+fn load_definition_sprites(mut definitions: ResMut<Definitions>, asset_server: Res<AssetServer>) {
+    for definition in definitions.enemies.values_mut() {
+        if let Some(path) = &definition.sprite {
+            definition.sprite_handle =
+                Some(asset_server.load(format!("{}://{}", MODS_ASSET_SOURCE, path)));
+        }
+    }
+}
+
+fn apply_active_stage(
+    mut commands: Commands,
+    definitions: Res<Definitions>,
+    mut wave_config: ResMut<WaveConfig>,
+) {
+    let Some(stage) = definitions.stage() else {
+        warn!(
+            "Unknown stage '{}', keeping default spawning",
+            definitions.active_stage
+        );
+        return;
+    };
+
+    info!("Playing stage: {}", stage.name);
+    wave_config.max_enemies = stage.max_enemies;
+    wave_config.run_length = stage.run_length;
+    wave_config.kill_goal = stage.kill_goal;
+
+    let mut bosses = stage.bosses.clone();
+    bosses.sort_by(|a, b| a.time.total_cmp(&b.time));
+    for boss in &bosses {
+        if !definitions.enemies.contains_key(&boss.enemy) {
+            warn!(
+                "Stage '{}' schedules unknown boss enemy '{}'",
+                definitions.active_stage, boss.enemy
+            );
+        }
+    }
+    wave_config.bosses = bosses;
+
+    let mut surges = stage.surges.clone();
+    surges.sort_by(|a, b| a.time.total_cmp(&b.time));
+    for surge in &surges {
+        if !definitions.enemies.contains_key(&surge.enemy) {
+            warn!(
+                "Stage '{}' schedules a surge of unknown enemy '{}'",
+                definitions.active_stage, surge.enemy
+            );
+        }
+    }
+    wave_config.surges = surges;
+
+    // A wave that never ends or never spends anything would stall the stage
+    let waves: Vec<WaveDefinition> = stage
+        .waves
+        .iter()
+        .filter(|wave| wave.duration > 0.0)
+        .cloned()
+        .collect();
+    if waves.len() < stage.waves.len() {
+        warn!(
+            "Stage '{}' has waves without a duration, skipping them",
+            definitions.active_stage
+        );
+    }
+    for enemy in waves.iter().flat_map(|wave| &wave.enemies) {
+        if !definitions.enemies.contains_key(&enemy.enemy) {
+            warn!(
+                "Stage '{}' has a wave with unknown enemy '{}'",
+                definitions.active_stage, enemy.enemy
+            );
+        }
+    }
+    if waves.is_empty() {
+        warn!(
+            "Stage '{}' has no waves, nothing will spawn",
+            definitions.active_stage
+        );
+    }
+    commands.insert_resource(WaveDirector::new(waves).with_strategy(stage.strategy));
+    commands.insert_resource(stage.difficulty.clone());
+    commands.insert_resource(stage.spawn_caps.clone());
+
+    if let Some((width, height)) = stage.bounds {
+        let bounds = Rect::from_center_size(Vec2::ZERO, Vec2::new(width, height));
+        info!("Stage is walled in to {}x{}", width, height);
+        spawn_stage_walls(&mut commands, bounds);
+        commands.insert_resource(StageBounds(bounds));
+    }
+}
+
+/// Fixed colliders just outside the bounds, so enemies pile up against them
+/// and spawn validation keeps new ones off the walls
+fn spawn_stage_walls(commands: &mut Commands, bounds: Rect) {
+    let half_thickness = WALL_THICKNESS * 0.5;
+    let size = bounds.size();
+    let walls = [
+        (
+            Vec2::new(0.0, bounds.max.y + half_thickness),
+            Vec2::new(size.x + WALL_THICKNESS * 2.0, WALL_THICKNESS),
+        ),
+        (
+            Vec2::new(0.0, bounds.min.y - half_thickness),
+            Vec2::new(size.x + WALL_THICKNESS * 2.0, WALL_THICKNESS),
+        ),
+        (
+            Vec2::new(bounds.min.x - half_thickness, 0.0),
+            Vec2::new(WALL_THICKNESS, size.y),
+        ),
+        (
+            Vec2::new(bounds.max.x + half_thickness, 0.0),
+            Vec2::new(WALL_THICKNESS, size.y),
+        ),
+    ];
+
+    for (center, wall_size) in walls {
+        commands.spawn((
+            StageWall,
+            Sprite::from_color(WALL_COLOR, wall_size),
+            Transform::from_translation(center.extend(0.0)),
+            RigidBody::Fixed,
+            Collider::cuboid(wall_size.x * 0.5, wall_size.y * 0.5),
+        ));
+    }
+}
+
+fn apply_weapon_definitions(
+    definitions: Res<Definitions>,
+    mut weapon_query: Query<
+        (
+            &WeaponMeta,
+            &mut WeaponCooldown,
+            &mut WeaponDamage,
+            Option<&mut WeaponArea>,
+        ),
+        Added<WeaponMeta>,
+    >,
+) {
+    for (meta, mut cooldown, mut damage, area) in weapon_query.iter_mut() {
+        let Some(definition) = definitions
+            .weapons
+            .values()
+            .find(|definition| definition.weapon == meta.weapon_type)
+        else {
+            continue;
+        };
+
+        if let Some(seconds) = definition.cooldown {
+            cooldown.base_duration = seconds;
+            cooldown.timer = PausableTimer::from_seconds(seconds, TimerMode::Repeating);
+        }
+        if let Some(amount) = definition.damage {
+            damage.base_amount = amount;
+        }
+        if let (Some(radius), Some(mut area)) = (definition.area, area) {
+            area.base_radius = radius;
+        }
+    }
+}
@@ -0,0 +1,309 @@
+use crate::menu::WeaponUpgradeConfirmedEvent;
+use crate::resources::GameState;
+use crate::weapons::arcane_bolt::ArcaneBolt;
+use crate::weapons::arcane_storm::ArcaneStorm;
+use crate::weapons::boomerang::Boomerang;
+use crate::weapons::lance::Lance;
+use crate::weapons::magick_circle::MagickCircle;
+use crate::weapons::orbitals::Orbitals;
+use crate::weapons::projectile::{Bounces, Homing, Pierce};
+use crate::weapons::weapon_upgrade::{WeaponUpgradeConfig, WeaponUpgradeSpec};
+use crate::weapons::{
+    Amount, Knockback, WeaponArea, WeaponCooldown, WeaponDamage, WeaponDuration, WeaponMeta,
+    WeaponType,
+};
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+/// Developer-only panels for trying things out on a live run
+pub struct DevToolsPlugin;
+
+impl Plugin for DevToolsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<UpgradeSandbox>().add_systems(
+            Update,
+            (
+                toggle_upgrade_sandbox,
+                drive_upgrade_sandbox,
+                update_upgrade_sandbox_panel,
+            )
+                .chain()
+                .run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+const SANDBOX_KEY: KeyCode = KeyCode::F8;
+const NEXT_WEAPON_KEY: KeyCode = KeyCode::Backslash;
+const PREVIOUS_SPEC_KEY: KeyCode = KeyCode::BracketLeft;
+const NEXT_SPEC_KEY: KeyCode = KeyCode::BracketRight;
+const APPLY_KEY: KeyCode = KeyCode::Equal;
+const REVERT_KEY: KeyCode = KeyCode::Minus;
+
+/// Puts one component back the way it was when the snapshot was taken
+type Restore = Box<dyn FnOnce(&mut EntityWorldMut) + Send + Sync>;
+
+/// Upgrade preview sandbox, toggled with F8. Applies any upgrade from a weapon's tables
+/// to the live weapon, and puts the weapon back the way it was before the first preview.
+#[derive(Resource, Default)]
+pub struct UpgradeSandbox {
+    pub open: bool,
+    pub weapon_index: usize,
+    pub spec_index: usize,
+    snapshots: HashMap<Entity, Vec<Restore>>,
+}
+
+impl UpgradeSandbox {
+    pub fn is_previewing(&self, weapon: Entity) -> bool {
+        self.snapshots.contains_key(&weapon)
+    }
+}
+
+/// Marks the sandbox panel's text
+#[derive(Component)]
+pub struct UpgradeSandboxPanel;
+
+/// Every upgrade in a weapon's tables with a label for the panel, regardless of the
+/// weapon's level or which patterns are unlocked
+pub fn sandbox_specs(
+    config: &WeaponUpgradeConfig,
+    weapon_type: WeaponType,
+) -> Vec<(String, WeaponUpgradeSpec)> {
+    let Some(data) = config.data.get(&weapon_type) else {
+        return Vec::new();
+    };
+
+    // progression[0] takes a weapon from level 1 to 2
+    let progression = data
+        .progression
+        .iter()
+        .enumerate()
+        .map(|(index, spec)| (format!("Lv {}", index + 2), spec.clone()));
+    let limit_breaks = data
+        .limit_breaks
+        .iter()
+        .enumerate()
+        .map(|(index, spec)| (format!("Limit break {}", index + 1), spec.clone()));
+    progression.chain(limit_breaks).collect()
+}
+
+fn keep<T: Component + Clone>(entity: EntityRef) -> Restore {
+    match entity.get::<T>().cloned() {
+        Some(component) => Box::new(move |entity| {
+            entity.insert(component);
+        }),
+        None => Box::new(|entity| {
+            entity.remove::<T>();
+        }),
+    }
+}
+
+/// Remembers everything an upgrade can change on the weapon, unless an earlier preview
+/// already did, so reverting always goes back to before the first one
+pub fn snapshot_weapon(world: &mut World, weapon: Entity) {
+    if world.resource::<UpgradeSandbox>().is_previewing(weapon) {
+        return;
+    }
+    let Ok(entity) = world.get_entity(weapon) else {
+        return;
+    };
+
+    let restores = vec![
+        keep::<WeaponMeta>(entity),
+        keep::<WeaponDamage>(entity),
+        keep::<WeaponArea>(entity),
+        keep::<WeaponCooldown>(entity),
+        keep::<WeaponDuration>(entity),
+        keep::<Bounces>(entity),
+        keep::<Pierce>(entity),
+        keep::<Homing>(entity),
+        keep::<Knockback>(entity),
+        keep::<Amount>(entity),
+        keep::<MagickCircle>(entity),
+        keep::<ArcaneBolt>(entity),
+        keep::<Orbitals>(entity),
+        keep::<Lance>(entity),
+        keep::<Boomerang>(entity),
+        keep::<ArcaneStorm>(entity),
+    ];
+    world
+        .resource_mut::<UpgradeSandbox>()
+        .snapshots
+        .insert(weapon, restores);
+}
+
+/// Puts the weapon back to its snapshot, false when it wasn't being previewed.
+/// Attacks already out in the world keep whatever the preview gave them until they expire.
+pub fn revert_weapon(world: &mut World, weapon: Entity) -> bool {
+    let Some(restores) = world
+        .resource_mut::<UpgradeSandbox>()
+        .snapshots
+        .remove(&weapon)
+    else {
+        return false;
+    };
+    let Ok(mut entity) = world.get_entity_mut(weapon) else {
+        return false;
+    };
+
+    for restore in restores {
+        restore(&mut entity);
+    }
+    true
+}
+
+fn toggle_upgrade_sandbox(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut sandbox: ResMut<UpgradeSandbox>,
+    panel_query: Query<Entity, With<UpgradeSandboxPanel>>,
+) {
+    if !keyboard.just_pressed(SANDBOX_KEY) {
+        return;
+    }
+
+    sandbox.open = !sandbox.open;
+    if !sandbox.open {
+        for entity in panel_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    }
+
+    commands.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: 16.0,
+            ..default()
+        },
+        TextColor(Color::srgb(0.6, 1.0, 0.6)),
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.75)),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.0),
+            right: Val::Px(10.0),
+            max_width: Val::Px(420.0),
+            padding: UiRect::all(Val::Px(8.0)),
+            ..default()
+        },
+        GlobalZIndex(50),
+        UpgradeSandboxPanel,
+    ));
+}
+
+/// The weapons the sandbox can pick from, in the order they were spawned
+fn sandbox_weapons(weapon_query: &Query<(Entity, &WeaponMeta)>) -> Vec<(Entity, WeaponMeta)> {
+    let mut weapons: Vec<(Entity, WeaponMeta)> = weapon_query
+        .iter()
+        .map(|(entity, meta)| (entity, meta.clone()))
+        .collect();
+    weapons.sort_by_key(|(entity, _)| *entity);
+    weapons
+}
+
+fn drive_upgrade_sandbox(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    config: Res<WeaponUpgradeConfig>,
+    mut sandbox: ResMut<UpgradeSandbox>,
+    weapon_query: Query<(Entity, &WeaponMeta)>,
+) {
+    // Snapshots of weapons that are gone, e.g. after an evolution or a restart
+    sandbox
+        .snapshots
+        .retain(|weapon, _| weapon_query.contains(*weapon));
+    if !sandbox.open {
+        return;
+    }
+
+    let weapons = sandbox_weapons(&weapon_query);
+    if sandbox.weapon_index >= weapons.len() {
+        sandbox.weapon_index = 0;
+    }
+    if keyboard.just_pressed(NEXT_WEAPON_KEY) && !weapons.is_empty() {
+        sandbox.weapon_index = (sandbox.weapon_index + 1) % weapons.len();
+        sandbox.spec_index = 0;
+    }
+    let Some((weapon, meta)) = weapons.get(sandbox.weapon_index).cloned() else {
+        return;
+    };
+
+    let specs = sandbox_specs(&config, meta.weapon_type);
+    if specs.is_empty() {
+        return;
+    }
+    if keyboard.just_pressed(NEXT_SPEC_KEY) {
+        sandbox.spec_index = (sandbox.spec_index + 1) % specs.len();
+    }
+    if keyboard.just_pressed(PREVIOUS_SPEC_KEY) {
+        sandbox.spec_index = (sandbox.spec_index + specs.len() - 1) % specs.len();
+    }
+    sandbox.spec_index = sandbox.spec_index.min(specs.len() - 1);
+
+    if keyboard.just_pressed(APPLY_KEY) {
+        let (label, spec) = specs[sandbox.spec_index].clone();
+        info!(
+            "Previewing {} upgrade {} on {}: {}",
+            meta.weapon_type, label, weapon, spec
+        );
+        // Goes through the same event as a level-up pick, so it also heals and shows up
+        // in the run history like one
+        commands.queue(move |world: &mut World| {
+            snapshot_weapon(world, weapon);
+            world.send_event(WeaponUpgradeConfirmedEvent {
+                weapon_type: meta.weapon_type,
+                upgrade_spec: spec,
+            });
+        });
+    }
+    if keyboard.just_pressed(REVERT_KEY) {
+        commands.queue(move |world: &mut World| {
+            if revert_weapon(world, weapon) {
+                info!("Reverted upgrade preview on {}", meta.weapon_type);
+            } else {
+                info!("No upgrade preview to revert on {}", meta.weapon_type);
+            }
+        });
+    }
+}
+
+fn update_upgrade_sandbox_panel(
+    config: Res<WeaponUpgradeConfig>,
+    sandbox: Res<UpgradeSandbox>,
+    weapon_query: Query<(Entity, &WeaponMeta)>,
+    mut panel_query: Query<&mut Text, With<UpgradeSandboxPanel>>,
+) {
+    let Ok(mut text) = panel_query.get_single_mut() else {
+        return;
+    };
+
+    let weapons = sandbox_weapons(&weapon_query);
+    let Some((weapon, meta)) = weapons.get(sandbox.weapon_index) else {
+        text.0 = "Upgrade sandbox: no weapons".to_string();
+        return;
+    };
+
+    let specs = sandbox_specs(&config, meta.weapon_type);
+    let spec = specs
+        .get(sandbox.spec_index)
+        .map_or("no upgrades".to_string(), |(label, spec)| {
+            format!("{}: {}", label, spec)
+        });
+    let preview = if sandbox.is_previewing(*weapon) {
+        "previewing, revert to undo"
+    } else {
+        "unchanged"
+    };
+
+    text.0 = format!(
+        "Upgrade sandbox\n{} (level {}, {}/{})\n{} [{}/{}]\n{}\n\\ weapon  [ ] upgrade  = apply  - revert",
+        meta.weapon_type,
+        meta.level,
+        sandbox.weapon_index + 1,
+        weapons.len(),
+        spec,
+        sandbox.spec_index + 1,
+        specs.len(),
+        preview,
+    );
+}
@@ -0,0 +1,155 @@
+use crate::boss::Boss;
+use crate::components::{Enemy, Health};
+use crate::definitions::Definitions;
+use crate::director::SpawnThrottle;
+use crate::resources::{GameState, RunModifiers, WaveConfig};
+use crate::waves::WaveDirector;
+use crate::GameplaySets;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Ramps a run up over time: later enemies are tougher and faster, and they come
+/// more often and in bigger crowds. Bosses are left alone, they have their own tuning.
+/// Experience drops scale up alongside, and with any curse on the run.
+pub struct DifficultyPlugin;
+
+impl Plugin for DifficultyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DifficultyScaling>()
+            .init_resource::<ExperienceScale>()
+            .add_systems(
+                Update,
+                (
+                    ramp_spawning.before(GameplaySets::Spawning),
+                    ramp_experience.before(GameplaySets::Combat),
+                    scale_new_enemies.after(GameplaySets::Spawning),
+                )
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+/// Multiplier over the course of a run, 1.0 at the start
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum DifficultyCurve {
+    Linear { per_minute: f32 },      // Adds this much every minute
+    Exponential { per_minute: f32 }, // Compounds by this much every minute
+    // (seconds, multiplier) points, interpolated between and held after the last one
+    Breakpoints(Vec<(f32, f32)>),
+}
+
+impl DifficultyCurve {
+    pub fn multiplier(&self, elapsed: f32) -> f32 {
+        let minutes = elapsed.max(0.0) / 60.0;
+        match self {
+            DifficultyCurve::Linear { per_minute } => 1.0 + per_minute * minutes,
+            DifficultyCurve::Exponential { per_minute } => (1.0 + per_minute).powf(minutes),
+            DifficultyCurve::Breakpoints(points) => {
+                let Some(next) = points.iter().position(|(time, _)| *time > elapsed) else {
+                    return points.last().map_or(1.0, |(_, multiplier)| *multiplier);
+                };
+                let (end_time, end) = points[next];
+                let (start_time, start) = next
+                    .checked_sub(1)
+                    .map_or((0.0, 1.0), |previous| points[previous]);
+                let t = ((elapsed - start_time) / (end_time - start_time)).clamp(0.0, 1.0);
+                start + (end - start) * t
+            }
+        }
+    }
+}
+
+/// How the active stage gets harder over time, one curve per knob
+#[derive(Resource, Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct DifficultyScaling {
+    pub health: DifficultyCurve,
+    pub speed: DifficultyCurve,
+    pub spawn_rate: DifficultyCurve,
+    pub max_enemies: DifficultyCurve,
+    pub experience: DifficultyCurve,
+}
+
+impl Default for DifficultyScaling {
+    fn default() -> Self {
+        Self {
+            health: DifficultyCurve::Linear { per_minute: 0.15 },
+            speed: DifficultyCurve::Breakpoints(vec![(300.0, 1.1), (600.0, 1.25), (900.0, 1.35)]),
+            spawn_rate: DifficultyCurve::Exponential { per_minute: 0.06 },
+            max_enemies: DifficultyCurve::Linear { per_minute: 0.2 },
+            experience: DifficultyCurve::Linear { per_minute: 0.05 },
+        }
+    }
+}
+
+/// What every experience drop is multiplied by right now, difficulty and curse together
+#[derive(Resource, Debug)]
+pub struct ExperienceScale(pub f32);
+
+impl Default for ExperienceScale {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+impl ExperienceScale {
+    // Rounded so even the smallest drop gives something
+    pub fn apply(&self, value: u32) -> u32 {
+        ((value as f32 * self.0).round() as u32).max(1)
+    }
+}
+
+/// Speeds up the wave budget and raises the enemy cap from the stage's starting values
+fn ramp_spawning(
+    time: Res<Time<Virtual>>,
+    scaling: Res<DifficultyScaling>,
+    throttle: Res<SpawnThrottle>,
+    definitions: Res<Definitions>,
+    mut wave_director: ResMut<WaveDirector>,
+    mut wave_config: ResMut<WaveConfig>,
+) {
+    let Some(stage) = definitions.stage() else {
+        return;
+    };
+    let elapsed = time.elapsed_secs();
+
+    let spawn_rate = scaling.spawn_rate.multiplier(elapsed) * throttle.spawn_rate();
+    if wave_director.spawn_rate != spawn_rate {
+        wave_director.spawn_rate = spawn_rate;
+    }
+
+    let max_enemies = (stage.max_enemies as f32 * scaling.max_enemies.multiplier(elapsed)) as u32;
+    if wave_config.max_enemies != max_enemies {
+        wave_config.max_enemies = max_enemies;
+    }
+}
+
+fn ramp_experience(
+    time: Res<Time<Virtual>>,
+    scaling: Res<DifficultyScaling>,
+    run_modifiers: Res<RunModifiers>,
+    mut experience_scale: ResMut<ExperienceScale>,
+) {
+    let multiplier =
+        scaling.experience.multiplier(time.elapsed_secs()) * run_modifiers.curse_multiplier();
+    if experience_scale.0 != multiplier {
+        experience_scale.0 = multiplier;
+    }
+}
+
+fn scale_new_enemies(
+    time: Res<Time<Virtual>>,
+    scaling: Res<DifficultyScaling>,
+    run_modifiers: Res<RunModifiers>,
+    mut enemy_query: Query<(&mut Enemy, &mut Health), (Added<Enemy>, Without<Boss>)>,
+) {
+    let elapsed = time.elapsed_secs();
+    let health_multiplier = scaling.health.multiplier(elapsed) * run_modifiers.curse_multiplier();
+    let speed_multiplier = scaling.speed.multiplier(elapsed);
+
+    for (mut enemy, mut health) in enemy_query.iter_mut() {
+        health.maximum = (health.maximum as f32 * health_multiplier).round() as i32;
+        health.current = health.maximum;
+        enemy.speed *= speed_multiplier;
+    }
+}
@@ -0,0 +1,346 @@
+use crate::announcer::DirectorMilestone;
+use crate::boss::Boss;
+use crate::components::{Enemy, Health, Player};
+use crate::death::MarkedForDeath;
+use crate::definitions::Definitions;
+use crate::elite::Elite;
+use crate::enemy_pool::EnemySpawner;
+use crate::experience::merge_experience_orbs;
+use crate::kill_feed::Notable;
+use crate::player_handle::PlayerHandle;
+use crate::resources::{GameRng, GameState, GameStats, WaveConfig};
+use crate::spawn_validation::SpawnValidator;
+use crate::GameplaySets;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use serde::{Deserialize, Serialize};
+
+/// Runs the stage's schedule on top of regular spawning: bosses, the final minute call-out
+/// and ending the run once its goal is reached. Also backs off spawning when the machine
+/// can't keep up, and keeps elites and bosses from piling up.
+pub struct DirectorPlugin;
+
+impl Plugin for DirectorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DirectorProgress>()
+            .init_resource::<SpawnThrottle>()
+            .init_resource::<SpawnCaps>()
+            .add_systems(
+                Update,
+                (
+                    count_live_archetypes.before(GameplaySets::Spawning),
+                    spawn_scheduled_bosses,
+                    announce_final_minute,
+                    check_victory,
+                    watch_spawn_load,
+                    merge_experience_orbs.run_if(|throttle: Res<SpawnThrottle>| throttle.active),
+                )
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+/// How far through the stage's schedule the run is
+#[derive(Resource, Default)]
+pub struct DirectorProgress {
+    pub next_boss: usize,
+    pub final_minute_announced: bool,
+}
+
+/// Eases off spawning while frames run long, so a slow machine settles down instead of
+/// falling further behind as enemies pile up
+#[derive(Resource)]
+pub struct SpawnThrottle {
+    pub active: bool,
+    pub frame_budget_ms: f32,
+    pub spawn_rate_multiplier: f32, // Applied to the spawn rate while active
+    pub engage_after: f32,          // Seconds over budget before throttling
+    pub release_after: f32,         // Seconds back under budget before letting go
+    pub over_budget: f32,
+    pub under_budget: f32,
+}
+
+impl Default for SpawnThrottle {
+    fn default() -> Self {
+        Self {
+            active: false,
+            frame_budget_ms: 25.0, // 40 FPS, same line the frame rate watch draws
+            spawn_rate_multiplier: 0.5,
+            engage_after: 3.0,
+            release_after: 5.0,
+            over_budget: 0.0,
+            under_budget: 0.0,
+        }
+    }
+}
+
+impl SpawnThrottle {
+    /// Counts a frame against the budget, returns true when the throttle flips
+    pub fn record_frame(&mut self, frame_secs: f32) -> bool {
+        if frame_secs * 1000.0 > self.frame_budget_ms {
+            self.over_budget += frame_secs;
+            self.under_budget = 0.0;
+        } else {
+            self.under_budget += frame_secs;
+            self.over_budget = 0.0;
+        }
+
+        let flip = if self.active {
+            self.under_budget >= self.release_after
+        } else {
+            self.over_budget >= self.engage_after
+        };
+        if flip {
+            self.active = !self.active;
+            self.over_budget = 0.0;
+            self.under_budget = 0.0;
+        }
+        flip
+    }
+
+    pub fn spawn_rate(&self) -> f32 {
+        if self.active {
+            self.spawn_rate_multiplier
+        } else {
+            1.0
+        }
+    }
+}
+
+/// Kinds of enemy with their own limit on how many can be alive at once,
+/// on top of the overall enemy cap
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EnemyArchetype {
+    Elite,
+    Boss,
+}
+
+/// Concurrent caps per archetype, so late runs don't turn into a wall of elites
+#[derive(Resource, Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct SpawnCaps {
+    pub limits: HashMap<EnemyArchetype, u32>, // Archetypes left out aren't capped
+    #[serde(skip)]
+    alive: HashMap<EnemyArchetype, u32>,
+}
+
+impl Default for SpawnCaps {
+    fn default() -> Self {
+        Self {
+            limits: HashMap::from_iter([(EnemyArchetype::Elite, 3), (EnemyArchetype::Boss, 1)]),
+            alive: HashMap::new(),
+        }
+    }
+}
+
+impl SpawnCaps {
+    pub fn alive(&self, archetype: EnemyArchetype) -> u32 {
+        self.alive.get(&archetype).copied().unwrap_or(0)
+    }
+
+    pub fn has_room(&self, archetype: EnemyArchetype) -> bool {
+        self.limits
+            .get(&archetype)
+            .is_none_or(|limit| self.alive(archetype) < *limit)
+    }
+
+    /// Counts a spawn straight away, so several in one frame can't overshoot the cap
+    pub fn record_spawn(&mut self, archetype: EnemyArchetype) {
+        *self.alive.entry(archetype).or_default() += 1;
+    }
+
+    pub fn set_alive(&mut self, archetype: EnemyArchetype, count: u32) {
+        self.alive.insert(archetype, count);
+    }
+}
+
+const BOSS_SPAWN_DISTANCE: f32 = 450.0;
+const FINAL_MINUTE: f32 = 60.0;
+
+fn format_clock(seconds: f32) -> String {
+    let seconds = seconds.max(0.0) as u32;
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+/// Human readable preview of the next few scheduled events, soonest first.
+/// Only covers the fixed schedule, never what regular spawning will roll.
+pub fn upcoming_milestones(
+    wave_config: &WaveConfig,
+    progress: &DirectorProgress,
+    definitions: &Definitions,
+    elapsed: f32,
+    limit: usize,
+) -> Vec<String> {
+    let bosses = wave_config
+        .bosses
+        .iter()
+        .skip(progress.next_boss)
+        .map(|boss| {
+            let name = definitions
+                .enemies
+                .get(&boss.enemy)
+                .map_or(boss.enemy.as_str(), |definition| definition.name.as_str());
+            (
+                boss.time,
+                format!("Boss: {} in {}", name, format_clock(boss.time - elapsed)),
+            )
+        });
+    let final_minute = wave_config
+        .run_length
+        .filter(|run_length| !progress.final_minute_announced && *run_length > FINAL_MINUTE)
+        .map(|run_length| {
+            let time = run_length - FINAL_MINUTE;
+            (
+                time,
+                format!("Final minute in {}", format_clock(time - elapsed)),
+            )
+        });
+    let victory = wave_config.run_length.map(|run_length| {
+        (
+            run_length,
+            format!("Victory at {}", format_clock(run_length)),
+        )
+    });
+
+    let mut milestones: Vec<_> = bosses.chain(final_minute).chain(victory).collect();
+    milestones.sort_by(|a, b| a.0.total_cmp(&b.0));
+    milestones
+        .into_iter()
+        .take(limit)
+        .map(|(_, text)| text)
+        .collect()
+}
+
+// Counted fresh every frame, dying and pooled enemies no longer take up a slot
+fn count_live_archetypes(
+    mut caps: ResMut<SpawnCaps>,
+    elite_query: Query<(), (With<Elite>, Without<MarkedForDeath>)>,
+    boss_query: Query<(), (With<Boss>, Without<MarkedForDeath>)>,
+) {
+    caps.set_alive(EnemyArchetype::Elite, elite_query.iter().count() as u32);
+    caps.set_alive(EnemyArchetype::Boss, boss_query.iter().count() as u32);
+}
+
+fn spawn_scheduled_bosses(
+    mut spawner: EnemySpawner,
+    time: Res<Time<Virtual>>,
+    wave_config: Res<WaveConfig>,
+    mut caps: ResMut<SpawnCaps>,
+    definitions: Res<Definitions>,
+    mut progress: ResMut<DirectorProgress>,
+    mut milestones: EventWriter<DirectorMilestone>,
+    player_handle: Res<PlayerHandle>,
+    player_query: Query<&Transform, With<Player>>,
+    spawn_validator: SpawnValidator,
+    mut game_rng: ResMut<GameRng>,
+) {
+    let Some(boss) = wave_config.bosses.get(progress.next_boss) else {
+        return;
+    };
+    // A boss held back by the cap comes in once a slot frees up
+    if time.elapsed_secs() < boss.time || !caps.has_room(EnemyArchetype::Boss) {
+        return;
+    }
+    let Some(player_transform) = player_handle.fetch(&player_query) else {
+        return;
+    };
+
+    // Boss is due; an unknown enemy id is skipped so it doesn't stall the rest of the schedule
+    progress.next_boss += 1;
+    let Some(definition) = definitions.enemies.get(&boss.enemy) else {
+        return;
+    };
+    let Some(position) = spawn_validator.find_position_around(
+        player_transform.translation.truncate(),
+        BOSS_SPAWN_DISTANCE,
+        definition.radius,
+        game_rng.as_mut(),
+    ) else {
+        warn!("No room to spawn boss '{}', skipping it", boss.enemy);
+        return;
+    };
+
+    info!("Spawning boss: {}", definition.name);
+    let health = (definition.health as f32 * boss.health_multiplier) as i32;
+    spawner
+        .spawn(definition, position.extend(player_transform.translation.z))
+        .insert((
+            Health {
+                current: health,
+                maximum: health,
+            },
+            Boss::new(definition.name.clone(), definition.speed),
+            Notable(definition.name.clone()),
+        ));
+    caps.record_spawn(EnemyArchetype::Boss);
+    milestones.send(DirectorMilestone::BossSpawn);
+}
+
+fn announce_final_minute(
+    time: Res<Time<Virtual>>,
+    wave_config: Res<WaveConfig>,
+    mut progress: ResMut<DirectorProgress>,
+    mut milestones: EventWriter<DirectorMilestone>,
+) {
+    if progress.final_minute_announced {
+        return;
+    }
+
+    let Some(remaining) = wave_config.time_remaining(time.elapsed_secs()) else {
+        return;
+    };
+    // Runs shorter than a minute don't get a call-out
+    if remaining <= FINAL_MINUTE && wave_config.run_length.unwrap_or(0.0) > FINAL_MINUTE {
+        progress.final_minute_announced = true;
+        milestones.send(DirectorMilestone::FinalMinute);
+    }
+}
+
+// Real time, a long frame is a long frame however slowly the run is going
+fn watch_spawn_load(
+    time: Res<Time<Real>>,
+    enemy_query: Query<(), With<Enemy>>,
+    mut throttle: ResMut<SpawnThrottle>,
+) {
+    if !throttle.record_frame(time.delta_secs()) {
+        return;
+    }
+
+    if throttle.active {
+        warn!(
+            "Frames over {:.0}ms for {:.0}s with {} enemies, throttling spawns to {:.0}%",
+            throttle.frame_budget_ms,
+            throttle.engage_after,
+            enemy_query.iter().count(),
+            throttle.spawn_rate_multiplier * 100.0
+        );
+    } else {
+        info!(
+            "Frames back under {:.0}ms, lifting the spawn throttle",
+            throttle.frame_budget_ms
+        );
+    }
+}
+
+fn check_victory(
+    time: Res<Time<Virtual>>,
+    wave_config: Res<WaveConfig>,
+    game_stats: Res<GameStats>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let survived = wave_config
+        .time_remaining(time.elapsed_secs())
+        .is_some_and(|remaining| remaining <= 0.0);
+    let killed_enough = wave_config
+        .kill_goal
+        .is_some_and(|goal| game_stats.enemies_killed >= goal);
+
+    if survived || killed_enough {
+        info!(
+            "Run won after {:.0}s with {} kills",
+            time.elapsed_secs(),
+            game_stats.enemies_killed
+        );
+        next_state.set(GameState::Victory);
+    }
+}
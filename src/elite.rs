@@ -0,0 +1,224 @@
+use crate::components::{Armor, Enemy, Health};
+use crate::death::MarkedForDeath;
+use crate::events::EntityDeathEvent;
+use crate::experience::spawn_experience_orb;
+use crate::kill_feed::Notable;
+use crate::materials::FlashMaterial;
+use crate::minimap::MinimapIcon;
+use crate::pickup::{spawn_pickup, Pickup};
+use crate::resources::{GameRng, GameState};
+use crate::supply_drop::spawn_supply_crate;
+use crate::GameplaySets;
+use bevy::prelude::*;
+use bevy_prototype_lyon::draw::{Fill, Stroke};
+use bevy_prototype_lyon::entity::ShapeBundle;
+use bevy_prototype_lyon::geometry::GeometryBuilder;
+use bevy_prototype_lyon::shapes;
+use rand::Rng;
+
+/// Tougher versions of regular enemies: more health and damage, bigger, outlined,
+/// and they always leave something behind. Each one hardens the enemies around it
+/// with an aura that pulls in as it's hurt and is gone the moment it dies.
+pub struct ElitePlugin;
+
+impl Plugin for ElitePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                (empower_elites, update_elite_auras)
+                    .chain()
+                    .before(GameplaySets::Combat),
+                (drop_elite_rewards, end_fallen_elite_auras).after(GameplaySets::Combat),
+            )
+                .run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+/// Marks a spawn as an elite, the stat boost is applied when it's added
+#[derive(Component)]
+pub struct Elite;
+
+/// Child of an elite showing how far its aura reaches
+#[derive(Component)]
+pub struct EliteAuraRing;
+
+/// Hardened by a nearby elite's aura, for as long as it stays inside it
+#[derive(Component)]
+pub struct Empowered {
+    pub source: Entity, // The elite whose aura it is
+}
+
+const ELITE_HEALTH_MULTIPLIER: f32 = 4.0;
+const ELITE_DAMAGE_MULTIPLIER: f32 = 2.0;
+const ELITE_SCALE: f32 = 1.4;
+const ELITE_OUTLINE_WIDTH: f32 = 1.5;
+const ELITE_COLOR: Color = Color::srgb(1.0, 0.75, 0.2);
+// Otherwise the drop is a big orb worth this many of the elite's regular orbs
+const ELITE_CHEST_CHANCE: f32 = 0.3;
+const ELITE_BOMB_CHANCE: f32 = 0.15;
+const ELITE_ORB_MULTIPLIER: u32 = 5;
+
+const ELITE_AURA_RADIUS: f32 = 140.0; // At full health
+const ELITE_AURA_MIN_SHARE: f32 = 0.3; // Of the radius still left when nearly dead
+const ELITE_AURA_ARMOR: i32 = 2; // Taken off every hit on an empowered enemy
+
+/// How far an elite's aura reaches, it pulls in as the elite loses health
+pub fn aura_radius(health: &Health) -> f32 {
+    let fraction = (health.current as f32 / health.maximum.max(1) as f32).clamp(0.0, 1.0);
+    ELITE_AURA_RADIUS * (ELITE_AURA_MIN_SHARE + (1.0 - ELITE_AURA_MIN_SHARE) * fraction)
+}
+
+fn empower_elites(
+    mut commands: Commands,
+    mut materials: ResMut<Assets<FlashMaterial>>,
+    mut elite_query: Query<
+        (
+            Entity,
+            &mut Enemy,
+            &mut Health,
+            &mut Transform,
+            Option<&MeshMaterial2d<FlashMaterial>>,
+        ),
+        Added<Elite>,
+    >,
+) {
+    for (entity, mut enemy, mut health, mut transform, material_handle) in elite_query.iter_mut() {
+        health.maximum = (health.maximum as f32 * ELITE_HEALTH_MULTIPLIER) as i32;
+        health.current = health.maximum;
+        enemy.contact_damage = (enemy.contact_damage as f32 * ELITE_DAMAGE_MULTIPLIER) as i32;
+        transform.scale *= ELITE_SCALE;
+
+        // Modded sprites don't use the shared material, so they only get the size bump
+        if let Some(material) =
+            material_handle.and_then(|material_handle| materials.get_mut(&material_handle.0))
+        {
+            material.outline_width = ELITE_OUTLINE_WIDTH;
+            material.outline_color = ELITE_COLOR.to_linear();
+        }
+
+        commands.entity(entity).try_insert((
+            Notable(format!("Elite {}", enemy.kind)),
+            MinimapIcon {
+                color: ELITE_COLOR,
+                size: 5.0,
+            },
+        ));
+
+        // Drawn at full size, update_elite_auras scales it down to the aura's reach
+        let ring = commands
+            .spawn((
+                EliteAuraRing,
+                ShapeBundle {
+                    path: GeometryBuilder::build_as(&shapes::Circle {
+                        radius: ELITE_AURA_RADIUS,
+                        center: Vec2::ZERO,
+                    }),
+                    transform: Transform::from_xyz(0.0, 0.0, -0.1)
+                        .with_scale(Vec3::splat(1.0 / transform.scale.x)),
+                    ..default()
+                },
+                Fill::color(ELITE_COLOR.with_alpha(0.08)),
+                Stroke::new(ELITE_COLOR.with_alpha(0.4), 2.0),
+            ))
+            .id();
+        commands.entity(entity).add_child(ring);
+    }
+}
+
+type AliveElite = (With<Elite>, Without<MarkedForDeath>);
+type RegularEnemy = (With<Enemy>, Without<Elite>);
+
+/// Sizes each elite's ring to its aura and hands out or takes back `Empowered`
+/// as enemies move in and out of range
+fn update_elite_auras(
+    mut commands: Commands,
+    elite_query: Query<(Entity, &Health, &Transform, &Children), AliveElite>,
+    mut ring_query: Query<&mut Transform, (With<EliteAuraRing>, Without<Enemy>)>,
+    enemy_query: Query<(Entity, &Transform, Option<&Empowered>), RegularEnemy>,
+) {
+    let mut auras = Vec::new();
+    for (entity, health, transform, children) in elite_query.iter() {
+        let radius = aura_radius(health);
+        // The ring is a child, so the elite's own size bump is countered
+        let scale = radius / ELITE_AURA_RADIUS / transform.scale.x.max(f32::EPSILON);
+        for &child in children.iter() {
+            if let Ok(mut ring_transform) = ring_query.get_mut(child) {
+                ring_transform.scale = Vec3::splat(scale);
+            }
+        }
+        auras.push((entity, transform.translation.truncate(), radius));
+    }
+
+    for (entity, transform, empowered) in enemy_query.iter() {
+        let position = transform.translation.truncate();
+        let covers =
+            |(_, center, radius): &&(Entity, Vec2, f32)| center.distance(position) <= *radius;
+        // An enemy stays with the aura it's in, so overlapping elites don't trade it back and forth
+        let kept = empowered.is_some_and(|empowered| {
+            auras
+                .iter()
+                .filter(covers)
+                .any(|(source, ..)| *source == empowered.source)
+        });
+        if kept {
+            continue;
+        }
+
+        match (auras.iter().find(covers), empowered) {
+            (Some((source, ..)), _) => {
+                commands
+                    .entity(entity)
+                    .try_insert((Empowered { source: *source }, Armor(ELITE_AURA_ARMOR)));
+            }
+            (None, Some(_)) => {
+                commands.entity(entity).remove::<(Empowered, Armor)>();
+            }
+            (None, None) => {}
+        }
+    }
+}
+
+/// Killing an elite strips its aura from everything it was hardening straight away,
+/// instead of waiting for the next aura update to notice it's gone
+fn end_fallen_elite_auras(
+    mut commands: Commands,
+    mut death_events: EventReader<EntityDeathEvent>,
+    empowered_query: Query<(Entity, &Empowered)>,
+) {
+    let fallen: Vec<Entity> = death_events
+        .read()
+        .filter(|event| event.elite)
+        .map(|event| event.entity)
+        .collect();
+    if fallen.is_empty() {
+        return;
+    }
+
+    for (entity, empowered) in empowered_query.iter() {
+        if fallen.contains(&empowered.source) {
+            commands.entity(entity).remove::<(Empowered, Armor)>();
+        }
+    }
+}
+
+fn drop_elite_rewards(
+    mut commands: Commands,
+    mut death_events: EventReader<EntityDeathEvent>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    for event in death_events.read().filter(|event| event.elite) {
+        let roll = game_rng.gen::<f32>();
+        if roll < ELITE_CHEST_CHANCE {
+            info!("Elite dropped a chest");
+            spawn_supply_crate(&mut commands, event.position);
+        } else if roll < ELITE_CHEST_CHANCE + ELITE_BOMB_CHANCE {
+            info!("Elite dropped a bomb");
+            spawn_pickup(&mut commands, event.position, Pickup::Bomb);
+        } else {
+            let value = event.exp_value.unwrap_or_default() * ELITE_ORB_MULTIPLIER;
+            spawn_experience_orb(&mut commands, event.position, value);
+        }
+    }
+}
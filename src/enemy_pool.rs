@@ -0,0 +1,132 @@
+use crate::definitions::EnemyDefinition;
+use crate::materials::SpriteMaterials;
+use crate::systems::insert_enemy;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use std::collections::VecDeque;
+
+/// Dead enemies are switched off and parked instead of despawned, then handed out again
+/// by the next spawns. Saves building and tearing down a physics body for every enemy,
+/// which is what hitches once hundreds of them are coming and going.
+pub struct EnemyPoolPlugin;
+
+impl Plugin for EnemyPoolPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EnemyPool>();
+    }
+}
+
+/// Parked enemy, hidden and with its body and collider disabled
+#[derive(Component)]
+pub struct Pooled;
+
+#[derive(Resource)]
+pub struct EnemyPool {
+    idle: VecDeque<(Entity, f32)>, // Oldest first, with when each was parked
+    pub capacity: usize,
+    pub reused: u32,
+}
+
+impl Default for EnemyPool {
+    fn default() -> Self {
+        Self {
+            idle: VecDeque::new(),
+            capacity: 300,
+            reused: 0,
+        }
+    }
+}
+
+// Parked enemies sit out this long before coming back, so anything still aimed at
+// the dead enemy (homing shots, queued damage) doesn't land on its replacement
+const POOL_REST: f32 = 0.5;
+
+// What a parked enemy keeps: its place in the world and its physics body
+type PooledComponents = (
+    Transform,
+    GlobalTransform,
+    Visibility,
+    InheritedVisibility,
+    ViewVisibility,
+    (
+        RigidBody,
+        Collider,
+        Velocity,
+        LockedAxes,
+        ActiveEvents,
+        CollisionGroups,
+        Damping,
+    ),
+    (
+        RapierRigidBodyHandle,
+        RapierColliderHandle,
+        RapierContextEntityLink,
+    ),
+);
+
+impl EnemyPool {
+    pub fn idle(&self) -> usize {
+        self.idle.len()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.idle.len() >= self.capacity
+    }
+
+    /// Strips a dead enemy down to its physics body and parks it
+    pub fn release(&mut self, commands: &mut Commands, entity: Entity, now: f32) {
+        commands
+            .entity(entity)
+            .despawn_descendants()
+            .retain::<PooledComponents>()
+            .insert((
+                Pooled,
+                RigidBodyDisabled,
+                ColliderDisabled,
+                Visibility::Hidden,
+            ));
+        self.idle.push_back((entity, now));
+    }
+
+    fn take(&mut self, now: f32) -> Option<Entity> {
+        let (_, parked_at) = self.idle.front()?;
+        if now - parked_at < POOL_REST {
+            return None;
+        }
+        self.idle.pop_front().map(|(entity, _)| entity)
+    }
+}
+
+/// Spawns enemies, reusing parked ones when there are any
+#[derive(SystemParam)]
+pub struct EnemySpawner<'w, 's> {
+    commands: Commands<'w, 's>,
+    sprite_materials: SpriteMaterials<'w>,
+    pool: ResMut<'w, EnemyPool>,
+    time: Res<'w, Time<Virtual>>,
+}
+
+impl EnemySpawner<'_, '_> {
+    pub fn spawn(&mut self, definition: &EnemyDefinition, position: Vec3) -> EntityCommands<'_> {
+        let pooled = self
+            .pool
+            .take(self.time.elapsed_secs())
+            .filter(|entity| self.commands.get_entity(*entity).is_some());
+
+        let mut enemy = match pooled {
+            Some(entity) => {
+                self.pool.reused += 1;
+                let mut enemy = self.commands.entity(entity);
+                enemy
+                    .remove::<(Pooled, RigidBodyDisabled, ColliderDisabled)>()
+                    .insert((Visibility::Inherited, Velocity::zero()));
+                enemy
+            }
+            None => self.commands.spawn_empty(),
+        };
+
+        insert_enemy(&mut enemy, &mut self.sprite_materials, definition, position);
+        enemy
+    }
+}
@@ -0,0 +1,202 @@
+use crate::combat::DamageEvent;
+use crate::components::{Enemy, EnemyBehavior, Player};
+use crate::death::{MarkedForDeath, MarkedForDespawn};
+use crate::game_time::PausableTimer;
+use crate::physics::{handle_rapier_context_error, ENEMY_PROJECTILE_GROUP};
+use crate::player_handle::PlayerHandle;
+use crate::resources::{GameRng, GameState, GameTextures};
+use crate::weapons::Lifetime;
+use crate::GameplaySets;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use rand::Rng;
+
+/// Lets ranged enemies shoot: gives them a launcher when they spawn, fires it at the player
+/// and cleans up bolts that hit or run out of time
+pub struct EnemyProjectilePlugin;
+
+impl Plugin for EnemyProjectilePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                arm_ranged_enemies,
+                fire_enemy_projectiles,
+                enemy_projectile_hits,
+                expire_enemy_projectiles,
+            )
+                .chain()
+                .after(GameplaySets::Movement)
+                .before(GameplaySets::Combat)
+                .run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+/// Fires at the player whenever the timer comes round, if they're in range
+#[derive(Component)]
+pub struct EnemyLauncher {
+    pub timer: PausableTimer,
+    pub range: f32,
+    pub speed: f32,
+}
+
+/// Bolt fired by an enemy, harmful only to the player
+#[derive(Component)]
+pub struct EnemyProjectile {
+    pub damage: i32,
+}
+
+/// Distance ranged enemies try to keep from the player
+pub const RANGED_PREFERRED_DISTANCE: f32 = 220.0;
+// Within this much of the preferred distance they hold position instead of shuffling
+pub const RANGED_DISTANCE_BAND: f32 = 30.0;
+
+const LAUNCHER_INTERVAL: f32 = 2.5;
+const LAUNCHER_RANGE: f32 = 350.0;
+const PROJECTILE_SPEED: f32 = 220.0;
+const PROJECTILE_RADIUS: f32 = 5.0;
+const PROJECTILE_LIFETIME: f32 = 3.0;
+const PROJECTILE_SPRITE: usize = 0;
+const PROJECTILE_COLOR: Color = Color::srgb(1.0, 0.35, 0.3);
+
+fn arm_ranged_enemies(
+    mut commands: Commands,
+    new_enemies: Query<(Entity, &Enemy), Added<Enemy>>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    for (entity, enemy) in new_enemies.iter() {
+        if enemy.behavior != EnemyBehavior::Ranged {
+            continue;
+        }
+
+        // Random first shot so a group spawned together doesn't fire in unison
+        let mut timer = PausableTimer::from_seconds(LAUNCHER_INTERVAL, TimerMode::Repeating);
+        let offset = timer.duration().mul_f32(game_rng.gen::<f32>());
+        timer.set_elapsed(offset);
+
+        commands.entity(entity).try_insert(EnemyLauncher {
+            timer,
+            range: LAUNCHER_RANGE,
+            speed: PROJECTILE_SPEED,
+        });
+    }
+}
+
+fn fire_enemy_projectiles(
+    mut commands: Commands,
+    time: Res<Time<Virtual>>,
+    textures: Res<GameTextures>,
+    player_handle: Res<PlayerHandle>,
+    player_query: Query<&Transform, With<Player>>,
+    mut launcher_query: Query<
+        (&Enemy, &Transform, &mut EnemyLauncher),
+        (Without<MarkedForDeath>, Without<Player>),
+    >,
+) {
+    let Some(player_transform) = player_handle.fetch(&player_query) else {
+        return;
+    };
+
+    for (enemy, transform, mut launcher) in launcher_query.iter_mut() {
+        if !launcher.timer.tick(&time).just_finished() {
+            continue;
+        }
+
+        let offset = (player_transform.translation - transform.translation).truncate();
+        if offset.length() > launcher.range {
+            continue;
+        }
+
+        spawn_enemy_projectile(
+            &mut commands,
+            &textures,
+            transform.translation,
+            offset.normalize_or_zero() * launcher.speed,
+            enemy.contact_damage,
+        );
+    }
+}
+
+pub fn spawn_enemy_projectile(
+    commands: &mut Commands,
+    textures: &GameTextures,
+    origin: Vec3,
+    velocity: Vec2,
+    damage: i32,
+) -> Entity {
+    let mut sprite = Sprite::from_atlas_image(
+        textures.projectiles.clone(),
+        TextureAtlas {
+            layout: textures.projectiles_layout.clone(),
+            index: PROJECTILE_SPRITE,
+        },
+    );
+    sprite.color = PROJECTILE_COLOR;
+
+    commands
+        .spawn((
+            EnemyProjectile { damage },
+            Lifetime {
+                timer: PausableTimer::from_seconds(PROJECTILE_LIFETIME, TimerMode::Once),
+            },
+            sprite,
+            Transform::from_translation(origin)
+                .with_rotation(Quat::from_rotation_z(velocity.to_angle())),
+            RigidBody::KinematicVelocityBased,
+            Velocity::linear(velocity),
+            Sensor,
+            Collider::ball(PROJECTILE_RADIUS),
+            // The player is kinematic too, which Rapier skips by default
+            ActiveCollisionTypes::default() | ActiveCollisionTypes::KINEMATIC_KINEMATIC,
+            ActiveEvents::COLLISION_EVENTS,
+            CollisionGroups::new(ENEMY_PROJECTILE_GROUP, Group::GROUP_1),
+        ))
+        .id()
+}
+
+/// Damages the player on contact, the bolt is spent either way
+fn enemy_projectile_hits(
+    mut commands: Commands,
+    projectile_query: Query<(Entity, &EnemyProjectile), Without<MarkedForDespawn>>,
+    player_handle: Res<PlayerHandle>,
+    context_query: Query<&RapierContext>,
+    mut damage_events: EventWriter<DamageEvent>,
+) {
+    // Bolts already in the air fly on harmlessly once the player is gone
+    let Some(player_entity) = player_handle.get() else {
+        return;
+    };
+    let rapier_context = context_query
+        .get_single()
+        .unwrap_or_else(|e| handle_rapier_context_error(e));
+
+    for (projectile_entity, projectile) in projectile_query.iter() {
+        if rapier_context.intersection_pair(projectile_entity, player_entity) != Some(true) {
+            continue;
+        }
+
+        damage_events.send(DamageEvent {
+            target: player_entity,
+            amount: projectile.damage,
+            source: Some(projectile_entity),
+        });
+        // Straight to despawn, bolts aren't kills or deaths anything should hear about
+        commands.entity(projectile_entity).insert(MarkedForDespawn);
+    }
+}
+
+fn expire_enemy_projectiles(
+    mut commands: Commands,
+    time: Res<Time<Virtual>>,
+    mut projectile_query: Query<
+        (Entity, &mut Lifetime),
+        (With<EnemyProjectile>, Without<MarkedForDespawn>),
+    >,
+) {
+    for (entity, mut lifetime) in projectile_query.iter_mut() {
+        if lifetime.timer.tick(&time).finished() {
+            commands.entity(entity).insert(MarkedForDespawn);
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use crate::components::{
+    AreaMultiplier, CooldownReduction, DamageMultiplier, Equipment, Health, Player,
+};
+use crate::types::EquipmentType;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Stat changes from passive items, summed over everything carried
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(default)]
+pub struct StatModifiers {
+    pub cooldown_reduction: f32, // Added to CooldownReduction.percent
+    pub area: f32,               // Added to AreaMultiplier.factor
+    pub damage: f32,             // Added to DamageMultiplier.factor
+    pub speed: f32,              // Share of movement speed added
+    pub max_health: i32,
+}
+
+impl StatModifiers {
+    pub fn times(&self, count: u32) -> Self {
+        let count_f = count as f32;
+        Self {
+            cooldown_reduction: self.cooldown_reduction * count_f,
+            area: self.area * count_f,
+            damage: self.damage * count_f,
+            speed: self.speed * count_f,
+            max_health: self.max_health * count as i32,
+        }
+    }
+
+    pub fn plus(&self, other: &Self) -> Self {
+        Self {
+            cooldown_reduction: self.cooldown_reduction + other.cooldown_reduction,
+            area: self.area + other.area,
+            damage: self.damage + other.damage,
+            speed: self.speed + other.speed,
+            max_health: self.max_health + other.max_health,
+        }
+    }
+
+    /// Short list of what changes, for upgrade descriptions
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if self.max_health != 0 {
+            parts.push(format!("+{} max health", self.max_health));
+        }
+        if self.cooldown_reduction != 0.0 {
+            parts.push(format!(
+                "{:.0}% cooldown reduction",
+                self.cooldown_reduction * 100.0
+            ));
+        }
+        if self.damage != 0.0 {
+            parts.push(format!("+{:.0}% damage", self.damage * 100.0));
+        }
+        if self.speed != 0.0 {
+            parts.push(format!("+{:.0}% movement speed", self.speed * 100.0));
+        }
+        if self.area != 0.0 {
+            parts.push(format!("+{:.0}% area", self.area * 100.0));
+        }
+        parts.join(", ")
+    }
+}
+
+/// What a passive item gives at each level, and how far it can be levelled
+#[derive(Debug, Clone, Copy)]
+pub struct EquipmentDefinition {
+    pub max_level: u32,
+    pub per_level: StatModifiers,
+}
+
+pub fn equipment_definition(equipment_type: EquipmentType) -> EquipmentDefinition {
+    let per_level = match equipment_type {
+        EquipmentType::Armor => StatModifiers {
+            max_health: 15,
+            ..default()
+        },
+        EquipmentType::Ring => StatModifiers {
+            cooldown_reduction: 0.05,
+            ..default()
+        },
+        EquipmentType::Amulet => StatModifiers {
+            damage: 0.10,
+            ..default()
+        },
+        EquipmentType::Boots => StatModifiers {
+            speed: 0.10,
+            ..default()
+        },
+        EquipmentType::Gloves => StatModifiers {
+            area: 0.10,
+            ..default()
+        },
+    };
+
+    EquipmentDefinition {
+        max_level: 5,
+        per_level,
+    }
+}
+
+/// Everything the carried items add up to at their current levels
+pub fn equipment_bonuses(equipment: &Equipment) -> StatModifiers {
+    equipment.items.iter().fold(
+        StatModifiers::default(),
+        |total, (equipment_type, level)| {
+            total.plus(
+                &equipment_definition(*equipment_type)
+                    .per_level
+                    .times(*level),
+            )
+        },
+    )
+}
+
+/// Swaps the stat bonuses last folded in from equipment for the ones it gives now.
+/// Only the difference is applied, so bonuses from anywhere else are left alone.
+pub fn apply_equipment_stats(
+    mut equipment_query: Query<(Entity, &mut Equipment), Changed<Equipment>>,
+    mut stats_query: Query<(
+        &mut Player,
+        &mut Health,
+        &mut CooldownReduction,
+        &mut DamageMultiplier,
+        &mut AreaMultiplier,
+    )>,
+) {
+    for (entity, mut equipment) in equipment_query.iter_mut() {
+        let bonuses = equipment_bonuses(&equipment);
+        // Checked through the plain reference so applying doesn't count as a change
+        let applied = equipment.applied;
+        if bonuses == applied {
+            continue;
+        }
+        let Ok((mut player, mut health, mut cooldown, mut damage, mut area)) =
+            stats_query.get_mut(entity)
+        else {
+            continue;
+        };
+
+        cooldown.percent += bonuses.cooldown_reduction - applied.cooldown_reduction;
+        damage.factor += bonuses.damage - applied.damage;
+        area.factor += bonuses.area - applied.area;
+        player.speed *= (1.0 + bonuses.speed) / (1.0 + applied.speed);
+        health.raise_maximum(bonuses.max_health - applied.max_health);
+
+        info!("Equipment bonuses now {:?}", bonuses);
+        equipment.applied = bonuses;
+    }
+}
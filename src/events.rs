@@ -5,4 +5,5 @@ pub struct EntityDeathEvent {
     pub entity: Entity,
     pub position: Vec2,
     pub exp_value: Option<u32>, // Only some entities give experience
+    pub elite: bool,
 }
@@ -1,18 +1,23 @@
 use crate::components::*;
 use crate::death::MarkedForDespawn;
+use crate::definitions::StageBounds;
 use crate::events::EntityDeathEvent;
+use crate::gameplay_events::{GameplayEvent, PickupKind};
+use crate::player_handle::PlayerHandle;
 use crate::resources::GameState;
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
+use std::collections::HashMap;
 
 pub struct ExperiencePlugin;
 
 impl Plugin for ExperiencePlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
+        app.init_resource::<OrbExpiry>().add_systems(
             Update,
             (
                 spawn_experience_orbs,
+                consolidate_expired_orbs,
                 vacuum_system,
                 collect_experience_orbs,
                 check_level_up,
@@ -32,17 +37,42 @@ pub struct Experience {
 #[derive(Component)]
 pub struct ExperienceOrb {
     pub value: u32,
+    pub age: f32, // Seconds spent lying on the ground
+}
+
+/// Orb left lying around too long has been swept into this one. There's only ever one,
+/// it never expires itself and moves ahead of the player again each time it grows.
+#[derive(Component)]
+pub struct ConsolidatedGem;
+
+/// Orbs nobody comes back for are gathered into a single big gem instead of lingering
+/// forever, so the XP isn't lost and the orb count stays bounded
+#[derive(Resource)]
+pub struct OrbExpiry {
+    pub lifetime: Option<f32>, // Seconds, None leaves orbs where they fell for good
+    pub gem_distance: f32,     // How far ahead of the player the gem lands
+}
+
+impl Default for OrbExpiry {
+    fn default() -> Self {
+        Self {
+            lifetime: Some(90.0),
+            gem_distance: 160.0,
+        }
+    }
 }
 
 #[derive(Component)]
 pub struct Vacuumable {
     pub base_speed: f32,
+    pub range_multiplier: f32, // Of the player's magnet strength
 }
 
 impl Default for Vacuumable {
     fn default() -> Self {
         Self {
             base_speed: 300.0, // Some reasonable default speed
+            range_multiplier: 1.0,
         }
     }
 }
@@ -62,6 +92,7 @@ fn calculate_experience_needed(level: u32) -> u32 {
 pub fn check_level_up(
     mut player_query: Query<&mut Experience, With<Player>>,
     mut next_state: ResMut<NextState<GameState>>,
+    mut gameplay_events: EventWriter<GameplayEvent>,
 ) {
     if let Ok(mut experience) = player_query.get_single_mut() {
         let xp_needed = calculate_experience_needed(experience.level);
@@ -70,6 +101,9 @@ pub fn check_level_up(
             // Bank the leftover XP
             experience.current -= xp_needed;
             experience.level += 1;
+            gameplay_events.send(GameplayEvent::LevelUp {
+                level: experience.level,
+            });
 
             // Trigger level up menu
             next_state.set(GameState::LevelUp);
@@ -80,32 +114,132 @@ pub fn check_level_up(
 fn spawn_experience_orbs(mut commands: Commands, mut death_events: EventReader<EntityDeathEvent>) {
     for event in death_events.read() {
         if let Some(exp_value) = event.exp_value {
-            commands.spawn((
-                ExperienceOrb { value: exp_value },
-                Vacuumable::default(),
+            spawn_experience_orb(&mut commands, event.position, exp_value);
+        }
+    }
+}
+
+pub fn spawn_experience_orb(commands: &mut Commands, position: Vec2, value: u32) -> Entity {
+    commands
+        .spawn((
+            ExperienceOrb { value, age: 0.0 },
+            Vacuumable::default(),
+            Sprite {
+                color: Color::srgb(0.5, 0.8, 1.0),
+                custom_size: Some(Vec2::new(8.0, 8.0)),
+                ..default()
+            },
+            Transform::from_translation(position.extend(0.0)),
+            // Add Rapier components
+            RigidBody::Dynamic,
+            Collider::ball(4.0), // Smaller collision radius than visual
+            Sensor,              // Make it a sensor so it doesn't affect physics
+            ActiveEvents::COLLISION_EVENTS,
+            CollisionGroups::new(
+                Group::GROUP_4, // Experience orb group
+                Group::GROUP_1, // Player group
+            ),
+            LockedAxes::ROTATION_LOCKED,
+            Damping {
+                linear_damping: 2.0,
+                angular_damping: 1.0,
+            },
+        ))
+        .id()
+}
+
+// Orbs closer together than this get folded into one when merging
+const ORB_MERGE_CELL: f32 = 32.0;
+
+/// Folds orbs sharing a small patch of ground into one worth the lot, keeps the
+/// orb count down when the director is throttling
+pub fn merge_experience_orbs(
+    mut commands: Commands,
+    mut orb_query: Query<(Entity, &Transform, &mut ExperienceOrb), Without<MarkedForDespawn>>,
+) {
+    let mut survivors: HashMap<IVec2, Entity> = HashMap::new();
+    let mut absorbed: Vec<(Entity, u32)> = Vec::new();
+
+    for (entity, transform, orb) in orb_query.iter() {
+        let cell = (transform.translation.truncate() / ORB_MERGE_CELL)
+            .floor()
+            .as_ivec2();
+        match survivors.get(&cell) {
+            Some(&survivor) => {
+                absorbed.push((survivor, orb.value));
+                commands.entity(entity).insert(MarkedForDespawn);
+            }
+            None => {
+                survivors.insert(cell, entity);
+            }
+        }
+    }
+
+    for (survivor, value) in absorbed {
+        if let Ok((_, _, mut orb)) = orb_query.get_mut(survivor) {
+            orb.value += value;
+        }
+    }
+}
+
+pub fn consolidate_expired_orbs(
+    mut commands: Commands,
+    time: Res<Time<Virtual>>,
+    expiry: Res<OrbExpiry>,
+    bounds: Option<Res<StageBounds>>,
+    mut orb_query: Query<(Entity, &mut ExperienceOrb), Without<ConsolidatedGem>>,
+    mut gem_query: Query<(Entity, &mut ExperienceOrb), With<ConsolidatedGem>>,
+    player_handle: Res<PlayerHandle>,
+    player_query: Query<(&Transform, &Facing), With<Player>>,
+) {
+    let Some(lifetime) = expiry.lifetime else {
+        return;
+    };
+    // Without a player there's nowhere to put the gem, the orbs wait
+    let Some((player_transform, facing)) = player_handle.fetch(&player_query) else {
+        return;
+    };
+
+    let mut expired = 0;
+    for (entity, mut orb) in orb_query.iter_mut() {
+        orb.age += time.delta_secs();
+        if orb.age >= lifetime {
+            // Taken rather than copied, so an orb still waiting on despawn can't count twice
+            expired += std::mem::take(&mut orb.value);
+            commands.entity(entity).insert(MarkedForDespawn);
+        }
+    }
+    if expired == 0 {
+        return;
+    }
+
+    // Somewhere the player is headed anyway, kept inside the walls
+    let mut position =
+        player_transform.translation.truncate() + facing.direction * expiry.gem_distance;
+    if let Some(bounds) = &bounds {
+        position = position.clamp(bounds.0.min, bounds.0.max);
+    }
+
+    match gem_query.iter_mut().next() {
+        Some((gem, mut orb)) => {
+            orb.value += expired;
+            commands
+                .entity(gem)
+                .insert(Transform::from_translation(position.extend(0.0)));
+        }
+        None => {
+            let gem = spawn_experience_orb(&mut commands, position, expired);
+            commands.entity(gem).insert((
+                ConsolidatedGem,
                 Sprite {
-                    color: Color::srgb(0.5, 0.8, 1.0),
-                    custom_size: Some(Vec2::new(8.0, 8.0)),
+                    color: Color::srgb(1.0, 0.85, 0.3),
+                    custom_size: Some(Vec2::new(16.0, 16.0)),
                     ..default()
                 },
-                Transform::from_translation(event.position.extend(0.0)),
-                // Add Rapier components
-                RigidBody::Dynamic,
-                Collider::ball(4.0), // Smaller collision radius than visual
-                Sensor,              // Make it a sensor so it doesn't affect physics
-                ActiveEvents::COLLISION_EVENTS,
-                CollisionGroups::new(
-                    Group::GROUP_4, // Experience orb group
-                    Group::GROUP_1, // Player group
-                ),
-                LockedAxes::ROTATION_LOCKED,
-                Damping {
-                    linear_damping: 2.0,
-                    angular_damping: 1.0,
-                },
             ));
         }
     }
+    info!("Swept {} experience from old orbs into a gem", expired);
 }
 
 fn vacuum_system(
@@ -139,9 +273,10 @@ fn vacuum_system(
     for (entity, item_transform, vacuumable, _velocity) in params.p1().iter() {
         let to_player = player_pos - item_transform.translation;
         let distance = to_player.length();
+        let magnet_range = magnet_strength * vacuumable.range_multiplier;
 
-        if distance < magnet_strength {
-            let vacuum_influence = 1.0 - (distance / magnet_strength).powi(2);
+        if distance < magnet_range {
+            let vacuum_influence = 1.0 - (distance / magnet_range).powi(2);
             let vacuum_direction = to_player.normalize();
 
             let speed = vacuumable.base_speed
@@ -161,6 +296,7 @@ fn collect_experience_orbs(
     mut player_query: Query<(Entity, &mut Experience), With<Player>>,
     orb_query: Query<(Entity, &ExperienceOrb), Without<MarkedForDespawn>>,
     mut collision_events: EventReader<CollisionEvent>,
+    mut gameplay_events: EventWriter<GameplayEvent>,
 ) {
     let Ok((player_entity, mut player_exp)) = player_query.get_single_mut() else {
         return;
@@ -180,6 +316,7 @@ fn collect_experience_orbs(
             if let Ok((orb_entity, exp_orb)) = orb_query.get(orb) {
                 info!("Collected {} experience", exp_orb.value);
                 player_exp.current += exp_orb.value;
+                gameplay_events.send(GameplayEvent::Pickup(PickupKind::Experience(exp_orb.value)));
                 commands.entity(orb_entity).insert(MarkedForDespawn);
             }
         }
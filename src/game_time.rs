@@ -0,0 +1,43 @@
+// Clocks for gameplay. Virtual time stops while the game is paused, in a menu or on the
+// results screen, and slows down for the kill cam and set pieces; real time never does.
+// Anything that's part of the run should only ever move on virtual time.
+
+use bevy::prelude::*;
+use std::time::Duration;
+
+/// A `Timer` that can only be ticked by the virtual clock, so it can't keep running
+/// through a pause by being handed a real time delta
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PausableTimer(Timer);
+
+impl PausableTimer {
+    pub fn from_seconds(seconds: f32, mode: TimerMode) -> Self {
+        Self(Timer::from_seconds(seconds, mode))
+    }
+
+    pub fn tick(&mut self, time: &Time<Virtual>) -> &Self {
+        self.0.tick(time.delta());
+        self
+    }
+
+    pub fn set_duration(&mut self, duration: Duration) {
+        self.0.set_duration(duration);
+    }
+
+    /// Starts partway through, e.g. so timers spawned together don't all go off at once
+    pub fn set_elapsed(&mut self, elapsed: Duration) {
+        self.0.set_elapsed(elapsed);
+    }
+
+    pub fn reset(&mut self) {
+        self.0.reset();
+    }
+}
+
+impl std::ops::Deref for PausableTimer {
+    type Target = Timer;
+
+    fn deref(&self) -> &Timer {
+        &self.0
+    }
+}
@@ -0,0 +1,129 @@
+use crate::announcer::DirectorMilestone;
+use crate::boss::BossPhase;
+use crate::combat::DamageEvent;
+use crate::components::{Enemy, EnemyKind};
+use crate::events::EntityDeathEvent;
+use crate::resources::GameState;
+use crate::GameplaySets;
+use bevy::prelude::*;
+
+pub struct GameplayEventsPlugin;
+
+impl Plugin for GameplayEventsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<GameplayEvent>()
+            .configure_sets(
+                Update,
+                (GameplayEventSet::Forward, GameplayEventSet::Observe).chain(),
+            )
+            .configure_sets(
+                Update,
+                // Dying entities are still queryable until cleanup
+                GameplayEventSet::Forward
+                    .after(GameplaySets::Combat)
+                    .before(GameplaySets::Cleanup),
+            )
+            .add_systems(
+                Update,
+                (forward_damage, forward_kills, forward_milestones)
+                    .in_set(GameplayEventSet::Forward)
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+/// Everything noteworthy that happens during a run, in one stream.
+/// This is the stable surface for anything outside the core game (mods, stats, tooling);
+/// internal systems keep using their own events.
+#[derive(Event, Debug, Clone)]
+pub enum GameplayEvent {
+    DamageDealt {
+        target: Entity,
+        amount: i32,
+        source: Option<Entity>,
+    },
+    Kill {
+        entity: Entity,
+        kind: EnemyKind,
+        position: Vec2,
+    },
+    LevelUp {
+        level: u32,
+    },
+    Pickup(PickupKind),
+    Milestone(DirectorMilestone),
+    BossPhase {
+        boss: Entity,
+        phase: BossPhase,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PickupKind {
+    Experience(u32),
+    Gold(u32),
+    Heart(i32), // Health actually restored
+    Bomb,
+}
+
+#[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
+pub enum GameplayEventSet {
+    Forward, // Translating internal events
+    Observe, // Registered observers
+}
+
+pub trait GameplayEventsAppExt {
+    /// Registers a system that watches the run through `EventReader<GameplayEvent>`,
+    /// scheduled after this frame's events have been forwarded
+    fn add_gameplay_observer<M>(&mut self, observer: impl IntoSystemConfigs<M>) -> &mut Self;
+}
+
+impl GameplayEventsAppExt for App {
+    fn add_gameplay_observer<M>(&mut self, observer: impl IntoSystemConfigs<M>) -> &mut Self {
+        self.add_systems(Update, observer.in_set(GameplayEventSet::Observe))
+    }
+}
+
+fn forward_damage(
+    mut damage_events: EventReader<DamageEvent>,
+    enemy_query: Query<(), With<Enemy>>,
+    mut gameplay_events: EventWriter<GameplayEvent>,
+) {
+    for event in damage_events.read() {
+        // Damage the player takes goes through the same event, only report what we deal
+        if event.amount <= 0 || !enemy_query.contains(event.target) {
+            continue;
+        }
+        gameplay_events.send(GameplayEvent::DamageDealt {
+            target: event.target,
+            amount: event.amount,
+            source: event.source,
+        });
+    }
+}
+
+fn forward_kills(
+    mut death_events: EventReader<EntityDeathEvent>,
+    enemy_query: Query<&Enemy>,
+    mut gameplay_events: EventWriter<GameplayEvent>,
+) {
+    for event in death_events.read() {
+        // Attacks expiring also count as deaths internally, only report enemies
+        if let Ok(enemy) = enemy_query.get(event.entity) {
+            gameplay_events.send(GameplayEvent::Kill {
+                entity: event.entity,
+                kind: enemy.kind,
+                position: event.position,
+            });
+        }
+    }
+}
+
+fn forward_milestones(
+    mut milestones: EventReader<DirectorMilestone>,
+    mut gameplay_events: EventWriter<GameplayEvent>,
+) {
+    for milestone in milestones.read() {
+        gameplay_events.send(GameplayEvent::Milestone(*milestone));
+    }
+}
@@ -0,0 +1,47 @@
+use crate::mods::ModsAppExt;
+use crate::save::{EphemeralSave, SaveData};
+use crate::SurvivorsGamePlugin;
+use bevy::log::LogPlugin;
+use bevy::prelude::*;
+use bevy::render::settings::{RenderCreation, WgpuSettings};
+use bevy::render::RenderPlugin;
+use bevy::time::TimeUpdateStrategy;
+use bevy::window::ExitCondition;
+use bevy::winit::WinitPlugin;
+use bevy_prototype_lyon::prelude::ShapePlugin;
+use std::time::Duration;
+
+/// The full game without a window, GPU or logging, for tooling that plays runs by itself.
+/// Every `update()` advances game time by exactly `timestep`, so runs go as fast as the CPU allows.
+/// `finish()` and `cleanup()` have already been called, drive it with `update()`.
+pub fn headless_app(timestep: Duration) -> App {
+    let mut app = App::new();
+    app.add_mods_asset_source()
+        .add_plugins(
+            DefaultPlugins
+                .set(WindowPlugin {
+                    primary_window: None,
+                    exit_condition: ExitCondition::DontExit,
+                    close_when_requested: false,
+                })
+                .set(RenderPlugin {
+                    render_creation: RenderCreation::Automatic(WgpuSettings {
+                        backends: None,
+                        ..default()
+                    }),
+                    ..default()
+                })
+                .disable::<WinitPlugin>()
+                .disable::<LogPlugin>(),
+        )
+        .add_plugins(ShapePlugin)
+        .add_plugins(SurvivorsGamePlugin)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(timestep))
+        // Account bonuses from the real save would skew every run
+        .insert_resource(SaveData::default())
+        .insert_resource(EphemeralSave);
+
+    app.finish();
+    app.cleanup();
+    app
+}
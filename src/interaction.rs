@@ -0,0 +1,153 @@
+use crate::components::Player;
+use crate::controls::{key_name, Action, ActionInput, KeyBindings};
+use crate::materials::Highlightable;
+use crate::player_handle::PlayerHandle;
+use crate::resources::GameState;
+use bevy::prelude::*;
+
+pub struct InteractionPlugin;
+
+impl Plugin for InteractionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<InteractEvent>()
+            .init_resource::<FocusedInteractable>()
+            .add_systems(
+                Update,
+                (
+                    attach_interaction_prompts,
+                    update_interaction_focus,
+                    update_interaction_prompts,
+                    handle_interact_input,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+/// Anything the player can walk up to and use (merchants, shrines, chests)
+#[derive(Component)]
+pub struct Interactable {
+    pub range: f32,
+    pub prompt: String,
+}
+
+impl Interactable {
+    pub fn new(prompt: impl Into<String>) -> Self {
+        Self {
+            range: 48.0,
+            prompt: prompt.into(),
+        }
+    }
+}
+
+// World-space "Press <key>" label, spawned as a child of the interactable
+#[derive(Component)]
+pub struct InteractionPrompt;
+
+#[derive(Event)]
+pub struct InteractEvent {
+    pub player: Entity,
+    pub target: Entity,
+}
+
+/// The closest interactable in range, if any. Only this one shows a prompt.
+#[derive(Resource, Default)]
+pub struct FocusedInteractable(pub Option<Entity>);
+
+const INTERACT_BUTTON: GamepadButton = GamepadButton::West;
+
+fn attach_interaction_prompts(
+    mut commands: Commands,
+    bindings: Res<KeyBindings>,
+    new_interactables: Query<(Entity, &Interactable, Option<&Highlightable>), Added<Interactable>>,
+) {
+    // Rebinding only happens from the main menu, so the key can't change under a prompt
+    let key = key_name(bindings.primary(Action::Interact));
+
+    for (entity, interactable, highlightable) in new_interactables.iter() {
+        // Outline kicks in at the same distance as the prompt
+        if highlightable.is_none() {
+            commands.entity(entity).insert(Highlightable {
+                range: interactable.range,
+                ..default()
+            });
+        }
+
+        commands.entity(entity).with_children(|parent| {
+            parent.spawn((
+                Text2d::new(format!("Press {}: {}", key, interactable.prompt)),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                Transform::from_xyz(0.0, 28.0, 1.0),
+                Visibility::Hidden,
+                InteractionPrompt,
+            ));
+        });
+    }
+}
+
+fn update_interaction_focus(
+    mut focused: ResMut<FocusedInteractable>,
+    player_handle: Res<PlayerHandle>,
+    player_query: Query<&GlobalTransform, With<Player>>,
+    interactables: Query<(Entity, &Interactable, &GlobalTransform)>,
+) {
+    let Some(player_transform) = player_handle.fetch(&player_query) else {
+        focused.0 = None;
+        return;
+    };
+    let player_pos = player_transform.translation().truncate();
+
+    let nearest = interactables
+        .iter()
+        .filter_map(|(entity, interactable, transform)| {
+            let distance = player_pos.distance(transform.translation().truncate());
+            (distance <= interactable.range).then_some((entity, distance))
+        })
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(entity, _)| entity);
+
+    if focused.0 != nearest {
+        focused.0 = nearest;
+    }
+}
+
+fn update_interaction_prompts(
+    focused: Res<FocusedInteractable>,
+    mut prompts: Query<(&Parent, &mut Visibility), With<InteractionPrompt>>,
+) {
+    if !focused.is_changed() {
+        return;
+    }
+
+    for (parent, mut visibility) in prompts.iter_mut() {
+        *visibility = if focused.0 == Some(parent.get()) {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+fn handle_interact_input(
+    actions: ActionInput,
+    gamepads: Query<&Gamepad>,
+    focused: Res<FocusedInteractable>,
+    player_handle: Res<PlayerHandle>,
+    mut interact_events: EventWriter<InteractEvent>,
+) {
+    let gamepad_pressed = gamepads
+        .iter()
+        .any(|gamepad| gamepad.just_pressed(INTERACT_BUTTON));
+    if !actions.just_pressed(Action::Interact) && !gamepad_pressed {
+        return;
+    }
+
+    if let (Some(target), Some(player)) = (focused.0, player_handle.get()) {
+        interact_events.send(InteractEvent { player, target });
+    }
+}
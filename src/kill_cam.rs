@@ -0,0 +1,115 @@
+use crate::camera::follow_player;
+use crate::resources::GameState;
+use bevy::prelude::*;
+
+/// When the player dies the game drops into slow motion and the camera closes in on
+/// whatever landed the killing blow, then the results screen comes up.
+/// Runs on real time so the slow motion doesn't drag it out.
+pub struct KillCamPlugin;
+
+impl Plugin for KillCamPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<KillCam>()
+            .add_systems(Update, run_kill_cam.run_if(in_state(GameState::Playing)))
+            .add_systems(
+                PostUpdate,
+                frame_killing_blow
+                    .after(follow_player)
+                    .before(TransformSystem::TransformPropagate)
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(OnEnter(GameState::GameOver), reset_kill_cam);
+    }
+}
+
+/// The death being replayed, if any
+#[derive(Resource, Default)]
+pub struct KillCam {
+    shot: Option<KillShot>,
+}
+
+struct KillShot {
+    killer: Option<Entity>,
+    position: Vec2, // Where the camera is headed, kept if the killer goes away
+    timer: Timer,
+}
+
+const KILL_CAM_DURATION: f32 = 1.5; // Real seconds
+const KILL_CAM_TIME_SCALE: f32 = 0.2;
+const KILL_CAM_ZOOM: f32 = 0.5; // Projection scale at the end of the zoom
+
+// How quickly the camera closes in, per second
+const KILL_CAM_RATE: f32 = 4.0;
+
+impl KillCam {
+    /// Starts on the killer, or on where the player fell when nothing is known about it
+    pub fn start(&mut self, killer: Option<Entity>, position: Vec2) {
+        self.shot = Some(KillShot {
+            killer,
+            position,
+            timer: Timer::from_seconds(KILL_CAM_DURATION, TimerMode::Once),
+        });
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.shot.is_some()
+    }
+
+    pub fn killer(&self) -> Option<Entity> {
+        self.shot.as_ref().and_then(|shot| shot.killer)
+    }
+}
+
+fn run_kill_cam(
+    real_time: Res<Time<Real>>,
+    mut kill_cam: ResMut<KillCam>,
+    mut time: ResMut<Time<Virtual>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let Some(shot) = kill_cam.shot.as_mut() else {
+        return;
+    };
+
+    time.set_relative_speed(KILL_CAM_TIME_SCALE);
+    if shot.timer.tick(real_time.delta()).finished() {
+        next_state.set(GameState::GameOver);
+    }
+}
+
+/// Eases the camera onto the killer and zooms in
+fn frame_killing_blow(
+    real_time: Res<Time<Real>>,
+    mut kill_cam: ResMut<KillCam>,
+    focus_query: Query<&GlobalTransform>,
+    mut camera_query: Query<(&mut Transform, &mut OrthographicProjection), With<Camera2d>>,
+) {
+    let Some(shot) = kill_cam.shot.as_mut() else {
+        return;
+    };
+    if let Some(focus) = shot.killer.and_then(|entity| focus_query.get(entity).ok()) {
+        shot.position = focus.translation().truncate();
+    }
+
+    let blend = 1.0 - (-KILL_CAM_RATE * real_time.delta_secs()).exp();
+    for (mut camera_transform, mut projection) in camera_query.iter_mut() {
+        let target = camera_transform
+            .translation
+            .truncate()
+            .lerp(shot.position, blend);
+        camera_transform.translation.x = target.x;
+        camera_transform.translation.y = target.y;
+        projection.scale += (KILL_CAM_ZOOM - projection.scale) * blend;
+    }
+}
+
+fn reset_kill_cam(
+    mut kill_cam: ResMut<KillCam>,
+    mut time: ResMut<Time<Virtual>>,
+    mut projection_query: Query<&mut OrthographicProjection, With<Camera2d>>,
+) {
+    kill_cam.shot = None;
+    time.set_relative_speed(1.0);
+    for mut projection in projection_query.iter_mut() {
+        projection.scale = 1.0;
+    }
+}
@@ -0,0 +1,178 @@
+use crate::events::EntityDeathEvent;
+use crate::interaction::InteractEvent;
+use crate::menu::WeaponUpgradeConfirmedEvent;
+use crate::resources::GameState;
+use crate::supply_drop::SupplyCrate;
+use crate::GameplaySets;
+use bevy::prelude::*;
+
+pub struct KillFeedPlugin;
+
+impl Plugin for KillFeedPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Playing), spawn_kill_feed)
+            .add_systems(OnExit(GameState::Playing), cleanup_kill_feed)
+            .add_systems(
+                Update,
+                (
+                    (
+                        // Dying entities are still around until cleanup, so we can still look at them
+                        feed_notable_deaths
+                            .after(GameplaySets::Combat)
+                            .before(GameplaySets::Cleanup),
+                        feed_weapon_upgrades,
+                        feed_chest_pickups.before(GameplaySets::Cleanup),
+                        fade_kill_feed_entries,
+                    ),
+                    // Once every row of the frame is in, so the cap sees all of them
+                    trim_kill_feed,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+/// Deaths worth calling out in the feed (elites, bosses), with the name to show
+#[derive(Component)]
+pub struct Notable(pub String);
+
+#[derive(Component)]
+pub struct KillFeed;
+
+#[derive(Component)]
+pub struct KillFeedEntry {
+    pub timer: Timer,
+}
+
+const MAX_FEED_ENTRIES: usize = 5;
+const FEED_ENTRY_DURATION: f32 = 4.0;
+
+fn spawn_kill_feed(mut commands: Commands) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(10.0),
+            top: Val::Px(44.0), // Just below the kill counter
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::FlexEnd,
+            row_gap: Val::Px(4.0),
+            ..default()
+        },
+        KillFeed,
+    ));
+}
+
+fn cleanup_kill_feed(mut commands: Commands, feed_query: Query<Entity, With<KillFeed>>) {
+    for entity in feed_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn push_feed_entry(commands: &mut Commands, feed_entity: Entity, message: String, color: Color) {
+    commands.entity(feed_entity).with_children(|parent| {
+        parent.spawn((
+            Text::new(message),
+            TextFont {
+                font_size: 16.0,
+                ..default()
+            },
+            TextColor(color),
+            KillFeedEntry {
+                timer: Timer::from_seconds(FEED_ENTRY_DURATION, TimerMode::Once),
+            },
+        ));
+    });
+}
+
+fn feed_notable_deaths(
+    mut commands: Commands,
+    mut death_events: EventReader<EntityDeathEvent>,
+    notable_query: Query<&Notable>,
+    feed_query: Query<Entity, With<KillFeed>>,
+) {
+    let Ok(feed) = feed_query.get_single() else {
+        return;
+    };
+
+    for event in death_events.read() {
+        if let Ok(notable) = notable_query.get(event.entity) {
+            push_feed_entry(
+                &mut commands,
+                feed,
+                format!("{} slain", notable.0),
+                Color::srgb(1.0, 0.5, 0.2),
+            );
+        }
+    }
+}
+
+fn feed_weapon_upgrades(
+    mut commands: Commands,
+    mut upgrade_events: EventReader<WeaponUpgradeConfirmedEvent>,
+    feed_query: Query<Entity, With<KillFeed>>,
+) {
+    let Ok(feed) = feed_query.get_single() else {
+        return;
+    };
+
+    for event in upgrade_events.read() {
+        push_feed_entry(
+            &mut commands,
+            feed,
+            format!("{} upgraded", event.weapon_type),
+            Color::srgb(0.6, 0.8, 1.0),
+        );
+    }
+}
+
+fn feed_chest_pickups(
+    mut commands: Commands,
+    mut interact_events: EventReader<InteractEvent>,
+    crate_query: Query<(), With<SupplyCrate>>,
+    feed_query: Query<Entity, With<KillFeed>>,
+) {
+    let Ok(feed) = feed_query.get_single() else {
+        return;
+    };
+
+    for event in interact_events.read() {
+        if crate_query.contains(event.target) {
+            push_feed_entry(
+                &mut commands,
+                feed,
+                "Supply crate opened".to_string(),
+                Color::srgb(1.0, 0.85, 0.3),
+            );
+        }
+    }
+}
+
+fn trim_kill_feed(mut commands: Commands, feed_query: Query<&Children, With<KillFeed>>) {
+    // Oldest rows are first, drop them to make room
+    for children in feed_query.iter() {
+        let excess = children.len().saturating_sub(MAX_FEED_ENTRIES);
+        for &old in children.iter().take(excess) {
+            commands.entity(old).despawn_recursive();
+        }
+    }
+}
+
+fn fade_kill_feed_entries(
+    mut commands: Commands,
+    time: Res<Time<Virtual>>,
+    mut entry_query: Query<(Entity, &mut KillFeedEntry, &mut TextColor)>,
+) {
+    for (entity, mut entry, mut color) in entry_query.iter_mut() {
+        entry.timer.tick(time.delta());
+
+        if entry.timer.finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        // Fully visible for most of its life, fades over the last quarter
+        let alpha = (entry.timer.remaining_secs() / (FEED_ENTRY_DURATION * 0.25)).min(1.0);
+        color.0.set_alpha(alpha);
+    }
+}
@@ -0,0 +1,314 @@
+pub mod aim;
+pub mod announcer;
+pub mod balance;
+pub mod bomb;
+pub mod boss;
+pub mod camera;
+pub mod character_select;
+pub mod codex;
+pub mod combat;
+pub mod components;
+pub mod controls;
+pub mod death;
+pub mod definitions;
+pub mod devtools;
+pub mod difficulty;
+pub mod director;
+pub mod elite;
+pub mod enemy_pool;
+pub mod enemy_projectile;
+pub mod equipment;
+pub mod events;
+pub mod experience;
+pub mod game_time;
+pub mod gameplay_events;
+pub mod headless;
+pub mod interaction;
+pub mod kill_cam;
+pub mod kill_feed;
+pub mod loadout;
+pub mod materials;
+pub mod menu;
+pub mod milestone;
+pub mod minimap;
+pub mod mods;
+pub mod number_format;
+pub mod past_runs;
+pub mod performance;
+pub mod physics;
+pub mod pickup;
+pub mod player_handle;
+pub mod post_processing;
+pub mod resources;
+pub mod run_history;
+pub mod run_reset;
+pub mod save;
+pub mod set_piece;
+pub mod settings;
+pub mod spatial_grid;
+pub mod spawn_validation;
+pub mod status;
+pub mod status_icons;
+pub mod supply_drop;
+pub mod systems;
+pub mod telemetry;
+pub mod trail;
+pub mod types;
+pub mod ui;
+pub mod unlock_reveal;
+pub mod upgrade;
+pub mod waves;
+pub mod weapons;
+
+use crate::aim::AimPlugin;
+use crate::announcer::AnnouncerPlugin;
+use crate::bomb::BombPlugin;
+use crate::boss::BossPlugin;
+use crate::camera::CameraPlugin;
+use crate::character_select::CharacterSelectPlugin;
+use crate::codex::CodexPlugin;
+use crate::combat::{handle_damage, tick_invulnerability, DamageEvent};
+use crate::controls::ControlsPlugin;
+use crate::death::{cleanup_marked_entities, death_system};
+use crate::definitions::DefinitionsPlugin;
+#[cfg(debug_assertions)]
+use crate::devtools::DevToolsPlugin;
+use crate::difficulty::DifficultyPlugin;
+use crate::director::DirectorPlugin;
+use crate::elite::ElitePlugin;
+use crate::enemy_pool::EnemyPoolPlugin;
+use crate::enemy_projectile::EnemyProjectilePlugin;
+use crate::equipment::apply_equipment_stats;
+use crate::events::EntityDeathEvent;
+use crate::experience::ExperiencePlugin;
+use crate::gameplay_events::GameplayEventsPlugin;
+use crate::interaction::InteractionPlugin;
+use crate::kill_cam::KillCamPlugin;
+use crate::kill_feed::KillFeedPlugin;
+use crate::loadout::LoadoutPlugin;
+use crate::materials::MaterialsPlugin;
+use crate::menu::{GenericUpgradeConfirmedEvent, MenuPlugin};
+use crate::milestone::MilestonePlugin;
+use crate::minimap::MinimapPlugin;
+use crate::past_runs::PastRunsPlugin;
+use crate::performance::PerformancePlugin;
+use crate::physics::PhysicsPlugin;
+use crate::pickup::PickupPlugin;
+use crate::player_handle::PlayerHandlePlugin;
+use crate::post_processing::PostProcessingPlugin;
+use crate::resources::{GameRng, GameState, GameStats, RunModifiers, SurgeProgress, WaveConfig};
+use crate::run_history::RunHistoryPlugin;
+use crate::run_reset::RunResetPlugin;
+use crate::save::SavePlugin;
+use crate::set_piece::SetPiecePlugin;
+use crate::settings::{QualitySettings, SettingsPlugin};
+use crate::spatial_grid::SpatialGridPlugin;
+use crate::spawn_validation::SpawnValidationPlugin;
+use crate::status::StatusEffectPlugin;
+use crate::status_icons::StatusIconPlugin;
+use crate::supply_drop::SupplyDropPlugin;
+use crate::systems::{
+    enemy_movement, gameplay_movement_system, handle_pause_state, load_textures, quit_game,
+    reset_run_clock, spawn_camera, spawn_player, spawn_surges, universal_input_system,
+};
+use crate::telemetry::TelemetryPlugin;
+use crate::trail::TrailPlugin;
+use crate::ui::{
+    apply_hud_preview, cleanup_ui, cycle_hud_preview, spawn_ui, toggle_weapon_stats_panel,
+    update_game_timer, update_health_ui, update_kill_counter, update_modifier_tooltip,
+    update_wave_text, update_weapon_stats_panel, HudPreview, WeaponStatsPanel,
+};
+use crate::unlock_reveal::UnlockRevealPlugin;
+use crate::upgrade::{
+    handle_equipment_upgrade, handle_generic_upgrade, heal_on_level_up, LevelUpHealing,
+};
+use crate::waves::WavePlugin;
+use crate::weapons::WeaponPlugin;
+use bevy::prelude::*;
+use upgrade::UpgradePool;
+
+// First, let's organize our systems into sets for better control
+#[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
+enum GameplaySets {
+    Input,
+    UI,
+    Movement,
+    Combat,
+    Spawning,
+    Physics,
+    Cleanup,
+}
+
+pub struct SurvivorsGamePlugin;
+
+impl Plugin for SurvivorsGamePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            // Resources
+            .init_resource::<Time<Virtual>>()
+            .init_resource::<GameStats>()
+            .init_resource::<SurgeProgress>()
+            .init_resource::<WaveConfig>()
+            .insert_resource(RunModifiers::from_args())
+            .insert_resource(GameRng::from_args())
+            .init_resource::<UpgradePool>()
+            .init_resource::<LevelUpHealing>()
+            .init_resource::<WeaponStatsPanel>()
+            .init_resource::<HudPreview>()
+            .insert_resource(QualitySettings::load())
+            // Events
+            .add_event::<DamageEvent>()
+            .add_event::<EntityDeathEvent>()
+            .add_event::<GenericUpgradeConfirmedEvent>()
+            // States
+            .insert_state(GameState::MainMenu)
+            // Plugins
+            .add_plugins(MenuPlugin)
+            .add_plugins(MaterialsPlugin)
+            .add_plugins(PhysicsPlugin)
+            .add_plugins(ExperiencePlugin)
+            .add_plugins(WeaponPlugin)
+            .add_plugins(TrailPlugin)
+            .add_plugins(PostProcessingPlugin)
+            .add_plugins(AnnouncerPlugin)
+            .add_plugins(InteractionPlugin)
+            .add_plugins(SupplyDropPlugin)
+            .add_plugins(SavePlugin)
+            .add_plugins(CodexPlugin)
+            .add_plugins(SpatialGridPlugin)
+            .add_plugins(LoadoutPlugin)
+            .add_plugins(CharacterSelectPlugin)
+            .add_plugins(SettingsPlugin)
+            .add_plugins(ControlsPlugin)
+            .add_plugins(TelemetryPlugin)
+            .add_plugins(MinimapPlugin)
+            .add_plugins(KillFeedPlugin)
+            .add_plugins(KillCamPlugin)
+            .add_plugins(UnlockRevealPlugin)
+            .add_plugins(RunHistoryPlugin)
+            .add_plugins(PastRunsPlugin)
+            .add_plugins(RunResetPlugin)
+            .add_plugins(MilestonePlugin)
+            .add_plugins(GameplayEventsPlugin)
+            .add_plugins(DefinitionsPlugin)
+            .add_plugins(SpawnValidationPlugin)
+            .add_plugins(DirectorPlugin)
+            .add_plugins(CameraPlugin)
+            .add_plugins(PerformancePlugin)
+            .add_plugins(StatusEffectPlugin)
+            .add_plugins(StatusIconPlugin)
+            .add_plugins(EnemyProjectilePlugin)
+            .add_plugins(ElitePlugin)
+            .add_plugins(BossPlugin)
+            .add_plugins(BombPlugin)
+            .add_plugins(PickupPlugin)
+            .add_plugins(PlayerHandlePlugin)
+            .add_plugins(DifficultyPlugin)
+            .add_plugins(WavePlugin)
+            .add_plugins(SetPiecePlugin)
+            .add_plugins(EnemyPoolPlugin)
+            .add_plugins(AimPlugin)
+            // Startup systems
+            .add_systems(
+                Startup,
+                (
+                    load_textures,
+                    spawn_player.after(load_textures),
+                    spawn_camera,
+                ),
+            )
+            // Configure system sets
+            .configure_sets(
+                Update,
+                (
+                    GameplaySets::Input,
+                    GameplaySets::UI,
+                    GameplaySets::Physics,
+                    GameplaySets::Movement,
+                    GameplaySets::Combat,
+                    GameplaySets::Spawning,
+                    GameplaySets::Cleanup,
+                )
+                    .chain(),
+            )
+            // Add systems by set
+            .add_systems(
+                Update,
+                (
+                    // Combat
+                    tick_invulnerability.before(handle_damage),
+                    handle_damage,
+                    death_system,
+                )
+                    .in_set(GameplaySets::Combat)
+                    .after(GameplaySets::Physics)
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(
+                Update,
+                cleanup_marked_entities
+                    .in_set(GameplaySets::Cleanup)
+                    .run_if(in_state(GameState::Playing)),
+            )
+            // Add systems to sets and run them only in Playing state
+            .add_systems(
+                Update,
+                (
+                    // Input
+                    (gameplay_movement_system, enemy_movement)
+                        .in_set(GameplaySets::Movement)
+                        .run_if(in_state(GameState::Playing)),
+                    // Spawning
+                    spawn_surges
+                        .in_set(GameplaySets::Spawning)
+                        .run_if(in_state(GameState::Playing)),
+                ),
+            )
+            .add_systems(
+                Update,
+                // Level-up healing goes last so it scales off a freshly raised maximum
+                (
+                    handle_generic_upgrade,
+                    handle_equipment_upgrade,
+                    apply_equipment_stats,
+                    heal_on_level_up,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            )
+            // UI-related systems
+            .add_systems(
+                OnEnter(GameState::Playing),
+                spawn_ui.in_set(GameplaySets::UI),
+            )
+            .add_systems(OnExit(GameState::Playing), cleanup_ui)
+            .add_systems(
+                Update,
+                (
+                    update_health_ui,
+                    update_game_timer,
+                    update_kill_counter,
+                    update_wave_text,
+                    (toggle_weapon_stats_panel, update_weapon_stats_panel).chain(),
+                    update_modifier_tooltip,
+                    (cycle_hud_preview, apply_hud_preview).chain(),
+                )
+                    .in_set(GameplaySets::UI)
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(OnEnter(GameState::Quit), quit_game)
+            .add_systems(OnExit(GameState::MainMenu), reset_run_clock)
+            // Universal input handling
+            .add_systems(Update, universal_input_system.in_set(GameplaySets::Input))
+            .add_systems(
+                Update,
+                handle_pause_state
+                    .in_set(GameplaySets::Input)
+                    .before(GameplaySets::Physics),
+            );
+
+        // Developer panels stay out of release builds
+        #[cfg(debug_assertions)]
+        app.add_plugins(DevToolsPlugin);
+    }
+}
@@ -0,0 +1,112 @@
+use crate::components::{Character, Player};
+use crate::menu::{MenuAction, MenuActionComponent, MenuInput, MenuItem};
+use crate::resources::{GameState, Mutator, RunModifiers};
+use crate::save::SaveData;
+use crate::weapons::{StartingWeapon, WeaponType};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub struct LoadoutPlugin;
+
+impl Plugin for LoadoutPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            handle_loadout_actions
+                .run_if(in_state(GameState::MainMenu).or(in_state(GameState::Paused))),
+        );
+    }
+}
+
+/// A saved build to start a run with, for quickly repeating the same test setup
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LoadoutPreset {
+    pub name: String,
+    pub character: Character,
+    pub starting_weapon: WeaponType,
+    pub mutators: Vec<Mutator>,
+}
+
+impl LoadoutPreset {
+    pub fn new(character: Character, starting_weapon: WeaponType, mutators: Vec<Mutator>) -> Self {
+        // Named after its contents, so saving the same build twice overwrites it
+        let mut name = format!("{} - {}", character, starting_weapon);
+        for mutator in &mutators {
+            name.push_str(&format!(" + {}", mutator));
+        }
+
+        Self {
+            name,
+            character,
+            starting_weapon,
+            mutators,
+        }
+    }
+}
+
+/// Sets who the next run is played as and what they start with.
+/// The player's inventory is only set up once Playing starts, so this is still in time.
+pub fn apply_loadout(
+    player_query: &mut Query<(&mut Character, &mut StartingWeapon), With<Player>>,
+    character: Character,
+    weapon: WeaponType,
+) {
+    if let Ok((mut player_character, mut starting_weapon)) = player_query.get_single_mut() {
+        *player_character = character;
+        starting_weapon.0 = weapon;
+    }
+}
+
+fn handle_loadout_actions(
+    menu_input: MenuInput,
+    menu_items: Query<(&MenuItem, &MenuActionComponent, Ref<Interaction>, &Children)>,
+    mut button_text: Query<&mut Text>,
+    mut player_query: Query<(&mut Character, &mut StartingWeapon), With<Player>>,
+    mut run_modifiers: ResMut<RunModifiers>,
+    mut save_data: ResMut<SaveData>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for (menu_item, action_component, interaction, children) in menu_items.iter() {
+        if !menu_input.is_confirmed(menu_item, &interaction) {
+            continue;
+        }
+
+        match &action_component.action {
+            MenuAction::SelectLoadout(preset) => {
+                info!("Starting run with loadout: {}", preset.name);
+                run_modifiers.mutators = preset.mutators.clone();
+                apply_loadout(&mut player_query, preset.character, preset.starting_weapon);
+                next_state.set(GameState::Playing);
+            }
+            MenuAction::SaveLoadout => {
+                let Ok((character, starting_weapon)) = player_query.get_single() else {
+                    continue;
+                };
+
+                let preset = LoadoutPreset::new(
+                    *character,
+                    starting_weapon.0,
+                    run_modifiers.mutators.clone(),
+                );
+                info!("Saving loadout: {}", preset.name);
+
+                let loadouts = &mut save_data.loadouts;
+                match loadouts
+                    .iter_mut()
+                    .find(|existing| existing.name == preset.name)
+                {
+                    Some(existing) => *existing = preset,
+                    None => loadouts.push(preset),
+                }
+
+                if let Some(mut text) = children
+                    .first()
+                    .and_then(|&child| button_text.get_mut(child).ok())
+                {
+                    text.0 = "Loadout Saved".to_string();
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,297 @@
+use crate::components::{Facing, Player};
+use crate::resources::GameTextures;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::render::render_resource::{AsBindGroup, ShaderRef};
+use bevy::sprite::{AlphaMode2d, Material2d, Material2dPlugin};
+
+pub struct MaterialsPlugin;
+
+impl Plugin for MaterialsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(Material2dPlugin::<FlashMaterial>::default())
+            .init_resource::<SpriteQuad>()
+            .add_systems(
+                Update,
+                (
+                    update_flash_effects,
+                    update_tints,
+                    update_highlights,
+                    flip_facing_sprites,
+                ),
+            );
+    }
+}
+
+/// Shared sprite material: every character samples the same texture, only the uniforms differ
+#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
+pub struct FlashMaterial {
+    #[uniform(0)]
+    pub tint: LinearRgba,
+    #[uniform(0)]
+    pub flash_color: LinearRgba,
+    #[uniform(0)]
+    pub uv_rect: Vec4, // Atlas sub-rect in UV space, min.x > max.x mirrors it
+    #[uniform(0)]
+    pub outline_color: LinearRgba,
+    #[uniform(0)]
+    pub flash_amount: f32,
+    #[uniform(0)]
+    pub outline_width: f32, // In texels, 0.0 disables the outline
+    #[texture(1)]
+    #[sampler(2)]
+    pub texture: Handle<Image>,
+}
+
+impl FlashMaterial {
+    pub fn new(texture: Handle<Image>, uv_rect: Vec4) -> Self {
+        Self {
+            tint: LinearRgba::WHITE,
+            flash_color: LinearRgba::WHITE,
+            uv_rect,
+            outline_color: LinearRgba::WHITE,
+            flash_amount: 0.0,
+            outline_width: 0.0,
+            texture,
+        }
+    }
+}
+
+impl Material2d for FlashMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/flash_material.wgsl".into()
+    }
+
+    fn alpha_mode(&self) -> AlphaMode2d {
+        AlphaMode2d::Blend
+    }
+}
+
+// All characters are drawn on the same 32x32 quad
+#[derive(Resource)]
+pub struct SpriteQuad(pub Handle<Mesh>);
+
+impl FromWorld for SpriteQuad {
+    fn from_world(world: &mut World) -> Self {
+        let mut meshes = world.resource_mut::<Assets<Mesh>>();
+        Self(meshes.add(Rectangle::new(32.0, 32.0)))
+    }
+}
+
+/// Briefly replaces the sprite's color, e.g. white on hit
+#[derive(Component)]
+pub struct FlashEffect {
+    pub color: Color,
+    pub timer: Timer,
+}
+
+impl FlashEffect {
+    pub fn new(color: Color, duration: f32) -> Self {
+        Self {
+            color,
+            timer: Timer::from_seconds(duration, TimerMode::Once),
+        }
+    }
+
+    pub fn hit() -> Self {
+        Self::new(Color::WHITE, 0.1)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StatusTint {
+    Bound,
+    Poisoned,
+    Frozen,
+    Burning,
+}
+
+/// Persistent color multiplier, removed when the status ends
+#[derive(Component, Clone, Copy)]
+pub struct Tint(pub Color);
+
+impl Tint {
+    pub fn status(status: StatusTint) -> Self {
+        match status {
+            StatusTint::Bound => Self(Color::srgb(0.75, 0.6, 1.0)),
+            StatusTint::Poisoned => Self(Color::srgb(0.55, 1.0, 0.45)),
+            StatusTint::Frozen => Self(Color::srgb(0.55, 0.85, 1.0)),
+            StatusTint::Burning => Self(Color::srgb(1.0, 0.6, 0.35)),
+        }
+    }
+}
+
+/// Outlines the sprite while the player is within `range`
+#[derive(Component)]
+pub struct Highlightable {
+    pub range: f32,
+    pub color: Color,
+}
+
+impl Default for Highlightable {
+    fn default() -> Self {
+        Self {
+            range: 64.0,
+            color: Color::srgb(1.0, 0.85, 0.3),
+        }
+    }
+}
+
+const HIGHLIGHT_OUTLINE_WIDTH: f32 = 1.0;
+
+/// Convenience access to the sprite sheets as `FlashMaterial` quads
+#[derive(SystemParam)]
+pub struct SpriteMaterials<'w> {
+    quad: Res<'w, SpriteQuad>,
+    textures: Res<'w, GameTextures>,
+    layouts: Res<'w, Assets<TextureAtlasLayout>>,
+    materials: ResMut<'w, Assets<FlashMaterial>>,
+}
+
+impl SpriteMaterials<'_> {
+    pub fn player(&mut self) -> (Mesh2d, MeshMaterial2d<FlashMaterial>) {
+        let texture = self.textures.player.clone();
+        let layout = self.textures.player_layout.clone();
+        self.atlas_sprite(texture, &layout, 0)
+    }
+
+    pub fn enemy(&mut self, index: usize) -> (Mesh2d, MeshMaterial2d<FlashMaterial>) {
+        let texture = self.textures.enemies.clone();
+        let layout = self.textures.enemies_layout.clone();
+        self.atlas_sprite(texture, &layout, index)
+    }
+
+    fn atlas_sprite(
+        &mut self,
+        texture: Handle<Image>,
+        layout: &Handle<TextureAtlasLayout>,
+        index: usize,
+    ) -> (Mesh2d, MeshMaterial2d<FlashMaterial>) {
+        let uv_rect = self
+            .layouts
+            .get(layout)
+            .map_or(Vec4::new(0.0, 0.0, 1.0, 1.0), |layout| {
+                atlas_uv_rect(layout, index)
+            });
+
+        (
+            Mesh2d(self.quad.0.clone()),
+            MeshMaterial2d(self.materials.add(FlashMaterial::new(texture, uv_rect))),
+        )
+    }
+}
+
+pub fn atlas_uv_rect(layout: &TextureAtlasLayout, index: usize) -> Vec4 {
+    let rect = layout.textures[index];
+    let size = layout.size.as_vec2();
+    Vec4::new(
+        rect.min.x as f32 / size.x,
+        rect.min.y as f32 / size.y,
+        rect.max.x as f32 / size.x,
+        rect.max.y as f32 / size.y,
+    )
+}
+
+// Mirrors the atlas rect horizontally whenever facing crosses over to the other side
+fn flip_facing_sprites(
+    mut materials: ResMut<Assets<FlashMaterial>>,
+    query: Query<(&Facing, &MeshMaterial2d<FlashMaterial>), Changed<Facing>>,
+) {
+    for (facing, material_handle) in query.iter() {
+        // Read first, `get_mut` would re-upload the material every frame the player moves
+        let Some(material) = materials.get(&material_handle.0) else {
+            continue;
+        };
+        let flipped = material.uv_rect.x > material.uv_rect.z;
+        if flipped == facing.is_left() {
+            continue;
+        }
+
+        if let Some(material) = materials.get_mut(&material_handle.0) {
+            let rect = material.uv_rect;
+            material.uv_rect = Vec4::new(rect.z, rect.y, rect.x, rect.w);
+        }
+    }
+}
+
+fn update_flash_effects(
+    mut commands: Commands,
+    time: Res<Time<Virtual>>,
+    mut materials: ResMut<Assets<FlashMaterial>>,
+    mut query: Query<(Entity, &mut FlashEffect, &MeshMaterial2d<FlashMaterial>)>,
+) {
+    for (entity, mut flash, material_handle) in query.iter_mut() {
+        flash.timer.tick(time.delta());
+
+        let Some(material) = materials.get_mut(&material_handle.0) else {
+            continue;
+        };
+
+        if flash.timer.finished() {
+            material.flash_amount = 0.0;
+            commands.entity(entity).remove::<FlashEffect>();
+        } else {
+            material.flash_color = flash.color.to_linear();
+            material.flash_amount = 1.0 - flash.timer.fraction();
+        }
+    }
+}
+
+fn update_tints(
+    mut materials: ResMut<Assets<FlashMaterial>>,
+    changed_tints: Query<(&Tint, &MeshMaterial2d<FlashMaterial>), Changed<Tint>>,
+    mut removed_tints: RemovedComponents<Tint>,
+    material_handles: Query<&MeshMaterial2d<FlashMaterial>>,
+) {
+    for (tint, material_handle) in changed_tints.iter() {
+        if let Some(material) = materials.get_mut(&material_handle.0) {
+            material.tint = tint.0.to_linear();
+        }
+    }
+
+    for entity in removed_tints.read() {
+        if let Ok(material_handle) = material_handles.get(entity) {
+            if let Some(material) = materials.get_mut(&material_handle.0) {
+                material.tint = LinearRgba::WHITE;
+            }
+        }
+    }
+}
+
+fn update_highlights(
+    mut materials: ResMut<Assets<FlashMaterial>>,
+    player_query: Query<&GlobalTransform, With<Player>>,
+    highlightables: Query<(
+        &Highlightable,
+        &GlobalTransform,
+        &MeshMaterial2d<FlashMaterial>,
+    )>,
+) {
+    let player_pos = player_query
+        .get_single()
+        .map(|transform| transform.translation().truncate())
+        .ok();
+
+    for (highlightable, transform, material_handle) in highlightables.iter() {
+        let in_range = player_pos.is_some_and(|player_pos| {
+            player_pos.distance(transform.translation().truncate()) <= highlightable.range
+        });
+        let outline_width = if in_range {
+            HIGHLIGHT_OUTLINE_WIDTH
+        } else {
+            0.0
+        };
+
+        // Only touch the asset when the state flips, get_mut forces a re-upload
+        let needs_update = materials
+            .get(&material_handle.0)
+            .is_some_and(|material| material.outline_width != outline_width);
+
+        if needs_update {
+            if let Some(material) = materials.get_mut(&material_handle.0) {
+                material.outline_width = outline_width;
+                material.outline_color = highlightable.color.to_linear();
+            }
+        }
+    }
+}
@@ -1,12 +1,26 @@
-use crate::components::{Luck, Player};
+use crate::components::{Character, Equipment, Luck, Player};
+use crate::controls::{Action, ActionInput};
 use crate::death::MarkedForDespawn;
-use crate::resources::GameState;
+use crate::definitions::Definitions;
+use crate::director::{upcoming_milestones, DirectorProgress};
+use crate::loadout::LoadoutPreset;
+use crate::past_runs::RunRecord;
+use crate::performance::quality_button_label;
+use crate::player_handle::PlayerHandle;
+use crate::resources::{GameRng, GameState, RunModifiers, WaveConfig};
+use crate::run_history::RunHistoryView;
+use crate::save::SaveData;
+use crate::settings::{GameSettings, QualitySettings, SettingKind};
 use crate::types::Rarity;
+use crate::ui::RunSummary;
+use crate::unlock_reveal::{newly_unlocked, RunStartCodex};
 use crate::upgrade;
 use crate::upgrade::{GenericUpgrade, UpgradePool, UpgradeType};
 use crate::weapons::weapon_upgrade::{WeaponUpgradeConfig, WeaponUpgradeSpec};
 use crate::weapons::{WeaponMeta, WeaponType};
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
+use strum::IntoEnumIterator;
 
 // Base menu components
 #[derive(Component)]
@@ -19,6 +33,11 @@ pub struct MenuItem {
     pub selected: bool,
 }
 
+/// Where an item sits when moving through a menu with keys or a gamepad, lowest first.
+/// Items spawned without one get their position among their siblings.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct FocusOrder(pub u32);
+
 #[derive(Component)]
 pub struct MenuActionComponent {
     pub action: MenuAction,
@@ -27,17 +46,46 @@ pub struct MenuActionComponent {
 #[derive(Component, Debug)]
 pub enum MenuType {
     Main,
+    CharacterSelect,
+    Settings,
+    Controls,
     Pause,
     LevelUp,
+    Milestone,
+    Results,
+    Unlocks,
+    RunHistory,
+    PastRuns,
 }
 
 // Simplified menu actions
 #[derive(Clone)]
 pub enum MenuAction {
     StartGame,
+    OpenCharacterSelect,
+    SelectCharacter(Character, WeaponType),
+    CloseCharacterSelect,
+    OpenSettings,
+    BackToMainMenu,
+    OpenControls,
+    RebindAction(Action),
+    ResetControls,
+    CloseControls,
     ResumeGame,
+    RestartGame,
+    ExitToMainMenu, // Ends the run, unlike going back from settings
     QuitGame,
     SelectUpgrade(UpgradeChoice),
+    SelectLoadout(LoadoutPreset),
+    SaveLoadout,
+    CycleQuality,
+    ChangeSetting(SettingKind),
+    RevealUnlocks,
+    OpenRunHistory,
+    CloseRunHistory,
+    OpenPastRuns(usize), // Page, newest runs first
+    ReplayRun(RunRecord),
+    ClosePastRuns,
 }
 
 // Level-up specific components
@@ -59,6 +107,64 @@ pub struct GenericUpgradeConfirmedEvent {
     pub generic_upgrade_type: GenericUpgrade,
 }
 
+// Auto-pick countdown shown when the choice timer mutator is active.
+// Ticks on real time since virtual time is paused during level up.
+#[derive(Component)]
+pub struct LevelUpCountdown {
+    pub timer: Timer,
+}
+
+/// Menu controls from the bound keys and any connected gamepad
+#[derive(SystemParam)]
+pub struct MenuInput<'w, 's> {
+    actions: ActionInput<'w>,
+    gamepads: Query<'w, 's, &'static Gamepad>,
+}
+
+impl MenuInput<'_, '_> {
+    fn just_pressed(&self, action: Action, buttons: &[GamepadButton]) -> bool {
+        self.actions.just_pressed(action)
+            || self
+                .gamepads
+                .iter()
+                .any(|gamepad| gamepad.any_just_pressed(buttons.iter().copied()))
+    }
+
+    pub fn previous(&self) -> bool {
+        self.just_pressed(Action::MenuUp, &[GamepadButton::DPadUp])
+    }
+
+    pub fn next(&self) -> bool {
+        self.just_pressed(Action::MenuDown, &[GamepadButton::DPadDown])
+    }
+
+    pub fn confirm(&self) -> bool {
+        self.just_pressed(Action::Confirm, &[GamepadButton::South])
+    }
+
+    /// Confirm on the selected item, or a click. Only the press itself counts,
+    /// holding the button down shouldn't keep triggering it.
+    pub fn is_confirmed(&self, menu_item: &MenuItem, interaction: &Ref<Interaction>) -> bool {
+        (menu_item.selected && self.confirm())
+            || (**interaction == Interaction::Pressed && interaction.is_changed())
+    }
+
+    /// The action of whichever of `items` was confirmed this frame
+    pub fn confirmed<'a, I>(&self, items: I) -> Option<&'a MenuAction>
+    where
+        I: IntoIterator<Item = (&'a MenuItem, &'a MenuActionComponent, Ref<'a, Interaction>)>,
+    {
+        items
+            .into_iter()
+            .find(|(menu_item, _, interaction)| self.is_confirmed(menu_item, interaction))
+            .map(|(_, action_component, _)| &action_component.action)
+    }
+}
+
+// Drawn around whichever menu item has focus
+const FOCUS_OUTLINE_WIDTH: f32 = 3.0;
+const FOCUS_OUTLINE_COLOR: Color = Color::srgb(1.0, 0.84, 0.0);
+
 #[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
 pub enum MenuSystemSet {
     Navigation,
@@ -70,16 +176,23 @@ pub fn spawn_level_up_menu(
     mut commands: Commands,
     weapon_upgrade_config: Res<WeaponUpgradeConfig>,
     upgrade_pool: Res<UpgradePool>,
+    mut game_rng: ResMut<GameRng>,
     existing_menu: Query<Entity, With<MenuRoot>>,
     weapon_query: Query<&WeaponMeta>,
-    luck_query: Query<(&Player, &Luck)>,
+    player_handle: Res<PlayerHandle>,
+    player_query: Query<(&Luck, &Equipment), With<Player>>,
+    run_modifiers: Res<RunModifiers>,
+    mut next_state: ResMut<NextState<GameState>>,
 ) {
     if !existing_menu.is_empty() {
         return;
     }
 
-    let Ok((_player, luck)) = luck_query.get_single() else {
-        panic!("Unable to get player luck");
+    // The player died on the frame it leveled, nobody to offer upgrades to
+    let Some((luck, equipment)) = player_handle.fetch(&player_query) else {
+        warn!("Level up without a player, back to the run");
+        next_state.set(GameState::Playing);
+        return;
     };
 
     let weapons = weapon_query.iter().collect::<Vec<_>>();
@@ -87,7 +200,13 @@ pub fn spawn_level_up_menu(
     info!("Generating choices for level up menu");
 
     // Generate 3 random upgrade choices
-    let choices = upgrade_pool.generate_choices(weapon_upgrade_config.as_ref(), luck, &weapons);
+    let choices = upgrade_pool.generate_choices(
+        weapon_upgrade_config.as_ref(),
+        luck,
+        &weapons,
+        equipment,
+        game_rng.as_mut(),
+    );
 
     info!("Choices: {:?}", choices);
 
@@ -137,6 +256,20 @@ pub fn spawn_level_up_menu(
                         TextColor(Color::srgb(1.0, 0.8, 0.0)), // Gold color
                     ));
 
+                    if let Some(seconds) = run_modifiers.choice_timer() {
+                        parent.spawn((
+                            Text::new(format!("{:.0}", seconds.ceil())),
+                            TextFont {
+                                font_size: 28.0,
+                                ..default()
+                            },
+                            TextColor(Color::srgb(1.0, 0.4, 0.3)),
+                            LevelUpCountdown {
+                                timer: Timer::from_seconds(seconds, TimerMode::Once),
+                            },
+                        ));
+                    }
+
                     // Spawn upgrade choices
                     for (index, choice) in choices.iter().enumerate() {
                         upgrade::spawn_upgrade_choice(parent, choice.clone(), index == 0);
@@ -155,10 +288,26 @@ pub(crate) fn get_rarity_color(rarity: &Rarity) -> Color {
     }
 }
 
+/// Gives items spawned without a focus order their position among their siblings
+pub fn assign_focus_order(
+    mut commands: Commands,
+    new_items: Query<(Entity, &Parent, Has<FocusOrder>), Added<MenuItem>>,
+    children_query: Query<&Children>,
+) {
+    for (entity, parent, _) in new_items.iter().filter(|(_, _, ordered)| !ordered) {
+        let index = children_query
+            .get(parent.get())
+            .ok()
+            .and_then(|children| children.iter().position(|child| *child == entity))
+            .unwrap_or_default();
+        commands.entity(entity).insert(FocusOrder(index as u32));
+    }
+}
+
 // Navigation systems
 pub fn menu_navigation(
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mut menu_query: Query<(Entity, &mut MenuItem, &MenuActionComponent, &Parent), With<Button>>,
+    menu_input: MenuInput,
+    mut menu_query: Query<(&mut MenuItem, &MenuActionComponent, Option<&FocusOrder>), With<Button>>,
     mut next_state: ResMut<NextState<GameState>>,
 ) {
     let mut items: Vec<_> = menu_query.iter_mut().collect();
@@ -166,40 +315,145 @@ pub fn menu_navigation(
     if items.is_empty() {
         return;
     }
+    items.sort_by_key(|(_, _, order)| order.copied());
 
     // Find currently selected item
     let current_selected = items
         .iter()
-        .position(|(_, item, _, _)| item.selected)
+        .position(|(item, _, _)| item.selected)
         .unwrap_or(0);
 
     // Calculate new selected index
     let items_len = items.len();
-    let new_selected = if keyboard.just_pressed(KeyCode::ArrowUp)
-        || keyboard.just_pressed(KeyCode::KeyW)
-    {
+    let new_selected = if menu_input.previous() {
         (current_selected + items_len - 1) % items_len
-    } else if keyboard.just_pressed(KeyCode::ArrowDown) || keyboard.just_pressed(KeyCode::KeyS) {
+    } else if menu_input.next() {
         (current_selected + 1) % items_len
     } else {
         current_selected
     };
 
-    // Update selection states
-    for (i, (_, ref mut item, _, _)) in items.iter_mut().enumerate() {
-        item.selected = i == new_selected;
+    // Update selection states, only touching items that change so outlines don't redraw
+    for (i, (ref mut item, _, _)) in items.iter_mut().enumerate() {
+        if item.selected != (i == new_selected) {
+            item.selected = i == new_selected;
+        }
     }
 
     // Handle selection
-    if keyboard.just_pressed(KeyCode::Enter) || keyboard.just_pressed(KeyCode::Space) {
-        if let Some((_, _, action_component, _)) = items.get(new_selected) {
+    if menu_input.confirm() {
+        if let Some((_, action_component, _)) = items.get(new_selected) {
             handle_menu_action(&action_component.action, &mut next_state);
         }
     }
 }
 
+pub fn spawn_main_menu(mut commands: Commands, save_data: Res<SaveData>) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+            MenuRoot {
+                menu_type: MenuType::Main,
+            },
+        ))
+        .with_children(|parent| {
+            spawn_menu_container(parent, |parent| {
+                spawn_menu_button(parent, "Start Game", MenuAction::StartGame, true);
+                spawn_menu_button(
+                    parent,
+                    "Character Select",
+                    MenuAction::OpenCharacterSelect,
+                    false,
+                );
+
+                // Saved loadouts start a run with that build in one click
+                for preset in &save_data.loadouts {
+                    spawn_menu_button(
+                        parent,
+                        &preset.name,
+                        MenuAction::SelectLoadout(preset.clone()),
+                        false,
+                    );
+                }
+
+                spawn_menu_button(parent, "Past Runs", MenuAction::OpenPastRuns(0), false);
+                spawn_menu_button(parent, "Settings", MenuAction::OpenSettings, false);
+                spawn_menu_button(parent, "Quit", MenuAction::QuitGame, false);
+            });
+        });
+}
+
+pub fn spawn_settings_menu(
+    mut commands: Commands,
+    quality: Res<QualitySettings>,
+    settings: Res<GameSettings>,
+) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+            MenuRoot {
+                menu_type: MenuType::Settings,
+            },
+        ))
+        .with_children(|parent| {
+            spawn_menu_container(parent, |parent| {
+                parent.spawn((
+                    Text::new("Settings"),
+                    TextFont {
+                        font_size: 36.0,
+                        ..default()
+                    },
+                    TextColor(Color::WHITE),
+                ));
+                for (index, kind) in SettingKind::iter().enumerate() {
+                    spawn_menu_button(
+                        parent,
+                        &settings.label(kind),
+                        MenuAction::ChangeSetting(kind),
+                        index == 0,
+                    );
+                }
+                spawn_menu_button(
+                    parent,
+                    &quality_button_label(&quality),
+                    MenuAction::CycleQuality,
+                    false,
+                );
+                spawn_menu_button(parent, "Controls", MenuAction::OpenControls, false);
+                spawn_menu_button(parent, "Back", MenuAction::BackToMainMenu, false);
+            });
+        });
+}
+
+// How many upcoming schedule entries the pause menu lists
+const PAUSE_PREVIEW_LENGTH: usize = 3;
+
 // Basic pause menu spawning system
-pub fn spawn_pause_menu(mut commands: Commands, existing_menu: Query<(Entity, &MenuRoot)>) {
+pub fn spawn_pause_menu(
+    mut commands: Commands,
+    existing_menu: Query<(Entity, &MenuRoot)>,
+    wave_config: Res<WaveConfig>,
+    progress: Res<DirectorProgress>,
+    definitions: Res<Definitions>,
+    quality: Res<QualitySettings>,
+    run_summary: RunSummary,
+) {
     for (entity, root) in existing_menu.iter() {
         info!(
             "Found existing menu: {:?} of type {:?}",
@@ -224,12 +478,158 @@ pub fn spawn_pause_menu(mut commands: Commands, existing_menu: Query<(Entity, &M
         ))
         .with_children(|parent| {
             spawn_menu_container(parent, |parent| {
+                run_summary.spawn(parent);
+
+                let milestones = upcoming_milestones(
+                    &wave_config,
+                    &progress,
+                    &definitions,
+                    run_summary.elapsed_secs(),
+                    PAUSE_PREVIEW_LENGTH,
+                );
+                if !milestones.is_empty() {
+                    parent.spawn((
+                        Text::new(format!("Coming up\n{}", milestones.join("\n"))),
+                        TextFont {
+                            font_size: 18.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.8, 0.8, 0.8)),
+                    ));
+                }
+
                 spawn_menu_button(parent, "Resume", MenuAction::ResumeGame, true);
+                spawn_menu_button(parent, "Restart", MenuAction::RestartGame, false);
+                spawn_menu_button(parent, "Main Menu", MenuAction::ExitToMainMenu, false);
+                spawn_menu_button(parent, "Run History", MenuAction::OpenRunHistory, false);
+                spawn_menu_button(parent, "Save Loadout", MenuAction::SaveLoadout, false);
+                spawn_menu_button(
+                    parent,
+                    &quality_button_label(&quality),
+                    MenuAction::CycleQuality,
+                    false,
+                );
                 spawn_menu_button(parent, "Quit", MenuAction::QuitGame, false);
             });
         });
 }
 
+/// Shown once a run is over, won or lost
+pub fn spawn_results_screen(
+    mut commands: Commands,
+    state: Res<State<GameState>>,
+    run_summary: RunSummary,
+    save_data: Res<SaveData>,
+    run_start: Res<RunStartCodex>,
+) {
+    let (title, color) = match state.get() {
+        GameState::Victory => ("Victory", Color::srgb(1.0, 0.84, 0.0)),
+        _ => ("Defeated", Color::srgb(0.9, 0.3, 0.3)),
+    };
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+            MenuRoot {
+                menu_type: MenuType::Results,
+            },
+        ))
+        .with_children(|parent| {
+            spawn_menu_container(parent, |parent| {
+                parent.spawn((
+                    Text::new(title),
+                    TextFont {
+                        font_size: 48.0,
+                        ..default()
+                    },
+                    TextColor(color),
+                ));
+                run_summary.spawn(parent);
+
+                // Only offered when the run actually added something to the codex
+                let unlocked = !newly_unlocked(&run_start.0, &save_data.codex).is_empty();
+                if unlocked {
+                    spawn_menu_button(parent, "Unlocks", MenuAction::RevealUnlocks, true);
+                }
+                spawn_menu_button(parent, "Restart", MenuAction::RestartGame, !unlocked);
+                spawn_menu_button(parent, "Main Menu", MenuAction::ExitToMainMenu, false);
+                spawn_menu_button(parent, "Run History", MenuAction::OpenRunHistory, false);
+                spawn_menu_button(parent, "Quit", MenuAction::QuitGame, false);
+            });
+        });
+}
+
+/// Swaps the pause menu or results screen for the run history screen and back.
+/// Going back rebuilds whichever menu it came from.
+pub fn toggle_run_history(
+    mut commands: Commands,
+    menu_input: MenuInput,
+    menu_items: Query<(&MenuItem, &MenuActionComponent, Ref<Interaction>)>,
+    menu_roots: Query<Entity, With<MenuRoot>>,
+    state: Res<State<GameState>>,
+    run_history: RunHistoryView,
+) {
+    let history_items = menu_items.iter().filter(|(_, action_component, _)| {
+        matches!(
+            action_component.action,
+            MenuAction::OpenRunHistory | MenuAction::CloseRunHistory
+        )
+    });
+    let Some(action) = menu_input.confirmed(history_items) else {
+        return;
+    };
+
+    for entity in menu_roots.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    if matches!(action, MenuAction::CloseRunHistory) {
+        match state.get() {
+            GameState::Paused => commands.run_system_cached(spawn_pause_menu),
+            _ => commands.run_system_cached(spawn_results_screen),
+        }
+        return;
+    }
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+            MenuRoot {
+                menu_type: MenuType::RunHistory,
+            },
+        ))
+        .with_children(|parent| {
+            spawn_menu_container(parent, |parent| {
+                parent.spawn((
+                    Text::new("Run History"),
+                    TextFont {
+                        font_size: 36.0,
+                        ..default()
+                    },
+                    TextColor(Color::WHITE),
+                ));
+                run_history.spawn(parent);
+                spawn_menu_button(parent, "Back", MenuAction::CloseRunHistory, true);
+            });
+        });
+}
+
 // Helper function to spawn menu buttons
 pub fn spawn_menu_button(
     parent: &mut ChildBuilder,
@@ -323,85 +723,114 @@ pub fn update_menu_buttons(
 }
 
 pub fn handle_menu_interactions(
-    mut buttons: Query<(&Interaction, &mut MenuItem, &MenuActionComponent), With<Button>>,
+    mut buttons: Query<
+        (
+            Entity,
+            Ref<Interaction>,
+            &mut MenuItem,
+            &MenuActionComponent,
+        ),
+        With<Button>,
+    >,
     mut next_state: ResMut<NextState<GameState>>,
 ) {
-    for (interaction, mut menu_item, action_component) in buttons.iter_mut() {
-        // Only modify selection via mouse if the item isn't already selected via keyboard
-        match *interaction {
-            Interaction::Pressed => {
-                handle_menu_action(&action_component.action, &mut next_state);
-            }
-            Interaction::Hovered => {
-                // Only update selection via hover if not already selected (preserves keyboard selection)
-                if !menu_item.selected {
-                    menu_item.selected = true;
-                }
-            }
-            Interaction::None => {
-                // Only deselect if this was a mouse selection (hover)
-                // This preserves keyboard selection
-                if menu_item.selected && button_was_hovered(interaction) {
-                    menu_item.selected = false;
-                }
+    // Moving the mouse onto an item focuses it, same as the keys do, so only one item has focus.
+    // A resting mouse doesn't keep pulling focus back from the keyboard.
+    let pointed_at = buttons
+        .iter()
+        .find(|(_, interaction, _, _)| {
+            interaction.is_changed() && **interaction != Interaction::None
+        })
+        .map(|(entity, ..)| entity);
+
+    for (entity, interaction, mut menu_item, action_component) in buttons.iter_mut() {
+        if *interaction == Interaction::Pressed {
+            handle_menu_action(&action_component.action, &mut next_state);
+        }
+        if let Some(pointed_at) = pointed_at {
+            if menu_item.selected != (entity == pointed_at) {
+                menu_item.selected = entity == pointed_at;
             }
         }
     }
 }
 
-fn button_was_hovered(interaction: &Interaction) -> bool {
-    matches!(interaction, Interaction::Hovered)
+/// Outlines the focused item, so it's clear where Enter or the gamepad's confirm will go
+pub fn update_focus_outline(
+    mut commands: Commands,
+    items: Query<(Entity, &MenuItem, Option<&Outline>), Changed<MenuItem>>,
+) {
+    for (entity, menu_item, outline) in items.iter() {
+        let color = if menu_item.selected {
+            FOCUS_OUTLINE_COLOR
+        } else {
+            Color::NONE
+        };
+        if outline.is_some_and(|outline| outline.color == color) {
+            continue;
+        }
+        commands.entity(entity).insert(Outline {
+            width: Val::Px(FOCUS_OUTLINE_WIDTH),
+            offset: Val::Px(2.0),
+            color,
+        });
+    }
 }
 
 fn handle_menu_action(action: &MenuAction, next_state: &mut NextState<GameState>) {
     match action {
         MenuAction::StartGame => next_state.set(GameState::Playing),
+        MenuAction::OpenSettings => next_state.set(GameState::Settings),
+        MenuAction::BackToMainMenu => next_state.set(GameState::MainMenu),
         MenuAction::ResumeGame => next_state.set(GameState::Playing),
-        // MenuAction::OpenSettings => next_state.set(GameState::Playing), // Until settings is implemented
+        MenuAction::RestartGame | MenuAction::ExitToMainMenu => {} // Handled by run reset system
         MenuAction::QuitGame => next_state.set(GameState::Quit),
         MenuAction::SelectUpgrade(_) => {} // Handled by upgrade system
+        MenuAction::SelectLoadout(_) | MenuAction::SaveLoadout => {} // Handled by loadout system
+        MenuAction::CycleQuality => {}     // Handled by performance system
+        MenuAction::ChangeSetting(_) => {} // Handled by settings system
+        MenuAction::RevealUnlocks => {}    // Handled by unlock reveal system
+        MenuAction::OpenRunHistory | MenuAction::CloseRunHistory => {} // Handled by toggle_run_history
+        MenuAction::OpenCharacterSelect
+        | MenuAction::SelectCharacter(..)
+        | MenuAction::CloseCharacterSelect => {} // Handled by character select system
+        MenuAction::OpenControls
+        | MenuAction::RebindAction(_)
+        | MenuAction::ResetControls
+        | MenuAction::CloseControls => {} // Handled by controls system
+        MenuAction::OpenPastRuns(_) | MenuAction::ReplayRun(_) | MenuAction::ClosePastRuns => {} // Handled by past runs system
     }
 }
 
 pub fn handle_upgrade_selection_and_confirmation(
     mut commands: Commands,
-    keyboard: Res<ButtonInput<KeyCode>>,
+    menu_input: MenuInput,
     menu_query: Query<(Entity, &MenuType)>,
     menu_items: Query<(&MenuItem, &MenuActionComponent, &Interaction)>,
     mut next_state: ResMut<NextState<GameState>>,
     mut weapon_upgrade_events: EventWriter<WeaponUpgradeConfirmedEvent>,
     mut generic_upgrade_events: EventWriter<GenericUpgradeConfirmedEvent>,
 ) {
-    // Only process for level up menu
+    // Only process for the level up and milestone menus
     if !menu_query
         .iter()
-        .any(|(_, menu_type)| matches!(menu_type, MenuType::LevelUp))
+        .any(|(_, menu_type)| matches!(menu_type, MenuType::LevelUp | MenuType::Milestone))
     {
         return;
     }
 
     // Handle confirmation via keyboard or mouse
     for (menu_item, action_component, interaction) in menu_items.iter() {
-        let should_confirm = (menu_item.selected
-            && (keyboard.just_pressed(KeyCode::Enter) || keyboard.just_pressed(KeyCode::Space)))
-            || *interaction == Interaction::Pressed;
+        let should_confirm =
+            (menu_item.selected && menu_input.confirm()) || *interaction == Interaction::Pressed;
 
         if should_confirm {
             if let MenuAction::SelectUpgrade(upgrade) = &action_component.action {
-                match &upgrade.upgrade_type {
-                    UpgradeType::Weapon(weapon_type, weapon_upgrade_spec) => {
-                        // Send the upgrade event
-                        weapon_upgrade_events.send(WeaponUpgradeConfirmedEvent {
-                            weapon_type: *weapon_type,
-                            upgrade_spec: weapon_upgrade_spec.clone(),
-                        });
-                    }
-                    UpgradeType::Generic(generic_upgrade) => {
-                        generic_upgrade_events.send(GenericUpgradeConfirmedEvent {
-                            generic_upgrade_type: generic_upgrade.clone(),
-                        });
-                    }
-                }
+                confirm_upgrade(
+                    upgrade,
+                    &mut weapon_upgrade_events,
+                    &mut generic_upgrade_events,
+                );
 
                 // Clean up menu
                 for (menu_entity, _) in menu_query.iter() {
@@ -416,6 +845,75 @@ pub fn handle_upgrade_selection_and_confirmation(
     }
 }
 
+fn confirm_upgrade(
+    upgrade: &UpgradeChoice,
+    weapon_upgrade_events: &mut EventWriter<WeaponUpgradeConfirmedEvent>,
+    generic_upgrade_events: &mut EventWriter<GenericUpgradeConfirmedEvent>,
+) {
+    match &upgrade.upgrade_type {
+        UpgradeType::Weapon(weapon_type, weapon_upgrade_spec) => {
+            // Send the upgrade event
+            weapon_upgrade_events.send(WeaponUpgradeConfirmedEvent {
+                weapon_type: *weapon_type,
+                upgrade_spec: weapon_upgrade_spec.clone(),
+            });
+        }
+        UpgradeType::Generic(generic_upgrade) => {
+            generic_upgrade_events.send(GenericUpgradeConfirmedEvent {
+                generic_upgrade_type: generic_upgrade.clone(),
+            });
+        }
+    }
+}
+
+pub fn tick_level_up_countdown(
+    time: Res<Time<Real>>,
+    mut countdown_query: Query<(&mut LevelUpCountdown, &mut Text)>,
+    menu_items: Query<(&MenuItem, &MenuActionComponent)>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut weapon_upgrade_events: EventWriter<WeaponUpgradeConfirmedEvent>,
+    mut generic_upgrade_events: EventWriter<GenericUpgradeConfirmedEvent>,
+) {
+    let Ok((mut countdown, mut text)) = countdown_query.get_single_mut() else {
+        return;
+    };
+
+    countdown.timer.tick(time.delta());
+    text.0 = format!("{:.0}", countdown.timer.remaining_secs().ceil());
+
+    if !countdown.timer.just_finished() {
+        return;
+    }
+
+    // Out of time: take whatever is highlighted, falling back to the first choice
+    let upgrades: Vec<_> = menu_items
+        .iter()
+        .filter_map(|(item, action_component)| match &action_component.action {
+            MenuAction::SelectUpgrade(upgrade) => Some((item.selected, upgrade)),
+            _ => None,
+        })
+        .collect();
+
+    let choice = upgrades
+        .iter()
+        .find(|(selected, _)| *selected)
+        .or(upgrades.first());
+
+    if let Some((_, upgrade)) = choice {
+        info!(
+            "Choice timer expired, auto-picking {:?}",
+            upgrade.upgrade_type
+        );
+        confirm_upgrade(
+            upgrade,
+            &mut weapon_upgrade_events,
+            &mut generic_upgrade_events,
+        );
+        // The menu itself is torn down by cleanup_menu_state on exit
+        next_state.set(GameState::Playing);
+    }
+}
+
 // Plugin to organize it all
 pub struct MenuPlugin;
 
@@ -435,18 +933,45 @@ impl Plugin for MenuPlugin {
             .add_systems(
                 Update,
                 (
+                    assign_focus_order,
                     menu_navigation,
                     handle_menu_interactions,
                     update_menu_buttons,
+                    update_focus_outline,
                     handle_upgrade_selection_and_confirmation,
+                    tick_level_up_countdown,
                 )
                     .chain()
-                    .run_if(in_state(GameState::LevelUp).or(in_state(GameState::Paused))),
+                    .run_if(
+                        in_state(GameState::MainMenu)
+                            .or(in_state(GameState::Settings))
+                            .or(in_state(GameState::LevelUp))
+                            .or(in_state(GameState::Milestone))
+                            .or(in_state(GameState::Paused))
+                            .or(in_state(GameState::GameOver))
+                            .or(in_state(GameState::Victory)),
+                    ),
+            )
+            .add_systems(
+                Update,
+                toggle_run_history.after(handle_menu_interactions).run_if(
+                    in_state(GameState::Paused)
+                        .or(in_state(GameState::GameOver))
+                        .or(in_state(GameState::Victory)),
+                ),
             )
             // State transitions
+            .add_systems(OnEnter(GameState::MainMenu), spawn_main_menu)
+            .add_systems(OnExit(GameState::MainMenu), cleanup_menu_state)
+            .add_systems(OnEnter(GameState::Settings), spawn_settings_menu)
+            .add_systems(OnExit(GameState::Settings), cleanup_menu_state)
             .add_systems(OnEnter(GameState::Paused), spawn_pause_menu)
             .add_systems(OnExit(GameState::Paused), cleanup_menu_state)
             .add_systems(OnEnter(GameState::LevelUp), spawn_level_up_menu)
-            .add_systems(OnExit(GameState::LevelUp), cleanup_menu_state);
+            .add_systems(OnExit(GameState::LevelUp), cleanup_menu_state)
+            .add_systems(OnEnter(GameState::GameOver), spawn_results_screen)
+            .add_systems(OnExit(GameState::GameOver), cleanup_menu_state)
+            .add_systems(OnEnter(GameState::Victory), spawn_results_screen)
+            .add_systems(OnExit(GameState::Victory), cleanup_menu_state);
     }
 }
@@ -0,0 +1,127 @@
+use crate::experience::check_level_up;
+use crate::kill_cam::KillCam;
+use crate::menu::{cleanup_menu_state, MenuRoot, MenuType};
+use crate::resources::GameState;
+use crate::upgrade::{spawn_upgrade_choice, UpgradePool};
+use bevy::prelude::*;
+
+/// At set marks on the run timer the game stops for a stat-only choice, apart from
+/// level ups, so runs that fall behind on experience still keep growing.
+pub struct MilestonePlugin;
+
+impl Plugin for MilestonePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MilestoneProgress>()
+            .add_systems(
+                Update,
+                schedule_milestones
+                    .after(check_level_up)
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(OnEnter(GameState::Milestone), spawn_milestone_menu)
+            .add_systems(OnExit(GameState::Milestone), cleanup_menu_state);
+    }
+}
+
+// Run time in seconds of each milestone: 2, 6 and 12 minutes
+pub const MILESTONE_TIMES: [f32; 3] = [120.0, 360.0, 720.0];
+
+/// How many milestones this run has reached
+#[derive(Resource, Default, Debug)]
+pub struct MilestoneProgress {
+    pub reached: usize,
+}
+
+impl MilestoneProgress {
+    /// The next milestone, once `elapsed` has passed it
+    pub fn due(&self, elapsed: f32) -> Option<f32> {
+        MILESTONE_TIMES
+            .get(self.reached)
+            .copied()
+            .filter(|time| elapsed >= *time)
+    }
+
+    pub fn last_reached(&self) -> Option<f32> {
+        self.reached
+            .checked_sub(1)
+            .and_then(|index| MILESTONE_TIMES.get(index))
+            .copied()
+    }
+}
+
+fn schedule_milestones(
+    time: Res<Time<Virtual>>,
+    kill_cam: Res<KillCam>,
+    mut progress: ResMut<MilestoneProgress>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    // A level up or anything else already on its way goes first, the milestone waits its turn
+    if kill_cam.is_playing() || matches!(*next_state, NextState::Pending(_)) {
+        return;
+    }
+    let Some(milestone) = progress.due(time.elapsed_secs()) else {
+        return;
+    };
+
+    progress.reached += 1;
+    info!("Reached the {:.0} minute milestone", milestone / 60.0);
+    next_state.set(GameState::Milestone);
+}
+
+fn spawn_milestone_menu(
+    mut commands: Commands,
+    upgrade_pool: Res<UpgradePool>,
+    progress: Res<MilestoneProgress>,
+) {
+    let choices = upgrade_pool.generate_milestone_choices();
+    let minutes = progress.last_reached().unwrap_or_default() / 60.0;
+    info!("Milestone choices: {:?}", choices);
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            GlobalZIndex(100),
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+            MenuRoot {
+                menu_type: MenuType::Milestone,
+            },
+            MenuType::Milestone,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Val::Px(20.0),
+                        width: Val::Px(600.0),
+                        padding: UiRect::all(Val::Px(30.0)),
+                        border: UiRect::all(Val::Px(2.0)),
+                        ..default()
+                    },
+                    BorderColor(Color::srgb(0.7, 0.7, 0.7)),
+                    BackgroundColor(Color::srgb(0.1, 0.1, 0.1)),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new(format!("{:.0} Minutes Survived", minutes)),
+                        TextFont {
+                            font_size: 40.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.4, 0.8, 1.0)),
+                    ));
+
+                    for (index, choice) in choices.into_iter().enumerate() {
+                        spawn_upgrade_choice(parent, choice, index == 0);
+                    }
+                });
+        });
+}
@@ -0,0 +1,141 @@
+use crate::components::Player;
+use crate::player_handle::PlayerHandle;
+use crate::resources::GameState;
+use crate::spatial_grid::EnemySpatialGrid;
+use bevy::prelude::*;
+
+pub struct MinimapPlugin;
+
+impl Plugin for MinimapPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MinimapRefresh>()
+            .add_systems(OnEnter(GameState::Playing), spawn_minimap)
+            .add_systems(OnExit(GameState::Playing), cleanup_minimap)
+            .add_systems(Update, update_minimap.run_if(in_state(GameState::Playing)));
+    }
+}
+
+/// Shows up as a dot on the minimap (bosses, chests, shrines...)
+#[derive(Component)]
+pub struct MinimapIcon {
+    pub color: Color,
+    pub size: f32, // In minimap pixels
+}
+
+#[derive(Component)]
+pub struct Minimap;
+
+// Dots are rebuilt wholesale on every refresh
+#[derive(Component)]
+pub struct MinimapDot;
+
+// Redrawing a few times a second is plenty for a map this small
+#[derive(Resource)]
+pub struct MinimapRefresh(pub Timer);
+
+impl Default for MinimapRefresh {
+    fn default() -> Self {
+        Self(Timer::from_seconds(0.25, TimerMode::Repeating))
+    }
+}
+
+const MINIMAP_SIZE: f32 = 160.0;
+const MINIMAP_WORLD_RADIUS: f32 = 800.0; // World units from the player to the map's edge
+const HORDE_DOT_SIZE: f32 = 6.0;
+
+fn spawn_minimap(mut commands: Commands) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(10.0),
+            bottom: Val::Px(10.0),
+            width: Val::Px(MINIMAP_SIZE),
+            height: Val::Px(MINIMAP_SIZE),
+            border: UiRect::all(Val::Px(2.0)),
+            overflow: Overflow::clip(),
+            ..default()
+        },
+        BorderColor(Color::srgb(0.7, 0.7, 0.7)),
+        BackgroundColor(Color::srgba(0.05, 0.05, 0.1, 0.7)),
+        Minimap,
+    ));
+}
+
+fn cleanup_minimap(mut commands: Commands, minimap_query: Query<Entity, With<Minimap>>) {
+    for entity in minimap_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn update_minimap(
+    mut commands: Commands,
+    time: Res<Time<Virtual>>,
+    mut refresh: ResMut<MinimapRefresh>,
+    grid: Res<EnemySpatialGrid>,
+    minimap_query: Query<Entity, With<Minimap>>,
+    player_handle: Res<PlayerHandle>,
+    player_query: Query<&Transform, With<Player>>,
+    icon_query: Query<(&MinimapIcon, &GlobalTransform)>,
+) {
+    if !refresh.0.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    let (Ok(minimap), Some(player_transform)) = (
+        minimap_query.get_single(),
+        player_handle.fetch(&player_query),
+    ) else {
+        return;
+    };
+    let center = player_transform.translation.truncate();
+
+    commands.entity(minimap).despawn_descendants();
+    commands.entity(minimap).with_children(|parent| {
+        // Horde density: one dot per occupied grid cell, more opaque the more crowded it is
+        for (cell_center, count) in grid.occupied_cells() {
+            let alpha = (count as f32 / 8.0).clamp(0.25, 1.0);
+            spawn_dot(
+                parent,
+                cell_center - center,
+                Color::srgba(0.9, 0.2, 0.2, alpha),
+                HORDE_DOT_SIZE,
+            );
+        }
+
+        for (icon, transform) in icon_query.iter() {
+            spawn_dot(
+                parent,
+                transform.translation().truncate() - center,
+                icon.color,
+                icon.size,
+            );
+        }
+
+        // Player always sits in the middle
+        spawn_dot(parent, Vec2::ZERO, Color::WHITE, 6.0);
+    });
+}
+
+fn spawn_dot(parent: &mut ChildBuilder, offset: Vec2, color: Color, size: f32) {
+    if offset.x.abs() > MINIMAP_WORLD_RADIUS || offset.y.abs() > MINIMAP_WORLD_RADIUS {
+        return;
+    }
+
+    // World y points up, UI y points down
+    let scale = MINIMAP_SIZE * 0.5 / MINIMAP_WORLD_RADIUS;
+    let position = Vec2::new(offset.x, -offset.y) * scale + MINIMAP_SIZE * 0.5;
+
+    parent.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(position.x - size * 0.5),
+            top: Val::Px(position.y - size * 0.5),
+            width: Val::Px(size),
+            height: Val::Px(size),
+            ..default()
+        },
+        BorderRadius::MAX,
+        BackgroundColor(color),
+        MinimapDot,
+    ));
+}
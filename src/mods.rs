@@ -0,0 +1,151 @@
+use crate::definitions::{Definitions, EnemyDefinition, StageDefinition, WeaponDefinition};
+use bevy::asset::io::AssetSource;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use serde::de::DeserializeOwned;
+use std::fs;
+use std::path::Path;
+
+pub const MODS_PATH: &str = "mods";
+/// Asset source for files shipped with mods, e.g. `mods://my_mod/sprites/bat.png`
+pub const MODS_ASSET_SOURCE: &str = "mods";
+
+pub trait ModsAppExt {
+    /// Serves files under `mods/` through the `mods://` asset source.
+    /// Must be called before the `AssetPlugin` is added.
+    fn add_mods_asset_source(&mut self) -> &mut Self;
+}
+
+impl ModsAppExt for App {
+    fn add_mods_asset_source(&mut self) -> &mut Self {
+        self.register_asset_source(
+            MODS_ASSET_SOURCE,
+            AssetSource::build()
+                .with_reader(AssetSource::get_default_reader(MODS_PATH.to_string())),
+        )
+    }
+}
+
+const BUILTIN_SOURCE: &str = "built-in";
+
+// A mod is a folder under mods/ with any of these files, each a map of id -> definition
+const ENEMIES_FILE: &str = "enemies.ron";
+const WEAPONS_FILE: &str = "weapons.ron";
+const STAGES_FILE: &str = "stages.ron";
+
+/// Built-in definitions with every mod in `mods/` merged on top.
+/// Mods load in alphabetical order, so when two define the same id the later one wins.
+pub fn load_definitions() -> Definitions {
+    let mut definitions = Definitions::builtin();
+
+    let mut mod_dirs: Vec<_> = match fs::read_dir(MODS_PATH) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir())
+            .collect(),
+        Err(_) => {
+            info!("No {} directory found, running without mods", MODS_PATH);
+            return definitions;
+        }
+    };
+    mod_dirs.sort();
+
+    let mut enemy_sources = builtin_sources(&definitions.enemies);
+    let mut weapon_sources = builtin_sources(&definitions.weapons);
+    let mut stage_sources = builtin_sources(&definitions.stages);
+    let mut conflicts = 0;
+
+    for dir in mod_dirs {
+        let Some(mod_name) = dir.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        info!("Loading mod: {}", mod_name);
+
+        if let Some(mut enemies) = read_definitions::<EnemyDefinition>(&dir, ENEMIES_FILE) {
+            // Sprites are relative to the mod's own folder
+            for enemy in enemies.values_mut() {
+                if let Some(sprite) = &mut enemy.sprite {
+                    *sprite = format!("{}/{}", mod_name, sprite);
+                }
+            }
+            conflicts += merge(
+                "enemy",
+                mod_name,
+                enemies,
+                &mut definitions.enemies,
+                &mut enemy_sources,
+            );
+        }
+        if let Some(weapons) = read_definitions::<WeaponDefinition>(&dir, WEAPONS_FILE) {
+            conflicts += merge(
+                "weapon",
+                mod_name,
+                weapons,
+                &mut definitions.weapons,
+                &mut weapon_sources,
+            );
+        }
+        if let Some(stages) = read_definitions::<StageDefinition>(&dir, STAGES_FILE) {
+            conflicts += merge(
+                "stage",
+                mod_name,
+                stages,
+                &mut definitions.stages,
+                &mut stage_sources,
+            );
+        }
+    }
+
+    if conflicts > 0 {
+        warn!("Mods loaded with {} conflicting definitions", conflicts);
+    }
+
+    definitions
+}
+
+fn builtin_sources<T>(registry: &HashMap<String, T>) -> HashMap<String, String> {
+    registry
+        .keys()
+        .map(|id| (id.clone(), BUILTIN_SOURCE.to_string()))
+        .collect()
+}
+
+fn read_definitions<T: DeserializeOwned>(dir: &Path, file: &str) -> Option<HashMap<String, T>> {
+    let path = dir.join(file);
+    // Mods only need the files they actually use
+    let contents = fs::read_to_string(&path).ok()?;
+
+    match ron::from_str(&contents) {
+        Ok(definitions) => Some(definitions),
+        Err(e) => {
+            warn!("Failed to parse {}, skipping it: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+/// Adds a mod's definitions to the registry, reporting any id that was already defined.
+/// Returns how many conflicts there were.
+fn merge<T>(
+    category: &str,
+    mod_name: &str,
+    definitions: HashMap<String, T>,
+    registry: &mut HashMap<String, T>,
+    sources: &mut HashMap<String, String>,
+) -> usize {
+    let mut conflicts = 0;
+
+    for (id, definition) in definitions {
+        if let Some(previous) = sources.insert(id.clone(), mod_name.to_string()) {
+            warn!(
+                "Mod conflict: {} '{}' from {} overrides the one from {}",
+                category, id, mod_name, previous
+            );
+            conflicts += 1;
+        }
+        registry.insert(id, definition);
+    }
+
+    conflicts
+}
@@ -0,0 +1,33 @@
+// Shared formatting for numbers shown to the player, so the HUD, upgrade tooltips and
+// stat panels all abbreviate the same way
+
+const SUFFIXES: [&str; 5] = ["", "k", "M", "B", "T"];
+
+/// Short form of a count or amount with at most one decimal: 17, 123.4, 1.2k, 3.4M
+pub fn abbreviate(value: f64) -> String {
+    let sign = if value < 0.0 { "-" } else { "" };
+    let mut scaled = value.abs();
+    let mut tier = 0;
+
+    // Compare the rounded value so 999_960 reads as 1M instead of 1000k
+    while tier + 1 < SUFFIXES.len() && (scaled * 10.0).round() / 10.0 >= 1000.0 {
+        scaled /= 1000.0;
+        tier += 1;
+    }
+
+    let mut digits = format!("{:.1}", scaled);
+    if digits.ends_with(".0") {
+        digits.truncate(digits.len() - 2);
+    }
+    format!("{}{}{}", sign, digits, SUFFIXES[tier])
+}
+
+/// A fraction as a percentage with one decimal, e.g. 0.125 -> "12.5%"
+pub fn percent(fraction: f32) -> String {
+    format!("{:.1}%", fraction * 100.0)
+}
+
+/// Whole percentage points (how stat bonuses are stored) with one decimal, e.g. 10 -> "10.0%"
+pub fn percent_points(points: i32) -> String {
+    percent(points as f32 / 100.0)
+}
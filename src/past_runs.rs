@@ -0,0 +1,291 @@
+use crate::components::{Character, Player};
+use crate::loadout::apply_loadout;
+use crate::menu::{
+    spawn_main_menu, spawn_menu_button, spawn_menu_container, MenuAction, MenuActionComponent,
+    MenuInput, MenuItem, MenuRoot, MenuType,
+};
+use crate::number_format::abbreviate;
+use crate::resources::{GameRng, GameState, GameStats};
+use crate::run_reset::reset_run;
+use crate::save::SaveData;
+use crate::weapons::{StartingWeapon, WeaponType};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Remembers how recent runs went, and lists them on the main menu so any of them can be
+/// played again from the same seed
+pub struct PastRunsPlugin;
+
+impl Plugin for PastRunsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::GameOver), record_run)
+            .add_systems(OnEnter(GameState::Victory), record_run)
+            .add_systems(
+                Update,
+                handle_past_runs.run_if(in_state(GameState::MainMenu)),
+            );
+    }
+}
+
+// Older runs drop off the end
+pub const MAX_PAST_RUNS: usize = 50;
+const PAGE_LENGTH: usize = 8;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunResult {
+    Victory,
+    Defeat,
+}
+
+impl std::fmt::Display for RunResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RunResult::Victory => write!(f, "Victory"),
+            RunResult::Defeat => write!(f, "Defeated"),
+        }
+    }
+}
+
+/// How a finished run went, and what it takes to play it again
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RunRecord {
+    pub finished_at: u64, // Unix seconds
+    pub seed: u64,
+    pub character: Character,
+    pub starting_weapon: WeaponType,
+    pub time: f32, // Seconds survived
+    pub kills: u32,
+    pub result: RunResult,
+}
+
+impl std::fmt::Display for RunRecord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let secs = self.time as u32;
+        write!(
+            f,
+            "{}  {} - {}  {:02}:{:02}  {} kills  {}\nSeed {}",
+            format_date(self.finished_at),
+            self.character,
+            self.starting_weapon,
+            secs / 60,
+            secs % 60,
+            abbreviate(self.kills as f64),
+            self.result,
+            self.seed
+        )
+    }
+}
+
+/// The most recent runs, newest first
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct PastRuns {
+    pub runs: Vec<RunRecord>,
+}
+
+impl PastRuns {
+    pub fn record(&mut self, record: RunRecord) {
+        self.runs.insert(0, record);
+        self.runs.truncate(MAX_PAST_RUNS);
+    }
+
+    pub fn pages(&self) -> usize {
+        self.runs.len().div_ceil(PAGE_LENGTH).max(1)
+    }
+
+    pub fn page(&self, page: usize) -> &[RunRecord] {
+        let start = (page * PAGE_LENGTH).min(self.runs.len());
+        let end = (start + PAGE_LENGTH).min(self.runs.len());
+        &self.runs[start..end]
+    }
+}
+
+/// Calendar date (UTC) of a unix timestamp, e.g. 2024-03-09
+pub fn format_date(unix_secs: u64) -> String {
+    // Days to civil date, from Howard Hinnant's date algorithms
+    let days = (unix_secs / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153; // Counted from March
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+fn record_run(
+    state: Res<State<GameState>>,
+    time: Res<Time<Virtual>>,
+    game_stats: Res<GameStats>,
+    game_rng: Res<GameRng>,
+    player_query: Query<(&Character, &StartingWeapon), With<Player>>,
+    mut save_data: ResMut<SaveData>,
+) {
+    let Ok((character, starting_weapon)) = player_query.get_single() else {
+        return;
+    };
+    let result = match state.get() {
+        GameState::Victory => RunResult::Victory,
+        _ => RunResult::Defeat,
+    };
+    let finished_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+
+    let record = RunRecord {
+        finished_at,
+        seed: game_rng.seed,
+        character: *character,
+        starting_weapon: starting_weapon.0,
+        time: time.elapsed_secs(),
+        kills: game_stats.enemies_killed,
+        result,
+    };
+    info!("Recorded run: {}", record.to_string().replace('\n', ", "));
+    save_data.past_runs.record(record);
+}
+
+fn handle_past_runs(
+    mut commands: Commands,
+    menu_input: MenuInput,
+    menu_items: Query<(&MenuItem, &MenuActionComponent, Ref<Interaction>)>,
+    menu_roots: Query<Entity, With<MenuRoot>>,
+    mut player_query: Query<(&mut Character, &mut StartingWeapon), With<Player>>,
+    save_data: Res<SaveData>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let past_run_items = menu_items.iter().filter(|(_, action_component, _)| {
+        matches!(
+            action_component.action,
+            MenuAction::OpenPastRuns(_) | MenuAction::ReplayRun(_) | MenuAction::ClosePastRuns
+        )
+    });
+    let Some(action) = menu_input.confirmed(past_run_items) else {
+        return;
+    };
+
+    // The main menu is torn down on its way out
+    if let MenuAction::ReplayRun(record) = action {
+        info!(
+            "Replaying seed {} as {} with {}",
+            record.seed, record.character, record.starting_weapon
+        );
+        apply_loadout(&mut player_query, record.character, record.starting_weapon);
+        // Anything left from an earlier run this session would throw the replay off,
+        // the reset keeps the loadout and hands out a fresh seed that we replace
+        commands.queue(reset_run);
+        commands.insert_resource(GameRng::from_seed(record.seed));
+        next_state.set(GameState::Playing);
+        return;
+    }
+
+    for entity in menu_roots.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    match action {
+        MenuAction::OpenPastRuns(page) => {
+            spawn_past_runs(&mut commands, &save_data.past_runs, *page)
+        }
+        _ => commands.run_system_cached(spawn_main_menu),
+    }
+}
+
+fn spawn_past_runs(commands: &mut Commands, past_runs: &PastRuns, page: usize) {
+    let page = page.min(past_runs.pages() - 1);
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+            MenuRoot {
+                menu_type: MenuType::PastRuns,
+            },
+        ))
+        .with_children(|parent| {
+            spawn_menu_container(parent, |parent| {
+                parent.spawn((
+                    Text::new(format!("Past Runs ({}/{})", page + 1, past_runs.pages())),
+                    TextFont {
+                        font_size: 36.0,
+                        ..default()
+                    },
+                    TextColor(Color::WHITE),
+                ));
+
+                let records = past_runs.page(page);
+                if records.is_empty() {
+                    parent.spawn((
+                        Text::new("No runs finished yet"),
+                        TextFont {
+                            font_size: 18.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.8, 0.8, 0.8)),
+                    ));
+                }
+                for (index, record) in records.iter().enumerate() {
+                    spawn_run_button(parent, record, index == 0);
+                }
+
+                if page + 1 < past_runs.pages() {
+                    spawn_menu_button(parent, "Older", MenuAction::OpenPastRuns(page + 1), false);
+                }
+                if page > 0 {
+                    spawn_menu_button(parent, "Newer", MenuAction::OpenPastRuns(page - 1), false);
+                }
+                // Back has focus when there's nothing to replay
+                spawn_menu_button(
+                    parent,
+                    "Back",
+                    MenuAction::ClosePastRuns,
+                    records.is_empty(),
+                );
+            });
+        });
+}
+
+// Like a menu button, wide enough for the whole record. Every button sits in the same
+// container so focus runs down the list in order.
+fn spawn_run_button(parent: &mut ChildBuilder, record: &RunRecord, selected: bool) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Val::Px(560.0),
+                height: Val::Px(56.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.3, 0.3, 0.3)),
+            MenuItem { selected },
+            MenuActionComponent {
+                action: MenuAction::ReplayRun(record.clone()),
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(record.to_string()),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                TextLayout::new_with_justify(JustifyText::Center),
+            ));
+        });
+}
@@ -0,0 +1,424 @@
+use crate::components::Enemy;
+use crate::controls::{key_name, Action, ActionInput, KeyBindings};
+use crate::director::DirectorProgress;
+use crate::menu::{MenuAction, MenuActionComponent, MenuInput, MenuItem};
+use crate::resources::{GameState, WaveConfig};
+use crate::settings::{QualityPreset, QualitySettings};
+use crate::trail::TrailSegment;
+use crate::weapons::magick_circle::PatternType;
+use crate::weapons::manifestation::ManifestationZone;
+use crate::weapons::{Area, Attack, Sigil};
+use bevy::core::FrameCount;
+use bevy::ecs::entity::Entities;
+use bevy::prelude::*;
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy_prototype_lyon::draw::Fill;
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+/// Keeps the game playable on weak machines: enforces the effect budget, swaps attack
+/// shapes for sprites when asked to, and suggests the Minimal preset when frames drop
+pub struct PerformancePlugin;
+
+impl Plugin for PerformancePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FrameRateWatch>()
+            .insert_resource(FrameSpikeWatchdog::from_args())
+            .add_systems(Startup, create_attack_sprites)
+            // Hitches during menus and level-ups count too
+            .add_systems(Last, report_frame_spikes)
+            .add_systems(
+                Update,
+                (
+                    enforce_effect_budget,
+                    swap_attack_shapes_for_sprites,
+                    resize_attack_sprites,
+                    watch_frame_rate,
+                    accept_quality_suggestion,
+                    fade_quality_hints,
+                )
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(
+                Update,
+                cycle_quality_preset
+                    .run_if(in_state(GameState::Settings).or(in_state(GameState::Paused))),
+            );
+    }
+}
+
+/// Short-lived visual (spark, burst) that counts towards the effect budget
+#[derive(Component)]
+pub struct EffectParticle;
+
+/// Drops the surplus once more effect particles are alive than the settings allow
+fn enforce_effect_budget(
+    mut commands: Commands,
+    settings: Res<QualitySettings>,
+    particle_query: Query<Entity, With<EffectParticle>>,
+) {
+    for entity in particle_query.iter().skip(settings.max_effect_particles) {
+        commands.entity(entity).despawn();
+    }
+}
+
+// Soft-edged white disc, tinted per attack
+#[derive(Resource)]
+pub struct AttackSprites {
+    pub circle: Handle<Image>,
+}
+
+const CIRCLE_SPRITE_RESOLUTION: u32 = 64;
+
+fn create_attack_sprites(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    let size = CIRCLE_SPRITE_RESOLUTION;
+    let mut data = Vec::with_capacity((size * size * 4) as usize);
+
+    for y in 0..size {
+        for x in 0..size {
+            let u = (x as f32 + 0.5) / size as f32 * 2.0 - 1.0;
+            let v = (y as f32 + 0.5) / size as f32 * 2.0 - 1.0;
+            let distance = (u * u + v * v).sqrt();
+
+            // One pixel of falloff so the rim isn't jagged
+            let alpha = ((1.0 - distance) * size as f32 * 0.5).clamp(0.0, 1.0);
+
+            data.extend_from_slice(&[255, 255, 255, (alpha * 255.0) as u8]);
+        }
+    }
+
+    let image = Image::new(
+        Extent3d {
+            width: size,
+            height: size,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+
+    commands.insert_resource(AttackSprites {
+        circle: images.add(image),
+    });
+}
+
+/// With sprite attacks on, new circles and zones drop their tessellated mesh for a tinted
+/// sprite, and their sigils become plain squares
+fn swap_attack_shapes_for_sprites(
+    mut commands: Commands,
+    settings: Res<QualitySettings>,
+    attack_sprites: Res<AttackSprites>,
+    attack_query: Query<
+        (Entity, &Area, &Fill, Option<&Children>),
+        Or<(Added<PatternType>, Added<ManifestationZone>)>,
+    >,
+    sigil_query: Query<(&Sigil, &Fill)>,
+) {
+    if !settings.sprite_attacks {
+        return;
+    }
+
+    for (entity, area, fill, children) in attack_query.iter() {
+        commands
+            .entity(entity)
+            .remove::<(Mesh2d, MeshMaterial2d<ColorMaterial>)>()
+            .insert(Sprite {
+                image: attack_sprites.circle.clone(),
+                color: fill.color,
+                custom_size: Some(Vec2::splat(area.radius * 2.0)),
+                ..default()
+            });
+
+        for &child in children.into_iter().flatten() {
+            if let Ok((sigil, sigil_fill)) = sigil_query.get(child) {
+                commands
+                    .entity(child)
+                    .remove::<(Mesh2d, MeshMaterial2d<ColorMaterial>)>()
+                    .insert(Sprite::from_color(
+                        sigil_fill.color,
+                        Vec2::splat(sigil.base_size),
+                    ));
+            }
+        }
+    }
+}
+
+// Circles can grow mid-cast, which only rebuilds their path
+fn resize_attack_sprites(mut attack_query: Query<(&Area, &mut Sprite), Changed<Area>>) {
+    for (area, mut sprite) in attack_query.iter_mut() {
+        sprite.custom_size = Some(Vec2::splat(area.radius * 2.0));
+    }
+}
+
+/// Averages the frame rate over a window and offers the Minimal preset once per session
+/// when it falls below the threshold
+#[derive(Resource)]
+pub struct FrameRateWatch {
+    pub window: Timer,
+    pub frames: u32,
+    pub threshold: f32, // Average frames per second
+    pub suggested: bool,
+}
+
+impl Default for FrameRateWatch {
+    fn default() -> Self {
+        Self {
+            window: Timer::from_seconds(5.0, TimerMode::Repeating),
+            frames: 0,
+            threshold: 40.0,
+            suggested: false,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct QualityHint {
+    pub timer: Timer,
+}
+
+const QUALITY_HINT_DURATION: f32 = 8.0;
+
+// Real time, so a slow frame still counts as one slow frame
+fn watch_frame_rate(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    settings: Res<QualitySettings>,
+    mut watch: ResMut<FrameRateWatch>,
+    bindings: Res<KeyBindings>,
+) {
+    watch.frames += 1;
+    if !watch.window.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    let average = watch.frames as f32 / watch.window.duration().as_secs_f32();
+    watch.frames = 0;
+
+    if watch.suggested || settings.preset == QualityPreset::Minimal || average >= watch.threshold {
+        return;
+    }
+
+    watch.suggested = true;
+    warn!(
+        "Averaging {:.0} FPS on {} quality, suggesting {}",
+        average,
+        settings.preset,
+        QualityPreset::Minimal
+    );
+
+    commands.spawn((
+        Text::new(format!(
+            "Running slowly ({:.0} FPS). Press {} for {} quality",
+            average,
+            key_name(bindings.primary(Action::QualityHint)),
+            QualityPreset::Minimal
+        )),
+        TextFont {
+            font_size: 18.0,
+            ..default()
+        },
+        TextColor(Color::srgb(1.0, 0.85, 0.4)),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(10.0),
+            left: Val::Px(10.0),
+            ..default()
+        },
+        QualityHint {
+            timer: Timer::from_seconds(QUALITY_HINT_DURATION, TimerMode::Once),
+        },
+    ));
+}
+
+fn accept_quality_suggestion(
+    mut commands: Commands,
+    actions: ActionInput,
+    mut settings: ResMut<QualitySettings>,
+    hint_query: Query<Entity, With<QualityHint>>,
+) {
+    if hint_query.is_empty() || !actions.just_pressed(Action::QualityHint) {
+        return;
+    }
+
+    info!("Switching to {} quality", QualityPreset::Minimal);
+    *settings = QualitySettings::from_preset(QualityPreset::Minimal);
+
+    for entity in hint_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn fade_quality_hints(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    mut hint_query: Query<(Entity, &mut QualityHint)>,
+) {
+    for (entity, mut hint) in hint_query.iter_mut() {
+        if hint.timer.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+pub fn quality_button_label(settings: &QualitySettings) -> String {
+    format!("Quality: {}", settings.preset)
+}
+
+fn cycle_quality_preset(
+    menu_input: MenuInput,
+    menu_items: Query<(&MenuItem, &MenuActionComponent, Ref<Interaction>, &Children)>,
+    mut button_text: Query<&mut Text>,
+    mut settings: ResMut<QualitySettings>,
+) {
+    for (menu_item, action_component, interaction, children) in menu_items.iter() {
+        if !matches!(action_component.action, MenuAction::CycleQuality) {
+            continue;
+        }
+
+        if !menu_input.is_confirmed(menu_item, &interaction) {
+            continue;
+        }
+
+        *settings = QualitySettings::from_preset(settings.next_preset());
+        info!("Quality preset set to {}", settings.preset);
+        // The settings screen never passes through pause, where dirty settings are usually flushed
+        settings.save();
+
+        if let Some(mut text) = children
+            .first()
+            .and_then(|&child| button_text.get_mut(child).ok())
+        {
+            text.0 = quality_button_label(&settings);
+        }
+    }
+}
+
+/// Logs every frame that took longer than the budget, with enough context to tell what
+/// the game was doing. Snapshots additionally write each report to `SPIKE_SNAPSHOT_DIR`.
+#[derive(Resource)]
+pub struct FrameSpikeWatchdog {
+    pub budget_ms: f32,
+    pub capture_snapshots: bool,
+    pub snapshot_cooldown: Timer, // Keeps a run of bad frames from flooding the folder
+}
+
+const DEFAULT_FRAME_BUDGET_MS: f32 = 50.0;
+const SPIKE_SNAPSHOT_DIR: &str = "spike_snapshots";
+
+impl FrameSpikeWatchdog {
+    // Tuned for playtest builds from the command line: --frame-budget <ms> --capture-spikes
+    pub fn from_args() -> Self {
+        let args: Vec<String> = std::env::args().collect();
+        let budget_ms = args
+            .iter()
+            .position(|arg| arg == "--frame-budget")
+            .and_then(|index| args.get(index + 1))
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_FRAME_BUDGET_MS);
+
+        let mut snapshot_cooldown = Timer::from_seconds(5.0, TimerMode::Once);
+        // Let the first spike through straight away
+        snapshot_cooldown.tick(snapshot_cooldown.duration());
+
+        Self {
+            budget_ms,
+            capture_snapshots: args.iter().any(|arg| arg == "--capture-spikes"),
+            snapshot_cooldown,
+        }
+    }
+}
+
+/// What the game looked like on a frame that blew the budget
+#[derive(Serialize, Debug)]
+pub struct FrameSpikeReport {
+    pub frame: u32,
+    pub frame_ms: f32,
+    pub budget_ms: f32,
+    pub state: String,
+    pub entities: u32,
+    pub enemies: usize,
+    pub attacks: usize,
+    pub effect_particles: usize,
+    pub trail_segments: usize,
+    pub max_enemies: u32,
+    pub next_boss: usize,
+    pub final_minute_announced: bool,
+}
+
+fn report_frame_spikes(
+    time: Res<Time<Real>>,
+    frame_count: Res<FrameCount>,
+    game_state: Res<State<GameState>>,
+    wave_config: Res<WaveConfig>,
+    progress: Res<DirectorProgress>,
+    mut watchdog: ResMut<FrameSpikeWatchdog>,
+    entities: &Entities,
+    enemies: Query<(), With<Enemy>>,
+    attacks: Query<(), With<Attack>>,
+    effect_particles: Query<(), With<EffectParticle>>,
+    trail_segments: Query<(), With<TrailSegment>>,
+) {
+    watchdog.snapshot_cooldown.tick(time.delta());
+
+    let frame_ms = time.delta_secs() * 1000.0;
+    if frame_ms <= watchdog.budget_ms {
+        return;
+    }
+
+    let report = FrameSpikeReport {
+        frame: frame_count.0,
+        frame_ms,
+        budget_ms: watchdog.budget_ms,
+        state: format!("{:?}", game_state.get()),
+        entities: entities.len(),
+        enemies: enemies.iter().count(),
+        attacks: attacks.iter().count(),
+        effect_particles: effect_particles.iter().count(),
+        trail_segments: trail_segments.iter().count(),
+        max_enemies: wave_config.max_enemies,
+        next_boss: progress.next_boss,
+        final_minute_announced: progress.final_minute_announced,
+    };
+
+    warn!(
+        "Frame {} took {:.1}ms (budget {:.1}ms) in {}: {} entities, {} enemies, {} attacks, \
+         {} effect particles, {} trail segments, director at boss {}",
+        report.frame,
+        report.frame_ms,
+        report.budget_ms,
+        report.state,
+        report.entities,
+        report.enemies,
+        report.attacks,
+        report.effect_particles,
+        report.trail_segments,
+        report.next_boss
+    );
+
+    if watchdog.capture_snapshots && watchdog.snapshot_cooldown.finished() {
+        watchdog.snapshot_cooldown.reset();
+        write_spike_snapshot(&report);
+    }
+}
+
+fn write_spike_snapshot(report: &FrameSpikeReport) {
+    let dir = Path::new(SPIKE_SNAPSHOT_DIR);
+    let contents = match ron::ser::to_string_pretty(report, ron::ser::PrettyConfig::default()) {
+        Ok(contents) => contents,
+        Err(e) => {
+            error!("Failed to serialize frame spike report: {}", e);
+            return;
+        }
+    };
+
+    let path = dir.join(format!("frame_{}.ron", report.frame));
+    if let Err(e) = fs::create_dir_all(dir).and_then(|_| fs::write(&path, contents)) {
+        error!("Failed to write {}: {}", path.display(), e);
+        return;
+    }
+    info!("Captured frame spike snapshot to {}", path.display());
+}
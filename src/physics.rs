@@ -1,6 +1,7 @@
 use crate::combat::DamageEvent;
 use crate::components::{Enemy, Player};
 use crate::death::{MarkedForDeath, MarkedForDespawn};
+use crate::player_handle::PlayerHandle;
 use crate::resources::GameState;
 use crate::GameplaySets;
 use bevy::ecs::query::QuerySingleError;
@@ -9,6 +10,12 @@ use bevy_rapier2d::prelude::*;
 
 pub struct PhysicsPlugin;
 
+pub const ENEMY_RADIUS: f32 = 12.0;
+// Default collider density is 1, so this is the enemy ball's area
+pub const ENEMY_MASS: f32 = std::f32::consts::PI * ENEMY_RADIUS * ENEMY_RADIUS;
+// Bolts fired by ranged enemies, which only collide with the player
+pub const ENEMY_PROJECTILE_GROUP: Group = Group::GROUP_5;
+
 // A component to mark our damage sensor
 #[derive(Component)]
 pub struct DamageSensor;
@@ -25,7 +32,8 @@ impl Plugin for PhysicsPlugin {
 
         #[cfg(debug_assertions)]
         {
-            app.add_plugins(RapierDebugRenderPlugin::default());
+            app.add_plugins(RapierDebugRenderPlugin::default())
+                .add_systems(Update, apply_debug_render_setting);
         }
 
         app.add_systems(
@@ -38,6 +46,17 @@ impl Plugin for PhysicsPlugin {
     }
 }
 
+// Only built alongside the debug render plugin, hence the inline path
+#[cfg(debug_assertions)]
+fn apply_debug_render_setting(
+    settings: Res<crate::settings::QualitySettings>,
+    mut debug_context: ResMut<DebugRenderContext>,
+) {
+    if settings.is_changed() {
+        debug_context.enabled = settings.debug_render;
+    }
+}
+
 fn configure_physics(
     mut commands: Commands,
     rapier_query: Query<(Entity, Option<&RapierConfiguration>), With<RapierContext>>,
@@ -137,7 +156,10 @@ pub fn setup_physics_bodies(
                     RigidBody::KinematicPositionBased,
                     Collider::ball(12.0),
                     ActiveEvents::COLLISION_EVENTS,
-                    CollisionGroups::new(player_group, enemy_group | experience_group),
+                    CollisionGroups::new(
+                        player_group,
+                        enemy_group | experience_group | ENEMY_PROJECTILE_GROUP,
+                    ),
                     Velocity::zero(),
                     LockedAxes::ROTATION_LOCKED,
                 ))
@@ -159,7 +181,7 @@ pub fn setup_physics_bodies(
         if commands.get_entity(entity).is_some() {
             commands.entity(entity).insert((
                 RigidBody::Dynamic,
-                Collider::ball(12.0),
+                Collider::ball(ENEMY_RADIUS),
                 Velocity::zero(),
                 LockedAxes::ROTATION_LOCKED,
                 ActiveEvents::COLLISION_EVENTS,
@@ -175,34 +197,24 @@ pub fn setup_physics_bodies(
 
 pub fn handle_player_enemy_collision(
     context_query: Query<&RapierContext>,
-    player_query: Query<(Entity, &Transform), With<Player>>,
-    enemy_query: Query<
-        Entity,
-        (
-            With<Enemy>,
-            Without<MarkedForDespawn>,
-            Without<MarkedForDeath>,
-        ),
-    >,
+    player_handle: Res<PlayerHandle>,
+    enemy_query: Query<&Enemy, (Without<MarkedForDespawn>, Without<MarkedForDeath>)>,
     damage_sensor_query: Query<(Entity, &Parent), With<DamageSensor>>,
     mut damage_events: EventWriter<DamageEvent>,
 ) {
-    // Get player entity and their damage sensor
-    let (player_entity, sensor_entity) = if let Ok((player_entity, _)) = player_query.get_single() {
-        if let Some((sensor_entity, _)) = damage_sensor_query
-            .iter()
-            .find(|(_, parent)| parent.get() == player_entity)
-        {
-            (player_entity, sensor_entity)
-        } else {
-            return;
-        }
-    } else {
+    // No player or no damage sensor, nothing to be hurt
+    let Some(player_entity) = player_handle.get() else {
+        return;
+    };
+    let Some((sensor_entity, _)) = damage_sensor_query
+        .iter()
+        .find(|(_, parent)| parent.get() == player_entity)
+    else {
         return;
     };
 
-    // Count intersecting enemies that aren't marked for death/despawn
-    let mut intersecting_enemies = 0;
+    // Total contact damage of intersecting enemies that aren't marked for death/despawn
+    let mut contact_damage = 0;
     let rapier_context = context_query
         .get_single()
         .unwrap_or_else(|e| handle_rapier_context_error(e));
@@ -220,16 +232,16 @@ pub fn handle_player_enemy_collision(
             collider1
         };
 
-        if enemy_query.contains(other_entity) {
-            intersecting_enemies += 1;
+        if let Ok(enemy) = enemy_query.get(other_entity) {
+            contact_damage += enemy.contact_damage;
         }
     }
 
     // Send damage event if there are intersecting enemies
-    if intersecting_enemies > 0 {
+    if contact_damage > 0 {
         damage_events.send(DamageEvent {
             target: player_entity,
-            amount: 1 * intersecting_enemies,
+            amount: contact_damage,
             source: None,
         });
     }
@@ -0,0 +1,143 @@
+use crate::bomb::{ScreenClearEvent, BOMB_DAMAGE};
+use crate::components::{Gold, Health, Player};
+use crate::death::MarkedForDespawn;
+use crate::experience::Vacuumable;
+use crate::gameplay_events::{GameplayEvent, PickupKind};
+use crate::minimap::MinimapIcon;
+use crate::resources::GameState;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+/// Loose items on the ground other than experience: gold, hearts and bombs.
+/// They get pulled in by the player's magnet and collected on touch, just like orbs.
+pub struct PickupPlugin;
+
+impl Plugin for PickupPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, collect_pickups.run_if(in_state(GameState::Playing)));
+    }
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub enum Pickup {
+    Gold(u32),
+    Heart(i32), // Amount healed
+    Bomb,
+}
+
+impl Pickup {
+    /// Scales the player's magnet range for this item, hearts have to be walked up to
+    pub fn magnet_range_multiplier(&self) -> f32 {
+        match self {
+            Pickup::Gold(_) => 1.5,
+            Pickup::Heart(_) => 0.5,
+            Pickup::Bomb => 0.75,
+        }
+    }
+
+    fn color(&self) -> Color {
+        match self {
+            Pickup::Gold(_) => Color::srgb(1.0, 0.85, 0.2),
+            Pickup::Heart(_) => Color::srgb(0.95, 0.25, 0.35),
+            Pickup::Bomb => Color::srgb(0.15, 0.15, 0.2),
+        }
+    }
+
+    fn size(&self) -> f32 {
+        match self {
+            Pickup::Gold(_) => 6.0,
+            Pickup::Heart(_) => 10.0,
+            Pickup::Bomb => 12.0,
+        }
+    }
+}
+
+pub fn spawn_pickup(commands: &mut Commands, position: Vec2, pickup: Pickup) -> Entity {
+    let mut entity = commands.spawn((
+        pickup,
+        Vacuumable {
+            range_multiplier: pickup.magnet_range_multiplier(),
+            ..default()
+        },
+        Sprite {
+            color: pickup.color(),
+            custom_size: Some(Vec2::splat(pickup.size())),
+            ..default()
+        },
+        // Diamonds, so they don't read as experience orbs at a glance
+        Transform::from_translation(position.extend(0.0))
+            .with_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_4)),
+        // Same physics as experience orbs
+        RigidBody::Dynamic,
+        Collider::ball(pickup.size() / 2.0),
+        Sensor,
+        ActiveEvents::COLLISION_EVENTS,
+        CollisionGroups::new(Group::GROUP_4, Group::GROUP_1),
+        LockedAxes::ROTATION_LOCKED,
+        Damping {
+            linear_damping: 2.0,
+            angular_damping: 1.0,
+        },
+    ));
+
+    // Rare enough to be worth finding on the minimap
+    if pickup == Pickup::Bomb {
+        entity.insert(MinimapIcon {
+            color: Color::srgb(1.0, 0.3, 0.3),
+            size: 5.0,
+        });
+    }
+
+    entity.id()
+}
+
+fn collect_pickups(
+    mut commands: Commands,
+    mut player_query: Query<(Entity, &mut Health, &mut Gold), With<Player>>,
+    pickup_query: Query<(Entity, &Pickup), Without<MarkedForDespawn>>,
+    mut collision_events: EventReader<CollisionEvent>,
+    mut gameplay_events: EventWriter<GameplayEvent>,
+    mut screen_clears: EventWriter<ScreenClearEvent>,
+) {
+    let Ok((player_entity, mut health, mut gold)) = player_query.get_single_mut() else {
+        return;
+    };
+
+    for event in collision_events.read() {
+        let CollisionEvent::Started(e1, e2, _) = event else {
+            continue;
+        };
+        let other = if *e1 == player_entity {
+            *e2
+        } else if *e2 == player_entity {
+            *e1
+        } else {
+            continue;
+        };
+        let Ok((pickup_entity, pickup)) = pickup_query.get(other) else {
+            continue;
+        };
+
+        match *pickup {
+            Pickup::Gold(amount) => {
+                gold.0 += amount;
+                gameplay_events.send(GameplayEvent::Pickup(PickupKind::Gold(amount)));
+            }
+            Pickup::Heart(amount) => {
+                let healed = health.heal(amount);
+                info!("Heart restored {} health", healed);
+                gameplay_events.send(GameplayEvent::Pickup(PickupKind::Heart(healed)));
+            }
+            Pickup::Bomb => {
+                info!("Bomb collected, clearing the screen");
+                screen_clears.send(ScreenClearEvent {
+                    damage: BOMB_DAMAGE,
+                    source: Some(pickup_entity),
+                });
+                gameplay_events.send(GameplayEvent::Pickup(PickupKind::Bomb));
+            }
+        }
+
+        commands.entity(pickup_entity).insert(MarkedForDespawn);
+    }
+}
@@ -0,0 +1,51 @@
+use crate::components::Player;
+use bevy::ecs::query::{QueryData, QueryFilter, ROQueryItem};
+use bevy::prelude::*;
+
+/// Keeps `PlayerHandle` pointing at the player, before any gameplay system runs
+pub struct PlayerHandlePlugin;
+
+impl Plugin for PlayerHandlePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PlayerHandle>()
+            .add_systems(PreUpdate, track_player);
+    }
+}
+
+/// The player entity, `None` before it spawns and once it's gone.
+/// Systems that only need to know who the player is read this instead of querying,
+/// and skip their work while there's no player.
+#[derive(Resource, Default, Debug)]
+pub struct PlayerHandle(Option<Entity>);
+
+impl PlayerHandle {
+    pub fn get(&self) -> Option<Entity> {
+        self.0
+    }
+
+    /// The player's item in `query`, `None` while there's no player
+    pub fn fetch<'a, D: QueryData, F: QueryFilter>(
+        &self,
+        query: &'a Query<'_, '_, D, F>,
+    ) -> Option<ROQueryItem<'a, D>> {
+        query.get(self.0?).ok()
+    }
+}
+
+fn track_player(
+    mut handle: ResMut<PlayerHandle>,
+    spawned: Query<Entity, Added<Player>>,
+    mut removed: RemovedComponents<Player>,
+) {
+    for entity in removed.read() {
+        if handle.0 == Some(entity) {
+            info!("Player {:?} is gone", entity);
+            handle.0 = None;
+        }
+    }
+
+    for entity in spawned.iter() {
+        info!("Tracking player {:?}", entity);
+        handle.0 = Some(entity);
+    }
+}
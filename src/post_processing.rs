@@ -0,0 +1,145 @@
+use crate::settings::{QualityPreset, QualitySettings};
+use bevy::core_pipeline::bloom::Bloom;
+use bevy::core_pipeline::post_process::ChromaticAberration;
+use bevy::prelude::*;
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::render::renderer::RenderAdapterInfo;
+use wgpu_types::DeviceType;
+
+pub struct PostProcessingPlugin;
+
+impl Plugin for PostProcessingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, (downgrade_on_software_adapter, spawn_vignette))
+            .add_systems(Update, apply_post_processing);
+    }
+}
+
+// Full-screen overlay standing in for a vignette pass, which Bevy doesn't ship yet
+#[derive(Component)]
+pub struct VignetteOverlay;
+
+const VIGNETTE_RESOLUTION: u32 = 128;
+
+fn downgrade_on_software_adapter(
+    adapter_info: Option<Res<RenderAdapterInfo>>,
+    mut settings: ResMut<QualitySettings>,
+) {
+    let Some(adapter_info) = adapter_info else {
+        return;
+    };
+
+    if adapter_info.device_type != DeviceType::Cpu
+        || matches!(settings.preset, QualityPreset::Minimal | QualityPreset::Low)
+    {
+        return;
+    }
+
+    // A preset the player saved stays theirs, even on a software renderer
+    if QualitySettings::is_saved() {
+        warn!(
+            "Software renderer detected ({}), {} quality may run better than {}",
+            adapter_info.name,
+            QualityPreset::Low,
+            settings.preset
+        );
+        return;
+    }
+
+    warn!(
+        "Software renderer detected ({}), switching to {} quality",
+        adapter_info.name,
+        QualityPreset::Low
+    );
+    *settings = QualitySettings::from_preset(QualityPreset::Low);
+}
+
+fn spawn_vignette(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    let size = VIGNETTE_RESOLUTION;
+    let mut data = Vec::with_capacity((size * size * 4) as usize);
+
+    for y in 0..size {
+        for x in 0..size {
+            // Distance from center, normalized so the corners sit at ~1.0
+            let u = (x as f32 + 0.5) / size as f32 * 2.0 - 1.0;
+            let v = (y as f32 + 0.5) / size as f32 * 2.0 - 1.0;
+            let distance = (u * u + v * v).sqrt() / std::f32::consts::SQRT_2;
+
+            let t = ((distance - 0.55) / 0.45).clamp(0.0, 1.0);
+            let alpha = t * t * (3.0 - 2.0 * t) * 0.6;
+
+            data.extend_from_slice(&[0, 0, 0, (alpha * 255.0) as u8]);
+        }
+    }
+
+    let image = Image::new(
+        Extent3d {
+            width: size,
+            height: size,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+
+    commands.spawn((
+        ImageNode::new(images.add(image)),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            ..default()
+        },
+        GlobalZIndex(-10), // Below the HUD and menus
+        Visibility::Hidden,
+        VignetteOverlay,
+    ));
+}
+
+fn apply_post_processing(
+    mut commands: Commands,
+    settings: Res<QualitySettings>,
+    mut camera_query: Query<(Entity, &mut Camera), With<Camera2d>>,
+    new_cameras: Query<(), Added<Camera2d>>,
+    mut vignette_query: Query<&mut Visibility, With<VignetteOverlay>>,
+) {
+    if !settings.is_changed() && new_cameras.is_empty() {
+        return;
+    }
+
+    for (entity, mut camera) in camera_query.iter_mut() {
+        // Bloom needs an HDR target; drop it again when nothing uses it
+        camera.hdr = settings.bloom;
+
+        if settings.bloom {
+            commands.entity(entity).insert(Bloom {
+                intensity: 0.1,
+                ..Bloom::NATURAL
+            });
+        } else {
+            commands.entity(entity).remove::<Bloom>();
+        }
+
+        if settings.chromatic_aberration {
+            commands.entity(entity).insert(ChromaticAberration {
+                intensity: 0.004,
+                ..default()
+            });
+        } else {
+            commands.entity(entity).remove::<ChromaticAberration>();
+        }
+    }
+
+    for mut visibility in vignette_query.iter_mut() {
+        *visibility = if settings.vignette {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+
+    info!("Applied {} quality post-processing", settings.preset);
+}
@@ -1,5 +1,9 @@
+use crate::definitions::{BossSpawn, SurgeEvent};
 use bevy::prelude::*;
 use bevy::sprite::TextureAtlasLayout;
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use serde::{Deserialize, Serialize};
 #[derive(Clone, Eq, PartialEq, Debug, Hash, Default, States)]
 pub enum GameState {
     #[default]
@@ -7,49 +11,197 @@ pub enum GameState {
     Settings,
     Playing,
     LevelUp,
+    Milestone, // Stat choice at a run timer milestone
     Paused,
     GameOver,
+    Victory,
     Quit,
 }
 
 #[derive(Resource)]
 pub struct GameStats {
     pub enemies_killed: u32,
+    pub bosses_killed: u32,
     pub time_elapsed: f32,
-    pub victory_threshold: u32,
 }
 
 impl Default for GameStats {
     fn default() -> Self {
         Self {
             enemies_killed: 0,
+            bosses_killed: 0,
             time_elapsed: 0.0,
-            victory_threshold: 200,
         }
     }
 }
 
-#[derive(Resource)]
-pub struct SpawnTimer(pub Timer);
-
-impl Default for SpawnTimer {
-    fn default() -> Self {
-        Self(Timer::from_seconds(2.0, TimerMode::Repeating))
-    }
+/// How many of the stage's surges have already gone off
+#[derive(Resource, Default)]
+pub struct SurgeProgress {
+    pub next_surge: usize,
 }
 
+/// Pacing and goals for the current run, filled in from the active stage
 #[derive(Resource)]
 pub struct WaveConfig {
     pub max_enemies: u32,
-    pub current_wave: u32,
+    pub run_length: Option<f32>, // Surviving this many seconds wins the run
+    pub kill_goal: Option<u32>,  // So does killing this many enemies
+    pub bosses: Vec<BossSpawn>,  // Sorted by time
+    pub surges: Vec<SurgeEvent>, // Sorted by time
+    pub elite_chance: f32,       // Share of regular spawns that come in as elites
 }
 
 impl Default for WaveConfig {
     fn default() -> Self {
         Self {
             max_enemies: 20,
-            current_wave: 0,
+            run_length: None,
+            kill_goal: None,
+            bosses: Vec::new(),
+            surges: Vec::new(),
+            elite_chance: 0.03,
+        }
+    }
+}
+
+impl WaveConfig {
+    /// Seconds left to survive, if the run has a time goal
+    pub fn time_remaining(&self, elapsed: f32) -> Option<f32> {
+        self.run_length
+            .map(|run_length| (run_length - elapsed).max(0.0))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Mutator {
+    /// Level-up choices must be made within this many seconds or one is picked automatically
+    ChoiceTimer(f32),
+    /// Enemies have this share more health and drop as much more experience
+    Curse(f32),
+}
+
+impl std::fmt::Display for Mutator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Mutator::ChoiceTimer(seconds) => write!(f, "Hasty Choices ({}s)", seconds),
+            Mutator::Curse(amount) => write!(f, "Curse (+{:.0}%)", amount * 100.0),
+        }
+    }
+}
+
+impl Mutator {
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Mutator::ChoiceTimer(_) => "⏳",
+            Mutator::Curse(_) => "💀",
+        }
+    }
+
+    pub fn description(&self) -> String {
+        match self {
+            Mutator::ChoiceTimer(seconds) => format!(
+                "Level-up rewards must be picked within {}s, or one is picked for you",
+                seconds
+            ),
+            Mutator::Curse(amount) => format!(
+                "Enemies have {:.0}% more health and drop {:.0}% more experience",
+                amount * 100.0,
+                amount * 100.0
+            ),
+        }
+    }
+}
+
+/// Optional rules layered on top of a run
+#[derive(Resource, Default)]
+pub struct RunModifiers {
+    pub mutators: Vec<Mutator>,
+}
+
+impl RunModifiers {
+    // Until there's a menu for it, mutators are opted into from the command line
+    pub fn from_args() -> Self {
+        let mut mutators = Vec::new();
+        if std::env::args().any(|arg| arg == "--hasty-choices") {
+            mutators.push(Mutator::ChoiceTimer(10.0));
+        }
+        if std::env::args().any(|arg| arg == "--curse") {
+            mutators.push(Mutator::Curse(0.5));
         }
+        Self { mutators }
+    }
+
+    pub fn choice_timer(&self) -> Option<f32> {
+        self.mutators.iter().find_map(|mutator| match mutator {
+            Mutator::ChoiceTimer(seconds) => Some(*seconds),
+            _ => None,
+        })
+    }
+
+    // Curses stack, 1.0 without any
+    pub fn curse_multiplier(&self) -> f32 {
+        1.0 + self
+            .mutators
+            .iter()
+            .map(|mutator| match mutator {
+                Mutator::Curse(amount) => *amount,
+                _ => 0.0,
+            })
+            .sum::<f32>()
+    }
+}
+
+/// Randomness for anything that should replay identically from the same seed
+#[derive(Resource)]
+pub struct GameRng {
+    pub seed: u64,
+    rng: StdRng,
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self::from_seed(rand::random())
+    }
+}
+
+impl GameRng {
+    pub fn from_seed(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    // `--seed <n>` pins the run for replays and bug reports
+    pub fn from_args() -> Self {
+        let args: Vec<String> = std::env::args().collect();
+        let game_rng = args
+            .iter()
+            .position(|arg| arg == "--seed")
+            .and_then(|index| args.get(index + 1))
+            .and_then(|value| value.parse().ok())
+            .map_or_else(Self::default, Self::from_seed);
+        info!("Run seed: {}", game_rng.seed);
+        game_rng
+    }
+}
+
+impl RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.rng.try_fill_bytes(dest)
     }
 }
 
@@ -0,0 +1,188 @@
+use crate::components::{
+    AreaMultiplier, Armor, CooldownReduction, DamageMultiplier, Equipment, Health, Luck, Player,
+};
+use crate::menu::{GenericUpgradeConfirmedEvent, WeaponUpgradeConfirmedEvent};
+use crate::resources::GameState;
+use crate::upgrade::UpgradeType;
+use crate::weapons::WeaponMeta;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+/// Keeps a log of every upgrade confirmed during the run, for the run history screen
+pub struct RunHistoryPlugin;
+
+impl Plugin for RunHistoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunHistory>()
+            .add_systems(OnExit(GameState::MainMenu), reset_run_history)
+            .add_systems(Update, record_upgrades);
+    }
+}
+
+/// Every upgrade taken this run, oldest first
+#[derive(Resource, Default, Debug)]
+pub struct RunHistory {
+    pub upgrades: Vec<UpgradeRecord>,
+}
+
+#[derive(Debug, Clone)]
+pub struct UpgradeRecord {
+    pub elapsed: f32, // Run time it was confirmed at
+    pub upgrade: UpgradeType,
+}
+
+impl std::fmt::Display for UpgradeRecord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let secs = self.elapsed as u32;
+        write!(f, "{:02}:{:02}  ", secs / 60, secs % 60)?;
+        match &self.upgrade {
+            UpgradeType::Weapon(weapon_type, spec) => write!(f, "{}: {}", weapon_type, spec),
+            UpgradeType::Generic(generic_upgrade) => write!(f, "{}", generic_upgrade),
+        }
+    }
+}
+
+impl RunHistory {
+    pub fn record(&mut self, elapsed: f32, upgrade: UpgradeType) {
+        self.upgrades.push(UpgradeRecord { elapsed, upgrade });
+    }
+}
+
+fn reset_run_history(mut history: ResMut<RunHistory>) {
+    history.upgrades.clear();
+}
+
+pub fn record_upgrades(
+    time: Res<Time<Virtual>>,
+    mut history: ResMut<RunHistory>,
+    mut weapon_events: EventReader<WeaponUpgradeConfirmedEvent>,
+    mut generic_events: EventReader<GenericUpgradeConfirmedEvent>,
+) {
+    for event in weapon_events.read() {
+        history.record(
+            time.elapsed_secs(),
+            UpgradeType::Weapon(event.weapon_type, event.upgrade_spec.clone()),
+        );
+    }
+    for event in generic_events.read() {
+        history.record(
+            time.elapsed_secs(),
+            UpgradeType::Generic(event.generic_upgrade_type),
+        );
+    }
+}
+
+// Older upgrades are summed up in a single line so the screen fits
+const HISTORY_SCREEN_LENGTH: usize = 12;
+
+type StatComponents = (
+    &'static Player,
+    &'static Health,
+    &'static Armor,
+    &'static DamageMultiplier,
+    &'static CooldownReduction,
+    &'static AreaMultiplier,
+    &'static Luck,
+);
+
+/// Everything the run history screen lists: the build as it stands and how it got there
+#[derive(SystemParam)]
+pub struct RunHistoryView<'w, 's> {
+    history: Res<'w, RunHistory>,
+    weapon_query: Query<'w, 's, &'static WeaponMeta>,
+    stats_query: Query<'w, 's, StatComponents>,
+    equipment_query: Query<'w, 's, &'static Equipment, With<Player>>,
+}
+
+impl RunHistoryView<'_, '_> {
+    pub fn spawn(&self, parent: &mut ChildBuilder) {
+        parent
+            .spawn(Node {
+                flex_direction: FlexDirection::Row,
+                column_gap: Val::Px(40.0),
+                ..default()
+            })
+            .with_children(|parent| {
+                spawn_column(parent, |parent| {
+                    spawn_heading(parent, "Weapons");
+                    let mut weapons: Vec<_> = self.weapon_query.iter().collect();
+                    weapons.sort_by_key(|meta| std::cmp::Reverse(meta.level));
+                    for meta in weapons {
+                        spawn_line(parent, format!("{} Lv {}", meta.weapon_type, meta.level));
+                    }
+
+                    if let Ok(equipment) = self.equipment_query.get_single() {
+                        if !equipment.items.is_empty() {
+                            spawn_heading(parent, "Passives");
+                        }
+                        for (equipment_type, level) in &equipment.items {
+                            spawn_line(parent, format!("{} Lv {}", equipment_type, level));
+                        }
+                    }
+
+                    if let Ok((player, health, armor, damage, cooldown, area, luck)) =
+                        self.stats_query.get_single()
+                    {
+                        spawn_heading(parent, "Stats");
+                        spawn_line(parent, format!("Max health: {}", health.maximum));
+                        spawn_line(parent, format!("Armor: {}", armor.0));
+                        spawn_line(parent, format!("Speed: {:.0}", player.speed));
+                        spawn_line(parent, format!("Damage: x{:.2}", damage.factor));
+                        spawn_line(
+                            parent,
+                            format!("Cooldown: -{:.0}%", cooldown.percent * 100.0),
+                        );
+                        spawn_line(parent, format!("Area: x{:.2}", area.factor));
+                        spawn_line(parent, format!("Luck: {}", luck.0));
+                    }
+                });
+
+                spawn_column(parent, |parent| {
+                    spawn_heading(parent, "Upgrades taken");
+                    let upgrades = &self.history.upgrades;
+                    let skipped = upgrades.len().saturating_sub(HISTORY_SCREEN_LENGTH);
+                    if skipped > 0 {
+                        spawn_line(parent, format!("... {} earlier", skipped));
+                    }
+                    for record in &upgrades[skipped..] {
+                        spawn_line(parent, record.to_string());
+                    }
+                    if upgrades.is_empty() {
+                        spawn_line(parent, "None yet".to_string());
+                    }
+                });
+            });
+    }
+}
+
+fn spawn_column(parent: &mut ChildBuilder, spawn_content: impl FnOnce(&mut ChildBuilder)) {
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(4.0),
+            ..default()
+        })
+        .with_children(spawn_content);
+}
+
+fn spawn_heading(parent: &mut ChildBuilder, text: &str) {
+    parent.spawn((
+        Text::new(text),
+        TextFont {
+            font_size: 20.0,
+            ..default()
+        },
+        TextColor(Color::srgb(1.0, 0.84, 0.0)),
+    ));
+}
+
+fn spawn_line(parent: &mut ChildBuilder, text: String) {
+    parent.spawn((
+        Text::new(text),
+        TextFont {
+            font_size: 16.0,
+            ..default()
+        },
+        TextColor(Color::srgb(0.8, 0.8, 0.8)),
+    ));
+}
@@ -0,0 +1,178 @@
+use crate::camera::CameraFollow;
+use crate::components::{Character, Player};
+use crate::definitions::{Definitions, StageWall};
+use crate::difficulty::ExperienceScale;
+use crate::director::DirectorProgress;
+use crate::enemy_pool::EnemyPool;
+use crate::interaction::FocusedInteractable;
+use crate::kill_cam::KillCam;
+use crate::menu::{MenuAction, MenuActionComponent, MenuInput, MenuItem};
+use crate::milestone::MilestoneProgress;
+use crate::resources::{GameRng, GameState, GameStats, SurgeProgress, WaveConfig};
+use crate::run_history::RunHistory;
+use crate::save::SaveData;
+use crate::set_piece::SetPiece;
+use crate::supply_drop::SupplyDropTimer;
+use crate::systems::spawn_player;
+use crate::telemetry::{RunTotals, WeaponStats};
+use crate::trail::TrailPool;
+use crate::unlock_reveal::RunStartCodex;
+use crate::waves::WaveDirector;
+use crate::weapons::StartingWeapon;
+use bevy::prelude::*;
+
+/// Starts a new run without relaunching. Restarting or heading back to the main menu from
+/// the pause menu or the results screen clears out the current run, so the next one starts
+/// the way the first one did.
+pub struct RunResetPlugin;
+
+impl Plugin for RunResetPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<RestartRunEvent>().add_systems(
+            Update,
+            (
+                request_reset.run_if(
+                    in_state(GameState::Paused)
+                        .or(in_state(GameState::GameOver))
+                        .or(in_state(GameState::Victory)),
+                ),
+                restart_run,
+            )
+                .chain(),
+        );
+    }
+}
+
+/// Throws the current run away and starts a fresh one
+#[derive(Event)]
+pub struct RestartRunEvent;
+
+fn request_reset(
+    mut commands: Commands,
+    menu_input: MenuInput,
+    menu_items: Query<(&MenuItem, &MenuActionComponent, Ref<Interaction>)>,
+    mut restart_events: EventWriter<RestartRunEvent>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let reset_items = menu_items.iter().filter(|(_, action_component, _)| {
+        matches!(
+            action_component.action,
+            MenuAction::RestartGame | MenuAction::ExitToMainMenu
+        )
+    });
+    match menu_input.confirmed(reset_items) {
+        Some(MenuAction::RestartGame) => {
+            restart_events.send(RestartRunEvent);
+        }
+        Some(_) => {
+            // Runs from this session can be browsed and replayed from there
+            info!("Leaving the run for the main menu");
+            commands.queue(reset_run);
+            next_state.set(GameState::MainMenu);
+        }
+        None => (),
+    }
+}
+
+fn restart_run(
+    mut commands: Commands,
+    mut restart_events: EventReader<RestartRunEvent>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if restart_events.is_empty() {
+        return;
+    }
+    restart_events.clear();
+
+    info!("Restarting the run");
+    commands.queue(reset_run);
+    next_state.set(GameState::Playing);
+}
+
+/// Despawns everything the run put into the world and puts every per-run resource back.
+/// Cameras, stage walls and UI stay, the UI is torn down by the states that own it.
+pub fn reset_run(world: &mut World) {
+    // The next run is played with the same character and starting weapon
+    let loadout = world
+        .query_filtered::<(&Character, &StartingWeapon), With<Player>>()
+        .get_single(world)
+        .ok()
+        .map(|(character, starting_weapon)| (*character, starting_weapon.0));
+
+    let leftovers: Vec<Entity> = world
+        .query_filtered::<Entity, (
+            With<Transform>,
+            Without<Parent>,
+            Without<Camera>,
+            Without<Node>,
+            Without<StageWall>,
+        )>()
+        .iter(world)
+        .collect();
+    info!("Resetting run, despawning {} entities", leftovers.len());
+    for entity in leftovers {
+        world.entity_mut(entity).despawn_recursive();
+    }
+
+    world.insert_resource(GameStats::default());
+    world.insert_resource(SurgeProgress::default());
+    world.insert_resource(DirectorProgress::default());
+    world.insert_resource(MilestoneProgress::default());
+    world.insert_resource(SetPiece::default());
+    world.insert_resource(ExperienceScale::default());
+    world.insert_resource(RunTotals::default());
+    world.insert_resource(WeaponStats::default());
+    world.insert_resource(RunHistory::default());
+    world.insert_resource(FocusedInteractable::default());
+    world.insert_resource(KillCam::default());
+    // Pooled entities went with everything else
+    world.insert_resource(EnemyPool::default());
+    world.insert_resource(TrailPool::default());
+    // Run time is virtual time, it starts over from zero
+    world.insert_resource(Time::<Virtual>::default());
+    // Each run gets its own seed, so past runs can be replayed one by one
+    let game_rng = GameRng::default();
+    info!("Run seed: {}", game_rng.seed);
+    world.insert_resource(game_rng);
+
+    // The new player starts back at the origin, the camera shouldn't sweep over to them
+    if let Some(mut follow) = world.get_resource_mut::<CameraFollow>() {
+        follow.snap();
+    }
+    if let Some(mut timer) = world.get_resource_mut::<SupplyDropTimer>() {
+        timer.0.reset();
+    }
+    if let Some(mut director) = world.get_resource_mut::<WaveDirector>() {
+        let waves = std::mem::take(&mut director.waves);
+        *director = WaveDirector::new(waves).with_strategy(director.strategy);
+    }
+    // The enemy cap ramps up over a run, it goes back to where the stage starts it
+    let max_enemies = world
+        .get_resource::<Definitions>()
+        .and_then(|definitions| definitions.stage())
+        .map(|stage| stage.max_enemies);
+    if let (Some(max_enemies), Some(mut wave_config)) =
+        (max_enemies, world.get_resource_mut::<WaveConfig>())
+    {
+        wave_config.max_enemies = max_enemies;
+    }
+    if let Some(codex) = world
+        .get_resource::<SaveData>()
+        .map(|save| save.codex.clone())
+    {
+        world.insert_resource(RunStartCodex(codex));
+    }
+
+    if let Err(e) = world.run_system_cached(spawn_player) {
+        error!("Failed to spawn the player for the next run: {}", e);
+        return;
+    }
+    if let Some((character, weapon_type)) = loadout {
+        let mut player_query =
+            world.query_filtered::<(&mut Character, &mut StartingWeapon), With<Player>>();
+        for (mut new_character, mut starting_weapon) in player_query.iter_mut(world) {
+            *new_character = character;
+            starting_weapon.0 = weapon_type;
+        }
+    }
+}
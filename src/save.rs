@@ -0,0 +1,72 @@
+use crate::codex::Codex;
+use crate::loadout::LoadoutPreset;
+use crate::past_runs::PastRuns;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(SaveData::load()).add_systems(
+            Last,
+            persist_save_data
+                .run_if(resource_changed::<SaveData>.and(not(resource_exists::<EphemeralSave>))),
+        );
+    }
+}
+
+const SAVE_PATH: &str = "save.ron";
+
+/// Account-wide progress that outlives a single run.
+/// Only mutate it when something actually changed, every change is written to disk.
+#[derive(Resource, Serialize, Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct SaveData {
+    pub codex: Codex,
+    pub loadouts: Vec<LoadoutPreset>,
+    pub past_runs: PastRuns,
+}
+
+/// Insert alongside a fresh `SaveData` to play without writing to the save file,
+/// so simulated runs neither use nor earn account progress
+#[derive(Resource)]
+pub struct EphemeralSave;
+
+impl SaveData {
+    pub fn load() -> Self {
+        match fs::read_to_string(SAVE_PATH) {
+            Ok(contents) => ron::from_str(&contents).unwrap_or_else(|e| {
+                warn!("Failed to parse {}, starting fresh: {}", SAVE_PATH, e);
+                Self::default()
+            }),
+            Err(_) => {
+                info!("No save file found at {}, starting fresh", SAVE_PATH);
+                Self::default()
+            }
+        }
+    }
+
+    pub fn save(&self) {
+        let contents = match ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default()) {
+            Ok(contents) => contents,
+            Err(e) => {
+                error!("Failed to serialize save data: {}", e);
+                return;
+            }
+        };
+
+        if let Err(e) = fs::write(SAVE_PATH, contents) {
+            error!("Failed to write {}: {}", SAVE_PATH, e);
+        }
+    }
+}
+
+fn persist_save_data(save_data: Res<SaveData>) {
+    // Nothing new to write on the frame it was loaded
+    if save_data.is_added() {
+        return;
+    }
+    save_data.save();
+}
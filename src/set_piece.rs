@@ -0,0 +1,184 @@
+use crate::camera::follow_player;
+use crate::resources::GameState;
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+/// Short scripted moments like boss entrances: the game slows down, the camera wanders
+/// off to look at something, a caption comes up, then everything goes back to normal.
+/// Steps run on real time so slowing the game doesn't drag them out.
+pub struct SetPiecePlugin;
+
+impl Plugin for SetPiecePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SetPiece>()
+            .add_event::<PlaySetPiece>()
+            .add_systems(
+                Update,
+                (queue_set_pieces, run_set_piece)
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(
+                PostUpdate,
+                pan_camera
+                    .after(follow_player)
+                    .before(TransformSystem::TransformPropagate)
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+/// Plays these steps in order, after whatever set piece is already running
+#[derive(Event, Debug, Clone)]
+pub struct PlaySetPiece(pub Vec<SetPieceStep>);
+
+#[derive(Debug, Clone)]
+pub struct SetPieceStep {
+    pub duration: f32,           // Real seconds
+    pub time_scale: f32,         // Game speed while the step runs
+    pub focus: Option<Entity>,   // Camera looks here instead of at the player
+    pub caption: Option<String>, // Shown across the screen while the step runs
+}
+
+impl Default for SetPieceStep {
+    fn default() -> Self {
+        Self {
+            duration: 1.0,
+            time_scale: 1.0,
+            focus: None,
+            caption: None,
+        }
+    }
+}
+
+/// Steps still to play, the front one is running
+#[derive(Resource, Default)]
+pub struct SetPiece {
+    steps: VecDeque<SetPieceStep>,
+    timer: Timer,
+    camera_offset: Vec2, // From where the camera would normally be
+}
+
+impl SetPiece {
+    pub fn is_playing(&self) -> bool {
+        !self.steps.is_empty()
+    }
+}
+
+#[derive(Component)]
+pub struct SetPieceCaption;
+
+// How quickly the camera closes in on where it should be looking, per second
+const CAMERA_PAN_RATE: f32 = 6.0;
+
+fn queue_set_pieces(
+    mut commands: Commands,
+    mut requests: EventReader<PlaySetPiece>,
+    mut set_piece: ResMut<SetPiece>,
+    mut time: ResMut<Time<Virtual>>,
+    caption_query: Query<Entity, With<SetPieceCaption>>,
+) {
+    for PlaySetPiece(steps) in requests.read() {
+        let was_idle = !set_piece.is_playing();
+        set_piece.steps.extend(steps.iter().cloned());
+        if was_idle {
+            begin_step(&mut commands, &mut set_piece, &mut time, &caption_query);
+        }
+    }
+}
+
+fn run_set_piece(
+    mut commands: Commands,
+    real_time: Res<Time<Real>>,
+    mut set_piece: ResMut<SetPiece>,
+    mut time: ResMut<Time<Virtual>>,
+    caption_query: Query<Entity, With<SetPieceCaption>>,
+) {
+    if !set_piece.is_playing() || !set_piece.timer.tick(real_time.delta()).finished() {
+        return;
+    }
+
+    set_piece.steps.pop_front();
+    begin_step(&mut commands, &mut set_piece, &mut time, &caption_query);
+}
+
+/// Sets up the front step, or hands control back once there are none left
+fn begin_step(
+    commands: &mut Commands,
+    set_piece: &mut SetPiece,
+    time: &mut Time<Virtual>,
+    caption_query: &Query<Entity, With<SetPieceCaption>>,
+) {
+    for entity in caption_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    let Some(step) = set_piece.steps.front() else {
+        time.set_relative_speed(1.0);
+        return;
+    };
+
+    time.set_relative_speed(step.time_scale);
+    if let Some(caption) = &step.caption {
+        spawn_caption(commands, caption);
+    }
+    set_piece.timer = Timer::from_seconds(step.duration, TimerMode::Once);
+}
+
+fn spawn_caption(commands: &mut Commands, caption: &str) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                top: Val::Percent(25.0),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            SetPieceCaption,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(caption),
+                TextFont {
+                    font_size: 48.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.5, 0.2)),
+                Node {
+                    padding: UiRect::axes(Val::Px(24.0), Val::Px(8.0)),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+            ));
+        });
+}
+
+/// Eases the camera towards the focus of the current step and back to the player after
+fn pan_camera(
+    real_time: Res<Time<Real>>,
+    mut set_piece: ResMut<SetPiece>,
+    focus_query: Query<&GlobalTransform, Without<Camera2d>>,
+    mut camera_query: Query<&mut Transform, With<Camera2d>>,
+) {
+    let Ok(mut camera_transform) = camera_query.get_single_mut() else {
+        return;
+    };
+
+    let focus = set_piece
+        .steps
+        .front()
+        .and_then(|step| step.focus)
+        .and_then(|entity| focus_query.get(entity).ok());
+    let target = focus.map_or(Vec2::ZERO, |focus| {
+        focus.translation().truncate() - camera_transform.translation.truncate()
+    });
+    if target == Vec2::ZERO && set_piece.camera_offset.length_squared() < 0.01 {
+        set_piece.camera_offset = Vec2::ZERO;
+        return;
+    }
+
+    let blend = 1.0 - (-CAMERA_PAN_RATE * real_time.delta_secs()).exp();
+    set_piece.camera_offset = set_piece.camera_offset.lerp(target, blend);
+    camera_transform.translation += set_piece.camera_offset.extend(0.0);
+}
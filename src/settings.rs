@@ -0,0 +1,396 @@
+use crate::menu::{MenuAction, MenuActionComponent, MenuInput, MenuItem};
+use crate::resources::GameState;
+use bevy::audio::Volume;
+use bevy::prelude::*;
+use bevy::window::{PresentMode, PrimaryWindow, WindowMode};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use strum_macros::EnumIter;
+
+const SETTINGS_PATH: &str = "settings.ron";
+const GAME_SETTINGS_PATH: &str = "game_settings.ron";
+
+/// Loads the player's preferences at startup, applies them to the window and audio,
+/// and lets the settings screen change them
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(GameSettings::load()).add_systems(
+            Update,
+            (
+                change_game_settings.run_if(in_state(GameState::Settings)),
+                apply_game_settings.run_if(resource_changed::<GameSettings>),
+            )
+                .chain(),
+        );
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum QualityPreset {
+    Minimal, // For machines that struggle even on Low
+    Low,
+    Medium,
+    High,
+}
+
+impl std::fmt::Display for QualityPreset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QualityPreset::Minimal => write!(f, "Minimal"),
+            QualityPreset::Low => write!(f, "Low"),
+            QualityPreset::Medium => write!(f, "Medium"),
+            QualityPreset::High => write!(f, "High"),
+        }
+    }
+}
+
+/// Rendering quality knobs that trade visual flair for frame time
+#[derive(Resource, Serialize, Deserialize, Debug)]
+pub struct QualitySettings {
+    pub preset: QualityPreset,
+    pub bloom: bool,
+    pub vignette: bool,
+    pub chromatic_aberration: bool,
+    pub trails_enabled: bool,
+    pub max_trail_segments: usize, // Global cap across every trail emitter
+    // Newer knobs fall back to their High values so older settings files still load
+    #[serde(default = "QualitySettings::default_debug_render")]
+    pub debug_render: bool, // Collider outlines, only drawn in debug builds
+    #[serde(default = "QualitySettings::default_max_effect_particles")]
+    pub max_effect_particles: usize, // Global cap on short-lived sparks and bursts
+    #[serde(default)]
+    pub sprite_attacks: bool, // Flat sprites instead of tessellated shapes for circles and zones
+    #[serde(default)]
+    pub enemy_cap: Option<u32>, // Caps the stage's own enemy limit
+}
+
+impl QualitySettings {
+    pub fn from_preset(preset: QualityPreset) -> Self {
+        match preset {
+            QualityPreset::Minimal => Self {
+                preset,
+                bloom: false,
+                vignette: false,
+                chromatic_aberration: false,
+                trails_enabled: false,
+                max_trail_segments: 64,
+                debug_render: false,
+                max_effect_particles: 16,
+                sprite_attacks: true,
+                enemy_cap: Some(40),
+            },
+            QualityPreset::Low => Self {
+                preset,
+                bloom: false,
+                vignette: false,
+                chromatic_aberration: false,
+                trails_enabled: true,
+                max_trail_segments: 128,
+                debug_render: true,
+                max_effect_particles: 64,
+                sprite_attacks: false,
+                enemy_cap: None,
+            },
+            QualityPreset::Medium => Self {
+                preset,
+                bloom: true,
+                vignette: true,
+                chromatic_aberration: false,
+                trails_enabled: true,
+                max_trail_segments: 256,
+                debug_render: true,
+                max_effect_particles: 128,
+                sprite_attacks: false,
+                enemy_cap: None,
+            },
+            QualityPreset::High => Self {
+                preset,
+                bloom: true,
+                vignette: true,
+                chromatic_aberration: true,
+                trails_enabled: true,
+                max_trail_segments: 512,
+                debug_render: true,
+                max_effect_particles: 256,
+                sprite_attacks: false,
+                enemy_cap: None,
+            },
+        }
+    }
+
+    /// The preset after this one, wrapping back around to the lowest
+    pub fn next_preset(&self) -> QualityPreset {
+        match self.preset {
+            QualityPreset::Minimal => QualityPreset::Low,
+            QualityPreset::Low => QualityPreset::Medium,
+            QualityPreset::Medium => QualityPreset::High,
+            QualityPreset::High => QualityPreset::Minimal,
+        }
+    }
+
+    fn default_debug_render() -> bool {
+        true
+    }
+
+    fn default_max_effect_particles() -> usize {
+        256
+    }
+}
+
+impl Default for QualitySettings {
+    fn default() -> Self {
+        // WebGL2 can't afford the extra fullscreen passes
+        if cfg!(target_arch = "wasm32") {
+            Self::from_preset(QualityPreset::Low)
+        } else {
+            Self::from_preset(QualityPreset::High)
+        }
+    }
+}
+
+impl QualitySettings {
+    pub fn load() -> Self {
+        match fs::read_to_string(SETTINGS_PATH) {
+            Ok(contents) => ron::from_str(&contents).unwrap_or_else(|e| {
+                warn!("Failed to parse {}, using defaults: {}", SETTINGS_PATH, e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Whether the player has a settings file, i.e. picked their quality at some point
+    pub fn is_saved() -> bool {
+        fs::metadata(SETTINGS_PATH).is_ok()
+    }
+
+    pub fn save(&self) {
+        let contents = match ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default()) {
+            Ok(contents) => contents,
+            Err(e) => {
+                error!("Failed to serialize settings: {}", e);
+                return;
+            }
+        };
+
+        if let Err(e) = fs::write(SETTINGS_PATH, contents) {
+            error!("Failed to write {}: {}", SETTINGS_PATH, e);
+        }
+    }
+}
+
+// Writes settings out only if they changed since the last flush
+pub fn flush_dirty_settings(settings: Res<QualitySettings>) {
+    if settings.is_changed() {
+        info!("Flushing settings to {}", SETTINGS_PATH);
+        settings.save();
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default, Serialize, Deserialize)]
+pub enum DisplayMode {
+    #[default]
+    Windowed,
+    Borderless,
+    Fullscreen,
+}
+
+impl std::fmt::Display for DisplayMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DisplayMode::Windowed => write!(f, "Windowed"),
+            DisplayMode::Borderless => write!(f, "Borderless"),
+            DisplayMode::Fullscreen => write!(f, "Fullscreen"),
+        }
+    }
+}
+
+impl DisplayMode {
+    pub fn next(self) -> Self {
+        match self {
+            DisplayMode::Windowed => DisplayMode::Borderless,
+            DisplayMode::Borderless => DisplayMode::Fullscreen,
+            DisplayMode::Fullscreen => DisplayMode::Windowed,
+        }
+    }
+
+    pub fn window_mode(self) -> WindowMode {
+        match self {
+            DisplayMode::Windowed => WindowMode::Windowed,
+            DisplayMode::Borderless => WindowMode::BorderlessFullscreen(MonitorSelection::Current),
+            DisplayMode::Fullscreen => WindowMode::Fullscreen(MonitorSelection::Current),
+        }
+    }
+}
+
+/// An entry on the settings screen
+#[derive(Debug, Clone, Copy, Eq, PartialEq, EnumIter)]
+pub enum SettingKind {
+    MasterVolume,
+    MusicVolume,
+    SfxVolume,
+    DisplayMode,
+    Vsync,
+    DamageNumbers,
+    ScreenShake,
+}
+
+// Volumes go up in these steps, wrapping from full back to silent
+const VOLUME_STEP: f32 = 0.2;
+
+/// Sound, display and feedback preferences, saved apart from the quality settings.
+/// Volumes are 0 to 1.
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct GameSettings {
+    pub master_volume: f32, // Scales every sound
+    pub music_volume: f32,  // Nothing plays music yet
+    pub sfx_volume: f32,
+    pub display_mode: DisplayMode,
+    pub vsync: bool,
+    pub damage_numbers: bool, // Kept for damage numbers, the game doesn't show any yet
+    pub screen_shake: bool,   // Kept for screen shake, the camera doesn't shake yet
+}
+
+impl Default for GameSettings {
+    fn default() -> Self {
+        Self {
+            master_volume: 1.0,
+            music_volume: 0.8,
+            sfx_volume: 1.0,
+            display_mode: DisplayMode::Windowed,
+            vsync: true,
+            damage_numbers: true,
+            screen_shake: true,
+        }
+    }
+}
+
+fn next_volume(volume: f32) -> f32 {
+    if volume >= 1.0 - f32::EPSILON {
+        0.0
+    } else {
+        (((volume / VOLUME_STEP).round() + 1.0) * VOLUME_STEP).min(1.0)
+    }
+}
+
+fn on_off(enabled: bool) -> &'static str {
+    if enabled {
+        "On"
+    } else {
+        "Off"
+    }
+}
+
+impl GameSettings {
+    pub fn load() -> Self {
+        match fs::read_to_string(GAME_SETTINGS_PATH) {
+            Ok(contents) => ron::from_str(&contents).unwrap_or_else(|e| {
+                warn!(
+                    "Failed to parse {}, using defaults: {}",
+                    GAME_SETTINGS_PATH, e
+                );
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn save(&self) {
+        let contents = match ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default()) {
+            Ok(contents) => contents,
+            Err(e) => {
+                error!("Failed to serialize game settings: {}", e);
+                return;
+            }
+        };
+
+        if let Err(e) = fs::write(GAME_SETTINGS_PATH, contents) {
+            error!("Failed to write {}: {}", GAME_SETTINGS_PATH, e);
+        }
+    }
+
+    /// Moves the setting on to its next value
+    pub fn cycle(&mut self, kind: SettingKind) {
+        match kind {
+            SettingKind::MasterVolume => self.master_volume = next_volume(self.master_volume),
+            SettingKind::MusicVolume => self.music_volume = next_volume(self.music_volume),
+            SettingKind::SfxVolume => self.sfx_volume = next_volume(self.sfx_volume),
+            SettingKind::DisplayMode => self.display_mode = self.display_mode.next(),
+            SettingKind::Vsync => self.vsync = !self.vsync,
+            SettingKind::DamageNumbers => self.damage_numbers = !self.damage_numbers,
+            SettingKind::ScreenShake => self.screen_shake = !self.screen_shake,
+        }
+    }
+
+    /// What the setting's button reads
+    pub fn label(&self, kind: SettingKind) -> String {
+        let percent = |volume: f32| (volume * 100.0).round() as u32;
+        match kind {
+            SettingKind::MasterVolume => format!("Master: {}%", percent(self.master_volume)),
+            SettingKind::MusicVolume => format!("Music: {}%", percent(self.music_volume)),
+            SettingKind::SfxVolume => format!("SFX: {}%", percent(self.sfx_volume)),
+            SettingKind::DisplayMode => format!("Window: {}", self.display_mode),
+            SettingKind::Vsync => format!("VSync: {}", on_off(self.vsync)),
+            SettingKind::DamageNumbers => {
+                format!("Damage Numbers: {}", on_off(self.damage_numbers))
+            }
+            SettingKind::ScreenShake => format!("Screen Shake: {}", on_off(self.screen_shake)),
+        }
+    }
+
+    /// Playback for a one-off sound effect at the effects volume
+    pub fn sfx_playback(&self) -> PlaybackSettings {
+        PlaybackSettings::DESPAWN.with_volume(Volume::new(self.sfx_volume))
+    }
+}
+
+fn change_game_settings(
+    menu_input: MenuInput,
+    menu_items: Query<(&MenuItem, &MenuActionComponent, Ref<Interaction>, &Children)>,
+    mut button_text: Query<&mut Text>,
+    mut settings: ResMut<GameSettings>,
+) {
+    for (menu_item, action_component, interaction, children) in menu_items.iter() {
+        let MenuAction::ChangeSetting(kind) = action_component.action else {
+            continue;
+        };
+
+        if !menu_input.is_confirmed(menu_item, &interaction) {
+            continue;
+        }
+
+        settings.cycle(kind);
+        info!("Setting changed: {}", settings.label(kind));
+        settings.save();
+
+        if let Some(mut text) = children
+            .first()
+            .and_then(|&child| button_text.get_mut(child).ok())
+        {
+            text.0 = settings.label(kind);
+        }
+    }
+}
+
+// Sounds already playing keep the volume they started with
+fn apply_game_settings(
+    settings: Res<GameSettings>,
+    global_volume: Option<ResMut<GlobalVolume>>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    if let Some(mut global_volume) = global_volume {
+        *global_volume = GlobalVolume::new(settings.master_volume);
+    }
+
+    if let Ok(mut window) = window_query.get_single_mut() {
+        window.mode = settings.display_mode.window_mode();
+        window.present_mode = if settings.vsync {
+            PresentMode::AutoVsync
+        } else {
+            PresentMode::AutoNoVsync
+        };
+    }
+}
@@ -0,0 +1,109 @@
+use crate::components::Enemy;
+use crate::death::MarkedForDeath;
+use crate::resources::GameState;
+use crate::GameplaySets;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+pub struct SpatialGridPlugin;
+
+impl Plugin for SpatialGridPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EnemySpatialGrid>().add_systems(
+            Update,
+            rebuild_enemy_grid
+                .after(GameplaySets::Movement)
+                .before(GameplaySets::Combat)
+                .run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+/// Uniform grid of live enemy positions, rebuilt once per frame.
+/// Cheap "who's near here" lookups for targeting without touching the physics world.
+#[derive(Resource)]
+pub struct EnemySpatialGrid {
+    pub cell_size: f32,
+    cells: HashMap<IVec2, Vec<(Entity, Vec2)>>,
+}
+
+impl Default for EnemySpatialGrid {
+    fn default() -> Self {
+        Self {
+            cell_size: 64.0,
+            cells: HashMap::new(),
+        }
+    }
+}
+
+impl EnemySpatialGrid {
+    fn cell(&self, position: Vec2) -> IVec2 {
+        (position / self.cell_size).floor().as_ivec2()
+    }
+
+    pub fn clear(&mut self) {
+        // Cells used last frame keep their allocations, the horde mostly stays in the same
+        // ones. Cells that were already empty go, so the map doesn't grow with every cell
+        // the horde ever passed through.
+        self.cells.retain(|_, entities| {
+            let occupied = !entities.is_empty();
+            entities.clear();
+            occupied
+        });
+    }
+
+    pub fn insert(&mut self, entity: Entity, position: Vec2) {
+        let cell = self.cell(position);
+        self.cells.entry(cell).or_default().push((entity, position));
+    }
+
+    /// Center and enemy count of every occupied cell, for density views
+    pub fn occupied_cells(&self) -> impl Iterator<Item = (Vec2, usize)> + '_ {
+        self.cells
+            .iter()
+            .filter(|(_, entities)| !entities.is_empty())
+            .map(|(cell, entities)| ((cell.as_vec2() + 0.5) * self.cell_size, entities.len()))
+    }
+
+    /// Every enemy within `radius` of `position`
+    pub fn query_radius(
+        &self,
+        position: Vec2,
+        radius: f32,
+    ) -> impl Iterator<Item = (Entity, Vec2)> + '_ {
+        let min = self.cell(position - Vec2::splat(radius));
+        let max = self.cell(position + Vec2::splat(radius));
+
+        (min.x..=max.x)
+            .flat_map(move |x| (min.y..=max.y).map(move |y| IVec2::new(x, y)))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .copied()
+            .filter(move |(_, other)| other.distance_squared(position) <= radius * radius)
+    }
+
+    /// Closest enemy within `max_distance`, skipping anything `exclude` rejects
+    pub fn nearest(
+        &self,
+        position: Vec2,
+        max_distance: f32,
+        exclude: impl Fn(Entity) -> bool,
+    ) -> Option<(Entity, Vec2)> {
+        self.query_radius(position, max_distance)
+            .filter(|(entity, _)| !exclude(*entity))
+            .min_by(|(_, a), (_, b)| {
+                a.distance_squared(position)
+                    .total_cmp(&b.distance_squared(position))
+            })
+    }
+}
+
+fn rebuild_enemy_grid(
+    mut grid: ResMut<EnemySpatialGrid>,
+    enemy_query: Query<(Entity, &Transform), (With<Enemy>, Without<MarkedForDeath>)>,
+) {
+    grid.clear();
+    for (entity, transform) in enemy_query.iter() {
+        grid.insert(entity, transform.translation.truncate());
+    }
+}
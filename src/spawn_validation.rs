@@ -0,0 +1,76 @@
+use crate::physics::handle_rapier_context_error;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use rand::Rng;
+
+/// Keeps spawns out of stage geometry.
+/// Walls and hazards are static colliders, so anything overlapping a fixed body is rejected.
+pub struct SpawnValidationPlugin;
+
+impl Plugin for SpawnValidationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpawnValidationSettings>();
+    }
+}
+
+#[derive(Resource)]
+pub struct SpawnValidationSettings {
+    pub max_attempts: u32,
+    pub backoff: f32, // Each blocked attempt pushes the next one this much further out
+    pub clearance: f32, // Extra room kept around the spawned collider
+}
+
+impl Default for SpawnValidationSettings {
+    fn default() -> Self {
+        Self {
+            max_attempts: 8,
+            backoff: 1.15,
+            clearance: 4.0,
+        }
+    }
+}
+
+#[derive(SystemParam)]
+pub struct SpawnValidator<'w, 's> {
+    context_query: Query<'w, 's, &'static RapierContext>,
+    settings: Res<'w, SpawnValidationSettings>,
+}
+
+impl SpawnValidator<'_, '_> {
+    /// Whether a ball of this radius fits at the position without touching stage geometry
+    pub fn is_clear(&self, position: Vec2, radius: f32) -> bool {
+        let rapier_context = self
+            .context_query
+            .get_single()
+            .unwrap_or_else(|e| handle_rapier_context_error(e));
+
+        let shape = Collider::ball(radius + self.settings.clearance);
+        rapier_context
+            .intersection_with_shape(position, 0.0, &shape, QueryFilter::only_fixed())
+            .is_none()
+    }
+
+    /// Picks a random clear point on a ring around `center`.
+    /// A blocked point is retried at a new angle on a wider ring, giving up after `max_attempts`.
+    pub fn find_position_around(
+        &self,
+        center: Vec2,
+        distance: f32,
+        radius: f32,
+        rng: &mut impl Rng,
+    ) -> Option<Vec2> {
+        let mut distance = distance;
+
+        for _ in 0..self.settings.max_attempts {
+            let angle = rng.gen::<f32>() * std::f32::consts::TAU;
+            let position = center + Vec2::from_angle(angle) * distance;
+            if self.is_clear(position, radius) {
+                return Some(position);
+            }
+            distance *= self.settings.backoff;
+        }
+
+        None
+    }
+}
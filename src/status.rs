@@ -0,0 +1,334 @@
+use crate::combat::DamageEvent;
+use crate::components::Enemy;
+use crate::death::MarkedForDeath;
+use crate::game_time::PausableTimer;
+use crate::materials::{StatusTint, Tint};
+use crate::resources::GameState;
+use crate::GameplaySets;
+use bevy::prelude::*;
+
+/// Lingering effects on enemies: damage over time, slows and holds.
+/// Anything can inflict one by sending an `ApplyStatusEvent`, or by putting `OnHitStatus`
+/// on an attack so every enemy it damages gets it.
+pub struct StatusEffectPlugin;
+
+impl Plugin for StatusEffectPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ApplyStatusEvent>().add_systems(
+            Update,
+            (
+                apply_on_hit_statuses,
+                apply_status_events,
+                tick_status_effects,
+                sync_status_tints,
+            )
+                .chain()
+                .before(GameplaySets::Combat)
+                .run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StatusEffect {
+    Burn {
+        damage: i32, // Per tick
+        duration: f32,
+    },
+    Chill {
+        slow: f32, // Share of movement speed taken away, 0 to 1
+        duration: f32,
+    },
+    Poison {
+        damage: i32, // Per tick, per stack
+        duration: f32,
+        max_stacks: u32,
+    },
+    Bind {
+        strength: f32, // Like a slow, 1 holds the enemy in place
+        duration: f32,
+    },
+    Fear {
+        duration: f32,
+    },
+}
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ApplyStatusEvent {
+    pub target: Entity,
+    pub effect: StatusEffect,
+    pub source: Option<Entity>, // Credited with the damage it deals
+}
+
+/// Inflicts its status on every enemy the attack damages
+#[derive(Component, Debug, Clone, Copy)]
+pub struct OnHitStatus(pub StatusEffect);
+
+const STATUS_TICK_RATE: f32 = 0.5;
+
+/// Damage every tick. Reapplying keeps the stronger burn and restarts its duration.
+#[derive(Component)]
+pub struct Burning {
+    pub damage: i32,
+    pub tick: PausableTimer,
+    pub remaining: PausableTimer,
+    pub source: Option<Entity>,
+}
+
+/// Slows movement. Reapplying keeps the stronger slow and restarts its duration.
+#[derive(Component)]
+pub struct Chilled {
+    pub slow: f32,
+    pub remaining: PausableTimer,
+}
+
+/// Damage every tick for each stack. Reapplying adds a stack, up to the cap,
+/// and restarts the duration for all of them.
+#[derive(Component)]
+pub struct Poisoned {
+    pub damage_per_stack: i32,
+    pub stacks: u32,
+    pub max_stacks: u32,
+    pub tick: PausableTimer,
+    pub remaining: PausableTimer,
+    pub source: Option<Entity>,
+}
+
+/// Held in place by a Binding circle, or slowed if the hold is weaker than full
+#[derive(Component)]
+pub struct Bound {
+    pub strength: f32,
+    pub remaining: PausableTimer,
+    pub source: Option<Entity>,
+}
+
+/// Runs away from the player instead of chasing them. Reapplying restarts the duration.
+#[derive(Component)]
+pub struct Feared {
+    pub remaining: PausableTimer,
+}
+
+/// Share of its normal speed a status-afflicted enemy still moves at
+pub fn movement_multiplier(bound: Option<&Bound>, chilled: Option<&Chilled>) -> f32 {
+    let hold = bound.map_or(0.0, |bound| bound.strength);
+    let slow = chilled.map_or(0.0, |chilled| chilled.slow);
+    ((1.0 - hold) * (1.0 - slow)).clamp(0.0, 1.0)
+}
+
+fn apply_on_hit_statuses(
+    mut damage_events: EventReader<DamageEvent>,
+    attack_query: Query<&OnHitStatus>,
+    mut status_events: EventWriter<ApplyStatusEvent>,
+) {
+    for event in damage_events.read() {
+        let Some(on_hit) = event
+            .source
+            .and_then(|source| attack_query.get(source).ok())
+        else {
+            continue;
+        };
+
+        status_events.send(ApplyStatusEvent {
+            target: event.target,
+            effect: on_hit.0,
+            source: event.source,
+        });
+    }
+}
+
+fn apply_status_events(
+    mut commands: Commands,
+    mut status_events: EventReader<ApplyStatusEvent>,
+    mut enemy_query: Query<
+        (
+            Option<&mut Burning>,
+            Option<&mut Chilled>,
+            Option<&mut Poisoned>,
+            Option<&mut Bound>,
+            Option<&mut Feared>,
+        ),
+        (With<Enemy>, Without<MarkedForDeath>),
+    >,
+) {
+    for event in status_events.read() {
+        let Ok((burning, chilled, poisoned, bound, feared)) = enemy_query.get_mut(event.target)
+        else {
+            continue;
+        };
+
+        match event.effect {
+            StatusEffect::Burn { damage, duration } => match burning {
+                Some(mut burning) => {
+                    burning.damage = burning.damage.max(damage);
+                    burning.remaining = PausableTimer::from_seconds(duration, TimerMode::Once);
+                    burning.source = event.source;
+                }
+                None => {
+                    commands.entity(event.target).try_insert(Burning {
+                        damage,
+                        tick: PausableTimer::from_seconds(STATUS_TICK_RATE, TimerMode::Repeating),
+                        remaining: PausableTimer::from_seconds(duration, TimerMode::Once),
+                        source: event.source,
+                    });
+                }
+            },
+            StatusEffect::Chill { slow, duration } => match chilled {
+                Some(mut chilled) => {
+                    chilled.slow = chilled.slow.max(slow);
+                    chilled.remaining = PausableTimer::from_seconds(duration, TimerMode::Once);
+                }
+                None => {
+                    commands.entity(event.target).try_insert(Chilled {
+                        slow,
+                        remaining: PausableTimer::from_seconds(duration, TimerMode::Once),
+                    });
+                }
+            },
+            StatusEffect::Poison {
+                damage,
+                duration,
+                max_stacks,
+            } => match poisoned {
+                Some(mut poisoned) => {
+                    poisoned.max_stacks = poisoned.max_stacks.max(max_stacks);
+                    poisoned.stacks = (poisoned.stacks + 1).min(poisoned.max_stacks);
+                    poisoned.damage_per_stack = poisoned.damage_per_stack.max(damage);
+                    poisoned.remaining = PausableTimer::from_seconds(duration, TimerMode::Once);
+                    poisoned.source = event.source;
+                }
+                None => {
+                    commands.entity(event.target).try_insert(Poisoned {
+                        damage_per_stack: damage,
+                        stacks: 1,
+                        max_stacks,
+                        tick: PausableTimer::from_seconds(STATUS_TICK_RATE, TimerMode::Repeating),
+                        remaining: PausableTimer::from_seconds(duration, TimerMode::Once),
+                        source: event.source,
+                    });
+                }
+            },
+            StatusEffect::Bind { strength, duration } => match bound {
+                Some(mut bound) => {
+                    bound.strength = bound.strength.max(strength);
+                    bound.remaining = PausableTimer::from_seconds(duration, TimerMode::Once);
+                    bound.source = event.source;
+                }
+                None => {
+                    commands.entity(event.target).try_insert(Bound {
+                        strength,
+                        remaining: PausableTimer::from_seconds(duration, TimerMode::Once),
+                        source: event.source,
+                    });
+                }
+            },
+            StatusEffect::Fear { duration } => match feared {
+                Some(mut feared) => {
+                    feared.remaining = PausableTimer::from_seconds(duration, TimerMode::Once);
+                }
+                None => {
+                    commands.entity(event.target).try_insert(Feared {
+                        remaining: PausableTimer::from_seconds(duration, TimerMode::Once),
+                    });
+                }
+            },
+        }
+    }
+}
+
+/// Deals damage over time and removes statuses once they run out
+fn tick_status_effects(
+    mut commands: Commands,
+    time: Res<Time<Virtual>>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut burning_query: Query<(Entity, &mut Burning)>,
+    mut poisoned_query: Query<(Entity, &mut Poisoned)>,
+    // Statuses that only wear off
+    mut chilled_query: Query<(Entity, &mut Chilled)>,
+    mut bound_query: Query<(Entity, &mut Bound)>,
+    mut feared_query: Query<(Entity, &mut Feared)>,
+) {
+    for (entity, mut burning) in burning_query.iter_mut() {
+        if burning.tick.tick(&time).just_finished() {
+            damage_events.send(DamageEvent {
+                target: entity,
+                amount: burning.damage,
+                source: burning.source,
+            });
+        }
+        if burning.remaining.tick(&time).finished() {
+            commands.entity(entity).remove::<Burning>();
+        }
+    }
+
+    for (entity, mut poisoned) in poisoned_query.iter_mut() {
+        if poisoned.tick.tick(&time).just_finished() {
+            damage_events.send(DamageEvent {
+                target: entity,
+                amount: poisoned.damage_per_stack * poisoned.stacks as i32,
+                source: poisoned.source,
+            });
+        }
+        if poisoned.remaining.tick(&time).finished() {
+            commands.entity(entity).remove::<Poisoned>();
+        }
+    }
+
+    for (entity, mut chilled) in chilled_query.iter_mut() {
+        if chilled.remaining.tick(&time).finished() {
+            commands.entity(entity).remove::<Chilled>();
+        }
+    }
+
+    for (entity, mut bound) in bound_query.iter_mut() {
+        if bound.remaining.tick(&time).finished() {
+            commands.entity(entity).remove::<Bound>();
+        }
+    }
+
+    for (entity, mut feared) in feared_query.iter_mut() {
+        if feared.remaining.tick(&time).finished() {
+            commands.entity(entity).remove::<Feared>();
+        }
+    }
+}
+
+/// Tints each afflicted enemy for its most telling status, and clears it once they're all gone
+fn sync_status_tints(
+    mut commands: Commands,
+    enemy_query: Query<
+        (
+            Entity,
+            Has<Bound>,
+            Has<Chilled>,
+            Has<Poisoned>,
+            Has<Burning>,
+            Option<&Tint>,
+        ),
+        (With<Enemy>, Without<MarkedForDeath>),
+    >,
+) {
+    for (entity, bound, chilled, poisoned, burning, current) in enemy_query.iter() {
+        let wanted = if bound {
+            Some(StatusTint::Bound)
+        } else if chilled {
+            Some(StatusTint::Frozen)
+        } else if poisoned {
+            Some(StatusTint::Poisoned)
+        } else if burning {
+            Some(StatusTint::Burning)
+        } else {
+            None
+        };
+
+        match (wanted.map(Tint::status), current) {
+            (Some(tint), Some(current)) if tint.0 == current.0 => {}
+            (Some(tint), _) => {
+                commands.entity(entity).try_insert(tint);
+            }
+            (None, Some(_)) => {
+                commands.entity(entity).remove::<Tint>();
+            }
+            (None, None) => {}
+        }
+    }
+}
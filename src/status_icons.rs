@@ -0,0 +1,132 @@
+use crate::components::Enemy;
+use crate::physics::ENEMY_RADIUS;
+use crate::resources::GameState;
+use crate::status::{Bound, Burning, Chilled, Feared, Poisoned};
+use crate::GameplaySets;
+use bevy::prelude::*;
+
+/// A row of small icons over each enemy for the statuses it's suffering from.
+/// Only enemies on screen get their row kept up to date, the rest are hidden.
+pub struct StatusIconPlugin;
+
+impl Plugin for StatusIconPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            update_status_icons
+                .after(GameplaySets::Combat)
+                .run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+/// Child of an enemy showing its status icons
+#[derive(Component)]
+pub struct StatusIconRow;
+
+// Past this many the row ends in a "+N" instead
+pub const MAX_STATUS_ICONS: usize = 3;
+const STATUS_ICON_SIZE: f32 = 12.0;
+const STATUS_ICON_GAP: f32 = 6.0; // Between the top of the enemy and the row
+
+// Enemies this far past the edge of the view still count as on screen
+const STATUS_ICON_MARGIN: f32 = 32.0;
+
+// Most telling first, the same order the tints go by
+type StatusFlags = (
+    Has<Bound>,
+    Has<Feared>,
+    Has<Chilled>,
+    Has<Poisoned>,
+    Has<Burning>,
+);
+
+/// Icons for the given statuses, capped at `MAX_STATUS_ICONS`
+pub fn status_icon_text(
+    (bound, feared, chilled, poisoned, burning): (bool, bool, bool, bool, bool),
+) -> String {
+    let icons: Vec<&str> = [
+        (bound, "🔗"),
+        (feared, "💢"),
+        (chilled, "❄️"),
+        (poisoned, "☠️"),
+        (burning, "🔥"),
+    ]
+    .into_iter()
+    .filter(|(active, _)| *active)
+    .map(|(_, icon)| icon)
+    .collect();
+
+    let mut text = icons
+        .iter()
+        .take(MAX_STATUS_ICONS)
+        .copied()
+        .collect::<String>();
+    if icons.len() > MAX_STATUS_ICONS {
+        text.push_str(&format!("+{}", icons.len() - MAX_STATUS_ICONS));
+    }
+    text
+}
+
+fn update_status_icons(
+    mut commands: Commands,
+    camera_query: Query<(&Transform, &OrthographicProjection), With<Camera2d>>,
+    // Dying enemies keep their row until it goes with them
+    enemy_query: Query<(Entity, &Transform, StatusFlags, Option<&Children>), With<Enemy>>,
+    mut row_query: Query<(&mut Text2d, &mut Visibility), With<StatusIconRow>>,
+) {
+    let Ok((camera_transform, projection)) = camera_query.get_single() else {
+        return;
+    };
+    let view = Rect::from_center_half_size(
+        camera_transform.translation.truncate(),
+        projection.area.half_size() + STATUS_ICON_MARGIN,
+    );
+
+    for (entity, transform, statuses, children) in enemy_query.iter() {
+        let row = children.and_then(|children| {
+            children
+                .iter()
+                .find(|child| row_query.contains(**child))
+                .copied()
+        });
+        let on_screen = view.contains(transform.translation.truncate());
+        let text = if on_screen {
+            status_icon_text(statuses)
+        } else {
+            String::new()
+        };
+
+        match row.and_then(|row| row_query.get_mut(row).ok()) {
+            Some((mut row_text, mut visibility)) => {
+                let wanted = if text.is_empty() {
+                    Visibility::Hidden
+                } else {
+                    Visibility::Inherited
+                };
+                visibility.set_if_neq(wanted);
+                if !text.is_empty() && row_text.0 != text {
+                    row_text.0 = text;
+                }
+            }
+            None if !text.is_empty() => {
+                // Counter the enemy's scale so every row is the same size
+                let scale = transform.scale.truncate().max_element().max(f32::EPSILON);
+                let row = commands
+                    .spawn((
+                        StatusIconRow,
+                        Text2d::new(text),
+                        TextFont {
+                            font_size: STATUS_ICON_SIZE,
+                            ..default()
+                        },
+                        Transform::from_xyz(0.0, ENEMY_RADIUS + STATUS_ICON_GAP, 1.0)
+                            .with_scale(Vec3::splat(1.0 / scale)),
+                    ))
+                    .id();
+                commands.entity(entity).add_child(row);
+            }
+            None => {}
+        }
+    }
+}
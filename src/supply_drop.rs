@@ -0,0 +1,357 @@
+use crate::combat::DamageEvent;
+use crate::components::{Enemy, Player};
+use crate::death::{MarkedForDeath, MarkedForDespawn};
+use crate::experience::spawn_experience_orb;
+use crate::game_time::PausableTimer;
+use crate::interaction::{InteractEvent, Interactable};
+use crate::materials::FlashMaterial;
+use crate::minimap::MinimapIcon;
+use crate::pickup::{spawn_pickup, Pickup};
+use crate::player_handle::PlayerHandle;
+use crate::resources::{GameRng, GameState};
+use bevy::prelude::*;
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy_prototype_lyon::draw::Fill;
+use bevy_prototype_lyon::entity::ShapeBundle;
+use bevy_prototype_lyon::geometry::GeometryBuilder;
+use bevy_prototype_lyon::shapes;
+use rand::Rng;
+
+pub struct SupplyDropPlugin;
+
+impl Plugin for SupplyDropPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SupplyDropConfig>()
+            .init_resource::<SupplyDropTimer>()
+            .init_resource::<CrateSprite>()
+            .add_systems(
+                Update,
+                (
+                    restart_supply_drop_timer.run_if(resource_changed::<SupplyDropConfig>),
+                    schedule_supply_drops,
+                    animate_supply_drops,
+                    land_supply_drops,
+                    dress_supply_crates,
+                    open_supply_crates,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+#[derive(Resource)]
+pub struct SupplyDropConfig {
+    pub interval: f32,
+    pub telegraph_duration: f32,
+    pub min_distance: f32, // From the player
+    pub max_distance: f32,
+    pub impact_radius: f32,
+    pub impact_damage: i32,
+    pub gold: (u32, u32), // Inclusive range, split across a few coins
+    pub gold_coins: u32,
+    pub heart_heal: i32,
+    pub orb_count: u32,
+    pub orb_value: u32,
+}
+
+impl Default for SupplyDropConfig {
+    fn default() -> Self {
+        Self {
+            interval: 60.0,
+            telegraph_duration: 3.0,
+            min_distance: 120.0,
+            max_distance: 260.0,
+            impact_radius: 40.0,
+            impact_damage: 50,
+            gold: (20, 50),
+            gold_coins: 4,
+            heart_heal: 25,
+            orb_count: 5,
+            orb_value: 25,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct SupplyDropTimer(pub PausableTimer);
+
+impl SupplyDropTimer {
+    pub fn new(config: &SupplyDropConfig) -> Self {
+        Self(PausableTimer::from_seconds(
+            config.interval,
+            TimerMode::Repeating,
+        ))
+    }
+}
+
+impl FromWorld for SupplyDropTimer {
+    fn from_world(world: &mut World) -> Self {
+        Self::new(world.resource::<SupplyDropConfig>())
+    }
+}
+
+/// Shadow on the ground marking where a crate will land
+#[derive(Component)]
+pub struct SupplyDropTelegraph {
+    pub timer: PausableTimer,
+}
+
+// The crate (and its parachute) descending onto the telegraph
+#[derive(Component)]
+pub struct FallingCrate;
+
+#[derive(Component)]
+pub struct SupplyCrate;
+
+const DROP_HEIGHT: f32 = 300.0;
+const CRATE_SIZE: f32 = 20.0;
+const CRATE_COLOR: Color = Color::srgb(0.55, 0.35, 0.15);
+// Side of the crate texture, the outermost ring of texels is left clear for the outline
+const CRATE_TEXELS: u32 = 12;
+
+// Landed crates are drawn with the sprite material so they get the interaction outline
+#[derive(Resource)]
+struct CrateSprite {
+    mesh: Handle<Mesh>,
+    texture: Handle<Image>,
+}
+
+impl FromWorld for CrateSprite {
+    fn from_world(world: &mut World) -> Self {
+        let size = CRATE_TEXELS;
+        let mut data = Vec::with_capacity((size * size * 4) as usize);
+        for y in 0..size {
+            for x in 0..size {
+                let rim = x == 0 || y == 0 || x == size - 1 || y == size - 1;
+                data.extend_from_slice(&[255, 255, 255, if rim { 0 } else { 255 }]);
+            }
+        }
+
+        let image = Image::new(
+            Extent3d {
+                width: size,
+                height: size,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::RENDER_WORLD,
+        );
+        let texture = world.resource_mut::<Assets<Image>>().add(image);
+
+        // Sized so the opaque part of the texture still covers CRATE_SIZE
+        let quad_size = CRATE_SIZE * size as f32 / (size - 2) as f32;
+        let mesh = world
+            .resource_mut::<Assets<Mesh>>()
+            .add(Rectangle::new(quad_size, quad_size));
+
+        Self { mesh, texture }
+    }
+}
+
+// A new interval counts down from the start, rather than from wherever the old one was
+fn restart_supply_drop_timer(config: Res<SupplyDropConfig>, mut timer: ResMut<SupplyDropTimer>) {
+    *timer = SupplyDropTimer::new(&config);
+}
+
+fn schedule_supply_drops(
+    mut commands: Commands,
+    time: Res<Time<Virtual>>,
+    config: Res<SupplyDropConfig>,
+    mut timer: ResMut<SupplyDropTimer>,
+    player_handle: Res<PlayerHandle>,
+    player_query: Query<&Transform, With<Player>>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    if !timer.0.tick(&time).just_finished() {
+        return;
+    }
+
+    let Some(player_transform) = player_handle.fetch(&player_query) else {
+        return;
+    };
+
+    let angle = game_rng.gen::<f32>() * std::f32::consts::TAU;
+    let distance = game_rng.gen_range(config.min_distance..=config.max_distance);
+    let position = player_transform.translation.truncate() + Vec2::from_angle(angle) * distance;
+
+    info!("Supply drop incoming at {:?}", position);
+
+    commands
+        .spawn((
+            SupplyDropTelegraph {
+                timer: PausableTimer::from_seconds(config.telegraph_duration, TimerMode::Once),
+            },
+            ShapeBundle {
+                path: GeometryBuilder::new()
+                    .add(&shapes::Circle {
+                        radius: config.impact_radius,
+                        center: Vec2::ZERO,
+                    })
+                    .build(),
+                transform: Transform::from_translation(position.extend(-0.5))
+                    .with_scale(Vec3::splat(0.2)),
+                ..default()
+            },
+            Fill::color(Color::srgba(0.0, 0.0, 0.0, 0.35)),
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    FallingCrate,
+                    Sprite {
+                        color: CRATE_COLOR,
+                        custom_size: Some(Vec2::splat(CRATE_SIZE)),
+                        ..default()
+                    },
+                    Transform::from_xyz(0.0, DROP_HEIGHT, 1.0),
+                ))
+                .with_children(|parent| {
+                    // Parachute canopy
+                    parent.spawn((
+                        Sprite {
+                            color: Color::srgb(0.9, 0.9, 0.85),
+                            custom_size: Some(Vec2::new(CRATE_SIZE * 2.0, CRATE_SIZE * 0.6)),
+                            ..default()
+                        },
+                        Transform::from_xyz(0.0, CRATE_SIZE * 1.5, 0.0),
+                    ));
+                });
+        });
+}
+
+fn animate_supply_drops(
+    time: Res<Time<Virtual>>,
+    mut telegraphs: Query<(&mut SupplyDropTelegraph, &mut Transform, &Children)>,
+    mut crates: Query<&mut Transform, (With<FallingCrate>, Without<SupplyDropTelegraph>)>,
+) {
+    for (mut telegraph, mut transform, children) in telegraphs.iter_mut() {
+        telegraph.timer.tick(&time);
+        let progress = telegraph.timer.fraction();
+
+        // Shadow grows as the crate gets closer; counter-scale the crate so it keeps its size
+        let scale = 0.2 + 0.8 * progress;
+        transform.scale = Vec3::splat(scale);
+
+        for &child in children.iter() {
+            if let Ok(mut crate_transform) = crates.get_mut(child) {
+                crate_transform.translation.y = DROP_HEIGHT * (1.0 - progress) / scale;
+                crate_transform.scale = Vec3::splat(1.0 / scale);
+            }
+        }
+    }
+}
+
+fn land_supply_drops(
+    mut commands: Commands,
+    config: Res<SupplyDropConfig>,
+    telegraphs: Query<(Entity, &SupplyDropTelegraph, &Transform)>,
+    enemy_query: Query<(Entity, &Transform), (With<Enemy>, Without<MarkedForDeath>)>,
+    mut damage_events: EventWriter<DamageEvent>,
+) {
+    for (telegraph_entity, telegraph, transform) in telegraphs.iter() {
+        if !telegraph.timer.finished() {
+            continue;
+        }
+
+        let landing = transform.translation.truncate();
+
+        // Anything caught under the crate gets flattened
+        for (enemy_entity, enemy_transform) in enemy_query.iter() {
+            if enemy_transform.translation.truncate().distance(landing) <= config.impact_radius {
+                damage_events.send(DamageEvent {
+                    target: enemy_entity,
+                    amount: config.impact_damage,
+                    source: Some(telegraph_entity),
+                });
+            }
+        }
+
+        commands.entity(telegraph_entity).despawn_recursive();
+        spawn_supply_crate(&mut commands, landing);
+    }
+}
+
+/// A crate sitting on the ground, ready to be opened. Its quad and material are added
+/// by `dress_supply_crates`, so anything with `Commands` can drop one.
+pub fn spawn_supply_crate(commands: &mut Commands, position: Vec2) -> Entity {
+    commands
+        .spawn((
+            SupplyCrate,
+            Interactable::new("Open Supply Crate"),
+            MinimapIcon {
+                color: Color::srgb(1.0, 0.85, 0.3),
+                size: 8.0,
+            },
+            Transform::from_translation(position.extend(0.0)),
+            Visibility::default(),
+        ))
+        .id()
+}
+
+// Each crate gets its own material, the outline is toggled per crate
+fn dress_supply_crates(
+    mut commands: Commands,
+    crate_sprite: Res<CrateSprite>,
+    mut materials: ResMut<Assets<FlashMaterial>>,
+    new_crates: Query<Entity, Added<SupplyCrate>>,
+) {
+    for entity in new_crates.iter() {
+        let material = FlashMaterial {
+            tint: CRATE_COLOR.to_linear(),
+            ..FlashMaterial::new(crate_sprite.texture.clone(), Vec4::new(0.0, 0.0, 1.0, 1.0))
+        };
+
+        commands.entity(entity).try_insert((
+            Mesh2d(crate_sprite.mesh.clone()),
+            MeshMaterial2d(materials.add(material)),
+        ));
+    }
+}
+
+fn open_supply_crates(
+    mut commands: Commands,
+    config: Res<SupplyDropConfig>,
+    mut interact_events: EventReader<InteractEvent>,
+    crate_query: Query<&Transform, (With<SupplyCrate>, Without<MarkedForDespawn>)>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    for event in interact_events.read() {
+        let Ok(crate_transform) = crate_query.get(event.target) else {
+            continue;
+        };
+        let center = crate_transform.translation.truncate();
+
+        let gold = game_rng.gen_range(config.gold.0..=config.gold.1);
+        info!("Opened supply crate: {} gold", gold);
+
+        // Coins below the crate and a heart above it, pulled in by the magnet like the orbs
+        let coins = config.gold_coins.max(1);
+        for i in 0..coins {
+            // Any remainder goes on the first coin
+            let amount = gold / coins + if i == 0 { gold % coins } else { 0 };
+            let offset = Vec2::new(i as f32 * 10.0 - 15.0, -24.0);
+            spawn_pickup(&mut commands, center + offset, Pickup::Gold(amount));
+        }
+        spawn_pickup(
+            &mut commands,
+            center + Vec2::new(0.0, 24.0),
+            Pickup::Heart(config.heart_heal),
+        );
+
+        // Scatter a handful of orbs around the crate
+        for i in 0..config.orb_count {
+            let angle = std::f32::consts::TAU * i as f32 / config.orb_count as f32;
+            spawn_experience_orb(
+                &mut commands,
+                center + Vec2::from_angle(angle) * 16.0,
+                config.orb_value,
+            );
+        }
+
+        commands.entity(event.target).insert(MarkedForDespawn);
+    }
+}
@@ -1,13 +1,23 @@
+use crate::announcer::DirectorMilestone;
 use crate::combat::DamageCooldown;
 use crate::components::{
-    AreaMultiplier, CooldownReduction, DamageMultiplier, Enemy, Health, Luck,
-    Player,
+    AmountBonus, AreaMultiplier, Armor, Character, CooldownReduction, DamageMultiplier, Enemy,
+    EnemyBehavior, Equipment, Facing, Gold, Health, Luck, Player,
 };
-use crate::resources::{GameState, GameTextures, SpawnTimer, WaveConfig};
-use crate::weapons::{BindingEffect, StartingWeapon, WeaponType};
+use crate::controls::{Action, ActionInput};
+use crate::definitions::{Definitions, EnemyDefinition, StageBounds, SurgeFormation};
+use crate::enemy_pool::EnemySpawner;
+use crate::enemy_projectile::{RANGED_DISTANCE_BAND, RANGED_PREFERRED_DISTANCE};
+use crate::experience::Experience;
+use crate::materials::SpriteMaterials;
+use crate::physics::ENEMY_RADIUS;
+use crate::player_handle::PlayerHandle;
+use crate::resources::{GameRng, GameState, GameTextures, SurgeProgress, WaveConfig};
+use crate::status::{movement_multiplier, Bound, Chilled, Feared};
+use crate::weapons::{KnockedBack, StartingWeapon, WeaponType};
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
-use crate::experience::Experience;
+use rand::Rng;
 
 // Startup system to load textures and create atlas layouts
 pub fn load_textures(
@@ -69,48 +79,59 @@ pub fn quit_game(mut app_exit_events: ResMut<Events<AppExit>>) {
 // Each input handler is focused but can check game state
 pub fn gameplay_movement_system(
     game_state: Res<State<GameState>>,
-    keyboard: Res<ButtonInput<KeyCode>>,
-    time: Res<Time>,
-    mut query: Query<(&Player, &mut Transform)>,
+    actions: ActionInput,
+    time: Res<Time<Virtual>>,
+    bounds: Option<Res<StageBounds>>,
+    mut query: Query<(&Player, &mut Transform, &mut Facing)>,
 ) {
     // Only process movement in Playing state
     if *game_state.get() != GameState::Playing {
         return;
     }
 
-    for (player, mut transform) in query.iter_mut() {
+    for (player, mut transform, mut facing) in query.iter_mut() {
         let mut direction = Vec3::ZERO;
 
-        if keyboard.pressed(KeyCode::KeyW) {
+        if actions.pressed(Action::MoveUp) {
             direction.y += 1.0;
         }
-        if keyboard.pressed(KeyCode::KeyS) {
+        if actions.pressed(Action::MoveDown) {
             direction.y -= 1.0;
         }
-        if keyboard.pressed(KeyCode::KeyA) {
+        if actions.pressed(Action::MoveLeft) {
             direction.x -= 1.0;
         }
-        if keyboard.pressed(KeyCode::KeyD) {
+        if actions.pressed(Action::MoveRight) {
             direction.x += 1.0;
         }
 
         if direction != Vec3::ZERO {
             direction = direction.normalize();
             transform.translation += direction * player.speed * time.delta_secs();
+            facing.face(direction.truncate());
+        }
+
+        // Walls are only there for enemies, the kinematic player is held in here instead
+        if let Some(bounds) = &bounds {
+            let position = transform
+                .translation
+                .truncate()
+                .clamp(bounds.0.min, bounds.0.max);
+            transform.translation = position.extend(transform.translation.z);
         }
     }
 }
 
 pub fn universal_input_system(
-    keyboard: Res<ButtonInput<KeyCode>>,
+    actions: ActionInput,
     game_state: Res<State<GameState>>,
     mut next_state: ResMut<NextState<GameState>>,
 ) {
-    if keyboard.just_pressed(KeyCode::Escape) {
+    if actions.just_pressed(Action::Pause) {
         match *game_state.get() {
             GameState::Playing => next_state.set(GameState::Paused),
             GameState::Paused => next_state.set(GameState::Playing),
-            GameState::Settings => next_state.set(GameState::Playing),
+            GameState::Settings => next_state.set(GameState::MainMenu),
             GameState::MainMenu => next_state.set(GameState::Quit),
             _ => {}
         }
@@ -129,8 +150,15 @@ pub fn handle_pause_state(
                 rapier_config.physics_pipeline_active = true;
                 time.unpause();
             }
-            GameState::Paused | GameState::LevelUp | GameState::GameOver => {
-                // Pause physics and time for any state where the game should be frozen
+            GameState::MainMenu
+            | GameState::Settings
+            | GameState::Paused
+            | GameState::LevelUp
+            | GameState::Milestone
+            | GameState::GameOver
+            | GameState::Victory => {
+                // Pause physics and time for any state where the game should be frozen.
+                // Run clocks read virtual time, so menus before a run mustn't advance it either.
                 rapier_config.physics_pipeline_active = false;
                 time.pause();
             }
@@ -139,26 +167,32 @@ pub fn handle_pause_state(
     }
 }
 
-pub fn spawn_player(mut commands: Commands, game_textures: Res<GameTextures>) {
+/// Run time is virtual time, a run started from the main menu starts it over from zero
+pub fn reset_run_clock(mut time: ResMut<Time<Virtual>>) {
+    *time = Time::<Virtual>::default();
+}
+
+pub fn spawn_player(mut commands: Commands, mut sprite_materials: SpriteMaterials) {
     commands.spawn((
         Player {
             speed: 150.0,
             magnet_strength: 150.0, // Base vacuum range
             magnet_speed: 1.0,      // Base vacuum speed multiplier
         },
-        CooldownReduction::default(), // Will be 0.0
-        DamageMultiplier::default(),  // Will be 1.0
-        AreaMultiplier::default(),    // Will be 1.0
+        Character::default(),
+        // Stats every weapon reads when it fires
+        (
+            CooldownReduction::default(), // Will be 0.0
+            DamageMultiplier::default(),  // Will be 1.0
+            AreaMultiplier::default(),    // Will be 1.0
+            AmountBonus::default(),       // Will be 0
+        ),
         Luck::default(),
-        Sprite {
-            image: game_textures.player.clone(),
-            custom_size: Some(Vec2::new(32.0, 32.0)),
-            texture_atlas: Some(TextureAtlas {
-                layout: game_textures.player_layout.clone(),
-                index: 0,
-            }),
-            ..default()
-        },
+        Armor::default(),
+        Gold::default(),
+        Equipment::default(),
+        Facing::default(),
+        sprite_materials.player(),
         Transform::from_xyz(0.0, 0.0, 0.0),
         Experience {
             current: 0,
@@ -172,81 +206,156 @@ pub fn spawn_player(mut commands: Commands, game_textures: Res<GameTextures>) {
         DamageCooldown::default(),
         StartingWeapon(WeaponType::MagickCircle),
     ));
+}
 
+pub fn spawn_camera(mut commands: Commands) {
     commands.spawn(Camera2d::default());
 }
 
-pub fn spawn_enemies(
-    mut commands: Commands,
-    game_textures: Res<GameTextures>,
+/// Gives a fresh or pooled entity everything an enemy of this definition starts with
+pub fn insert_enemy(
+    enemy: &mut EntityCommands,
+    sprite_materials: &mut SpriteMaterials,
+    definition: &EnemyDefinition,
+    position: Vec3,
+) {
+    enemy.insert((
+        Enemy {
+            kind: definition.kind,
+            speed: definition.speed,
+            experience_value: definition.experience_value,
+            contact_damage: definition.contact_damage,
+            behavior: definition.behavior,
+        },
+        // Physics scales the collider along with the sprite
+        Transform::from_translation(position)
+            .with_scale(Vec3::splat(definition.radius / ENEMY_RADIUS)),
+        Health {
+            current: definition.health,
+            maximum: definition.health,
+        },
+    ));
+
+    // Modded sprites are standalone images rather than frames of the enemy atlas
+    match &definition.sprite_handle {
+        Some(image) => enemy.insert(Sprite::from_image(image.clone())),
+        None => enemy.insert(sprite_materials.enemy(definition.kind.sprite_index())),
+    };
+}
+
+// Rings are drawn this far from the player, walls come in from this far out and are this wide
+const SURGE_RING_RADIUS: f32 = 380.0;
+const SURGE_WALL_DISTANCE: f32 = 420.0;
+const SURGE_WALL_WIDTH: f32 = 640.0;
+
+/// Scripted hordes on their own schedule, separate from the regular spawn timer
+pub fn spawn_surges(
+    mut spawner: EnemySpawner,
     time: Res<Time<Virtual>>,
-    mut timer: ResMut<SpawnTimer>,
     wave_config: Res<WaveConfig>,
-    enemy_query: Query<&Enemy>,
+    mut progress: ResMut<SurgeProgress>,
+    definitions: Res<Definitions>,
+    mut milestones: EventWriter<DirectorMilestone>,
+    player_handle: Res<PlayerHandle>,
     player_query: Query<&Transform, With<Player>>,
+    mut game_rng: ResMut<GameRng>,
 ) {
-    if timer.0.tick(time.delta()).just_finished()
-        && enemy_query.iter().count() < wave_config.max_enemies as usize
-    {
-        // Use get_single() instead of single() to handle missing player gracefully
-        let player_transform = match player_query.get_single() {
-            Ok(transform) => transform,
-            Err(_) => return, // If no player exists, just return
-        };
-
-        let spawn_distance = 400.0;
-        let random_angle = rand::random::<f32>() * std::f32::consts::TAU;
-        let spawn_position = player_transform.translation
-            + Vec3::new(
-                random_angle.cos() * spawn_distance,
-                random_angle.sin() * spawn_distance,
-                0.0,
-            );
-
-        let sprite_index = if rand::random::<f32>() > 0.5 { 0 } else { 1 };
-
-        commands.spawn((
-            Enemy {
-                speed: 100.0,
-                experience_value: 50,
-            },
-            Sprite {
-                image: game_textures.enemies.clone(),
-                custom_size: Some(Vec2::new(32.0, 32.0)),
-                texture_atlas: Some(TextureAtlas {
-                    layout: game_textures.enemies_layout.clone(),
-                    index: sprite_index,
-                }),
-                ..default()
-            },
-            Transform::from_translation(spawn_position),
-            Health {
-                current: 20,
-                maximum: 20,
-            },
-        ));
+    let Some(surge) = wave_config.surges.get(progress.next_surge) else {
+        return;
+    };
+    if time.elapsed_secs() < surge.time {
+        return;
+    }
+    let Some(player_transform) = player_handle.fetch(&player_query) else {
+        return;
+    };
+
+    // Unknown enemies are skipped so they don't hold up later surges
+    progress.next_surge += 1;
+    let Some(definition) = definitions.enemies.get(&surge.enemy) else {
+        return;
+    };
+
+    info!("Surge of {} {}", surge.count, definition.name);
+    let mut definition = definition.clone();
+    definition.speed *= surge.speed_multiplier;
+
+    let center = player_transform.translation.truncate();
+    for position in surge_positions(surge.formation, surge.count, center, game_rng.as_mut()) {
+        spawner.spawn(&definition, position.extend(player_transform.translation.z));
     }
+    milestones.send(DirectorMilestone::SurgeStart);
 }
 
+/// Where each enemy of a surge starts out around `center`
+pub fn surge_positions(
+    formation: SurgeFormation,
+    count: u32,
+    center: Vec2,
+    rng: &mut impl Rng,
+) -> Vec<Vec2> {
+    match formation {
+        SurgeFormation::Ring => (0..count)
+            .map(|i| {
+                let angle = std::f32::consts::TAU * i as f32 / count as f32;
+                center + Vec2::from_angle(angle) * SURGE_RING_RADIUS
+            })
+            .collect(),
+        SurgeFormation::Wall => {
+            let heading = Vec2::from_angle(rng.gen::<f32>() * std::f32::consts::TAU);
+            let across = heading.perp();
+            let spacing = SURGE_WALL_WIDTH / count.max(1) as f32;
+            (0..count)
+                .map(|i| {
+                    let offset = (i as f32 + 0.5) * spacing - SURGE_WALL_WIDTH / 2.0;
+                    center + heading * SURGE_WALL_DISTANCE + across * offset
+                })
+                .collect()
+        }
+    }
+}
+
+// Swarmers weave this far off their heading, at this many radians per second
+const SWARM_WEAVE: f32 = 0.6;
+const SWARM_WEAVE_SPEED: f32 = 4.0;
+
 pub fn enemy_movement(
+    time: Res<Time<Virtual>>,
+    player_handle: Res<PlayerHandle>,
     player_query: Query<&Transform, With<Player>>,
-    mut enemy_query: Query<(Entity, &Transform, &Enemy, &mut Velocity)>,
-    binding_query: Query<&BindingEffect>,
+    mut enemy_query: Query<(Entity, &Transform, &Enemy, &mut Velocity), Without<KnockedBack>>,
+    status_query: Query<(Option<&Bound>, Option<&Chilled>, Has<Feared>)>,
 ) {
-    if let Ok(player_transform) = player_query.get_single() {
+    if let Some(player_transform) = player_handle.fetch(&player_query) {
         for (entity, transform, enemy, mut velocity) in enemy_query.iter_mut() {
-            let direction = (player_transform.translation - transform.translation).normalize();
-            let base_velocity = direction.truncate() * enemy.speed * 0.8;
-
-            // Check if enemy is under binding effect
-            let binding_strength = if let Ok(binding) = binding_query.get(entity) {
-                binding.strength
-            } else {
-                0.0
-            };
-
-            // Apply movement reduction based on binding strength
-            velocity.linvel = base_velocity * (1.0 - binding_strength);
+            let offset = (player_transform.translation - transform.translation).truncate();
+            let mut direction = offset.normalize_or_zero();
+            match enemy.behavior {
+                EnemyBehavior::Chase => {}
+                EnemyBehavior::Swarm => {
+                    // Offset by entity so a pack doesn't sway in lockstep
+                    let phase = time.elapsed_secs() * SWARM_WEAVE_SPEED + entity.index() as f32;
+                    direction = Vec2::from_angle(phase.sin() * SWARM_WEAVE).rotate(direction);
+                }
+                EnemyBehavior::Ranged => {
+                    // Close in until in range, back off if the player gets too near
+                    let gap = offset.length() - RANGED_PREFERRED_DISTANCE;
+                    if gap.abs() < RANGED_DISTANCE_BAND {
+                        direction = Vec2::ZERO;
+                    } else if gap < 0.0 {
+                        direction = -direction;
+                    }
+                }
+            }
+            let (bound, chilled, feared) = status_query.get(entity).unwrap_or_default();
+            if feared {
+                // Scatter straight away from the player until the fear wears off
+                direction = -offset.normalize_or_zero();
+            }
+            let base_velocity = direction * enemy.speed * 0.8;
+
+            // Binding and chill both hold enemies back
+            velocity.linvel = base_velocity * movement_multiplier(bound, chilled);
         }
     }
 }
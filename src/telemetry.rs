@@ -0,0 +1,217 @@
+use crate::components::{Gold, Health, Player};
+use crate::experience::Experience;
+use crate::gameplay_events::{GameplayEvent, GameplayEventsAppExt, PickupKind};
+use crate::resources::{GameState, GameStats};
+use crate::settings::flush_dirty_settings;
+use crate::weapons::{FiredBy, WeaponMeta, WeaponType};
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::Write;
+
+pub struct TelemetryPlugin;
+
+impl Plugin for TelemetryPlugin {
+    fn build(&self, app: &mut App) {
+        // Pausing is the closest thing to a checkpoint, so a run killed mid-way
+        // still leaves its latest numbers on disk
+        app.init_resource::<RunTotals>()
+            .init_resource::<WeaponStats>()
+            .add_gameplay_observer((tally_run_totals, tally_weapon_stats))
+            .add_systems(
+                OnEnter(GameState::Paused),
+                (snapshot_run_stats, flush_dirty_settings),
+            );
+    }
+}
+
+const TELEMETRY_PATH: &str = "telemetry.ron";
+
+/// Running sums that no single component tracks
+#[derive(Resource, Default, Debug)]
+pub struct RunTotals {
+    pub damage_dealt: i64,
+    pub experience_collected: u32,
+    pub gold_collected: u32,
+}
+
+/// Damage, hits and kills credited to each weapon over the run
+#[derive(Resource, Default, Debug)]
+pub struct WeaponStats {
+    pub weapons: HashMap<WeaponType, WeaponTally>,
+}
+
+#[derive(Default, Debug, Clone, Copy)]
+pub struct WeaponTally {
+    pub damage: i64,
+    pub hits: u32,
+    pub kills: u32,
+}
+
+impl WeaponTally {
+    /// Average damage per second over `elapsed` seconds of the run
+    pub fn dps(&self, elapsed: f32) -> f32 {
+        self.damage as f32 / elapsed.max(1.0)
+    }
+
+    /// Highest kill milestone reached this run
+    pub fn mastery(&self) -> Option<MasteryTier> {
+        MasteryTier::ALL
+            .into_iter()
+            .rev()
+            .find(|tier| self.kills >= tier.kills())
+    }
+}
+
+/// Kill milestones a weapon can reach within a run
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MasteryTier {
+    Bronze,
+    Silver,
+    Gold,
+}
+
+impl MasteryTier {
+    pub const ALL: [MasteryTier; 3] = [MasteryTier::Bronze, MasteryTier::Silver, MasteryTier::Gold];
+
+    pub fn kills(&self) -> u32 {
+        match self {
+            MasteryTier::Bronze => 50,
+            MasteryTier::Silver => 250,
+            MasteryTier::Gold => 1000,
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            MasteryTier::Bronze => Color::srgb(0.8, 0.5, 0.2),
+            MasteryTier::Silver => Color::srgb(0.75, 0.75, 0.8),
+            MasteryTier::Gold => Color::srgb(1.0, 0.84, 0.0),
+        }
+    }
+}
+
+impl std::fmt::Display for MasteryTier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MasteryTier::Bronze => write!(f, "Bronze"),
+            MasteryTier::Silver => write!(f, "Silver"),
+            MasteryTier::Gold => write!(f, "Gold"),
+        }
+    }
+}
+
+/// One line of the telemetry file
+#[derive(Serialize, Debug)]
+pub struct RunSnapshot {
+    pub run_time: f32, // Seconds of gameplay, pauses excluded
+    pub kills: u32,
+    pub level: u32,
+    pub health: i32,
+    pub gold: u32,
+    pub damage_dealt: i64,
+    pub experience_collected: u32,
+    pub weapons: Vec<(WeaponType, u32)>,
+}
+
+fn tally_run_totals(
+    mut gameplay_events: EventReader<GameplayEvent>,
+    mut totals: ResMut<RunTotals>,
+) {
+    for event in gameplay_events.read() {
+        match event {
+            GameplayEvent::DamageDealt { amount, .. } => totals.damage_dealt += *amount as i64,
+            GameplayEvent::Pickup(PickupKind::Experience(value)) => {
+                totals.experience_collected += value
+            }
+            GameplayEvent::Pickup(PickupKind::Gold(value)) => totals.gold_collected += value,
+            _ => {}
+        }
+    }
+}
+
+fn tally_weapon_stats(
+    mut gameplay_events: EventReader<GameplayEvent>,
+    fired_by_query: Query<&FiredBy>,
+    mut stats: ResMut<WeaponStats>,
+) {
+    // A kill is reported in the same frame as the hit that caused it,
+    // so the last weapon to hit an enemy this frame gets the credit
+    let mut last_hit = HashMap::new();
+    let mut kills = Vec::new();
+
+    for event in gameplay_events.read() {
+        match event {
+            GameplayEvent::DamageDealt {
+                target,
+                amount,
+                source,
+            } => {
+                // Enemies hurting the player have no weapon to credit
+                let Some(fired_by) = source.and_then(|attack| fired_by_query.get(attack).ok())
+                else {
+                    continue;
+                };
+                let tally = stats.weapons.entry(fired_by.0).or_default();
+                tally.damage += *amount as i64;
+                tally.hits += 1;
+                last_hit.insert(*target, fired_by.0);
+            }
+            GameplayEvent::Kill { entity, .. } => kills.push(*entity),
+            _ => {}
+        }
+    }
+
+    for entity in kills {
+        if let Some(weapon_type) = last_hit.get(&entity) {
+            stats.weapons.entry(*weapon_type).or_default().kills += 1;
+        }
+    }
+}
+
+fn snapshot_run_stats(
+    time: Res<Time<Virtual>>,
+    game_stats: Res<GameStats>,
+    totals: Res<RunTotals>,
+    player_query: Query<(&Experience, &Health, &Gold), With<Player>>,
+    weapon_query: Query<&WeaponMeta>,
+) {
+    let Ok((experience, health, gold)) = player_query.get_single() else {
+        return;
+    };
+
+    let snapshot = RunSnapshot {
+        run_time: time.elapsed_secs(),
+        kills: game_stats.enemies_killed,
+        level: experience.level,
+        health: health.current,
+        gold: gold.0,
+        damage_dealt: totals.damage_dealt,
+        experience_collected: totals.experience_collected,
+        weapons: weapon_query
+            .iter()
+            .map(|meta| (meta.weapon_type, meta.level))
+            .collect(),
+    };
+
+    let line = match ron::to_string(&snapshot) {
+        Ok(line) => line,
+        Err(e) => {
+            error!("Failed to serialize run snapshot: {}", e);
+            return;
+        }
+    };
+
+    // Append-only, one snapshot per line
+    let result = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(TELEMETRY_PATH)
+        .and_then(|mut file| writeln!(file, "{}", line));
+
+    match result {
+        Ok(()) => info!("Wrote run snapshot to {}", TELEMETRY_PATH),
+        Err(e) => error!("Failed to write {}: {}", TELEMETRY_PATH, e),
+    }
+}
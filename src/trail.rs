@@ -0,0 +1,161 @@
+use crate::resources::GameState;
+use crate::settings::QualitySettings;
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+pub struct TrailPlugin;
+
+impl Plugin for TrailPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TrailPool>().add_systems(
+            Update,
+            (emit_trail_segments, fade_trail_segments)
+                .chain()
+                .run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+/// Attach to anything that should leave a ribbon behind it (dashes, fast projectiles, orbiters)
+#[derive(Component)]
+pub struct Trail {
+    pub color: Color,
+    pub width: f32,
+    pub segment_lifetime: f32,
+    pub min_segment_length: f32, // Distance the emitter must travel before a new segment is laid
+    pub last_position: Option<Vec3>,
+}
+
+impl Trail {
+    pub fn new(color: Color, width: f32, segment_lifetime: f32) -> Self {
+        Self {
+            color,
+            width,
+            segment_lifetime,
+            min_segment_length: 4.0,
+            last_position: None,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct TrailSegment {
+    pub timer: Timer,
+    pub base_color: Color,
+    pub base_width: f32,
+    pub length: f32,
+    pub active: bool,
+}
+
+/// Segment entities are recycled instead of despawned so long fights don't churn the world
+#[derive(Resource, Default)]
+pub struct TrailPool {
+    free: Vec<Entity>,
+    active: VecDeque<Entity>, // Oldest first, used for eviction once the cap is hit
+    total: usize,
+}
+
+impl TrailPool {
+    fn acquire(&mut self, commands: &mut Commands, cap: usize) -> Option<Entity> {
+        let entity = if let Some(entity) = self.free.pop() {
+            entity
+        } else if self.total < cap {
+            self.total += 1;
+            commands
+                .spawn((Sprite::default(), Transform::default()))
+                .id()
+        } else {
+            // At the cap: steal the oldest live segment rather than growing the pool
+            self.active.pop_front()?
+        };
+
+        self.active.push_back(entity);
+        Some(entity)
+    }
+
+    fn release(&mut self, entity: Entity) {
+        self.active.retain(|e| *e != entity);
+        self.free.push(entity);
+    }
+}
+
+fn emit_trail_segments(
+    mut commands: Commands,
+    settings: Res<QualitySettings>,
+    mut pool: ResMut<TrailPool>,
+    mut emitters: Query<(&mut Trail, &GlobalTransform)>,
+) {
+    if !settings.trails_enabled {
+        return;
+    }
+
+    for (mut trail, global_transform) in emitters.iter_mut() {
+        let current = global_transform.translation();
+
+        let Some(last) = trail.last_position else {
+            trail.last_position = Some(current);
+            continue;
+        };
+
+        let delta = (current - last).truncate();
+        let length = delta.length();
+        if length < trail.min_segment_length {
+            continue;
+        }
+
+        let Some(segment) = pool.acquire(&mut commands, settings.max_trail_segments) else {
+            continue;
+        };
+
+        // Stretch a single sprite between the previous and current position
+        let midpoint = (last + current) / 2.0;
+        let angle = delta.y.atan2(delta.x);
+
+        commands.entity(segment).insert((
+            Sprite {
+                color: trail.color,
+                custom_size: Some(Vec2::new(length, trail.width)),
+                ..default()
+            },
+            Transform::from_translation(midpoint.truncate().extend(current.z - 0.1))
+                .with_rotation(Quat::from_rotation_z(angle)),
+            Visibility::Visible,
+            TrailSegment {
+                timer: Timer::from_seconds(trail.segment_lifetime, TimerMode::Once),
+                base_color: trail.color,
+                base_width: trail.width,
+                length,
+                active: true,
+            },
+        ));
+
+        trail.last_position = Some(current);
+    }
+}
+
+fn fade_trail_segments(
+    time: Res<Time<Virtual>>,
+    mut pool: ResMut<TrailPool>,
+    mut segments: Query<(Entity, &mut TrailSegment, &mut Sprite, &mut Visibility)>,
+) {
+    for (entity, mut segment, mut sprite, mut visibility) in segments.iter_mut() {
+        if !segment.active {
+            continue;
+        }
+
+        segment.timer.tick(time.delta());
+
+        if segment.timer.finished() {
+            segment.active = false;
+            *visibility = Visibility::Hidden;
+            pool.release(entity);
+            continue;
+        }
+
+        // Fade and taper towards the tail
+        let remaining = 1.0 - segment.timer.fraction();
+        let base_alpha = segment.base_color.alpha();
+        sprite.color = segment.base_color.with_alpha(base_alpha * remaining);
+        sprite.custom_size = Some(Vec2::new(segment.length, segment.base_width * remaining));
+    }
+}
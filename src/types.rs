@@ -1,4 +1,6 @@
 // Basic type definitions
+use serde::{Deserialize, Serialize};
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum Rarity {
     Common,
@@ -8,7 +10,20 @@ pub enum Rarity {
     Legendary,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+impl Rarity {
+    /// How often something of this rarity comes up next to a common one
+    pub fn weight(&self) -> f32 {
+        match self {
+            Rarity::Common => 1.0,
+            Rarity::Uncommon => 0.6,
+            Rarity::Rare => 0.3,
+            Rarity::Epic => 0.15,
+            Rarity::Legendary => 0.05,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub enum EquipmentType {
     Armor,
     Ring,
@@ -1,6 +1,15 @@
-use crate::components::{Health, Player};
-use crate::resources::GameStats;
+use crate::components::{Gold, Health, Player};
+use crate::controls::{Action, ActionInput};
+use crate::difficulty::ExperienceScale;
+use crate::experience::Experience;
+use crate::number_format::abbreviate;
+use crate::resources::{GameStats, Mutator, RunModifiers, WaveConfig};
+use crate::telemetry::{MasteryTier, RunTotals, WeaponStats};
+use crate::waves::{DirectorStrategy, WaveDirector};
+use crate::weapons::{WeaponMeta, WeaponType};
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
 
 // Root node marker
 #[derive(Component)]
@@ -18,28 +27,380 @@ pub struct GameTimer;
 #[derive(Component)]
 pub struct KillCounter;
 
-pub fn spawn_ui(mut commands: Commands) {
-    // Root node with marker component
+#[derive(Component)]
+pub struct WaveText;
+
+#[derive(Component)]
+pub struct WeaponStatsText;
+
+/// One active run modifier in the HUD strip, hover it for the details
+#[derive(Component)]
+pub struct ModifierIcon(pub Mutator);
+
+#[derive(Component)]
+pub struct ModifierTooltip;
+
+/// Whether the per-weapon damage panel is open, kept across pauses since the UI is rebuilt
+#[derive(Resource, Default)]
+pub struct WeaponStatsPanel {
+    pub visible: bool,
+}
+
+// Placeholder glyphs until weapons get proper icons
+pub fn weapon_icon(weapon_type: WeaponType) -> &'static str {
+    match weapon_type {
+        WeaponType::MagickCircle => "🔮",
+        WeaponType::Beam => "🔆",
+        WeaponType::ArcaneBolt => "✨",
+        WeaponType::Orbitals => "🌀",
+        WeaponType::Lance => "🔱",
+        WeaponType::Boomerang => "🪃",
+        WeaponType::ArcaneStorm => "🌩️",
+    }
+}
+
+const MASTERY_PIP_SIZE: f32 = 6.0;
+const UNEARNED_PIP_COLOR: Color = Color::srgb(0.25, 0.25, 0.25);
+
+/// Row of owned weapons, each as its icon over its level and kill milestone pips
+pub fn spawn_weapon_tray(
+    parent: &mut ChildBuilder,
+    weapons: &[(WeaponType, u32)],
+    weapon_stats: &WeaponStats,
+) {
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Row,
+            column_gap: Val::Px(12.0),
+            ..default()
+        })
+        .with_children(|parent| {
+            for (weapon_type, level) in weapons {
+                parent
+                    .spawn(Node {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        parent.spawn((
+                            Text::new(weapon_icon(*weapon_type)),
+                            TextFont {
+                                font_size: 28.0,
+                                ..default()
+                            },
+                        ));
+                        parent.spawn((
+                            Text::new(format!("Lv {}", level)),
+                            TextFont {
+                                font_size: 14.0,
+                                ..default()
+                            },
+                            TextColor(Color::srgb(0.8, 0.8, 0.8)),
+                        ));
+
+                        let mastery = weapon_stats
+                            .weapons
+                            .get(weapon_type)
+                            .and_then(|tally| tally.mastery());
+                        spawn_mastery_pips(parent, mastery);
+                    });
+            }
+        });
+}
+
+// Bronze, silver and gold pips, lit up to the milestone reached
+fn spawn_mastery_pips(parent: &mut ChildBuilder, mastery: Option<MasteryTier>) {
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Row,
+            column_gap: Val::Px(3.0),
+            margin: UiRect::top(Val::Px(2.0)),
+            ..default()
+        })
+        .with_children(|parent| {
+            for tier in MasteryTier::ALL {
+                let earned = mastery.is_some_and(|mastery| mastery >= tier);
+                parent.spawn((
+                    Node {
+                        width: Val::Px(MASTERY_PIP_SIZE),
+                        height: Val::Px(MASTERY_PIP_SIZE),
+                        ..default()
+                    },
+                    BorderRadius::MAX,
+                    BackgroundColor(if earned {
+                        tier.color()
+                    } else {
+                        UNEARNED_PIP_COLOR
+                    }),
+                ));
+            }
+        });
+}
+
+/// Where the run stands, for screens that show it at a glance (the pause menu)
+#[derive(SystemParam)]
+pub struct RunSummary<'w, 's> {
+    time: Res<'w, Time<Virtual>>,
+    game_stats: Res<'w, GameStats>,
+    player_query: Query<'w, 's, (&'static Experience, &'static Gold), With<Player>>,
+    weapon_query: Query<'w, 's, &'static WeaponMeta>,
+    run_modifiers: Res<'w, RunModifiers>,
+    weapon_stats: Res<'w, WeaponStats>,
+    run_totals: Res<'w, RunTotals>,
+    experience_scale: Res<'w, ExperienceScale>,
+}
+
+impl RunSummary<'_, '_> {
+    pub fn elapsed_secs(&self) -> f32 {
+        self.time.elapsed_secs()
+    }
+
+    pub fn spawn(&self, parent: &mut ChildBuilder) {
+        let total_secs = self.elapsed_secs() as u32;
+        let (level, gold) = self
+            .player_query
+            .get_single()
+            .map_or((1, 0), |(experience, gold)| (experience.level, gold.0));
+
+        let mut weapons: Vec<_> = self
+            .weapon_query
+            .iter()
+            .map(|meta| (meta.weapon_type, meta.level))
+            .collect();
+        // Highest level first, like a loadout screen
+        weapons.sort_by_key(|(_, level)| std::cmp::Reverse(*level));
+
+        let mut summary = format!(
+            "{:02}:{:02}  |  Kills: {}  |  Damage: {}  |  Level {}  |  Gold: {}",
+            total_secs / 60,
+            total_secs % 60,
+            abbreviate(self.game_stats.enemies_killed as f64),
+            abbreviate(self.run_totals.damage_dealt as f64),
+            level,
+            abbreviate(gold as f64)
+        );
+        if self.game_stats.bosses_killed > 0 {
+            summary.push_str(&format!("  |  Bosses: {}", self.game_stats.bosses_killed));
+        }
+        summary.push_str(&format!("  |  XP x{:.2}", self.experience_scale.0));
+
+        parent.spawn((
+            Text::new(summary),
+            TextFont {
+                font_size: 20.0,
+                ..default()
+            },
+            TextColor(Color::WHITE),
+        ));
+        spawn_weapon_tray(parent, &weapons, &self.weapon_stats);
+
+        // Paused, there's time to read what each modifier does in full
+        for mutator in &self.run_modifiers.mutators {
+            parent.spawn((
+                Text::new(format!(
+                    "{} {}: {}",
+                    mutator.icon(),
+                    mutator,
+                    mutator.description()
+                )),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.8, 0.8, 0.8)),
+            ));
+        }
+    }
+}
+
+// The HUD keeps this far in from the window edges, as a share of the shorter side
+const HUD_SAFE_MARGIN: f32 = 2.0;
+// Past this aspect ratio the HUD stops widening, so on ultrawide screens it stays near the middle
+const HUD_MAX_ASPECT: f32 = 21.0 / 9.0;
+
+/// The HUD's frame inside the screen, capped at `HUD_MAX_ASPECT`
+#[derive(Component)]
+pub struct HudFrame;
+
+/// Debug view that fits the HUD into other screen shapes, cycled with F9
+#[derive(Resource, Default)]
+pub struct HudPreview {
+    pub aspect: Option<f32>, // None fills the window as normal
+}
+
+const HUD_PREVIEW_KEY: KeyCode = KeyCode::F9;
+const HUD_PREVIEW_OUTLINE: Color = Color::srgb(1.0, 0.0, 1.0);
+
+// Old monitors, the usual widescreen and two ultrawides
+const HUD_PREVIEW_ASPECTS: [f32; 4] = [4.0 / 3.0, 16.0 / 9.0, 21.0 / 9.0, 32.0 / 9.0];
+
+impl HudPreview {
+    /// The next shape in the cycle, back to off after the widest
+    pub fn next(&self) -> Option<f32> {
+        match self.aspect {
+            None => Some(HUD_PREVIEW_ASPECTS[0]),
+            Some(current) => HUD_PREVIEW_ASPECTS
+                .into_iter()
+                .find(|aspect| *aspect > current + f32::EPSILON),
+        }
+    }
+
+    /// The part of a window this size the HUD gets, letterboxed in the middle
+    pub fn screen_rect(&self, window: Vec2) -> Rect {
+        let size = match self.aspect {
+            Some(aspect) if window.x > window.y * aspect => Vec2::new(window.y * aspect, window.y),
+            Some(aspect) => Vec2::new(window.x, window.x / aspect),
+            None => window,
+        };
+        Rect::from_center_size(window * 0.5, size)
+    }
+}
+
+pub fn spawn_ui(
+    mut commands: Commands,
+    weapon_stats_panel: Res<WeaponStatsPanel>,
+    run_modifiers: Res<RunModifiers>,
+) {
+    // Root node with marker component, stands in for the screen
     commands
         .spawn((
             Node {
                 width: Val::Percent(100.0),
                 height: Val::Percent(100.0),
                 position_type: PositionType::Absolute,
-                padding: UiRect::all(Val::Px(10.0)),
+                justify_content: JustifyContent::Center,
                 ..default()
             },
+            // Only shows while previewing other screen shapes
+            Outline::new(Val::Px(2.0), Val::ZERO, Color::NONE),
             GameUI,
         ))
         .with_children(|parent| {
-            // Health bar container
+            parent
+                .spawn((
+                    Node {
+                        width: Val::Percent(100.0),
+                        max_width: Val::Vh(HUD_MAX_ASPECT * 100.0),
+                        height: Val::Percent(100.0),
+                        padding: UiRect::all(Val::VMin(HUD_SAFE_MARGIN)),
+                        flex_direction: FlexDirection::Column,
+                        justify_content: JustifyContent::SpaceBetween,
+                        ..default()
+                    },
+                    HudFrame,
+                ))
+                .with_children(|parent| {
+                    parent
+                        .spawn(Node {
+                            flex_direction: FlexDirection::Row,
+                            column_gap: Val::Px(10.0),
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            spawn_hud_anchor(parent, AlignItems::FlexStart, |parent| {
+                                spawn_health_display(parent);
+
+                                // Per-weapon damage, toggled with the weapon stats key
+                                parent.spawn((
+                                    Text::new(""),
+                                    TextFont {
+                                        font_size: 18.0,
+                                        ..default()
+                                    },
+                                    Node {
+                                        padding: UiRect::all(Val::Px(8.0)),
+                                        ..default()
+                                    },
+                                    TextColor(Color::WHITE),
+                                    BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+                                    if weapon_stats_panel.visible {
+                                        Visibility::Inherited
+                                    } else {
+                                        Visibility::Hidden
+                                    },
+                                    WeaponStatsText,
+                                ));
+                            });
+
+                            spawn_hud_anchor(parent, AlignItems::Center, |parent| {
+                                parent.spawn((
+                                    Text::new("00:00"),
+                                    TextFont {
+                                        font_size: 32.0,
+                                        ..default()
+                                    },
+                                    TextColor(Color::WHITE),
+                                    GameTimer,
+                                ));
+                            });
+
+                            spawn_hud_anchor(parent, AlignItems::FlexEnd, |parent| {
+                                parent.spawn((
+                                    Text::new("Kills: 0"),
+                                    TextFont {
+                                        font_size: 24.0,
+                                        ..default()
+                                    },
+                                    TextColor(Color::WHITE),
+                                    KillCounter,
+                                ));
+
+                                // Current wave and how long until the next one
+                                parent.spawn((
+                                    Text::new(""),
+                                    TextFont {
+                                        font_size: 18.0,
+                                        ..default()
+                                    },
+                                    TextColor(Color::srgb(0.8, 0.8, 0.8)),
+                                    WaveText,
+                                ));
+                            });
+                        });
+
+                    if !run_modifiers.mutators.is_empty() {
+                        spawn_modifier_strip(parent, &run_modifiers.mutators);
+                    }
+                });
+        });
+}
+
+/// One of the top row's three equal columns, its content pushed to `align`
+fn spawn_hud_anchor(
+    parent: &mut ChildBuilder,
+    align: AlignItems,
+    spawn_content: impl FnOnce(&mut ChildBuilder),
+) {
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Column,
+            flex_grow: 1.0,
+            flex_basis: Val::Px(0.0),
+            align_items: align,
+            row_gap: Val::Px(8.0),
+            ..default()
+        })
+        .with_children(spawn_content);
+}
+
+fn spawn_health_display(parent: &mut ChildBuilder) {
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(10.0),
+            ..default()
+        })
+        .with_children(|parent| {
+            // Health bar container, narrows with the window rather than pushing past it
             parent
                 .spawn((
                     Node {
                         width: Val::Px(200.0),
+                        max_width: Val::Vw(25.0),
                         height: Val::Px(30.0),
                         border: UiRect::all(Val::Px(2.0)),
-
                         ..default()
                     },
                     BorderColor(Color::srgb(0.7, 0.7, 0.7)),
@@ -58,15 +419,8 @@ pub fn spawn_ui(mut commands: Commands) {
                     ));
                 });
 
-            // Health text
             parent.spawn((
                 Text::new("100/100"),
-                Node {
-                    position_type: PositionType::Absolute,
-                    left: Val::Px(220.0),
-                    top: Val::Px(2.0),
-                    ..default()
-                },
                 TextFont {
                     font_size: 24.0,
                     ..default()
@@ -74,48 +428,143 @@ pub fn spawn_ui(mut commands: Commands) {
                 TextColor(Color::WHITE),
                 HealthText,
             ));
+        });
+}
 
-            // Game Timer
-            parent.spawn((
-                Text::new("00:00"),
-                Node {
-                    position_type: PositionType::Absolute,
-                    left: Val::Percent(50.0),
-                    top: Val::Px(10.0),
-                    // Center the text horizontally
-                    margin: UiRect {
-                        left: Val::Px(-40.0), // Approximately half the text width
-                        ..default()
-                    },
-                    ..default()
-                },
-                TextFont {
-                    font_size: 32.0,
-                    ..default()
-                },
-                TextColor(Color::WHITE),
-                GameTimer,
-            ));
-
-            // Kill Counter
+/// Compact row of active modifier icons in the bottom left, with a tooltip above it
+fn spawn_modifier_strip(parent: &mut ChildBuilder, mutators: &[Mutator]) {
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::FlexStart,
+            row_gap: Val::Px(4.0),
+            ..default()
+        })
+        .with_children(|parent| {
             parent.spawn((
-                Text::new("Kills: 0"),
+                Text::new(""),
                 TextFont {
-                    font_size: 24.0,
+                    font_size: 16.0,
                     ..default()
                 },
                 Node {
-                    position_type: PositionType::Absolute,
-                    right: Val::Px(10.0),
-                    top: Val::Px(10.0),
+                    padding: UiRect::all(Val::Px(6.0)),
                     ..default()
                 },
                 TextColor(Color::WHITE),
-                KillCounter,
+                BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+                Visibility::Hidden,
+                ModifierTooltip,
             ));
+
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(6.0),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for mutator in mutators {
+                        parent.spawn((
+                            Text::new(mutator.icon()),
+                            TextFont {
+                                font_size: 22.0,
+                                ..default()
+                            },
+                            Node {
+                                padding: UiRect::all(Val::Px(4.0)),
+                                ..default()
+                            },
+                            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.4)),
+                            Interaction::default(),
+                            ModifierIcon(*mutator),
+                        ));
+                    }
+                });
         });
 }
 
+pub fn update_modifier_tooltip(
+    icon_query: Query<(&Interaction, &ModifierIcon)>,
+    mut tooltip_query: Query<(&mut Text, &mut Visibility), With<ModifierTooltip>>,
+) {
+    let Ok((mut text, mut visibility)) = tooltip_query.get_single_mut() else {
+        return;
+    };
+
+    let hovered = icon_query
+        .iter()
+        .find(|(interaction, _)| **interaction != Interaction::None);
+
+    match hovered {
+        Some((_, icon)) => {
+            let tooltip = format!("{}: {}", icon.0, icon.0.description());
+            if text.0 != tooltip {
+                text.0 = tooltip;
+            }
+            *visibility = Visibility::Inherited;
+        }
+        None => *visibility = Visibility::Hidden,
+    }
+}
+
+pub fn cycle_hud_preview(keyboard: Res<ButtonInput<KeyCode>>, mut preview: ResMut<HudPreview>) {
+    if !keyboard.just_pressed(HUD_PREVIEW_KEY) {
+        return;
+    }
+
+    preview.aspect = preview.next();
+    match preview.aspect {
+        Some(aspect) => info!("Previewing the HUD at {:.2}:1", aspect),
+        None => info!("HUD preview off"),
+    }
+}
+
+/// Sizes the HUD to the previewed screen shape, or the whole window when not previewing
+pub fn apply_hud_preview(
+    preview: Res<HudPreview>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut root_query: Query<(&mut Node, &mut Outline), With<GameUI>>,
+    mut frame_query: Query<&mut Node, (With<HudFrame>, Without<GameUI>)>,
+) {
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let screen = preview.screen_rect(window.size());
+    let (width, height, max_width, outline) = match preview.aspect {
+        Some(_) => (
+            Val::Px(screen.width()),
+            Val::Px(screen.height()),
+            Val::Px(screen.height() * HUD_MAX_ASPECT),
+            HUD_PREVIEW_OUTLINE,
+        ),
+        None => (
+            Val::Percent(100.0),
+            Val::Percent(100.0),
+            Val::Vh(HUD_MAX_ASPECT * 100.0),
+            Color::NONE,
+        ),
+    };
+
+    // Written only on change, so the layout isn't redone every frame
+    for (mut node, mut root_outline) in root_query.iter_mut() {
+        if node.width != width || node.height != height {
+            node.width = width;
+            node.height = height;
+            node.left = Val::Px(screen.min.x);
+            node.top = Val::Px(screen.min.y);
+        }
+        if root_outline.color != outline {
+            root_outline.color = outline;
+        }
+    }
+    for mut node in frame_query.iter_mut() {
+        if node.max_width != max_width {
+            node.max_width = max_width;
+        }
+    }
+}
+
 pub fn cleanup_ui(mut commands: Commands, ui_query: Query<Entity, With<GameUI>>) {
     for entity in ui_query.iter() {
         commands.entity(entity).despawn_recursive();
@@ -124,16 +573,59 @@ pub fn cleanup_ui(mut commands: Commands, ui_query: Query<Entity, With<GameUI>>)
 
 pub fn update_game_timer(
     time: Res<Time<Virtual>>,
+    wave_config: Res<WaveConfig>,
     mut timer_query: Query<&mut Text, With<GameTimer>>,
 ) {
     if let Ok(mut text) = timer_query.get_single_mut() {
-        let total_secs = time.elapsed_secs() as u32;
+        // Runs with a time goal count down to it instead of up
+        let remaining = wave_config.time_remaining(time.elapsed_secs());
+        let total_secs = remaining.map_or(time.elapsed_secs(), f32::ceil) as u32;
         let minutes = total_secs / 60;
         let seconds = total_secs % 60;
-        text.0 = format!("{:02}:{:02}", minutes, seconds);
+        text.0 = match remaining {
+            Some(_) => format!("Survive {:02}:{:02}", minutes, seconds),
+            None => format!("{:02}:{:02}", minutes, seconds),
+        };
     }
 }
 
+pub fn update_wave_text(
+    wave_director: Res<WaveDirector>,
+    mut wave_query: Query<&mut Text, With<WaveText>>,
+) {
+    let Ok(mut text) = wave_query.get_single_mut() else {
+        return;
+    };
+    if wave_director.current().is_none() {
+        text.0.clear();
+        return;
+    }
+
+    // Bracketed waves have no timer, they last until they're cleared
+    if let Some(intermission) = wave_director.intermission_left() {
+        let time_left = intermission.max(0.0).ceil() as u32;
+        text.0 = format!(
+            "Wave {} cleared - next in {}:{:02}",
+            wave_director.number + 1,
+            time_left / 60,
+            time_left % 60
+        );
+        return;
+    }
+    if let DirectorStrategy::Bracketed(_) = wave_director.strategy {
+        text.0 = format!("Wave {}", wave_director.number + 1);
+        return;
+    }
+
+    let time_left = wave_director.time_left().ceil() as u32;
+    text.0 = format!(
+        "Wave {} - next in {}:{:02}",
+        wave_director.number + 1,
+        time_left / 60,
+        time_left % 60
+    );
+}
+
 pub fn update_health_ui(
     mut health_bar_query: Query<&mut Node, With<HealthBar>>,
     mut health_text_query: Query<&mut Text, With<HealthText>>,
@@ -142,7 +634,8 @@ pub fn update_health_ui(
     if let Ok(player_health) = player_query.get_single() {
         // Update health bar width
         if let Ok(mut style) = health_bar_query.get_single_mut() {
-            let health_percent = (player_health.current as f32 / player_health.maximum as f32 * 100.0).max(0.0);
+            let health_percent =
+                (player_health.current as f32 / player_health.maximum as f32 * 100.0).max(0.0);
             style.width = Val::Percent(health_percent);
         }
 
@@ -150,8 +643,8 @@ pub fn update_health_ui(
         if let Ok(mut text) = health_text_query.get_single_mut() {
             text.0 = format!(
                 "{}/{}",
-                player_health.current,
-                player_health.maximum
+                abbreviate(player_health.current as f64),
+                abbreviate(player_health.maximum as f64)
             );
         }
     }
@@ -162,6 +655,57 @@ pub fn update_kill_counter(
     mut kill_counter_query: Query<&mut Text, With<KillCounter>>,
 ) {
     if let Ok(mut text) = kill_counter_query.get_single_mut() {
-        text.0 = format!("Kills: {}", game_stats.enemies_killed);
+        text.0 = format!("Kills: {}", abbreviate(game_stats.enemies_killed as f64));
+    }
+}
+
+pub fn toggle_weapon_stats_panel(
+    actions: ActionInput,
+    mut weapon_stats_panel: ResMut<WeaponStatsPanel>,
+    mut panel_query: Query<&mut Visibility, With<WeaponStatsText>>,
+) {
+    if !actions.just_pressed(Action::WeaponStats) {
+        return;
     }
+
+    weapon_stats_panel.visible = !weapon_stats_panel.visible;
+    if let Ok(mut visibility) = panel_query.get_single_mut() {
+        *visibility = if weapon_stats_panel.visible {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+pub fn update_weapon_stats_panel(
+    time: Res<Time<Virtual>>,
+    weapon_stats: Res<WeaponStats>,
+    weapon_stats_panel: Res<WeaponStatsPanel>,
+    mut panel_query: Query<&mut Text, With<WeaponStatsText>>,
+) {
+    if !weapon_stats_panel.visible {
+        return;
+    }
+    let Ok(mut text) = panel_query.get_single_mut() else {
+        return;
+    };
+
+    // Biggest damage dealers first
+    let mut weapons: Vec<_> = weapon_stats.weapons.iter().collect();
+    weapons.sort_by_key(|(_, tally)| std::cmp::Reverse(tally.damage));
+
+    let elapsed = time.elapsed_secs();
+    let mut lines = vec!["Weapon DPS".to_string()];
+    lines.extend(weapons.into_iter().map(|(weapon_type, tally)| {
+        format!(
+            "{}: {} DPS ({} dmg, {} hits, {} kills)",
+            weapon_type,
+            abbreviate(tally.dps(elapsed) as f64),
+            abbreviate(tally.damage as f64),
+            abbreviate(tally.hits as f64),
+            abbreviate(tally.kills as f64)
+        )
+    }));
+    text.0 = lines.join("\n");
 }
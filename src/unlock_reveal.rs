@@ -0,0 +1,230 @@
+use crate::codex::{Codex, CodexSection};
+use crate::components::EnemyKind;
+use crate::menu::{
+    spawn_menu_button, spawn_menu_container, MenuAction, MenuActionComponent, MenuInput, MenuItem,
+    MenuRoot, MenuType,
+};
+use crate::resources::GameState;
+use crate::save::SaveData;
+use crate::telemetry::MasteryTier;
+use crate::weapons::magick_circle::PatternType;
+use crate::weapons::WeaponType;
+use bevy::prelude::*;
+use strum::IntoEnumIterator;
+
+/// After the results screen, walks through everything the run added to the codex,
+/// found by comparing the codex from when the run started against the one now saved.
+pub struct UnlockRevealPlugin;
+
+impl Plugin for UnlockRevealPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunStartCodex>()
+            .add_systems(OnExit(GameState::MainMenu), remember_run_start_codex)
+            .add_systems(
+                Update,
+                (open_unlock_reveal, reveal_unlock_cards)
+                    .chain()
+                    .run_if(in_state(GameState::GameOver).or(in_state(GameState::Victory))),
+            );
+    }
+}
+
+/// The codex as it was when the run left the main menu
+#[derive(Resource, Default)]
+pub struct RunStartCodex(pub Codex);
+
+/// Something the codex didn't have before this run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unlock {
+    Section(CodexSection),
+    Pattern(PatternType),
+    Mastery(WeaponType, MasteryTier),
+    WeaponMaxed(WeaponType),
+    Enemy(EnemyKind),
+}
+
+impl Unlock {
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Unlock::Section(_) => "📖",
+            Unlock::Pattern(_) => "🔯",
+            Unlock::Mastery(..) => "🏅",
+            Unlock::WeaponMaxed(_) => "⚔️",
+            Unlock::Enemy(_) => "👁️",
+        }
+    }
+}
+
+impl std::fmt::Display for Unlock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Unlock::Section(section) => write!(f, "{} complete", section),
+            Unlock::Pattern(pattern) => write!(f, "{} pattern", pattern),
+            Unlock::Mastery(weapon_type, tier) => write!(f, "{} {} mastery", weapon_type, tier),
+            Unlock::WeaponMaxed(weapon_type) => write!(f, "{} reached max level", weapon_type),
+            Unlock::Enemy(kind) => write!(f, "{} added to the bestiary", kind),
+        }
+    }
+}
+
+/// What `after` has that `before` didn't, biggest news first
+pub fn newly_unlocked(before: &Codex, after: &Codex) -> Vec<Unlock> {
+    let mut unlocks: Vec<Unlock> = CodexSection::ALL
+        .into_iter()
+        .filter(|section| section.is_complete(after) && !section.is_complete(before))
+        .map(Unlock::Section)
+        .collect();
+
+    unlocks.extend(
+        PatternType::iter()
+            .filter(|pattern| after.has_pattern(*pattern) && !before.has_pattern(*pattern))
+            .map(Unlock::Pattern),
+    );
+    unlocks.extend(WeaponType::iter().filter_map(|weapon_type| {
+        let tier = *after.weapon_mastery.get(&weapon_type)?;
+        let improved = before
+            .weapon_mastery
+            .get(&weapon_type)
+            .is_none_or(|best| tier > *best);
+        improved.then_some(Unlock::Mastery(weapon_type, tier))
+    }));
+    unlocks.extend(
+        WeaponType::iter()
+            .filter(|weapon_type| {
+                after.weapons_maxed.contains(weapon_type)
+                    && !before.weapons_maxed.contains(weapon_type)
+            })
+            .map(Unlock::WeaponMaxed),
+    );
+    unlocks.extend(
+        EnemyKind::iter()
+            .filter(|kind| {
+                after.enemies_defeated.contains(kind) && !before.enemies_defeated.contains(kind)
+            })
+            .map(Unlock::Enemy),
+    );
+
+    unlocks
+}
+
+/// A line on the reveal screen, faded in once its delay is up
+#[derive(Component)]
+pub struct UnlockCard {
+    delay: Timer,
+    fade: Timer,
+}
+
+// Real seconds between one card starting to show and the next
+const REVEAL_STAGGER: f32 = 0.4;
+const REVEAL_FADE: f32 = 0.3;
+const UNLOCK_CARD_COLOR: Color = Color::srgb(0.2, 0.18, 0.1);
+
+fn remember_run_start_codex(save_data: Res<SaveData>, mut run_start: ResMut<RunStartCodex>) {
+    run_start.0 = save_data.codex.clone();
+}
+
+fn open_unlock_reveal(
+    mut commands: Commands,
+    menu_input: MenuInput,
+    menu_items: Query<(&MenuItem, &MenuActionComponent, Ref<Interaction>)>,
+    menu_roots: Query<Entity, With<MenuRoot>>,
+    save_data: Res<SaveData>,
+    run_start: Res<RunStartCodex>,
+) {
+    let reveal_items = menu_items.iter().filter(|(_, action_component, _)| {
+        matches!(action_component.action, MenuAction::RevealUnlocks)
+    });
+    if menu_input.confirmed(reveal_items).is_none() {
+        return;
+    }
+
+    // Takes the place of the results screen
+    for entity in menu_roots.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    let unlocks = newly_unlocked(&run_start.0, &save_data.codex);
+    info!("Revealing {} unlocks", unlocks.len());
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+            MenuRoot {
+                menu_type: MenuType::Unlocks,
+            },
+        ))
+        .with_children(|parent| {
+            spawn_menu_container(parent, |parent| {
+                parent.spawn((
+                    Text::new("Unlocked"),
+                    TextFont {
+                        font_size: 48.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(1.0, 0.84, 0.0)),
+                ));
+
+                for (index, unlock) in unlocks.iter().enumerate() {
+                    parent
+                        .spawn((
+                            Node {
+                                width: Val::Px(420.0),
+                                padding: UiRect::axes(Val::Px(12.0), Val::Px(8.0)),
+                                ..default()
+                            },
+                            BackgroundColor(UNLOCK_CARD_COLOR.with_alpha(0.0)),
+                            UnlockCard {
+                                delay: Timer::from_seconds(
+                                    index as f32 * REVEAL_STAGGER,
+                                    TimerMode::Once,
+                                ),
+                                fade: Timer::from_seconds(REVEAL_FADE, TimerMode::Once),
+                            },
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                Text::new(format!("{} {}", unlock.icon(), unlock)),
+                                TextFont {
+                                    font_size: 22.0,
+                                    ..default()
+                                },
+                                TextColor(Color::WHITE.with_alpha(0.0)),
+                            ));
+                        });
+                }
+
+                spawn_menu_button(parent, "Restart", MenuAction::RestartGame, true);
+                spawn_menu_button(parent, "Main Menu", MenuAction::ExitToMainMenu, false);
+                spawn_menu_button(parent, "Quit", MenuAction::QuitGame, false);
+            });
+        });
+}
+
+/// Fades each card in, one after another
+fn reveal_unlock_cards(
+    time: Res<Time<Real>>,
+    mut card_query: Query<(&mut UnlockCard, &mut BackgroundColor, &Children)>,
+    mut text_query: Query<&mut TextColor>,
+) {
+    for (mut card, mut background, children) in card_query.iter_mut() {
+        if card.fade.finished() || !card.delay.tick(time.delta()).finished() {
+            continue;
+        }
+
+        let alpha = card.fade.tick(time.delta()).fraction();
+        background.0 = UNLOCK_CARD_COLOR.with_alpha(alpha);
+        for &child in children.iter() {
+            if let Ok(mut text_color) = text_query.get_mut(child) {
+                text_color.0 = text_color.0.with_alpha(alpha);
+            }
+        }
+    }
+}
@@ -1,10 +1,13 @@
-use crate::components::{Health, Luck, Player};
+use crate::components::{AmountBonus, Armor, Equipment, Health, Luck, Player};
+use crate::equipment::equipment_definition;
 use crate::menu;
 use crate::menu::{
     GenericUpgradeConfirmedEvent, MenuAction, MenuActionComponent, MenuItem, UpgradeChoice,
+    WeaponUpgradeConfirmedEvent,
 };
 use crate::types::{EquipmentType, Rarity, StatType};
-use crate::weapons::weapon_upgrade::{WeaponUpgradeConfig, WeaponUpgradeSpec};
+use crate::ui::weapon_icon;
+use crate::weapons::weapon_upgrade::{WeaponUpgradeConfig, WeaponUpgradeSpec, MAX_WEAPON_LEVEL};
 use crate::weapons::{WeaponMeta, WeaponType};
 use bevy::color::{Alpha, Color};
 use bevy::hierarchy::{BuildChildren, ChildBuilder};
@@ -15,30 +18,125 @@ use std::cmp::Ordering;
 
 pub fn handle_generic_upgrade(
     mut upgrade_events: EventReader<GenericUpgradeConfirmedEvent>,
-    mut player_query: Query<&mut Health, With<Player>>,
+    mut player_query: Query<(&mut Health, Option<&mut AmountBonus>), With<Player>>,
+    mut stat_query: Query<(&mut Player, Option<&mut Armor>)>,
 ) {
     for generic_upgrade_event in upgrade_events.read() {
         match generic_upgrade_event.generic_upgrade_type {
             GenericUpgrade::HealthPickup(amount) => {
-                if let Ok(mut health) = player_query.get_single_mut() {
-                    let new_health = (health.current + amount).min(health.maximum);
+                if let Ok((mut health, _)) = player_query.get_single_mut() {
+                    let current = health.current;
+                    health.heal(amount);
                     info!(
                         "Healing player for {amount} (from {current} to {new})",
                         amount = amount,
-                        current = health.current,
-                        new = new_health
+                        current = current,
+                        new = health.current
+                    );
+                }
+            }
+            GenericUpgrade::MaxHealth(amount) => {
+                if let Ok((mut health, _)) = player_query.get_single_mut() {
+                    health.raise_maximum(amount);
+                    info!(
+                        "Raising max health by {} to {} ({} current)",
+                        amount, health.maximum, health.current
                     );
-                    health.current = new_health;
                 }
             }
             GenericUpgrade::ResourcePickup(_) => {
                 // We'll implement this later
                 info!("Resource pickup not yet implemented");
             }
+            GenericUpgrade::Amount(count) => {
+                if let Ok((_, Some(mut amount_bonus))) = player_query.get_single_mut() {
+                    amount_bonus.0 += count;
+                    info!("Every weapon now fires {} extra attacks", amount_bonus.0);
+                }
+            }
+            GenericUpgrade::Speed(fraction) => {
+                if let Ok((mut player, _)) = stat_query.get_single_mut() {
+                    player.speed *= 1.0 + fraction;
+                    info!("Movement speed raised to {}", player.speed);
+                }
+            }
+            GenericUpgrade::Defense(amount) => {
+                if let Ok((_, Some(mut armor))) = stat_query.get_single_mut() {
+                    armor.0 += amount;
+                    info!("Armor raised to {}", armor.0);
+                }
+            }
+            GenericUpgrade::Equipment(_) => {} // Handled by handle_equipment_upgrade
         }
     }
 }
 
+// Adds a picked passive item to the player or levels up the one they carry.
+// Its stats are folded in by apply_equipment_stats.
+pub fn handle_equipment_upgrade(
+    mut upgrade_events: EventReader<GenericUpgradeConfirmedEvent>,
+    mut player_query: Query<&mut Equipment, With<Player>>,
+) {
+    for generic_upgrade_event in upgrade_events.read() {
+        let GenericUpgrade::Equipment(equipment_type) = generic_upgrade_event.generic_upgrade_type
+        else {
+            continue;
+        };
+        let Ok(mut equipment) = player_query.get_single_mut() else {
+            continue;
+        };
+
+        let level = equipment.add(equipment_type);
+        info!("Equipped {} at level {}", equipment_type, level);
+    }
+}
+
+fn stat_upgrade(stat_type: &StatType) -> Option<(GenericUpgrade, &'static str)> {
+    match stat_type {
+        StatType::Health => Some((
+            GenericUpgrade::MaxHealth(20),
+            "Raise maximum health with a Heart of Iron",
+        )),
+        StatType::Speed => Some((GenericUpgrade::Speed(0.08), "+8% movement speed")),
+        StatType::Defense => Some((
+            GenericUpgrade::Defense(1),
+            "Take 1 less damage from every hit",
+        )),
+        StatType::Attack | StatType::Luck => None,
+    }
+}
+
+/// Share of max health restored every time a level-up choice is confirmed
+#[derive(Resource)]
+pub struct LevelUpHealing {
+    pub percent: f32, // e.g., 0.10 for 10% of max health
+}
+
+impl Default for LevelUpHealing {
+    fn default() -> Self {
+        Self { percent: 0.10 }
+    }
+}
+
+// Every level up ends in exactly one confirmed choice, weapon or generic
+pub fn heal_on_level_up(
+    mut weapon_upgrade_events: EventReader<WeaponUpgradeConfirmedEvent>,
+    mut generic_upgrade_events: EventReader<GenericUpgradeConfirmedEvent>,
+    healing: Res<LevelUpHealing>,
+    mut player_query: Query<&mut Health, With<Player>>,
+) {
+    let level_ups = weapon_upgrade_events.read().count() + generic_upgrade_events.read().count();
+    if level_ups == 0 {
+        return;
+    }
+
+    if let Ok(mut health) = player_query.get_single_mut() {
+        let amount = (health.maximum as f32 * healing.percent).round() as i32;
+        let healed: i32 = (0..level_ups).map(|_| health.heal(amount)).sum();
+        info!("Level up restored {} health", healed);
+    }
+}
+
 #[derive(Resource)]
 pub struct UpgradePool {
     weapons: Vec<(WeaponType, Rarity)>,
@@ -49,14 +147,24 @@ pub struct UpgradePool {
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum GenericUpgrade {
     HealthPickup(i32),   // Amount to heal
+    MaxHealth(i32),      // Amount added to max health, healed as well
     ResourcePickup(u32), // Amount of resource to gain
+    Amount(u32),         // Extra attacks for every weapon
+    Speed(f32),          // Share of movement speed added, e.g. 0.08 for 8%
+    Defense(i32),        // Added to armor
+    Equipment(EquipmentType),
 }
 
 impl std::fmt::Display for GenericUpgrade {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             GenericUpgrade::HealthPickup(_) => write!(f, "Philosopher's Elixir"),
+            GenericUpgrade::MaxHealth(_) => write!(f, "Heart of Iron"),
             GenericUpgrade::ResourcePickup(_) => write!(f, "Void Shards"),
+            GenericUpgrade::Amount(_) => write!(f, "Mirror Shard"),
+            GenericUpgrade::Speed(_) => write!(f, "Quicksilver Draught"),
+            GenericUpgrade::Defense(_) => write!(f, "Stoneskin Rune"),
+            GenericUpgrade::Equipment(equipment_type) => write!(f, "{}", equipment_type),
         }
     }
 }
@@ -76,7 +184,14 @@ impl Default for UpgradePool {
 impl UpgradePool {
     pub fn new() -> Self {
         Self {
-            weapons: vec![(WeaponType::MagickCircle, Rarity::Common)],
+            weapons: vec![
+                (WeaponType::MagickCircle, Rarity::Common),
+                (WeaponType::Beam, Rarity::Uncommon),
+                (WeaponType::ArcaneBolt, Rarity::Common),
+                (WeaponType::Orbitals, Rarity::Uncommon),
+                (WeaponType::Lance, Rarity::Uncommon),
+                (WeaponType::Boomerang, Rarity::Common),
+            ],
             equipment: vec![
                 (EquipmentType::Armor, Rarity::Common),
                 (EquipmentType::Ring, Rarity::Uncommon),
@@ -101,6 +216,11 @@ impl UpgradePool {
                 description: "Restore health with a Philosopher's Elixir".to_string(),
                 rarity: Rarity::Common,
             },
+            UpgradeChoice {
+                upgrade_type: UpgradeType::Generic(GenericUpgrade::Amount(1)),
+                description: "Every weapon fires one more attack".to_string(),
+                rarity: Rarity::Rare,
+            },
             UpgradeChoice {
                 upgrade_type: UpgradeType::Generic(GenericUpgrade::ResourcePickup(100)),
                 description: "Gather Void Shards".to_string(),
@@ -128,15 +248,13 @@ impl UpgradePool {
 
             // Convert each `WeaponUpgradeSpec` to an `UpgradeChoice`
             for spec in specs {
-                let description = format!(
-                    "{} Level {}: {:?}",
-                    weapon_meta.weapon_type, next_level, spec
-                );
+                let description =
+                    format!("{} Level {}: {}", weapon_meta.weapon_type, next_level, spec);
 
                 upgrades.push(UpgradeChoice {
                     upgrade_type: UpgradeType::Weapon(weapon_meta.weapon_type, spec.clone()),
                     description,
-                    rarity: Rarity::Common, // or more advanced logic
+                    rarity: spec.rarity(),
                 });
             }
         }
@@ -146,39 +264,160 @@ impl UpgradePool {
         upgrades
     }
 
+    // Weapons from the pool the player doesn't have yet, offered as a level 1 pickup
+    pub fn generate_weapon_unlocks(&self, weapons: &[&WeaponMeta]) -> Vec<UpgradeChoice> {
+        self.weapons
+            .iter()
+            .filter(|(weapon_type, _)| {
+                !weapons
+                    .iter()
+                    .any(|weapon_meta| weapon_meta.weapon_type == *weapon_type)
+            })
+            .map(|(weapon_type, rarity)| UpgradeChoice {
+                upgrade_type: UpgradeType::Weapon(
+                    *weapon_type,
+                    WeaponUpgradeSpec { changes: vec![] },
+                ),
+                description: format!("New weapon: {}", weapon_type),
+                rarity: rarity.clone(),
+            })
+            .collect()
+    }
+
+    // One stat boost, rarer stats rolled less often. Stats without an upgrade yet are skipped.
+    pub fn generate_stat_choice(&self, rng: &mut impl Rng) -> Option<UpgradeChoice> {
+        let (stat_type, rarity) = self
+            .stats
+            .iter()
+            .filter(|(stat_type, _)| stat_upgrade(stat_type).is_some())
+            .collect::<Vec<_>>()
+            .choose_weighted(rng, |(_, rarity)| rarity.weight())
+            .ok()?;
+        let (generic_upgrade, description) = stat_upgrade(stat_type)?;
+
+        Some(UpgradeChoice {
+            upgrade_type: UpgradeType::Generic(generic_upgrade),
+            description: description.to_string(),
+            rarity: rarity.clone(),
+        })
+    }
+
+    // Every stat boost there is, offered at the run timer milestones where nothing else is
+    pub fn generate_milestone_choices(&self) -> Vec<UpgradeChoice> {
+        self.stats
+            .iter()
+            .filter_map(|(stat_type, rarity)| {
+                let (generic_upgrade, description) = stat_upgrade(stat_type)?;
+                Some(UpgradeChoice {
+                    upgrade_type: UpgradeType::Generic(generic_upgrade),
+                    description: description.to_string(),
+                    rarity: rarity.clone(),
+                })
+            })
+            .collect()
+    }
+
+    // Passive items the player isn't carrying yet, and carried ones that can still level up
+    pub fn generate_equipment_choices(&self, equipment: &Equipment) -> Vec<UpgradeChoice> {
+        self.equipment
+            .iter()
+            .filter_map(|(equipment_type, rarity)| {
+                let definition = equipment_definition(*equipment_type);
+                let level = equipment.level(*equipment_type);
+                if level >= definition.max_level {
+                    return None;
+                }
+
+                let bonus = definition.per_level.describe();
+                let description = if level == 0 {
+                    format!("Equip {}: {}", equipment_type, bonus)
+                } else {
+                    format!("{} Level {}: {}", equipment_type, level + 1, bonus)
+                };
+                Some(UpgradeChoice {
+                    upgrade_type: UpgradeType::Generic(GenericUpgrade::Equipment(*equipment_type)),
+                    description,
+                    rarity: rarity.clone(),
+                })
+            })
+            .collect()
+    }
+
+    // Maxed weapons whose catalyst the player carries, offered as the evolved weapon itself.
+    // Confirming it unlocks the evolution and evolve_weapons retires the base weapon.
+    pub fn generate_evolutions(
+        weapon_upgrade_config: &WeaponUpgradeConfig,
+        weapons: &[&WeaponMeta],
+        equipment: &Equipment,
+    ) -> Vec<UpgradeChoice> {
+        weapons
+            .iter()
+            .filter(|weapon_meta| weapon_meta.level >= MAX_WEAPON_LEVEL)
+            .filter_map(|weapon_meta| weapon_upgrade_config.evolution_from(weapon_meta.weapon_type))
+            .filter(|evolution| {
+                equipment.has(evolution.catalyst)
+                    && !weapons
+                        .iter()
+                        .any(|weapon_meta| weapon_meta.weapon_type == evolution.evolved)
+            })
+            .map(|evolution| UpgradeChoice {
+                upgrade_type: UpgradeType::Weapon(
+                    evolution.evolved,
+                    WeaponUpgradeSpec { changes: vec![] },
+                ),
+                description: format!(
+                    "Evolve {} into {} with your {}",
+                    evolution.base, evolution.evolved, evolution.catalyst
+                ),
+                rarity: Rarity::Legendary,
+            })
+            .collect()
+    }
+
     pub fn generate_choices(
         &self,
         weapon_upgrade_config: &WeaponUpgradeConfig,
         luck: &Luck,
         weapons: &[&WeaponMeta],
+        equipment: &Equipment,
+        rng: &mut impl Rng,
     ) -> Vec<UpgradeChoice> {
-        let mut rng = thread_rng();
+        // Query order shifts as components come and go, so pin it down before rolling
+        let mut weapons = weapons.to_vec();
+        weapons.sort_by_key(|weapon_meta| weapon_meta.weapon_type);
+        let weapons = weapons.as_slice();
+
+        // Evolutions are always offered and take up slots of their own
+        let evolutions = Self::generate_evolutions(weapon_upgrade_config, weapons, equipment);
 
         // Determine the number of upgrades to show
-        let count = Self::calculate_count(luck, &mut rng);
+        let count = Self::calculate_count(luck, rng).saturating_sub(evolutions.len());
 
         // Generate weapon-specific upgrades
         let mut choices = Self::generate_weapon_upgrades(weapon_upgrade_config, weapons);
+        choices.extend(self.generate_weapon_unlocks(weapons));
+        choices.extend(self.generate_equipment_choices(equipment));
+        choices.extend(self.generate_stat_choice(rng));
 
         // Adjust the list to ensure the correct count
         match choices.len().cmp(&count) {
             Ordering::Greater => {
                 // Randomly select the required number of upgrades
-                choices = Self::select_random_owned(choices, count, &mut rng);
+                choices = Self::select_random_owned(choices, count, rng);
             }
             Ordering::Less => {
                 // Randomly select the needed number of generics to fill the gap
                 let needed_generics = Self::select_random_owned(
                     Self::generate_generic_choices(),
                     count - choices.len(),
-                    &mut rng,
+                    rng,
                 );
                 choices.extend(needed_generics);
             }
             Ordering::Equal => (), // No adjustments needed
         }
 
-        choices
+        evolutions.into_iter().chain(choices).collect()
     }
 
     fn calculate_count(luck: &Luck, rng: &mut impl Rng) -> usize {
@@ -282,21 +521,26 @@ pub fn spawn_upgrade_choice(parent: &mut ChildBuilder, choice: UpgradeChoice, is
 
 fn get_upgrade_display_info(choice: &UpgradeChoice) -> (&'static str, String, String) {
     match &choice.upgrade_type {
-        UpgradeType::Weapon(weapon_type, ..) => {
-            let icon = match weapon_type {
-                WeaponType::MagickCircle => "🔮",
-                // We can add more weapon types here as we implement them
-            };
-            (
-                icon,
-                format!("{} Weapon", weapon_type),
-                choice.description.clone(),
-            )
-        }
+        UpgradeType::Weapon(weapon_type, ..) => (
+            weapon_icon(*weapon_type),
+            format!("{} Weapon", weapon_type),
+            choice.description.clone(),
+        ),
         UpgradeType::Generic(generic_type) => {
             let icon = match generic_type {
                 GenericUpgrade::HealthPickup(_) => "⚗️",
+                GenericUpgrade::MaxHealth(_) => "❤️",
                 GenericUpgrade::ResourcePickup(_) => "💎",
+                GenericUpgrade::Amount(_) => "🪞",
+                GenericUpgrade::Speed(_) => "💨",
+                GenericUpgrade::Defense(_) => "🪨",
+                GenericUpgrade::Equipment(equipment_type) => match equipment_type {
+                    EquipmentType::Armor => "🛡️",
+                    EquipmentType::Ring => "💍",
+                    EquipmentType::Amulet => "📿",
+                    EquipmentType::Boots => "👢",
+                    EquipmentType::Gloves => "🧤",
+                },
             };
             (
                 icon,
@@ -0,0 +1,346 @@
+use crate::announcer::DirectorMilestone;
+use crate::components::{Enemy, Gold, Player};
+use crate::death::MarkedForDeath;
+use crate::definitions::{Definitions, EnemyDefinition};
+use crate::director::{EnemyArchetype, SpawnCaps};
+use crate::elite::Elite;
+use crate::enemy_pool::EnemySpawner;
+use crate::player_handle::PlayerHandle;
+use crate::resources::{GameRng, GameState, WaveConfig};
+use crate::settings::QualitySettings;
+use crate::spawn_validation::SpawnValidator;
+use crate::GameplaySets;
+use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Regular spawning. The stage is split into waves, each with its own enemy mix and a
+/// point budget that gets spent on spawns over the wave's duration.
+/// How one wave leads into the next is up to the stage's `DirectorStrategy`.
+pub struct WavePlugin;
+
+impl Plugin for WavePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WaveDirector>().add_systems(
+            Update,
+            (advance_waves, spawn_wave_enemies, clear_bracketed_waves)
+                .chain()
+                .in_set(GameplaySets::Spawning)
+                .run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+/// One stretch of a stage. Once the last wave ends it starts over, so endless stages keep going.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WaveDefinition {
+    pub duration: f32, // Seconds
+    pub budget: u32,   // Points spent on spawns over the whole wave
+    pub enemies: Vec<WaveEnemy>,
+}
+
+/// How a stage moves from one wave to the next
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum DirectorStrategy {
+    /// Each wave runs for its duration and hands over to the next, whoever is still alive
+    #[default]
+    Continuous,
+    /// Each wave sends in its whole budget then waits to be cleared, and clearing it pays out
+    /// and gives a quiet moment before the next one. A wave's duration paces its arrivals.
+    Bracketed(BracketedWaves),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct BracketedWaves {
+    pub intermission: f32, // Seconds without spawns after a wave is cleared
+    pub gold_reward: u32,  // For each wave cleared
+}
+
+/// An enemy a wave can spend its budget on
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WaveEnemy {
+    pub enemy: String, // Enemy definition id
+    #[serde(default = "WaveEnemy::default_weight")]
+    pub weight: f32, // Relative chance of being picked within the wave
+}
+
+impl WaveEnemy {
+    fn default_weight() -> f32 {
+        1.0
+    }
+}
+
+/// Where the run is in the stage's waves and how much budget is waiting to be spent
+#[derive(Resource)]
+pub struct WaveDirector {
+    pub waves: Vec<WaveDefinition>,
+    pub number: u32,     // Waves started so far, repeats of the last one included
+    pub elapsed: f32,    // Seconds into the current wave
+    pub points: f32,     // Earned but not yet spent
+    pub spent: u32,      // Over the current wave
+    pub spawn_rate: f32, // Scales how fast points come in
+    pub strategy: DirectorStrategy,
+    next: Option<(String, u32)>, // Picked enemy and its cost, waiting on points
+    earned: f32,                 // Over the current wave, bracketed waves stop at the budget
+    intermission: Option<f32>,   // Seconds left before a bracketed wave starts
+}
+
+impl Default for WaveDirector {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+// Unspent points stop piling up past this, so a full arena doesn't flood once it clears
+const BANKED_POINT_LIMIT: f32 = 12.0;
+const MAX_SPAWNS_PER_FRAME: u32 = 4;
+const SPAWN_DISTANCE: f32 = 400.0;
+
+impl WaveDirector {
+    pub fn new(waves: Vec<WaveDefinition>) -> Self {
+        Self {
+            waves,
+            number: 0,
+            elapsed: 0.0,
+            points: 0.0,
+            spent: 0,
+            spawn_rate: 1.0,
+            strategy: DirectorStrategy::default(),
+            next: None,
+            earned: 0.0,
+            intermission: None,
+        }
+    }
+
+    pub fn with_strategy(mut self, strategy: DirectorStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    pub fn current(&self) -> Option<&WaveDefinition> {
+        let last = self.waves.len().saturating_sub(1);
+        self.waves.get((self.number as usize).min(last))
+    }
+
+    pub fn time_left(&self) -> f32 {
+        self.current()
+            .map_or(0.0, |wave| (wave.duration - self.elapsed).max(0.0))
+    }
+
+    /// Seconds until the next bracketed wave, while between waves
+    pub fn intermission_left(&self) -> Option<f32> {
+        self.intermission
+    }
+
+    /// Earns points for `delta` seconds, returns true when that started a new wave
+    pub fn advance(&mut self, delta: f32) -> bool {
+        if let Some(left) = self.intermission.as_mut() {
+            *left -= delta;
+            if *left > 0.0 {
+                return false;
+            }
+            self.intermission = None;
+            self.next_wave();
+            self.elapsed = 0.0;
+            return true;
+        }
+
+        let Some(wave) = self.current() else {
+            return false;
+        };
+        let duration = wave.duration;
+        let budget = wave.budget as f32;
+        let income = budget / duration * self.spawn_rate * delta;
+        let limit = self.next.as_ref().map_or(BANKED_POINT_LIMIT, |(_, cost)| {
+            BANKED_POINT_LIMIT.max(*cost as f32)
+        });
+
+        let banked = self.points;
+        self.points = (self.points + income).min(limit);
+        self.elapsed += delta;
+
+        if let DirectorStrategy::Bracketed(_) = self.strategy {
+            // Only what actually got banked counts, so a full arena doesn't shrink the wave
+            let room = (budget - self.earned).max(0.0);
+            let gained = (self.points - banked).clamp(0.0, room);
+            self.points = banked + gained;
+            // Lands exactly on the budget, so rounding can't leave a wave never quite spawned
+            self.earned = if gained < room {
+                self.earned + gained
+            } else {
+                budget
+            };
+            return false;
+        }
+        if self.elapsed < duration {
+            return false;
+        }
+
+        self.next_wave();
+        self.elapsed -= duration;
+        true
+    }
+
+    // Leftover points don't carry over, the next wave brings its own mix
+    fn next_wave(&mut self) {
+        self.number += 1;
+        self.points = 0.0;
+        self.spent = 0;
+        self.earned = 0.0;
+        self.next = None;
+    }
+
+    /// Bracketed waves: the whole budget has come in and what's left can't pay for another enemy
+    pub fn fully_spawned(&self) -> bool {
+        let Some(wave) = self.current() else {
+            return false;
+        };
+        // Nothing lined up means no spend was tried since the last spawn, unless it's all gone
+        let left = self.next.as_ref().map_or(1.0, |(_, cost)| *cost as f32);
+        self.earned >= wave.budget as f32 && self.points < left
+    }
+
+    /// Ends a bracketed wave once it's spawned and beaten, returns its settings if it did
+    pub fn clear(&mut self, enemies_left: bool) -> Option<BracketedWaves> {
+        let DirectorStrategy::Bracketed(bracketed) = self.strategy else {
+            return None;
+        };
+        if enemies_left || self.intermission.is_some() || !self.fully_spawned() {
+            return None;
+        }
+
+        self.intermission = Some(bracketed.intermission);
+        Some(bracketed)
+    }
+
+    /// Next enemy the points can pay for, if any. `roll` (0 to 1) picks one by weight
+    /// when nothing is lined up yet; a picked enemy waits until it can be afforded.
+    pub fn spend<'a>(
+        &mut self,
+        definitions: &'a Definitions,
+        roll: f32,
+    ) -> Option<&'a EnemyDefinition> {
+        if self.next.is_none() {
+            self.next = self.pick(definitions, roll);
+        }
+        let (id, cost) = self.next.as_ref()?;
+        if self.points < *cost as f32 {
+            return None;
+        }
+
+        self.points -= *cost as f32;
+        self.spent += cost;
+        let definition = definitions.enemies.get(id);
+        self.next = None;
+        definition
+    }
+
+    fn pick(&self, definitions: &Definitions, roll: f32) -> Option<(String, u32)> {
+        let candidates: Vec<(&WaveEnemy, &EnemyDefinition)> = self
+            .current()?
+            .enemies
+            .iter()
+            .filter(|entry| entry.weight > 0.0)
+            .filter_map(|entry| Some((entry, definitions.enemies.get(&entry.enemy)?)))
+            .collect();
+
+        let total: f32 = candidates.iter().map(|(entry, _)| entry.weight).sum();
+        let mut roll = roll * total;
+        let picked = candidates
+            .iter()
+            .find(|(entry, _)| {
+                let hit = roll < entry.weight;
+                roll -= entry.weight;
+                hit
+            })
+            // Rounding can leave the roll just past the last weight
+            .or(candidates.last())?;
+
+        Some((picked.0.enemy.clone(), picked.1.cost))
+    }
+}
+
+fn advance_waves(time: Res<Time<Virtual>>, mut director: ResMut<WaveDirector>) {
+    if director.advance(time.delta_secs()) {
+        let enemies = director.current().map_or(0, |wave| wave.enemies.len());
+        info!(
+            "Wave {} begins with {} kinds of enemy",
+            director.number + 1,
+            enemies
+        );
+    }
+}
+
+fn spawn_wave_enemies(
+    mut spawner: EnemySpawner,
+    mut director: ResMut<WaveDirector>,
+    definitions: Res<Definitions>,
+    wave_config: Res<WaveConfig>,
+    quality: Res<QualitySettings>,
+    mut caps: ResMut<SpawnCaps>,
+    enemy_query: Query<(), With<Enemy>>,
+    player_handle: Res<PlayerHandle>,
+    player_query: Query<&Transform, With<Player>>,
+    spawn_validator: SpawnValidator,
+    mut game_rng: ResMut<GameRng>,
+) {
+    let Some(player_transform) = player_handle.fetch(&player_query) else {
+        return;
+    };
+    let max_enemies = quality.enemy_cap.map_or(wave_config.max_enemies, |cap| {
+        cap.min(wave_config.max_enemies)
+    });
+    let mut alive = enemy_query.iter().count() as u32;
+
+    for _ in 0..MAX_SPAWNS_PER_FRAME {
+        if alive >= max_enemies {
+            return;
+        }
+        let Some(definition) = director.spend(&definitions, game_rng.gen()) else {
+            return;
+        };
+
+        // Skip this spawn if every spot tried was inside a wall
+        let Some(spawn_position) = spawn_validator.find_position_around(
+            player_transform.translation.truncate(),
+            SPAWN_DISTANCE,
+            definition.radius,
+            game_rng.as_mut(),
+        ) else {
+            continue;
+        };
+
+        let mut enemy = spawner.spawn(
+            definition,
+            spawn_position.extend(player_transform.translation.z),
+        );
+        if game_rng.gen::<f32>() < wave_config.elite_chance && caps.has_room(EnemyArchetype::Elite)
+        {
+            enemy.insert(Elite);
+            caps.record_spawn(EnemyArchetype::Elite);
+        }
+        alive += 1;
+    }
+}
+
+fn clear_bracketed_waves(
+    mut director: ResMut<WaveDirector>,
+    mut milestones: EventWriter<DirectorMilestone>,
+    enemy_query: Query<(), (With<Enemy>, Without<MarkedForDeath>)>,
+    mut player_query: Query<&mut Gold, With<Player>>,
+) {
+    let Some(bracketed) = director.clear(!enemy_query.is_empty()) else {
+        return;
+    };
+
+    info!(
+        "Wave {} cleared, {} gold and {:.0}s until the next one",
+        director.number + 1,
+        bracketed.gold_reward,
+        bracketed.intermission
+    );
+    for mut gold in player_query.iter_mut() {
+        gold.0 += bracketed.gold_reward;
+    }
+    milestones.send(DirectorMilestone::WaveCleared);
+}
@@ -0,0 +1,98 @@
+use crate::menu::WeaponUpgradeConfirmedEvent;
+use crate::resources::GameTextures;
+use crate::weapons::projectile::{spawn_projectile, Homing};
+use crate::weapons::weapon_upgrade::{WeaponBaseStats, WeaponUpgradeChange};
+use crate::weapons::{LiveAttackStats, WeaponMeta, WeaponType};
+use bevy::prelude::*;
+use rand::Rng;
+
+/// Volley of seeking bolts: every projectile it fires homes in on the nearest enemy
+#[derive(Component, Clone)]
+pub struct ArcaneBolt {
+    pub projectiles: u32, // Bolts per volley
+    pub speed: f32,
+}
+
+// Frame in the projectile atlas
+const ARCANE_BOLT_SPRITE: usize = 1;
+
+pub fn spawn_arcane_bolt(commands: &mut Commands, player_entity: Entity, base: &WeaponBaseStats) {
+    info!("Spawning arcane bolt for player: {:?}", player_entity);
+    commands.entity(player_entity).with_children(|parent| {
+        parent.spawn((
+            WeaponMeta {
+                weapon_type: WeaponType::ArcaneBolt,
+                level: 1,
+            },
+            base.cooldown(),
+            base.damage(),
+            // For bolts the area is the projectile's hit radius
+            base.area(),
+            ArcaneBolt {
+                projectiles: 1,
+                speed: 350.0,
+            },
+            // Projectiles pick these up when fired
+            Homing {
+                turn_rate: 4.0,
+                max_speed: 400.0,
+            },
+            LiveAttackStats,
+        ));
+    });
+}
+
+/// Fires one volley plus `extra` bolts, fanned out evenly from a random angle so the bolts
+/// curve in from all sides
+pub fn spawn_arcane_bolt_volley(
+    commands: &mut Commands,
+    textures: &GameTextures,
+    origin: Vec3,
+    damage: i32,
+    radius: f32,
+    bolt: &ArcaneBolt,
+    extra: u32,
+    rng: &mut impl Rng,
+) -> Vec<Entity> {
+    let projectiles = bolt.projectiles + extra;
+    let start_angle = rng.gen::<f32>() * std::f32::consts::TAU;
+    let angle_step = std::f32::consts::TAU / projectiles as f32;
+
+    (0..projectiles)
+        .map(|i| {
+            let direction = Vec2::from_angle(start_angle + angle_step * i as f32);
+            spawn_projectile(
+                commands,
+                textures,
+                ARCANE_BOLT_SPRITE,
+                origin,
+                direction * bolt.speed,
+                damage,
+                radius,
+            )
+        })
+        .collect()
+}
+
+pub fn apply_arcane_bolt_weapon_upgrades(
+    mut upgrade_events: EventReader<WeaponUpgradeConfirmedEvent>,
+    mut weapon_query: Query<(&mut ArcaneBolt, &WeaponMeta)>,
+) {
+    for upgrade_event in upgrade_events.read() {
+        for (mut bolt, meta) in weapon_query.iter_mut() {
+            if meta.weapon_type != upgrade_event.weapon_type {
+                continue;
+            }
+
+            for change in &upgrade_event.upgrade_spec.changes {
+                if let WeaponUpgradeChange::ExtraProjectiles(count) = change {
+                    bolt.projectiles += *count;
+                    info!(
+                        "Arcane Bolt now fires {} bolts at level {}",
+                        bolt.projectiles, meta.level
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use crate::menu::WeaponUpgradeConfirmedEvent;
+use crate::resources::GameTextures;
+use crate::trail::Trail;
+use crate::weapons::projectile::{spawn_projectile, Bounces, Homing};
+use crate::weapons::weapon_upgrade::{WeaponBaseStats, WeaponUpgradeChange, MAX_WEAPON_LEVEL};
+use crate::weapons::{LiveAttackStats, WeaponMeta, WeaponType};
+use bevy::prelude::*;
+use rand::Rng;
+
+/// Evolved Arcane Bolt: every volley is two staggered rings of seeking bolts,
+/// the outer one slower so they reach enemies in waves
+#[derive(Component, Clone)]
+pub struct ArcaneStorm {
+    pub bolts_per_ring: u32,
+    pub speed: f32, // Inner ring, the outer ring flies at `OUTER_RING_SPEED` of this
+}
+
+// Same frame as Arcane Bolt, tinted and trailed so the evolution reads differently on screen
+const ARCANE_STORM_SPRITE: usize = 1;
+const ARCANE_STORM_COLOR: Color = Color::srgb(1.0, 0.55, 1.0);
+const OUTER_RING_SPEED: f32 = 0.6;
+
+pub fn spawn_arcane_storm(commands: &mut Commands, player_entity: Entity, base: &WeaponBaseStats) {
+    info!("Spawning arcane storm for player: {:?}", player_entity);
+    commands.entity(player_entity).with_children(|parent| {
+        parent.spawn((
+            // Evolutions replace a maxed weapon, so they start where it left off
+            WeaponMeta {
+                weapon_type: WeaponType::ArcaneStorm,
+                level: MAX_WEAPON_LEVEL,
+            },
+            base.cooldown(),
+            base.damage(),
+            base.area(),
+            ArcaneStorm {
+                bolts_per_ring: 6,
+                speed: 420.0,
+            },
+            Homing {
+                turn_rate: 8.0,
+                max_speed: 520.0,
+            },
+            Bounces(2),
+            LiveAttackStats,
+        ));
+    });
+}
+
+/// Fires both rings, the outer one rotated half a step so its bolts fill the gaps.
+/// `extra` bolts are added to each ring.
+pub fn spawn_arcane_storm_volley(
+    commands: &mut Commands,
+    textures: &GameTextures,
+    origin: Vec3,
+    damage: i32,
+    radius: f32,
+    storm: &ArcaneStorm,
+    extra: u32,
+    rng: &mut impl Rng,
+) -> Vec<Entity> {
+    let bolts_per_ring = storm.bolts_per_ring + extra;
+    let start_angle = rng.gen::<f32>() * std::f32::consts::TAU;
+    let angle_step = std::f32::consts::TAU / bolts_per_ring as f32;
+
+    let rings = [
+        (start_angle, storm.speed),
+        (
+            start_angle + angle_step * 0.5,
+            storm.speed * OUTER_RING_SPEED,
+        ),
+    ];
+
+    rings
+        .into_iter()
+        .flat_map(|(ring_angle, speed)| {
+            (0..bolts_per_ring).map(move |i| (ring_angle + angle_step * i as f32, speed))
+        })
+        .map(|(angle, speed)| {
+            let bolt = spawn_projectile(
+                commands,
+                textures,
+                ARCANE_STORM_SPRITE,
+                origin,
+                Vec2::from_angle(angle) * speed,
+                damage,
+                radius,
+            );
+            // Swaps in a tinted copy of the sprite spawn_projectile gave it
+            let mut sprite = Sprite::from_atlas_image(
+                textures.projectiles.clone(),
+                TextureAtlas {
+                    layout: textures.projectiles_layout.clone(),
+                    index: ARCANE_STORM_SPRITE,
+                },
+            );
+            sprite.color = ARCANE_STORM_COLOR;
+            commands.entity(bolt).insert((
+                sprite,
+                Trail::new(ARCANE_STORM_COLOR.with_alpha(0.4), radius, 0.2),
+            ));
+            bolt
+        })
+        .collect()
+}
+
+pub fn apply_arcane_storm_weapon_upgrades(
+    mut upgrade_events: EventReader<WeaponUpgradeConfirmedEvent>,
+    mut weapon_query: Query<(&mut ArcaneStorm, &WeaponMeta)>,
+) {
+    for upgrade_event in upgrade_events.read() {
+        for (mut storm, meta) in weapon_query.iter_mut() {
+            if meta.weapon_type != upgrade_event.weapon_type {
+                continue;
+            }
+
+            for change in &upgrade_event.upgrade_spec.changes {
+                if let WeaponUpgradeChange::ExtraProjectiles(count) = change {
+                    storm.bolts_per_ring += *count;
+                    info!(
+                        "Arcane Storm rings now hold {} bolts at level {}",
+                        storm.bolts_per_ring, meta.level
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use crate::death::MarkedForDeath;
+use crate::weapons::{Attack, Lifetime};
+use bevy::prelude::*;
+
+/// Limits how many timed attacks can be alive at once so stacked cooldown reduction
+/// can't flood the world with shapes. Past the cap the oldest attacks expire early.
+#[derive(Resource)]
+pub struct AttackCap {
+    pub max_attacks: usize,
+    pub evicted_total: u64,
+    evicted_since_warning: u64,
+    warning_timer: Timer, // Eviction warnings are batched instead of logged every frame
+}
+
+impl Default for AttackCap {
+    fn default() -> Self {
+        Self {
+            max_attacks: 250,
+            evicted_total: 0,
+            evicted_since_warning: 0,
+            warning_timer: Timer::from_seconds(5.0, TimerMode::Repeating),
+        }
+    }
+}
+
+pub fn enforce_attack_cap(
+    mut commands: Commands,
+    time: Res<Time<Virtual>>,
+    mut cap: ResMut<AttackCap>,
+    attack_query: Query<(Entity, &Lifetime), (With<Attack>, Without<MarkedForDeath>)>,
+) {
+    let count = attack_query.iter().len();
+    if count > cap.max_attacks {
+        let mut attacks: Vec<_> = attack_query
+            .iter()
+            .map(|(entity, lifetime)| (entity, lifetime.timer.elapsed_secs()))
+            .collect();
+        // Longest lived first
+        attacks.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        let excess = count - cap.max_attacks;
+        // Straight to death rather than through the lifetime system, so evicted
+        // attacks don't leave split circles behind and refill the cap
+        for (entity, _) in attacks.into_iter().take(excess) {
+            commands.entity(entity).insert(MarkedForDeath);
+        }
+
+        cap.evicted_total += excess as u64;
+        cap.evicted_since_warning += excess as u64;
+    }
+
+    if cap.warning_timer.tick(time.delta()).just_finished() && cap.evicted_since_warning > 0 {
+        warn!(
+            "Attack cap of {} reached: {} attacks expired early ({} this run)",
+            cap.max_attacks, cap.evicted_since_warning, cap.evicted_total
+        );
+        cap.evicted_since_warning = 0;
+    }
+}
@@ -0,0 +1,189 @@
+use crate::combat::DamageEvent;
+use crate::components::{Enemy, Player};
+use crate::death::MarkedForDeath;
+use crate::game_time::PausableTimer;
+use crate::physics::handle_rapier_context_error;
+use crate::weapons::weapon_upgrade::WeaponBaseStats;
+use crate::weapons::{Area, Attack, Damage, Lifetime, LiveAttackStats, WeaponMeta, WeaponType};
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+use bevy_rapier2d::prelude::*;
+
+/// Channelled beam: locks onto the nearest enemy and burns everything along the line
+#[derive(Component)]
+pub struct Beam {
+    pub range: f32,
+    pub duration: f32,  // How long each channel lasts
+    pub tick_rate: f32, // Seconds between damage ticks while channelling
+}
+
+#[derive(Component)]
+pub struct BeamAttack {
+    pub owner: Entity, // Beams stay anchored to whoever fired them
+    pub target: Option<Entity>,
+    pub direction: Vec2,
+    pub range: f32,
+    pub tick_timer: PausableTimer,
+}
+
+const BEAM_COLOR: Color = Color::srgba(1.0, 0.45, 0.9, 0.85);
+
+pub fn spawn_beam(commands: &mut Commands, player_entity: Entity, base: &WeaponBaseStats) {
+    info!("Spawning beam for player: {:?}", player_entity);
+    commands.entity(player_entity).with_children(|parent| {
+        parent.spawn((
+            WeaponMeta {
+                weapon_type: WeaponType::Beam,
+                level: 1,
+            },
+            base.cooldown(),
+            base.damage(),
+            // For beams the area is the beam's width
+            base.area(),
+            Beam {
+                range: 280.0,
+                duration: 1.5,
+                tick_rate: 0.1,
+            },
+            LiveAttackStats,
+        ));
+    });
+}
+
+pub fn spawn_beam_attack(
+    commands: &mut Commands,
+    owner: Entity,
+    origin: Vec3,
+    damage: i32,
+    width: f32,
+    beam: &Beam,
+) -> Entity {
+    info!("Spawning beam attack at position: {:?}", origin);
+    commands
+        .spawn((
+            Attack,
+            BeamAttack {
+                owner,
+                target: None,
+                direction: Vec2::X,
+                range: beam.range,
+                tick_timer: PausableTimer::from_seconds(beam.tick_rate, TimerMode::Repeating),
+            },
+            Lifetime {
+                timer: PausableTimer::from_seconds(beam.duration, TimerMode::Once),
+            },
+            Damage { amount: damage },
+            Area { radius: width },
+            // A stretched sprite grown out of the owner towards the target
+            Sprite {
+                color: BEAM_COLOR,
+                custom_size: Some(Vec2::new(beam.range, width)),
+                anchor: Anchor::CenterLeft,
+                ..default()
+            },
+            Transform::from_translation(origin.truncate().extend(0.5)),
+            // Nothing to aim at until the first lock-on
+            Visibility::Hidden,
+        ))
+        .id()
+}
+
+/// Keeps beams on their owner and pointed at the nearest live enemy, re-locking when the target dies
+pub fn aim_beam_attacks(
+    mut beam_query: Query<
+        (
+            &mut BeamAttack,
+            &Area,
+            &mut Transform,
+            &mut Sprite,
+            &mut Visibility,
+        ),
+        Without<Enemy>,
+    >,
+    owner_query: Query<&Transform, (With<Player>, Without<BeamAttack>)>,
+    enemy_query: Query<(Entity, &Transform), (With<Enemy>, Without<MarkedForDeath>)>,
+) {
+    for (mut beam, area, mut transform, mut sprite, mut visibility) in beam_query.iter_mut() {
+        let Ok(owner_transform) = owner_query.get(beam.owner) else {
+            continue;
+        };
+        let origin = owner_transform.translation.truncate();
+
+        let target_position = beam
+            .target
+            .and_then(|target| enemy_query.get(target).ok())
+            .map(|(_, target_transform)| target_transform.translation.truncate())
+            .filter(|position| position.distance(origin) <= beam.range);
+
+        let target_position = match target_position {
+            Some(position) => Some(position),
+            None => {
+                let nearest = enemy_query
+                    .iter()
+                    .map(|(entity, enemy_transform)| {
+                        let position = enemy_transform.translation.truncate();
+                        (entity, position, position.distance(origin))
+                    })
+                    .filter(|(_, _, distance)| *distance <= beam.range)
+                    .min_by(|(_, _, a), (_, _, b)| a.total_cmp(b));
+
+                beam.target = nearest.map(|(entity, _, _)| entity);
+                nearest.map(|(_, position, _)| position)
+            }
+        };
+
+        let Some(target_position) = target_position else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+
+        beam.direction = (target_position - origin).normalize_or(beam.direction);
+        transform.translation = origin.extend(transform.translation.z);
+        transform.rotation = Quat::from_rotation_z(beam.direction.to_angle());
+        sprite.custom_size = Some(Vec2::new(beam.range, area.radius));
+        *visibility = Visibility::Inherited;
+    }
+}
+
+/// Damages every enemy overlapping the beam's line each tick
+pub fn beam_damage_system(
+    time: Res<Time<Virtual>>,
+    mut beam_query: Query<(Entity, &mut BeamAttack, &Damage, &Area, &Transform)>,
+    context_query: Query<&RapierContext>,
+    enemy_query: Query<Entity, (With<Enemy>, Without<MarkedForDeath>)>,
+    mut damage_events: EventWriter<DamageEvent>,
+) {
+    let rapier_context = context_query
+        .get_single()
+        .unwrap_or_else(|e| handle_rapier_context_error(e));
+
+    for (beam_entity, mut beam, damage, area, transform) in beam_query.iter_mut() {
+        if !beam.tick_timer.tick(&time).just_finished() || beam.target.is_none() {
+            continue;
+        }
+
+        // Box covering the whole beam, centered halfway along it
+        let half_length = beam.range * 0.5;
+        let center = transform.translation.truncate() + beam.direction * half_length;
+        let shape = Collider::cuboid(half_length, area.radius * 0.5);
+        let filter =
+            QueryFilter::new().groups(CollisionGroups::new(Group::GROUP_3, Group::GROUP_2));
+
+        rapier_context.intersections_with_shape(
+            center,
+            beam.direction.to_angle(),
+            &shape,
+            filter,
+            |entity| {
+                if enemy_query.contains(entity) {
+                    damage_events.send(DamageEvent {
+                        target: entity,
+                        amount: damage.amount,
+                        source: Some(beam_entity),
+                    });
+                }
+                true // Keep going, the beam pierces
+            },
+        );
+    }
+}
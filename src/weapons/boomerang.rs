@@ -0,0 +1,182 @@
+use crate::combat::DamageEvent;
+use crate::components::Enemy;
+use crate::death::MarkedForDeath;
+use crate::game_time::PausableTimer;
+use crate::menu::WeaponUpgradeConfirmedEvent;
+use crate::physics::handle_rapier_context_error;
+use crate::weapons::weapon_upgrade::{WeaponBaseStats, WeaponUpgradeChange};
+use crate::weapons::{
+    Area, Attack, Damage, Lifetime, ReturnPhase, WeaponMeta, WeaponMovement, WeaponType,
+};
+use bevy::prelude::*;
+use bevy_prototype_lyon::draw::Fill;
+use bevy_prototype_lyon::entity::ShapeBundle;
+use bevy_prototype_lyon::geometry::GeometryBuilder;
+use bevy_prototype_lyon::shapes;
+use bevy_rapier2d::prelude::*;
+
+/// Thrown blades that fly out in the facing direction and come back to be caught
+#[derive(Component, Clone)]
+pub struct Boomerang {
+    pub count: u32, // Blades per throw
+    pub range: f32,
+    pub speed: f32,
+}
+
+/// Remembers who a blade has hit on each leg, so every enemy can be hit once going out
+/// and once more on the way back
+#[derive(Component, Default)]
+pub struct BoomerangAttack {
+    pub hit_outbound: Vec<Entity>,
+    pub hit_returning: Vec<Entity>,
+}
+
+const BOOMERANG_COLOR: Color = Color::srgba(1.0, 0.75, 0.3, 0.9);
+// Angle between blades when throwing more than one
+const SPREAD_ANGLE: f32 = 0.35;
+// Blades that never make it back (e.g. the player outran them) are cleaned up after this
+const MAX_FLIGHT_TIME: f32 = 6.0;
+
+pub fn spawn_boomerang(commands: &mut Commands, player_entity: Entity, base: &WeaponBaseStats) {
+    info!("Spawning boomerang for player: {:?}", player_entity);
+    commands.entity(player_entity).with_children(|parent| {
+        parent.spawn((
+            WeaponMeta {
+                weapon_type: WeaponType::Boomerang,
+                level: 1,
+            },
+            base.cooldown(),
+            base.damage(),
+            // For boomerangs the area is the blade's hit radius
+            base.area(),
+            Boomerang {
+                count: 1,
+                range: 260.0,
+                speed: 420.0,
+            },
+        ));
+    });
+}
+
+/// Throws every blade plus `extra` more, fanned out around the facing direction
+pub fn spawn_boomerang_throw(
+    commands: &mut Commands,
+    origin: Vec3,
+    direction: Vec2,
+    damage: i32,
+    radius: f32,
+    boomerang: &Boomerang,
+    extra: u32,
+) -> Vec<Entity> {
+    let count = boomerang.count + extra;
+    let first_angle = direction.to_angle() - SPREAD_ANGLE * (count - 1) as f32 * 0.5;
+
+    (0..count)
+        .map(|i| {
+            let direction = Vec2::from_angle(first_angle + SPREAD_ANGLE * i as f32);
+            commands
+                .spawn((
+                    Attack,
+                    BoomerangAttack::default(),
+                    WeaponMovement::ReturnToPlayer {
+                        phase: ReturnPhase::Outbound,
+                        direction,
+                        speed: boomerang.speed,
+                        range: boomerang.range,
+                        traveled: 0.0,
+                    },
+                    Lifetime {
+                        timer: PausableTimer::from_seconds(MAX_FLIGHT_TIME, TimerMode::Once),
+                    },
+                    Damage { amount: damage },
+                    Area { radius },
+                    ShapeBundle {
+                        path: GeometryBuilder::build_as(&shapes::RegularPolygon {
+                            sides: 3,
+                            feature: shapes::RegularPolygonFeature::Radius(radius),
+                            ..default()
+                        }),
+                        transform: Transform::from_translation(origin + Vec3::Z * 0.5),
+                        ..default()
+                    },
+                    Fill::color(BOOMERANG_COLOR),
+                    Sensor,
+                    Collider::ball(radius),
+                    ActiveEvents::COLLISION_EVENTS,
+                    CollisionGroups::new(Group::GROUP_3, Group::GROUP_2),
+                ))
+                .id()
+        })
+        .collect()
+}
+
+/// Damages enemies a blade passes through, once per leg of its flight
+pub fn boomerang_hit_system(
+    mut blade_query: Query<
+        (Entity, &Damage, &WeaponMovement, &mut BoomerangAttack),
+        Without<MarkedForDeath>,
+    >,
+    context_query: Query<&RapierContext>,
+    enemy_query: Query<Entity, (With<Enemy>, Without<MarkedForDeath>)>,
+    mut damage_events: EventWriter<DamageEvent>,
+) {
+    let rapier_context = context_query
+        .get_single()
+        .unwrap_or_else(|e| handle_rapier_context_error(e));
+
+    for (blade_entity, damage, movement, mut blade) in blade_query.iter_mut() {
+        let hit = match movement {
+            WeaponMovement::ReturnToPlayer {
+                phase: ReturnPhase::Returning,
+                ..
+            } => &mut blade.hit_returning,
+            _ => &mut blade.hit_outbound,
+        };
+
+        for (collider1, collider2, intersecting) in
+            rapier_context.intersection_pairs_with(blade_entity)
+        {
+            if !intersecting {
+                continue;
+            }
+
+            let enemy_entity = if collider1 == blade_entity {
+                collider2
+            } else {
+                collider1
+            };
+
+            if enemy_query.contains(enemy_entity) && !hit.contains(&enemy_entity) {
+                hit.push(enemy_entity);
+                damage_events.send(DamageEvent {
+                    target: enemy_entity,
+                    amount: damage.amount,
+                    source: Some(blade_entity),
+                });
+            }
+        }
+    }
+}
+
+pub fn apply_boomerang_weapon_upgrades(
+    mut upgrade_events: EventReader<WeaponUpgradeConfirmedEvent>,
+    mut weapon_query: Query<(&mut Boomerang, &WeaponMeta)>,
+) {
+    for upgrade_event in upgrade_events.read() {
+        for (mut boomerang, meta) in weapon_query.iter_mut() {
+            if meta.weapon_type != upgrade_event.weapon_type {
+                continue;
+            }
+
+            for change in &upgrade_event.upgrade_spec.changes {
+                if let WeaponUpgradeChange::ExtraProjectiles(count) = change {
+                    boomerang.count += *count;
+                    info!(
+                        "Boomerang now throws {} blades at level {}",
+                        boomerang.count, meta.level
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use crate::performance::EffectParticle;
+use bevy::prelude::*;
+
+/// Brief flash on an enemy caught between an Invocation and a Banishment circle
+#[derive(Component)]
+pub struct ComboSpark {
+    pub timer: Timer,
+}
+
+const SPARK_COLOR: Color = Color::srgb(1.0, 0.85, 0.4);
+const SPARK_SIZE: f32 = 14.0;
+const SPARK_LIFETIME: f32 = 0.25;
+const SPARK_GROWTH: f32 = 1.5; // Extra scale reached by the end of its lifetime
+
+pub fn spawn_combo_spark(commands: &mut Commands, position: Vec3) {
+    commands.spawn((
+        ComboSpark {
+            timer: Timer::from_seconds(SPARK_LIFETIME, TimerMode::Once),
+        },
+        EffectParticle,
+        Sprite {
+            color: SPARK_COLOR,
+            custom_size: Some(Vec2::splat(SPARK_SIZE)),
+            ..default()
+        },
+        // Above the enemy it's drawn on
+        Transform::from_translation(position.truncate().extend(position.z + 1.0))
+            .with_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_4)),
+    ));
+}
+
+/// Grows and fades sparks out, despawning them once they're gone
+pub fn fade_combo_sparks(
+    mut commands: Commands,
+    time: Res<Time<Virtual>>,
+    mut spark_query: Query<(Entity, &mut ComboSpark, &mut Sprite, &mut Transform)>,
+) {
+    for (entity, mut spark, mut sprite, mut transform) in spark_query.iter_mut() {
+        spark.timer.tick(time.delta());
+        if spark.timer.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let progress = spark.timer.fraction();
+        sprite.color = SPARK_COLOR.with_alpha(1.0 - progress);
+        transform.scale = Vec3::splat(1.0 + progress * SPARK_GROWTH);
+    }
+}
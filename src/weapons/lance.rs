@@ -0,0 +1,178 @@
+use crate::combat::DamageEvent;
+use crate::components::Enemy;
+use crate::death::MarkedForDeath;
+use crate::game_time::PausableTimer;
+use crate::menu::WeaponUpgradeConfirmedEvent;
+use crate::physics::handle_rapier_context_error;
+use crate::weapons::weapon_upgrade::{WeaponBaseStats, WeaponUpgradeChange};
+use crate::weapons::{
+    Area, Attack, Damage, Lifetime, LiveAttackStats, PiercingAttack, WeaponMeta, WeaponType,
+};
+use bevy::prelude::*;
+use bevy_prototype_lyon::draw::Fill;
+use bevy_prototype_lyon::entity::ShapeBundle;
+use bevy_prototype_lyon::geometry::GeometryBuilder;
+use bevy_prototype_lyon::shapes;
+use bevy_rapier2d::prelude::*;
+
+/// Piercing thrust in the direction the player is facing.
+/// Skewers everything along its length, up to its pierce count.
+#[derive(Component, Clone)]
+pub struct Lance {
+    pub length: f32,
+    pub pierce_count: u32, // Enemies it passes through after the first
+    pub thrust_duration: f32,
+}
+
+#[derive(Component)]
+pub struct LanceAttack;
+
+const LANCE_COLOR: Color = Color::srgba(0.75, 0.9, 1.0, 0.85);
+// Angle between thrusts when a weapon fires more than one
+const SPREAD_ANGLE: f32 = 0.3;
+
+pub fn spawn_lance(commands: &mut Commands, player_entity: Entity, base: &WeaponBaseStats) {
+    info!("Spawning lance for player: {:?}", player_entity);
+    commands.entity(player_entity).with_children(|parent| {
+        parent.spawn((
+            WeaponMeta {
+                weapon_type: WeaponType::Lance,
+                level: 1,
+            },
+            base.cooldown(),
+            base.damage(),
+            // For lances the area is the thrust's width
+            base.area(),
+            Lance {
+                length: 220.0,
+                pierce_count: 2,
+                thrust_duration: 0.2,
+            },
+            // Thrusts look up the weapon's knockback through their source
+            LiveAttackStats,
+        ));
+    });
+}
+
+pub fn spawn_lance_attack(
+    commands: &mut Commands,
+    origin: Vec3,
+    direction: Vec2,
+    damage: i32,
+    width: f32,
+    lance: &Lance,
+) -> Entity {
+    // Centered halfway along the thrust so the collider and the shape line up
+    let half_length = lance.length * 0.5;
+    let center = origin.truncate() + direction * half_length;
+
+    commands
+        .spawn((
+            Attack,
+            LanceAttack,
+            PiercingAttack::new(lance.pierce_count),
+            Lifetime {
+                timer: PausableTimer::from_seconds(lance.thrust_duration, TimerMode::Once),
+            },
+            Damage { amount: damage },
+            Area { radius: width },
+            ShapeBundle {
+                path: GeometryBuilder::build_as(&shapes::Rectangle {
+                    extents: Vec2::new(lance.length, width),
+                    origin: shapes::RectangleOrigin::Center,
+                    radii: None,
+                }),
+                transform: Transform::from_translation(center.extend(origin.z + 0.5))
+                    .with_rotation(Quat::from_rotation_z(direction.to_angle())),
+                ..default()
+            },
+            Fill::color(LANCE_COLOR),
+            Sensor,
+            Collider::cuboid(half_length, width * 0.5),
+            ActiveEvents::COLLISION_EVENTS,
+            CollisionGroups::new(Group::GROUP_3, Group::GROUP_2),
+        ))
+        .id()
+}
+
+/// Thrusts once plus `extra` more, fanned out around the facing direction
+pub fn spawn_lance_thrusts(
+    commands: &mut Commands,
+    origin: Vec3,
+    direction: Vec2,
+    damage: i32,
+    width: f32,
+    lance: &Lance,
+    extra: u32,
+) -> Vec<Entity> {
+    let first_angle = direction.to_angle() - SPREAD_ANGLE * extra as f32 * 0.5;
+
+    (0..=extra)
+        .map(|i| {
+            let direction = Vec2::from_angle(first_angle + SPREAD_ANGLE * i as f32);
+            spawn_lance_attack(commands, origin, direction, damage, width, lance)
+        })
+        .collect()
+}
+
+/// Damages each enemy the thrust overlaps once, until it runs out of pierce
+pub fn lance_damage_system(
+    mut lance_query: Query<
+        (Entity, &Damage, &mut PiercingAttack),
+        (With<LanceAttack>, Without<MarkedForDeath>),
+    >,
+    context_query: Query<&RapierContext>,
+    enemy_query: Query<Entity, (With<Enemy>, Without<MarkedForDeath>)>,
+    mut damage_events: EventWriter<DamageEvent>,
+) {
+    let rapier_context = context_query
+        .get_single()
+        .unwrap_or_else(|e| handle_rapier_context_error(e));
+
+    for (lance_entity, damage, mut piercing) in lance_query.iter_mut() {
+        for (collider1, collider2, intersecting) in
+            rapier_context.intersection_pairs_with(lance_entity)
+        {
+            if !intersecting {
+                continue;
+            }
+
+            let enemy_entity = if collider1 == lance_entity {
+                collider2
+            } else {
+                collider1
+            };
+
+            if enemy_query.contains(enemy_entity) && piercing.try_hit(enemy_entity) {
+                damage_events.send(DamageEvent {
+                    target: enemy_entity,
+                    amount: damage.amount,
+                    source: Some(lance_entity),
+                });
+            }
+        }
+    }
+}
+
+pub fn apply_lance_weapon_upgrades(
+    mut upgrade_events: EventReader<WeaponUpgradeConfirmedEvent>,
+    mut weapon_query: Query<(&mut Lance, &WeaponMeta)>,
+) {
+    for upgrade_event in upgrade_events.read() {
+        for (mut lance, meta) in weapon_query.iter_mut() {
+            if meta.weapon_type != upgrade_event.weapon_type {
+                continue;
+            }
+
+            for change in &upgrade_event.upgrade_spec.changes {
+                if let WeaponUpgradeChange::Pierce(count) = change {
+                    lance.pierce_count += *count;
+                    info!(
+                        "Lance now pierces {} enemies at level {}",
+                        lance.pierce_count, meta.level
+                    );
+                }
+            }
+        }
+    }
+}
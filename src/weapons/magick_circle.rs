@@ -1,28 +1,84 @@
+use crate::game_time::PausableTimer;
 use crate::menu::WeaponUpgradeConfirmedEvent;
-use crate::weapons::weapon_upgrade::WeaponUpgradeChange;
+use crate::trail::Trail;
+use crate::weapons::manifestation::ManifestationSpawner;
+use crate::weapons::weapon_upgrade::{WeaponBaseStats, WeaponUpgradeChange};
 use crate::weapons::{
-    Area, AreaEffect, Attack, Damage, Lifetime, Orbits, Rotates, Sigil, WeaponArea,
-    WeaponCooldown, WeaponDamage, WeaponMeta, WeaponMovement, WeaponType,
+    Area, AreaEffect, Attack, Damage, Lifetime, LiveAttackStats, Orbits, Rotates, Sigil,
+    SourceWeapon, WeaponDuration, WeaponMeta, WeaponMovement, WeaponType,
 };
-use bevy::color::Color;
 use bevy::log::info;
 use bevy::math::{Vec2, Vec3};
 use bevy::prelude::*;
 use bevy_prototype_lyon::draw::Fill;
-use bevy_prototype_lyon::entity::ShapeBundle;
+use bevy_prototype_lyon::entity::{Path, ShapeBundle};
 use bevy_prototype_lyon::geometry::GeometryBuilder;
-use bevy_prototype_lyon::prelude::RectangleOrigin;
 use bevy_prototype_lyon::shapes;
 use bevy_rapier2d::geometry::{ActiveEvents, Collider, CollisionGroups, Group, Sensor};
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumIter;
 
 /// Specialized MagickCircle components
-#[derive(Component)]
+#[derive(Component, Clone)]
 pub struct MagickCircle {
     pub patterns: Vec<PatternType>,
-    pub num_sigils: u32,
+    pub sigils: SigilOrbit,
+    pub split_on_expire: Option<SplitOnExpire>,
 }
 
-#[derive(Component, Debug, Copy, Clone, Eq, PartialEq)]
+/// How the sigils of each circle orbit it. Limit breaks can turn them around, add an
+/// inner ring that turns the other way, or stretch the orbit into an ellipse.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SigilOrbit {
+    pub count: u32,
+    pub counter_count: u32, // Sigils in the inner ring, none until a limit break adds it
+    pub reversed: bool,
+    pub axes: Vec2, // Stretch of the orbit along x and y, (1, 1) is a circle
+}
+
+impl SigilOrbit {
+    pub fn ring(count: u32) -> Self {
+        Self {
+            count,
+            counter_count: 0,
+            reversed: false,
+            axes: Vec2::ONE,
+        }
+    }
+
+    /// Where a sigil of either ring orbits, starting at `angle`
+    pub fn orbits(&self, radius: f32, spin: f32, angle: f32, counter: bool) -> Orbits {
+        Orbits {
+            radius,
+            axes: self.ring_axes(counter),
+            speed: spin,
+            current_angle: angle,
+            reversed: self.reversed != counter,
+        }
+    }
+
+    pub fn ring_axes(&self, counter: bool) -> Vec2 {
+        if counter {
+            self.axes * COUNTER_RING_SCALE
+        } else {
+            self.axes
+        }
+    }
+}
+
+// Inner ring sits this far out compared to the outer one
+const COUNTER_RING_SCALE: f32 = 0.6;
+// Stacked stretches stop here, past it sigils spend most of their time off the circle
+const MAX_ORBIT_STRETCH: f32 = 2.5;
+
+/// Circle breaks into `count` smaller circles when its lifetime runs out
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct SplitOnExpire {
+    pub count: u32,
+    pub damage_factor: f32, // Applied to both damage and radius of the children
+}
+
+#[derive(Component, Debug, Copy, Clone, Eq, PartialEq, Hash, EnumIter, Serialize, Deserialize)]
 pub enum PatternType {
     Protection,    // Basic defensive circle
     Binding,       // Slows/holds enemies
@@ -43,8 +99,10 @@ impl std::fmt::Display for PatternType {
     }
 }
 
+const DEFAULT_CIRCLE_DURATION: f32 = 3.0;
+
 /// Spawns a magick circle weapon with default configuration
-pub fn spawn_magick_circle(commands: &mut Commands, player_entity: Entity) {
+pub fn spawn_magick_circle(commands: &mut Commands, player_entity: Entity, base: &WeaponBaseStats) {
     info!("Spawning magick circle for player: {:?}", player_entity);
     commands.entity(player_entity).with_children(|parent| {
         // Spawn the base weapon entity
@@ -54,23 +112,19 @@ pub fn spawn_magick_circle(commands: &mut Commands, player_entity: Entity) {
                 weapon_type: WeaponType::MagickCircle,
                 level: 1,
             },
-            WeaponCooldown {
-                timer: Timer::from_seconds(3.5, TimerMode::Repeating),
-                base_duration: 3.5,
-                cooldown_bonus: 0,
-            },
-            WeaponDamage {
-                base_amount: 10,
-                damage_bonus: 0,
-            },
-            WeaponArea {
-                base_radius: 64.0,
-                area_bonus: 0,
-            },
+            base.cooldown(),
+            base.damage(),
+            base.area(),
+            // Circles always expire, so they need a lifetime even if none is defined
+            base.duration().unwrap_or(WeaponDuration {
+                base_duration: DEFAULT_CIRCLE_DURATION,
+                duration_bonus: 0,
+            }),
             // MagickCircle specific components
             MagickCircle {
                 patterns: vec![PatternType::Banishment],
-                num_sigils: 4,
+                sigils: SigilOrbit::ring(4),
+                split_on_expire: None,
             },
             // Optional modifiers
             AreaEffect {
@@ -78,6 +132,8 @@ pub fn spawn_magick_circle(commands: &mut Commands, player_entity: Entity) {
                 tick_rate: 0.5,
                 last_tick: 0.0,
             },
+            // Circles on the field grow and hit harder as soon as an upgrade lands
+            LiveAttackStats,
             // Could add other modifiers like PiercingAttack or Knockback
             // based on configuration
         ));
@@ -90,8 +146,9 @@ pub fn spawn_magick_circle_attack(
     center_pos: Vec3,
     damage: i32,
     radius: f32,
+    duration: f32,
     pattern_type: PatternType,
-    num_sigils: u32,
+    sigils: SigilOrbit,
     offset_angle: Option<f32>,
 ) -> Entity {
     // Only calculate offset if angle is provided
@@ -108,15 +165,17 @@ pub fn spawn_magick_circle_attack(
     };
 
     info!("Spawning attack at position: {:?}", spawn_pos);
+    let style = pattern_type.style();
+
     // First spawn the attack entity
     let attack_entity = commands
         .spawn((
             Attack,
             Lifetime {
-                timer: Timer::from_seconds(3.0, TimerMode::Once),
+                timer: PausableTimer::from_seconds(duration, TimerMode::Once),
             },
             Rotates {
-                speed: 1.0,
+                speed: style.spin,
                 current_angle: 0.0,
             },
             Damage { amount: damage },
@@ -136,7 +195,7 @@ pub fn spawn_magick_circle_attack(
                 transform: Transform::from_translation(spawn_pos),
                 ..default()
             },
-            Fill::color(Color::srgba(0.5, 0.5, 1.0, 0.3)),
+            Fill::color(style.fill),
             Sensor,
             Collider::ball(radius),
             ActiveEvents::COLLISION_EVENTS,
@@ -146,49 +205,146 @@ pub fn spawn_magick_circle_attack(
         ))
         .id();
 
+    if pattern_type == PatternType::Manifestation {
+        commands
+            .entity(attack_entity)
+            .insert(ManifestationSpawner::default());
+    }
+
     // Then spawn sigils as children of the attack
+    spawn_sigils(
+        commands,
+        attack_entity,
+        radius,
+        pattern_type,
+        &sigils,
+        false,
+    );
+    if sigils.counter_count > 0 {
+        spawn_sigils(commands, attack_entity, radius, pattern_type, &sigils, true);
+    }
+
+    attack_entity
+}
+
+/// Spawns one ring of sigils around a circle, the inner ring when `counter` is set
+fn spawn_sigils(
+    commands: &mut Commands,
+    attack_entity: Entity,
+    radius: f32,
+    pattern_type: PatternType,
+    sigils: &SigilOrbit,
+    counter: bool,
+) {
+    let style = pattern_type.style();
+    let count = if counter {
+        sigils.counter_count
+    } else {
+        sigils.count
+    };
     let sigil_size = radius * 0.25;
-    for i in 0..num_sigils {
+
+    for i in 0..count {
+        let angle = (i as f32) * std::f32::consts::TAU / count as f32;
         let sigil_entity = commands
             .spawn((
                 Sigil {
                     index: i,
                     base_size: sigil_size,
+                    counter,
                 },
-                Orbits {
-                    radius,
-                    speed: 1.0,
-                    current_angle: (i as f32) * std::f32::consts::TAU / num_sigils as f32,
-                },
+                sigils.orbits(radius, style.spin, angle, counter),
                 ShapeBundle {
-                    path: GeometryBuilder::new()
-                        .add(&shapes::Rectangle {
-                            extents: Vec2::splat(sigil_size),
-                            origin: RectangleOrigin::Center,
-                            ..default()
-                        })
-                        .build(),
+                    path: style.sigil_shape.build(sigil_size),
                     transform: Transform::default(),
                     ..default()
                 },
-                Fill::color(Color::srgba(0.7, 0.7, 1.0, 0.8)),
+                Fill::color(style.sigil_color),
+                Trail::new(style.sigil_color.with_alpha(0.4), sigil_size * 0.5, 0.25),
             ))
             .id();
 
         commands.entity(attack_entity).add_child(sigil_entity);
     }
+}
 
-    attack_entity
+// Keeps the circle outline, sensor and sigil orbit in step with a live `Area`
+pub fn resize_magick_circle_attacks(
+    mut attack_query: Query<
+        (Ref<Area>, &mut Collider, &mut Path, Option<&Children>),
+        (With<Attack>, With<PatternType>),
+    >,
+    mut sigil_query: Query<&mut Orbits, With<Sigil>>,
+) {
+    for (area, mut collider, mut path, children) in attack_query.iter_mut() {
+        // Spawning already built everything at the right size
+        if !area.is_changed() || area.is_added() {
+            continue;
+        }
+
+        *collider = Collider::ball(area.radius);
+        *path = GeometryBuilder::build_as(&shapes::Circle {
+            radius: area.radius,
+            center: Vec2::ZERO,
+        });
+
+        for &child in children.into_iter().flatten() {
+            if let Ok(mut orbits) = sigil_query.get_mut(child) {
+                orbits.radius = area.radius;
+            }
+        }
+    }
+}
+
+/// Spawns the follow-up circles of an expired circle, evenly spaced around where it was.
+/// Children don't split again and keep no link to the weapon, their stats are final.
+/// They last as long as the circle they came from.
+pub fn spawn_split_circles(
+    commands: &mut Commands,
+    split: &SplitOnExpire,
+    center_pos: Vec3,
+    damage: i32,
+    radius: f32,
+    duration: f32,
+    pattern_type: PatternType,
+) -> Vec<Entity> {
+    let child_damage = ((damage as f32 * split.damage_factor).floor() as i32).max(1);
+    let child_radius = radius * split.damage_factor;
+    let angle_step = std::f32::consts::TAU / split.count as f32;
+
+    info!(
+        "Splitting circle into {} (damage {}, radius {})",
+        split.count, child_damage, child_radius
+    );
+
+    (0..split.count)
+        .map(|i| {
+            spawn_magick_circle_attack(
+                commands,
+                center_pos,
+                child_damage,
+                child_radius,
+                duration,
+                pattern_type,
+                SigilOrbit::ring(2),
+                Some(angle_step * i as f32),
+            )
+        })
+        .collect()
 }
 
 pub fn apply_magick_circle_weapon_upgrades(
+    mut commands: Commands,
     mut upgrade_events: EventReader<WeaponUpgradeConfirmedEvent>,
-    mut weapon_query: Query<(&mut MagickCircle, &WeaponMeta)>,
+    mut weapon_query: Query<(Entity, &mut MagickCircle, &WeaponMeta)>,
+    attack_query: Query<(Entity, &SourceWeapon, &Area, &PatternType, &Children), With<Attack>>,
+    mut sigil_query: Query<(Entity, &Sigil, &mut Orbits)>,
 ) {
     for upgrade_event in upgrade_events.read() {
         // We already have the final `upgrade_spec` in `upgrade_event`
-        for (mut circle, meta) in weapon_query.iter_mut() {
+        for (weapon_entity, mut circle, meta) in weapon_query.iter_mut() {
             if meta.weapon_type == upgrade_event.weapon_type {
+                let previous_sigils = circle.sigils;
                 for change in &upgrade_event.upgrade_spec.changes {
                     match &change {
                         WeaponUpgradeChange::AddCircle { pattern } => {
@@ -198,10 +354,88 @@ pub fn apply_magick_circle_weapon_upgrades(
                             );
                             circle.patterns.push(*pattern);
                         }
+                        WeaponUpgradeChange::SplitOnExpire { count } => {
+                            // Repeat picks add more children
+                            let split = circle.split_on_expire.get_or_insert(SplitOnExpire {
+                                count: 0,
+                                damage_factor: 0.5,
+                            });
+                            split.count += *count;
+                            info!(
+                                "Magick Circles now split into {} at level {}",
+                                split.count, meta.level
+                            );
+                        }
+                        WeaponUpgradeChange::ReverseOrbit => {
+                            circle.sigils.reversed = !circle.sigils.reversed;
+                            info!("Sigils now orbit the other way at level {}", meta.level);
+                        }
+                        WeaponUpgradeChange::CounterRing { sigils } => {
+                            // Repeat picks fill out the inner ring
+                            circle.sigils.counter_count += *sigils;
+                            info!(
+                                "Inner sigil ring now has {} sigils at level {}",
+                                circle.sigils.counter_count, meta.level
+                            );
+                        }
+                        WeaponUpgradeChange::EllipticalOrbit { stretch } => {
+                            let axes = &mut circle.sigils.axes;
+                            axes.x = (axes.x * stretch).min(MAX_ORBIT_STRETCH);
+                            info!(
+                                "Sigil orbits stretched to {:?} at level {}",
+                                axes, meta.level
+                            );
+                        }
                         _ => (),
                     }
                 }
+
+                if circle.sigils != previous_sigils {
+                    reshape_live_sigils(
+                        &mut commands,
+                        weapon_entity,
+                        &circle.sigils,
+                        previous_sigils.counter_count,
+                        &attack_query,
+                        &mut sigil_query,
+                    );
+                }
             }
         }
     }
 }
+
+/// Brings the sigils of circles already on the field in line with the weapon's orbit.
+/// A grown inner ring is rebuilt from scratch so its sigils stay evenly spaced.
+fn reshape_live_sigils(
+    commands: &mut Commands,
+    weapon_entity: Entity,
+    sigils: &SigilOrbit,
+    previous_counter_count: u32,
+    attack_query: &Query<(Entity, &SourceWeapon, &Area, &PatternType, &Children), With<Attack>>,
+    sigil_query: &mut Query<(Entity, &Sigil, &mut Orbits)>,
+) {
+    let ring_grew = sigils.counter_count != previous_counter_count;
+
+    for (attack_entity, source, area, pattern, children) in attack_query.iter() {
+        if source.0 != weapon_entity {
+            continue;
+        }
+
+        for &child in children {
+            let Ok((sigil_entity, sigil, mut orbits)) = sigil_query.get_mut(child) else {
+                continue;
+            };
+            if sigil.counter && ring_grew {
+                commands.entity(sigil_entity).despawn_recursive();
+                continue;
+            }
+            orbits.axes = sigils.ring_axes(sigil.counter);
+            orbits.reversed = sigils.reversed != sigil.counter;
+        }
+
+        if ring_grew {
+            spawn_sigils(commands, attack_entity, area.radius, *pattern, sigils, true);
+        }
+    }
+}
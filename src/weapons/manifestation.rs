@@ -0,0 +1,154 @@
+use crate::combat::DamageEvent;
+use crate::components::Enemy;
+use crate::death::MarkedForDeath;
+use crate::game_time::PausableTimer;
+use crate::physics::handle_rapier_context_error;
+use crate::resources::GameRng;
+use crate::weapons::magick_circle::PatternType;
+use crate::weapons::{Area, Attack, Damage, FiredBy, Lifetime};
+use bevy::prelude::*;
+use bevy_prototype_lyon::draw::Fill;
+use bevy_prototype_lyon::entity::ShapeBundle;
+use bevy_prototype_lyon::geometry::GeometryBuilder;
+use bevy_prototype_lyon::shapes;
+use bevy_rapier2d::prelude::*;
+use rand::Rng;
+
+/// Put on Manifestation circles: while the circle lives it keeps leaving damage zones behind
+#[derive(Component)]
+pub struct ManifestationSpawner {
+    pub timer: PausableTimer,
+}
+
+impl Default for ManifestationSpawner {
+    fn default() -> Self {
+        Self {
+            timer: PausableTimer::from_seconds(0.6, TimerMode::Repeating),
+        }
+    }
+}
+
+/// Small lingering patch that hurts everything standing in it
+#[derive(Component)]
+pub struct ManifestationZone {
+    pub tick_timer: PausableTimer,
+}
+
+// Zones are a fraction of their circle in both size and per-tick damage
+const ZONE_SCALE: f32 = 0.3;
+const ZONE_LIFETIME: f32 = 2.0;
+const ZONE_TICK_RATE: f32 = 0.5;
+
+pub fn spawn_manifestation_zone(
+    commands: &mut Commands,
+    position: Vec3,
+    damage: i32,
+    radius: f32,
+) -> Entity {
+    commands
+        .spawn((
+            Attack,
+            ManifestationZone {
+                tick_timer: PausableTimer::from_seconds(ZONE_TICK_RATE, TimerMode::Repeating),
+            },
+            Lifetime {
+                timer: PausableTimer::from_seconds(ZONE_LIFETIME, TimerMode::Once),
+            },
+            Damage { amount: damage },
+            Area { radius },
+            ShapeBundle {
+                path: GeometryBuilder::build_as(&shapes::Circle {
+                    radius,
+                    center: Vec2::ZERO,
+                }),
+                // Under the circle that made it
+                transform: Transform::from_translation(position.truncate().extend(-0.1)),
+                ..default()
+            },
+            Fill::color(Color::srgba(0.8, 0.4, 1.0, 0.35)),
+            Sensor,
+            Collider::ball(radius),
+            ActiveEvents::COLLISION_EVENTS,
+            CollisionGroups::new(Group::GROUP_3, Group::GROUP_2),
+        ))
+        .id()
+}
+
+/// Drops zones at random points inside each Manifestation circle
+pub fn spawn_manifestation_zones(
+    mut commands: Commands,
+    time: Res<Time<Virtual>>,
+    mut circle_query: Query<
+        (
+            &mut ManifestationSpawner,
+            &Damage,
+            &Area,
+            &Transform,
+            Option<&FiredBy>,
+        ),
+        (With<PatternType>, Without<MarkedForDeath>),
+    >,
+    mut game_rng: ResMut<GameRng>,
+) {
+    for (mut spawner, damage, area, transform, fired_by) in circle_query.iter_mut() {
+        if !spawner.timer.tick(&time).just_finished() {
+            continue;
+        }
+
+        // sqrt keeps the points evenly spread instead of bunching at the center
+        let angle = game_rng.gen::<f32>() * std::f32::consts::TAU;
+        let distance = game_rng.gen::<f32>().sqrt() * area.radius;
+        let offset = Vec2::from_angle(angle) * distance;
+
+        let zone = spawn_manifestation_zone(
+            &mut commands,
+            transform.translation + offset.extend(0.0),
+            ((damage.amount as f32 * ZONE_SCALE).ceil() as i32).max(1),
+            area.radius * ZONE_SCALE,
+        );
+        if let Some(fired_by) = fired_by {
+            commands.entity(zone).insert(*fired_by);
+        }
+    }
+}
+
+/// Damages every enemy inside a zone each tick
+pub fn manifestation_zone_damage(
+    time: Res<Time<Virtual>>,
+    mut zone_query: Query<(Entity, &mut ManifestationZone, &Damage), Without<MarkedForDeath>>,
+    context_query: Query<&RapierContext>,
+    enemy_query: Query<Entity, (With<Enemy>, Without<MarkedForDeath>)>,
+    mut damage_events: EventWriter<DamageEvent>,
+) {
+    let rapier_context = context_query
+        .get_single()
+        .unwrap_or_else(|e| handle_rapier_context_error(e));
+
+    for (zone_entity, mut zone, damage) in zone_query.iter_mut() {
+        if !zone.tick_timer.tick(&time).just_finished() {
+            continue;
+        }
+
+        for (collider1, collider2, intersecting) in
+            rapier_context.intersection_pairs_with(zone_entity)
+        {
+            if !intersecting {
+                continue;
+            }
+
+            let enemy_entity = if collider1 == zone_entity {
+                collider2
+            } else {
+                collider1
+            };
+
+            if enemy_query.contains(enemy_entity) {
+                damage_events.send(DamageEvent {
+                    target: enemy_entity,
+                    amount: damage.amount,
+                    source: Some(zone_entity),
+                });
+            }
+        }
+    }
+}
@@ -1,23 +1,62 @@
 use crate::combat::DamageEvent;
-use crate::components::{AreaMultiplier, CooldownReduction, DamageMultiplier, Enemy, Player};
+use crate::components::{
+    AmountBonus, AreaMultiplier, CooldownReduction, DamageMultiplier, Enemy, Facing, Player,
+};
 use crate::death::MarkedForDeath;
-use crate::physics::handle_rapier_context_error;
-use crate::resources::GameState;
+use crate::game_time::PausableTimer;
+use crate::menu::WeaponUpgradeConfirmedEvent;
+use crate::physics::{handle_rapier_context_error, ENEMY_MASS, ENEMY_RADIUS};
+use crate::player_handle::PlayerHandle;
+use crate::resources::{GameRng, GameState, GameTextures};
+use crate::spatial_grid::EnemySpatialGrid;
+use crate::status::{ApplyStatusEvent, StatusEffect};
+use crate::weapons::arcane_bolt::{spawn_arcane_bolt, spawn_arcane_bolt_volley, ArcaneBolt};
+use crate::weapons::arcane_storm::{spawn_arcane_storm, spawn_arcane_storm_volley, ArcaneStorm};
+use crate::weapons::attack_cap::{enforce_attack_cap, AttackCap};
+use crate::weapons::beam::{
+    aim_beam_attacks, beam_damage_system, spawn_beam, spawn_beam_attack, Beam,
+};
+use crate::weapons::boomerang::{
+    boomerang_hit_system, spawn_boomerang, spawn_boomerang_throw, Boomerang,
+};
+use crate::weapons::combo::{fade_combo_sparks, spawn_combo_spark};
+use crate::weapons::lance::{lance_damage_system, spawn_lance, spawn_lance_thrusts, Lance};
 use crate::weapons::magick_circle::{
-    apply_magick_circle_weapon_upgrades, spawn_magick_circle, spawn_magick_circle_attack,
-    MagickCircle, PatternType,
+    resize_magick_circle_attacks, spawn_magick_circle, spawn_magick_circle_attack,
+    spawn_split_circles, MagickCircle, PatternType, SplitOnExpire,
+};
+use crate::weapons::manifestation::{manifestation_zone_damage, spawn_manifestation_zones};
+use crate::weapons::orbitals::{
+    familiar_contact_damage, resize_familiar_orbits, spawn_orbitals, sync_familiars,
+};
+use crate::weapons::pattern_style::{
+    fade_pattern_bursts, play_pattern_expire_effects, play_pattern_spawn_effects,
 };
-use crate::weapons::weapon_upgrade::{
-    apply_common_weapon_upgrades, update_weapon_level, WeaponUpgradeConfig,
+use crate::weapons::projectile::{
+    projectile_hit_system, steer_homing_projectiles, Bounces, Homing, Pierce,
 };
+use crate::weapons::weapon_upgrade::{WeaponUpgradeConfig, WeaponUpgradePlugin};
+use crate::GameplaySets;
 use bevy::prelude::*;
-use bevy::utils::HashMap;
+use bevy::utils::{HashMap, HashSet};
 use bevy_rapier2d::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::fmt::Formatter;
 use std::time::Duration;
 use strum_macros::EnumIter;
 
-mod magick_circle;
+pub mod arcane_bolt;
+pub mod arcane_storm;
+mod attack_cap;
+mod beam;
+pub mod boomerang;
+mod combo;
+pub mod lance;
+pub mod magick_circle;
+pub mod manifestation;
+pub mod orbitals;
+mod pattern_style;
+pub mod projectile;
 pub mod weapon_upgrade;
 
 /// Plugin to register all weapon-related systems
@@ -25,42 +64,80 @@ pub struct WeaponPlugin;
 
 impl Plugin for WeaponPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<WeaponUpgradeConfig>()
+        app.add_plugins(WeaponUpgradePlugin)
             .add_event::<AddWeaponEvent>()
             .add_event::<AreaEffectEvent>()
-            .add_event::<BindingEvent>()
+            .add_event::<InvocationEvent>()
+            .init_resource::<InvocationSettings>()
+            .init_resource::<BanishmentSettings>()
+            .init_resource::<AreaFalloff>()
+            .init_resource::<AttackCap>()
             .add_systems(
                 Update,
                 (
-                    update_weapon_level,
                     (
-                        apply_common_weapon_upgrades,
-                        apply_magick_circle_weapon_upgrades,
+                        setup_player_inventory,
+                        unlock_new_weapons,
+                        evolve_weapons,
+                        handle_new_weapons,
                     )
-                        .after(update_weapon_level),
-                ),
-            )
-            .add_systems(
-                Update,
-                (
-                    setup_player_inventory,
-                    handle_new_weapons,
+                        .chain(),
                     weapon_firing_system,
                     update_weapon_positions,
+                    resolve_live_attack_stats,
+                    resize_magick_circle_attacks,
+                    (
+                        sync_familiars,
+                        resize_familiar_orbits,
+                        familiar_contact_damage,
+                    )
+                        .chain(),
                     area_effect_system,
-                    handle_binding_events,
+                    spawn_manifestation_zones,
+                    manifestation_zone_damage,
+                    (aim_beam_attacks, beam_damage_system, lance_damage_system).chain(),
+                    steer_homing_projectiles,
+                    projectile_hit_system,
+                    boomerang_hit_system,
+                    handle_invocation_events,
                     attack_lifetime_system,
                     attack_rotation_system,
                     orbital_movement_system,
                 )
                     .chain()
                     .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(
+                Update,
+                (
+                    apply_knockback,
+                    recover_from_knockback,
+                    expire_spent_piercing_attacks,
+                    enforce_attack_cap,
+                    fade_combo_sparks,
+                    (
+                        play_pattern_spawn_effects,
+                        play_pattern_expire_effects,
+                        fade_pattern_bursts,
+                    ),
+                )
+                    .chain()
+                    .after(attack_lifetime_system)
+                    .run_if(in_state(GameState::Playing)),
+            )
+            // Pull has to land after enemy movement has set this frame's velocity
+            .add_systems(
+                Update,
+                apply_invocation_pull
+                    .after(GameplaySets::Movement)
+                    .before(GameplaySets::Combat)
+                    .run_if(in_state(GameState::Playing)),
             );
     }
 }
 
 /// Core weapon type identifier
-#[derive(Debug, Component)]
+#[derive(Debug, Component, Clone)]
 pub struct WeaponMeta {
     pub weapon_type: WeaponType,
     pub level: u32,
@@ -81,16 +158,94 @@ pub struct AddWeaponEvent {
 #[derive(Event)]
 pub enum AreaEffectEvent {
     Damage(DamageEvent),
-    Binding(BindingEvent),
+    Status(ApplyStatusEvent),
+    Invocation(InvocationEvent),
     // Protection(ProtectionEvent),
     // etc.
 }
 
 #[derive(Event)]
-pub struct BindingEvent {
+pub struct InvocationEvent {
     pub target: Entity,
-    pub strength: f32, // How strongly they're held
-    pub source: Entity,
+    pub source: Entity, // The circle pulling them in
+}
+
+#[derive(Resource)]
+pub struct InvocationSettings {
+    pub pull_strength: f32, // Pull speed per unit of circle radius
+}
+
+impl Default for InvocationSettings {
+    fn default() -> Self {
+        Self { pull_strength: 1.5 }
+    }
+}
+
+#[derive(Resource)]
+pub struct BanishmentSettings {
+    pub push_strength: f32,          // Knockback speed per unit of circle radius
+    pub invocation_combo_bonus: f32, // Extra damage share on enemies an Invocation circle holds
+}
+
+impl Default for BanishmentSettings {
+    fn default() -> Self {
+        Self {
+            push_strength: 2.0,
+            invocation_combo_bonus: 0.5,
+        }
+    }
+}
+
+/// How a circle's damage drops off from its center out to its edge
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DamageFalloff {
+    Linear { edge: f32 }, // Share of full damage left at the edge
+    Smooth { edge: f32 }, // Same at the edge, but holds near full damage further out
+}
+
+impl DamageFalloff {
+    /// Damage multiplier `distance` from the center of a circle this size
+    pub fn factor(&self, distance: f32, radius: f32) -> f32 {
+        // Enemies only touching the edge count as being on it
+        let t = if radius > 0.0 {
+            (distance / radius).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        match self {
+            DamageFalloff::Linear { edge } => 1.0 - (1.0 - edge) * t,
+            DamageFalloff::Smooth { edge } => 1.0 - (1.0 - edge) * t * t,
+        }
+    }
+}
+
+/// Falloff for each pattern's circle damage, patterns left out hit just as hard everywhere
+#[derive(Resource)]
+pub struct AreaFalloff {
+    pub patterns: HashMap<PatternType, DamageFalloff>,
+}
+
+impl AreaFalloff {
+    /// `damage` after falloff, never below 1 so a hit at the edge still lands
+    pub fn apply(&self, pattern: PatternType, damage: i32, distance: f32, radius: f32) -> i32 {
+        match self.patterns.get(&pattern) {
+            Some(falloff) => {
+                ((damage as f32 * falloff.factor(distance, radius)).round() as i32).max(1)
+            }
+            None => damage,
+        }
+    }
+}
+
+impl Default for AreaFalloff {
+    fn default() -> Self {
+        Self {
+            patterns: HashMap::from([(
+                PatternType::Banishment,
+                DamageFalloff::Linear { edge: 0.6 },
+            )]),
+        }
+    }
 }
 
 #[derive(Component)]
@@ -100,10 +255,66 @@ pub struct WeaponInventory;
 pub fn setup_player_inventory(
     mut commands: Commands,
     query: Query<(Entity, &StartingWeapon), (Added<Player>, Without<WeaponInventory>)>,
+    weapon_upgrade_config: Res<WeaponUpgradeConfig>,
 ) {
     for (player_entity, starting_weapon) in query.iter() {
         commands.entity(player_entity).insert(WeaponInventory);
-        spawn_weapon(&mut commands, player_entity, starting_weapon.0);
+        spawn_weapon(
+            &mut commands,
+            player_entity,
+            starting_weapon.0,
+            &weapon_upgrade_config,
+        );
+    }
+}
+
+// Confirming an upgrade for a weapon the player doesn't own yet grants it at level 1
+pub fn unlock_new_weapons(
+    mut upgrade_events: EventReader<WeaponUpgradeConfirmedEvent>,
+    mut add_weapon_events: EventWriter<AddWeaponEvent>,
+    player_handle: Res<PlayerHandle>,
+    weapon_query: Query<&WeaponMeta>,
+) {
+    // Nobody to hand the weapon to, the confirmation is dropped
+    let Some(player) = player_handle.get() else {
+        return;
+    };
+
+    for event in upgrade_events.read() {
+        if weapon_query
+            .iter()
+            .any(|meta| meta.weapon_type == event.weapon_type)
+        {
+            continue;
+        }
+
+        info!("Unlocking new weapon: {}", event.weapon_type);
+        add_weapon_events.send(AddWeaponEvent {
+            player,
+            weapon_type: event.weapon_type,
+        });
+    }
+}
+
+// Confirming an evolved weapon retires the maxed weapon it grew out of,
+// unlock_new_weapons has already queued the evolution itself
+pub fn evolve_weapons(
+    mut commands: Commands,
+    mut upgrade_events: EventReader<WeaponUpgradeConfirmedEvent>,
+    weapon_upgrade_config: Res<WeaponUpgradeConfig>,
+    weapon_query: Query<(Entity, &WeaponMeta)>,
+) {
+    for event in upgrade_events.read() {
+        let Some(evolution) = weapon_upgrade_config.evolution_into(event.weapon_type) else {
+            continue;
+        };
+
+        for (weapon_entity, meta) in weapon_query.iter() {
+            if meta.weapon_type == evolution.base {
+                info!("Evolving {} into {}", evolution.base, evolution.evolved);
+                commands.entity(weapon_entity).despawn_recursive();
+            }
+        }
     }
 }
 
@@ -111,30 +322,68 @@ pub fn setup_player_inventory(
 pub fn handle_new_weapons(
     mut commands: Commands,
     mut events: EventReader<AddWeaponEvent>,
+    weapon_upgrade_config: Res<WeaponUpgradeConfig>,
 ) {
     for event in events.read() {
-        spawn_weapon(&mut commands, event.player, event.weapon_type);
+        spawn_weapon(
+            &mut commands,
+            event.player,
+            event.weapon_type,
+            &weapon_upgrade_config,
+        );
     }
 }
 
-#[derive(Debug, Component, Copy, Clone, Eq, PartialEq, Hash, EnumIter)]
+#[derive(
+    Debug,
+    Component,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    EnumIter,
+    Serialize,
+    Deserialize,
+)]
 pub enum WeaponType {
     MagickCircle,
+    Beam,
+    ArcaneBolt,
+    Orbitals,
+    Lance,
+    Boomerang,
+    // Evolutions, only obtainable by evolving a maxed weapon
+    ArcaneStorm,
     // Future weapon types...
 }
 
+impl WeaponType {
+    pub fn is_evolution(&self) -> bool {
+        matches!(self, Self::ArcaneStorm)
+    }
+}
+
 impl std::fmt::Display for WeaponType {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::MagickCircle => write!(f, "Magick Circle"),
+            Self::Beam => write!(f, "Beam"),
+            Self::ArcaneBolt => write!(f, "Arcane Bolt"),
+            Self::Orbitals => write!(f, "Orbitals"),
+            Self::Lance => write!(f, "Lance"),
+            Self::Boomerang => write!(f, "Boomerang"),
+            Self::ArcaneStorm => write!(f, "Arcane Storm"),
         }
     }
 }
 
 /// Base weapon statistics
-#[derive(Component)]
+#[derive(Component, Clone)]
 pub struct WeaponCooldown {
-    pub timer: Timer,
+    pub timer: PausableTimer,
     pub base_duration: f32,
     pub cooldown_bonus: i32, // Negative numbers speed it up
 }
@@ -142,7 +391,7 @@ pub struct WeaponCooldown {
 impl Default for WeaponCooldown {
     fn default() -> Self {
         Self {
-            timer: Timer::from_seconds(1.0, TimerMode::Repeating),
+            timer: PausableTimer::from_seconds(1.0, TimerMode::Repeating),
             base_duration: 1.0,
             cooldown_bonus: 0,
         }
@@ -155,13 +404,30 @@ pub enum WeaponMovement {
     Stationary,
     /// Weapon follows player position
     FollowPlayer,
+    /// Flies out along `direction` until it has covered `range`,
+    /// then heads back to the player and is caught on arrival
+    ReturnToPlayer {
+        phase: ReturnPhase,
+        direction: Vec2,
+        speed: f32,
+        range: f32,
+        traveled: f32,
+    },
     // Could add more variants like:
     // OrbitalRotation(f32), // Rotates around player at given radius
-    // ReturnToPlayer,       // Boomerang-style
     // LeashToPlayer(f32),   // Follows but with max distance
 }
 
-#[derive(Component)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReturnPhase {
+    Outbound,
+    Returning,
+}
+
+// How close a returning attack has to get before the player catches it
+const CATCH_DISTANCE: f32 = 16.0;
+
+#[derive(Component, Clone)]
 pub struct WeaponDamage {
     pub base_amount: i32,
     pub damage_bonus: i32, // Positive numbers increase damage
@@ -172,7 +438,7 @@ pub struct Damage {
     pub amount: i32,
 }
 
-#[derive(Component)]
+#[derive(Component, Clone)]
 pub struct WeaponArea {
     pub base_radius: f32,
     pub area_bonus: i32, // Positive numbers increase area
@@ -183,13 +449,66 @@ pub struct Area {
     pub radius: f32,
 }
 
+/// Extra attacks spawned every time the weapon fires, on top of its own count
+#[derive(Component, Default, Clone)]
+pub struct Amount(pub u32);
+
+/// How long the attacks a weapon spawns stay on the field
+#[derive(Component, Clone)]
+pub struct WeaponDuration {
+    pub base_duration: f32,
+    pub duration_bonus: i32, // Positive numbers make attacks last longer
+}
+
+/// Attacks spawned by a weapon with this follow its current stats instead of
+/// keeping the `Damage`/`Area` snapshot they were spawned with
+#[derive(Component)]
+pub struct LiveAttackStats;
+
+// The weapon an attack resolves its live stats from
+#[derive(Component)]
+pub struct SourceWeapon(pub Entity);
+
+/// The kind of weapon an attack's damage is credited to in `WeaponStats`.
+/// Unlike `SourceWeapon` every attack carries it, including ones left behind by other attacks.
+#[derive(Component, Clone, Copy)]
+pub struct FiredBy(pub WeaponType);
+
+// Cooldown upgrades can stack past -100%, never let a weapon fire faster than this share of its base
+const MIN_COOLDOWN_PERCENT: f32 = 0.1;
+
+pub fn effective_cooldown(
+    cooldown: &WeaponCooldown,
+    cooldown_reduction: &CooldownReduction,
+) -> f32 {
+    let cooldown_percent =
+        ((100 + cooldown.cooldown_bonus) as f32 / 100.0).max(MIN_COOLDOWN_PERCENT);
+    // Player's cooldown reduction applies on top
+    cooldown.base_duration * cooldown_percent * (1.0 - cooldown_reduction.percent)
+}
+
+pub fn effective_damage(damage: &WeaponDamage, damage_multiplier: &DamageMultiplier) -> i32 {
+    let damage_percent = (100 + damage.damage_bonus) as f32 / 100.0;
+    (damage.base_amount as f32 * damage_percent * damage_multiplier.factor).floor() as i32
+}
+
+pub fn effective_radius(area: &WeaponArea, area_multiplier: &AreaMultiplier) -> f32 {
+    let area_percent = (100 + area.area_bonus) as f32 / 100.0;
+    area.base_radius * area_percent * area_multiplier.factor
+}
+
+pub fn effective_duration(duration: &WeaponDuration) -> f32 {
+    let duration_percent = (100 + duration.duration_bonus) as f32 / 100.0;
+    duration.base_duration * duration_percent
+}
+
 /// Attack-specific components
 #[derive(Component)]
 pub struct Attack; // Formerly held an AttackType enum
 
 #[derive(Component)]
 pub struct Lifetime {
-    pub timer: Timer,
+    pub timer: PausableTimer,
 }
 
 #[derive(Component)]
@@ -202,19 +521,83 @@ pub struct Rotates {
 pub struct Sigil {
     pub index: u32,
     pub base_size: f32,
+    pub counter: bool, // Part of the inner ring that turns the other way
 }
 
 #[derive(Component)]
 pub struct Orbits {
     pub radius: f32,
+    pub axes: Vec2, // Per-axis multiplier on the radius, anything but (1, 1) is an ellipse
     pub speed: f32,
     pub current_angle: f32,
+    pub reversed: bool, // Turns against the sign of `speed`
+}
+
+impl Orbits {
+    pub fn circular(radius: f32, speed: f32, current_angle: f32) -> Self {
+        Self {
+            radius,
+            axes: Vec2::ONE,
+            speed,
+            current_angle,
+            reversed: false,
+        }
+    }
+
+    pub fn offset(&self) -> Vec2 {
+        Vec2::new(self.current_angle.cos(), self.current_angle.sin()) * self.axes * self.radius
+    }
 }
 
 /// Optional weapon modifiers
+///
+/// Limits an attack to damaging each enemy once. Every new enemy hit uses up one pierce,
+/// and the hit after they've run out is its last: the attack is then marked for death.
 #[derive(Component)]
 pub struct PiercingAttack {
-    pub pierce_count: u32,
+    pub pierce_count: u32, // Enemies it can still pass through
+    pub hit: HashSet<Entity>,
+    pub spent: bool,
+}
+
+impl PiercingAttack {
+    pub fn new(pierce_count: u32) -> Self {
+        Self {
+            pierce_count,
+            hit: HashSet::new(),
+            spent: false,
+        }
+    }
+
+    /// Whether the attack may damage this enemy, recording the hit if so
+    pub fn try_hit(&mut self, target: Entity) -> bool {
+        if self.spent || !self.hit.insert(target) {
+            return false;
+        }
+
+        match self.pierce_count.checked_sub(1) {
+            Some(remaining) => self.pierce_count = remaining,
+            None => self.spent = true,
+        }
+        true
+    }
+}
+
+/// Damage systems run every hit through this, so attacks without `PiercingAttack` hit freely
+pub fn can_hit(piercing: Option<&mut PiercingAttack>, target: Entity) -> bool {
+    piercing.is_none_or(|piercing| piercing.try_hit(target))
+}
+
+/// Removes attacks once they have hit their last enemy
+pub fn expire_spent_piercing_attacks(
+    mut commands: Commands,
+    query: Query<(Entity, &PiercingAttack), (Changed<PiercingAttack>, Without<MarkedForDeath>)>,
+) {
+    for (entity, piercing) in query.iter() {
+        if piercing.spent {
+            commands.entity(entity).insert(MarkedForDeath);
+        }
+    }
 }
 
 #[derive(Component)]
@@ -224,15 +607,29 @@ pub struct AreaEffect {
     pub last_tick: f32,
 }
 
-#[derive(Component)]
+/// Pushes enemies an attack damages away from it.
+/// Put it on a weapon to give every attack it fires knockback.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
 pub struct Knockback {
-    pub force: f32,
+    pub force: f32, // Speed an enemy is knocked back at
 }
 
 /// Spawns a weapon for the player based on weapon type
-pub fn spawn_weapon(commands: &mut Commands, player_entity: Entity, weapon_type: WeaponType) {
+pub fn spawn_weapon(
+    commands: &mut Commands,
+    player_entity: Entity,
+    weapon_type: WeaponType,
+    config: &WeaponUpgradeConfig,
+) {
+    let base = config.base_stats(weapon_type);
     match weapon_type {
-        WeaponType::MagickCircle => spawn_magick_circle(commands, player_entity),
+        WeaponType::MagickCircle => spawn_magick_circle(commands, player_entity, &base),
+        WeaponType::Beam => spawn_beam(commands, player_entity, &base),
+        WeaponType::ArcaneBolt => spawn_arcane_bolt(commands, player_entity, &base),
+        WeaponType::Orbitals => spawn_orbitals(commands, player_entity, &base),
+        WeaponType::Lance => spawn_lance(commands, player_entity, &base),
+        WeaponType::Boomerang => spawn_boomerang(commands, player_entity, &base),
+        WeaponType::ArcaneStorm => spawn_arcane_storm(commands, player_entity, &base),
         // Add other weapon types here
     }
 }
@@ -249,6 +646,8 @@ pub fn weapon_firing_system(
             &DamageMultiplier,
             &AreaMultiplier,
             &Transform,
+            &Facing,
+            &AmountBonus,
         ),
         With<Player>,
     >,
@@ -260,21 +659,44 @@ pub fn weapon_firing_system(
         &WeaponDamage,
         &WeaponArea,
         &WeaponMeta,
+        Option<&Amount>,
+        Has<LiveAttackStats>,
     )>,
     // Query specific weapon types for their unique properties
-    magick_circle_query: Query<&MagickCircle>,
+    magick_circle_query: Query<(&MagickCircle, &WeaponDuration)>,
+    beam_query: Query<&Beam>,
+    arcane_bolt_query: Query<(
+        &ArcaneBolt,
+        Option<&Homing>,
+        Option<&Bounces>,
+        Option<&Pierce>,
+    )>,
+    arcane_storm_query: Query<(
+        &ArcaneStorm,
+        Option<&Homing>,
+        Option<&Bounces>,
+        Option<&Pierce>,
+    )>,
+    lance_query: Query<&Lance>,
+    boomerang_query: Query<&Boomerang>,
+    textures: Res<GameTextures>,
+    mut game_rng: ResMut<GameRng>,
 ) {
     // info!("Checking weapons - found {} weapons", weapon_query.iter().count());
 
-    for (weapon_entity, parent, mut cooldown, damage, area, weapon) in weapon_query.iter_mut() {
+    for (weapon_entity, parent, mut cooldown, damage, area, weapon, amount, live_stats) in
+        weapon_query.iter_mut()
+    {
         // info!("Processing weapon: {:?}", weapon_entity);
 
         if let Ok((
-            _player_entity,
+            player_entity,
             cooldown_reduction,
             damage_multiplier,
             area_multiplier,
             player_transform,
+            facing,
+            amount_bonus,
         )) = player_query.get(parent.get())
         {
             // info!("Found player stats - CD reduction: {}, damage mult: {}, area mult: {}",
@@ -283,22 +705,16 @@ pub fn weapon_firing_system(
             //     area_multiplier.factor
             // );
 
-            let cooldown_percent = (100 + cooldown.cooldown_bonus) as f32 / 100.0;
-            let effective_cooldown =
-                cooldown.base_duration * cooldown_percent * (1.0 - cooldown_reduction.percent); // Player's cooldown reduction
+            let effective_cooldown = effective_cooldown(&cooldown, cooldown_reduction);
 
             cooldown
                 .timer
                 .set_duration(Duration::from_secs_f32(effective_cooldown));
-            cooldown.timer.tick(time.delta());
+            cooldown.timer.tick(&time);
 
-            let damage_percent = (100 + damage.damage_bonus) as f32 / 100.0;
-            let effective_damage =
-                (damage.base_amount as f32 * damage_percent * damage_multiplier.factor).floor()
-                    as i32;
-
-            let area_percent = (100 + area.area_bonus) as f32 / 100.0;
-            let effective_radius = area.base_radius * area_percent * area_multiplier.factor;
+            let effective_damage = effective_damage(damage, damage_multiplier);
+            let effective_radius = effective_radius(area, area_multiplier);
+            let extra_attacks = amount.map_or(0, |amount| amount.0) + amount_bonus.0;
 
             // info!("Timer progress: {}/{}",
             //     cooldown.timer.elapsed_secs(),
@@ -315,40 +731,209 @@ pub fn weapon_firing_system(
                 match weapon.weapon_type {
                     WeaponType::MagickCircle => {
                         // info!("Attempting to spawn MagickCircle attack");
-                        if let Ok(magick_circle) = magick_circle_query.get(weapon_entity) {
+                        if let Ok((magick_circle, duration)) =
+                            magick_circle_query.get(weapon_entity)
+                        {
+                            let effective_duration = effective_duration(duration);
+                            // Extra circles cycle through the weapon's patterns
+                            let patterns: Vec<PatternType> = magick_circle
+                                .patterns
+                                .iter()
+                                .cycle()
+                                .take(magick_circle.patterns.len() + extra_attacks as usize)
+                                .copied()
+                                .collect();
+                            let mut attacks = Vec::with_capacity(patterns.len());
+
                             // First circle always spawns centered
-                            spawn_magick_circle_attack(
+                            attacks.push(spawn_magick_circle_attack(
                                 &mut commands,
                                 player_transform.translation,
                                 effective_damage,
                                 effective_radius,
-                                magick_circle.patterns[0],
-                                magick_circle.num_sigils,
+                                effective_duration,
+                                patterns[0],
+                                magick_circle.sigils,
                                 None, // No offset for first circle
-                            );
+                            ));
 
                             // info!("Spawning MagickCircle attack at position: {:?}", player_transform.translation);
                             // Additional circles are evenly spaced
-                            if magick_circle.patterns.len() > 1 {
-                                let angle_step = std::f32::consts::TAU
-                                    / (magick_circle.patterns.len() - 1) as f32;
-                                for (i, pattern) in magick_circle.patterns[1..].iter().enumerate() {
+                            if patterns.len() > 1 {
+                                let angle_step =
+                                    std::f32::consts::TAU / (patterns.len() - 1) as f32;
+                                for (i, pattern) in patterns[1..].iter().enumerate() {
                                     let angle = angle_step * i as f32;
-                                    spawn_magick_circle_attack(
+                                    attacks.push(spawn_magick_circle_attack(
                                         &mut commands,
                                         player_transform.translation,
                                         effective_damage,
                                         effective_radius,
+                                        effective_duration,
                                         *pattern,
-                                        magick_circle.num_sigils,
+                                        magick_circle.sigils,
                                         Some(angle),
-                                    );
+                                    ));
+                                }
+                            }
+
+                            for attack in attacks {
+                                commands.entity(attack).insert(FiredBy(weapon.weapon_type));
+                                if live_stats {
+                                    commands.entity(attack).insert(SourceWeapon(weapon_entity));
+                                }
+                                if let Some(split) = magick_circle.split_on_expire {
+                                    commands.entity(attack).insert(split);
                                 }
                             }
                         } else {
                             info!("Failed to get MagickCircle component from weapon entity");
                         }
                     }
+                    WeaponType::Beam => {
+                        if let Ok(beam) = beam_query.get(weapon_entity) {
+                            for _ in 0..=extra_attacks {
+                                let attack = spawn_beam_attack(
+                                    &mut commands,
+                                    player_entity,
+                                    player_transform.translation,
+                                    effective_damage,
+                                    effective_radius,
+                                    beam,
+                                );
+
+                                commands.entity(attack).insert(FiredBy(weapon.weapon_type));
+                                if live_stats {
+                                    commands.entity(attack).insert(SourceWeapon(weapon_entity));
+                                }
+                            }
+                        } else {
+                            info!("Failed to get Beam component from weapon entity");
+                        }
+                    }
+                    WeaponType::ArcaneBolt => {
+                        if let Ok((bolt, homing, bounces, pierce)) =
+                            arcane_bolt_query.get(weapon_entity)
+                        {
+                            let projectiles = spawn_arcane_bolt_volley(
+                                &mut commands,
+                                &textures,
+                                player_transform.translation,
+                                effective_damage,
+                                effective_radius,
+                                bolt,
+                                extra_attacks,
+                                game_rng.as_mut(),
+                            );
+
+                            for projectile in projectiles {
+                                commands
+                                    .entity(projectile)
+                                    .insert(FiredBy(weapon.weapon_type));
+                                if live_stats {
+                                    commands
+                                        .entity(projectile)
+                                        .insert(SourceWeapon(weapon_entity));
+                                }
+                                if let Some(homing) = homing {
+                                    commands.entity(projectile).insert(*homing);
+                                }
+                                if let Some(bounces) = bounces {
+                                    commands.entity(projectile).insert(*bounces);
+                                }
+                                if let Some(pierce) = pierce {
+                                    commands
+                                        .entity(projectile)
+                                        .insert(PiercingAttack::new(pierce.0));
+                                }
+                            }
+                        } else {
+                            info!("Failed to get ArcaneBolt component from weapon entity");
+                        }
+                    }
+                    WeaponType::ArcaneStorm => {
+                        if let Ok((storm, homing, bounces, pierce)) =
+                            arcane_storm_query.get(weapon_entity)
+                        {
+                            let projectiles = spawn_arcane_storm_volley(
+                                &mut commands,
+                                &textures,
+                                player_transform.translation,
+                                effective_damage,
+                                effective_radius,
+                                storm,
+                                extra_attacks,
+                                game_rng.as_mut(),
+                            );
+
+                            for projectile in projectiles {
+                                commands
+                                    .entity(projectile)
+                                    .insert(FiredBy(weapon.weapon_type));
+                                if live_stats {
+                                    commands
+                                        .entity(projectile)
+                                        .insert(SourceWeapon(weapon_entity));
+                                }
+                                if let Some(homing) = homing {
+                                    commands.entity(projectile).insert(*homing);
+                                }
+                                if let Some(bounces) = bounces {
+                                    commands.entity(projectile).insert(*bounces);
+                                }
+                                if let Some(pierce) = pierce {
+                                    commands
+                                        .entity(projectile)
+                                        .insert(PiercingAttack::new(pierce.0));
+                                }
+                            }
+                        } else {
+                            info!("Failed to get ArcaneStorm component from weapon entity");
+                        }
+                    }
+                    // Familiars are persistent, the cooldown only paces their contact damage
+                    WeaponType::Orbitals => {}
+                    WeaponType::Lance => {
+                        if let Ok(lance) = lance_query.get(weapon_entity) {
+                            let attacks = spawn_lance_thrusts(
+                                &mut commands,
+                                player_transform.translation,
+                                facing.direction,
+                                effective_damage,
+                                effective_radius,
+                                lance,
+                                extra_attacks,
+                            );
+
+                            for attack in attacks {
+                                commands.entity(attack).insert(FiredBy(weapon.weapon_type));
+                                if live_stats {
+                                    commands.entity(attack).insert(SourceWeapon(weapon_entity));
+                                }
+                            }
+                        } else {
+                            info!("Failed to get Lance component from weapon entity");
+                        }
+                    }
+                    WeaponType::Boomerang => {
+                        if let Ok(boomerang) = boomerang_query.get(weapon_entity) {
+                            let blades = spawn_boomerang_throw(
+                                &mut commands,
+                                player_transform.translation,
+                                facing.direction,
+                                effective_damage,
+                                effective_radius,
+                                boomerang,
+                                extra_attacks,
+                            );
+
+                            for blade in blades {
+                                commands.entity(blade).insert(FiredBy(weapon.weapon_type));
+                            }
+                        } else {
+                            info!("Failed to get Boomerang component from weapon entity");
+                        }
+                    }
                 }
             }
         } else {
@@ -358,8 +943,13 @@ pub fn weapon_firing_system(
 }
 
 fn update_weapon_positions(
+    mut commands: Commands,
+    time: Res<Time<Virtual>>,
     mut param_set: ParamSet<(
-        Query<(&mut Transform, &WeaponMovement), With<Attack>>,
+        Query<
+            (Entity, &mut Transform, &mut WeaponMovement),
+            (With<Attack>, Without<MarkedForDeath>),
+        >,
         Query<&Transform, With<Player>>,
     )>,
 ) {
@@ -371,50 +961,105 @@ fn update_weapon_positions(
     };
 
     // Then update weapon positions
-    for (mut weapon_transform, movement) in &mut param_set.p0() {
-        match movement {
+    for (entity, mut weapon_transform, mut movement) in &mut param_set.p0() {
+        match movement.as_mut() {
             WeaponMovement::Stationary => (), // Do nothing
             WeaponMovement::FollowPlayer => {
                 weapon_transform.translation = player_pos;
             }
+            WeaponMovement::ReturnToPlayer {
+                phase,
+                direction,
+                speed,
+                range,
+                traveled,
+            } => {
+                let step = *speed * time.delta_secs();
+                match phase {
+                    ReturnPhase::Outbound => {
+                        weapon_transform.translation += (*direction * step).extend(0.0);
+                        *traveled += step;
+                        if *traveled >= *range {
+                            *phase = ReturnPhase::Returning;
+                        }
+                    }
+                    ReturnPhase::Returning => {
+                        let offset =
+                            player_pos.truncate() - weapon_transform.translation.truncate();
+                        if offset.length() <= step.max(CATCH_DISTANCE) {
+                            commands.entity(entity).insert(MarkedForDeath);
+                        } else {
+                            weapon_transform.translation += (offset.normalize() * step).extend(0.0);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Re-resolves damage and area for attacks tied to their weapon, so upgrades
+/// picked mid-lifetime apply to attacks already on the field
+fn resolve_live_attack_stats(
+    mut attack_query: Query<(&SourceWeapon, &mut Damage, &mut Area), With<Attack>>,
+    weapon_query: Query<(&Parent, &WeaponDamage, &WeaponArea)>,
+    player_query: Query<(&DamageMultiplier, &AreaMultiplier), With<Player>>,
+) {
+    for (source, mut damage, mut area) in attack_query.iter_mut() {
+        // Weapon is gone, keep whatever was resolved last
+        let Ok((parent, weapon_damage, weapon_area)) = weapon_query.get(source.0) else {
+            continue;
+        };
+        let Ok((damage_multiplier, area_multiplier)) = player_query.get(parent.get()) else {
+            continue;
+        };
+
+        // Only write on change so `Changed<Area>` stays meaningful for resizing
+        let amount = effective_damage(weapon_damage, damage_multiplier);
+        if damage.amount != amount {
+            damage.amount = amount;
+        }
+
+        let radius = effective_radius(weapon_area, area_multiplier);
+        if area.radius != radius {
+            area.radius = radius;
         }
     }
 }
 
+// Outlasts the circles' tick rate, so a hold only lapses once the enemy is out of the circle
+const BINDING_DURATION: f32 = 0.6;
+
 /// System to manage area effects for weapons that have them
 pub fn area_effect_system(
+    mut commands: Commands,
     time: Res<Time<Virtual>>,
-    mut effect_query: Query<(Entity, &mut AreaEffect, &Damage, &Area, &PatternType), With<Attack>>,
+    banishment_settings: Res<BanishmentSettings>,
+    falloff: Res<AreaFalloff>,
+    mut effect_query: Query<(Entity, &mut AreaEffect, &Damage, &PatternType), With<Attack>>,
+    circle_query: Query<(&Transform, &Area), With<AreaEffect>>,
     mut damage_events: EventWriter<DamageEvent>,
-    mut binding_events: EventWriter<BindingEvent>,
-    context_query: Query<&RapierContext>,
-    enemy_query: Query<Entity, (With<Enemy>, Without<MarkedForDeath>)>,
+    mut status_events: EventWriter<ApplyStatusEvent>,
+    mut invocation_events: EventWriter<InvocationEvent>,
+    grid: Res<EnemySpatialGrid>,
+    enemy_query: Query<(&Transform, Has<InvocationPull>), (With<Enemy>, Without<MarkedForDeath>)>,
 ) {
-    let rapier_context = context_query
-        .get_single()
-        .unwrap_or_else(|e| handle_rapier_context_error(e));
-
     // Track which enemies are affected by which circles
     let mut enemy_effects: HashMap<Entity, Vec<(Entity, PatternType)>> = HashMap::new();
 
     // First pass: collect all circle effects affecting each enemy
-    for (circle_entity, mut area_effect, _, _, pattern) in effect_query.iter_mut() {
+    for (circle_entity, mut area_effect, _, pattern) in effect_query.iter_mut() {
         if time.elapsed_secs() - area_effect.last_tick >= area_effect.tick_rate {
             area_effect.last_tick = time.elapsed_secs();
 
-            for (collider1, collider2, intersecting) in
-                rapier_context.intersection_pairs_with(circle_entity)
-            {
-                if !intersecting {
-                    continue;
-                }
-
-                let enemy_entity = if collider1 == circle_entity {
-                    collider2
-                } else {
-                    collider1
-                };
-
+            let Ok((circle_transform, area)) = circle_query.get(circle_entity) else {
+                continue;
+            };
+            // Only nearby cells get looked at, so the cost follows how crowded the circle
+            // is rather than how many enemies are alive. The grid holds enemy centers, so
+            // reach out by an enemy's radius to catch the ones just touching the edge.
+            let center = circle_transform.translation.truncate();
+            for (enemy_entity, _) in grid.query_radius(center, area.radius + ENEMY_RADIUS) {
                 if enemy_query.contains(enemy_entity) {
                     enemy_effects
                         .entry(enemy_entity)
@@ -427,24 +1072,68 @@ pub fn area_effect_system(
 
     // Second pass: apply effects for each enemy
     for (enemy_entity, affecting_circles) in enemy_effects.iter() {
+        let Ok((enemy_transform, pulled)) = enemy_query.get(*enemy_entity) else {
+            continue;
+        };
+        // Enemies an Invocation circle is holding take extra Banishment damage.
+        // Circles tick on their own schedules, so a pull from an earlier tick counts too.
+        let combo = pulled
+            || affecting_circles
+                .iter()
+                .any(|(_, pattern)| *pattern == PatternType::Invocation);
+        let mut sparked = false;
+
         for (circle_entity, pattern) in affecting_circles {
             match pattern {
                 PatternType::Banishment => {
-                    if let Ok((_, _, damage, _, _)) = effect_query.get(*circle_entity) {
+                    if let Ok((_, _, damage, _)) = effect_query.get(*circle_entity) {
+                        // Weaker towards the edge of the circle, when the pattern has falloff
+                        let Ok((circle_transform, area)) = circle_query.get(*circle_entity) else {
+                            continue;
+                        };
+                        let distance = enemy_transform
+                            .translation
+                            .truncate()
+                            .distance(circle_transform.translation.truncate());
+                        let base = falloff.apply(*pattern, damage.amount, distance, area.radius);
+
+                        let amount = if combo {
+                            (base as f32 * (1.0 + banishment_settings.invocation_combo_bonus))
+                                .round() as i32
+                        } else {
+                            base
+                        };
                         damage_events.send(DamageEvent {
                             target: *enemy_entity,
-                            amount: damage.amount,
+                            amount,
                             source: Some(*circle_entity),
                         });
+
+                        // One spark per enemy, however many circles overlap it
+                        if combo && !sparked {
+                            spawn_combo_spark(&mut commands, enemy_transform.translation);
+                            sparked = true;
+                        }
                     }
                 }
                 PatternType::Binding => {
-                    binding_events.send(BindingEvent {
+                    status_events.send(ApplyStatusEvent {
+                        target: *enemy_entity,
+                        effect: StatusEffect::Bind {
+                            strength: 1.0, // We can make this configurable later
+                            duration: BINDING_DURATION,
+                        },
+                        source: Some(*circle_entity),
+                    });
+                }
+                PatternType::Invocation => {
+                    invocation_events.send(InvocationEvent {
                         target: *enemy_entity,
-                        strength: 1.0, // We can make this configurable later
                         source: *circle_entity,
                     });
                 }
+                // Damage comes from the zones it leaves behind instead
+                PatternType::Manifestation => {}
                 // Add other pattern types here as we implement them
                 _ => {
                     // Log unhandled pattern types in debug builds
@@ -456,37 +1145,178 @@ pub fn area_effect_system(
     }
 }
 
+/// Enemy sliding from a knockback. Enemy movement leaves it to physics until this runs out.
 #[derive(Component)]
-pub struct BindingEffect {
-    pub strength: f32,
+pub struct KnockedBack {
+    pub timer: PausableTimer,
+}
+
+const KNOCKBACK_DURATION: f32 = 0.2;
+
+/// Knocks damaged enemies away from the attack that hit them, using the attack's own
+/// `Knockback` or, failing that, its weapon's.
+/// Banishment circles push on top of that, harder the bigger they are.
+pub fn apply_knockback(
+    mut commands: Commands,
+    mut damage_events: EventReader<DamageEvent>,
+    banishment_settings: Res<BanishmentSettings>,
+    attack_query: Query<
+        (
+            &Transform,
+            Option<&Knockback>,
+            Option<&SourceWeapon>,
+            Option<(&PatternType, &Area)>,
+        ),
+        With<Attack>,
+    >,
+    weapon_query: Query<&Knockback, With<WeaponMeta>>,
+    enemy_query: Query<&Transform, (With<Enemy>, Without<MarkedForDeath>)>,
+) {
+    for event in damage_events.read() {
+        let Some((attack_transform, knockback, source, circle)) = event
+            .source
+            .and_then(|source| attack_query.get(source).ok())
+        else {
+            continue;
+        };
+        let knockback =
+            knockback.or_else(|| source.and_then(|source| weapon_query.get(source.0).ok()));
+        let push = match circle {
+            Some((PatternType::Banishment, area)) => {
+                area.radius * banishment_settings.push_strength
+            }
+            _ => 0.0,
+        };
+        let force = knockback.map_or(0.0, |knockback| knockback.force) + push;
+        let Ok(enemy_transform) = enemy_query.get(event.target) else {
+            continue;
+        };
+        if force <= 0.0 {
+            continue;
+        }
+
+        let direction = (enemy_transform.translation - attack_transform.translation)
+            .truncate()
+            .normalize_or_zero();
+        if direction == Vec2::ZERO {
+            continue;
+        }
+
+        commands.entity(event.target).try_insert((
+            ExternalImpulse {
+                impulse: direction * force * ENEMY_MASS,
+                torque_impulse: 0.0,
+            },
+            KnockedBack {
+                timer: PausableTimer::from_seconds(KNOCKBACK_DURATION, TimerMode::Once),
+            },
+        ));
+    }
+}
+
+fn recover_from_knockback(
+    mut commands: Commands,
+    time: Res<Time<Virtual>>,
+    mut query: Query<(Entity, &mut KnockedBack)>,
+) {
+    for (entity, mut knocked_back) in query.iter_mut() {
+        if knocked_back.timer.tick(&time).finished() {
+            commands.entity(entity).remove::<KnockedBack>();
+        }
+    }
+}
+
+/// Enemy being drawn towards an Invocation circle's center
+#[derive(Component)]
+pub struct InvocationPull {
     pub source: Entity,
 }
 
-pub fn handle_binding_events(
+pub fn handle_invocation_events(
     mut commands: Commands,
-    mut binding_events: EventReader<BindingEvent>,
+    mut invocation_events: EventReader<InvocationEvent>,
 ) {
-    for event in binding_events.read() {
-        commands.entity(event.target).insert(BindingEffect {
-            strength: event.strength,
+    for event in invocation_events.read() {
+        commands.entity(event.target).try_insert(InvocationPull {
             source: event.source,
         });
     }
 }
 
-/// Handles lifetime of attacks and marks them for death when expired
+/// Continuously pulls enemies towards the circle that caught them for as long as
+/// they stay inside its sensor. Bigger circles pull harder.
+pub fn apply_invocation_pull(
+    mut commands: Commands,
+    settings: Res<InvocationSettings>,
+    context_query: Query<&RapierContext>,
+    mut enemy_query: Query<(Entity, &InvocationPull, &Transform, &mut Velocity), With<Enemy>>,
+    circle_query: Query<(&Transform, &Area), (With<Attack>, Without<MarkedForDeath>)>,
+) {
+    let rapier_context = context_query
+        .get_single()
+        .unwrap_or_else(|e| handle_rapier_context_error(e));
+
+    for (enemy_entity, pull, transform, mut velocity) in enemy_query.iter_mut() {
+        let inside = rapier_context.intersection_pair(pull.source, enemy_entity) == Some(true);
+        let Some((circle_transform, area)) = circle_query.get(pull.source).ok().filter(|_| inside)
+        else {
+            commands.entity(enemy_entity).remove::<InvocationPull>();
+            continue;
+        };
+
+        let offset = circle_transform.translation.truncate() - transform.translation.truncate();
+        let distance = offset.length();
+        if distance <= f32::EPSILON {
+            continue;
+        }
+
+        // Ease off near the center so enemies settle instead of jittering across it
+        let falloff = (distance / area.radius).min(1.0);
+        velocity.linvel += offset / distance * settings.pull_strength * area.radius * falloff;
+    }
+}
+
+/// Handles lifetime of attacks and marks them for death when expired,
+/// spawning any follow-up attacks the expiring one leaves behind
 pub fn attack_lifetime_system(
     mut commands: Commands,
     time: Res<Time<Virtual>>,
-    mut query: Query<(Entity, &mut Lifetime), (With<Attack>, Without<MarkedForDeath>)>,
+    mut query: Query<
+        (
+            Entity,
+            &mut Lifetime,
+            Option<(&SplitOnExpire, &Damage, &Area, &PatternType, &Transform)>,
+            Option<&FiredBy>,
+        ),
+        (With<Attack>, Without<MarkedForDeath>),
+    >,
 ) {
-    for (entity, mut lifetime) in query.iter_mut() {
-        lifetime.timer.tick(time.delta());
+    for (entity, mut lifetime, split, fired_by) in query.iter_mut() {
+        lifetime.timer.tick(&time);
         if lifetime.timer.finished() {
             // First check if the entity still exists
             if commands.get_entity(entity).is_some() {
                 commands.entity(entity).insert(MarkedForDeath);
             }
+
+            if let Some((split, damage, area, pattern, transform)) = split {
+                let circles = spawn_split_circles(
+                    &mut commands,
+                    split,
+                    transform.translation,
+                    damage.amount,
+                    area.radius,
+                    lifetime.timer.duration().as_secs_f32(),
+                    *pattern,
+                );
+
+                // Still the same weapon's damage as far as stats are concerned
+                if let Some(fired_by) = fired_by {
+                    for circle in circles {
+                        commands.entity(circle).insert(*fired_by);
+                    }
+                }
+            }
         }
     }
 }
@@ -496,10 +1326,9 @@ pub fn attack_rotation_system(time: Res<Time<Virtual>>, mut query: Query<&mut Ro
     for mut rotates in query.iter_mut() {
         rotates.current_angle += rotates.speed * time.delta_secs();
 
-        // Normalize angle to prevent potential float overflow in very long sessions
-        if rotates.current_angle > std::f32::consts::TAU {
-            rotates.current_angle -= std::f32::consts::TAU;
-        }
+        // Normalize angle to prevent potential float overflow in very long sessions.
+        // Either direction, some patterns turn clockwise
+        rotates.current_angle = rotates.current_angle.rem_euclid(std::f32::consts::TAU);
     }
 }
 
@@ -508,17 +1337,13 @@ fn orbital_movement_system(
     mut query: Query<(&mut Transform, &mut Orbits)>,
 ) {
     for (mut transform, mut orbits) in &mut query {
-        orbits.current_angle += orbits.speed * time.delta_secs();
+        let direction = if orbits.reversed { -1.0 } else { 1.0 };
+        orbits.current_angle += orbits.speed * direction * time.delta_secs();
 
-        // Normalize angle
-        if orbits.current_angle > std::f32::consts::TAU {
-            orbits.current_angle -= std::f32::consts::TAU;
-        }
+        // Normalize angle, orbits can run either way
+        orbits.current_angle = orbits.current_angle.rem_euclid(std::f32::consts::TAU);
 
-        let offset = Vec2::new(
-            orbits.current_angle.cos() * orbits.radius,
-            orbits.current_angle.sin() * orbits.radius,
-        );
+        let offset = orbits.offset();
 
         // Since we're using parent-relative transforms, this will work
         // whether the entity is parented to the player or to a circle
@@ -0,0 +1,208 @@
+use crate::combat::DamageEvent;
+use crate::components::Enemy;
+use crate::death::MarkedForDeath;
+use crate::menu::WeaponUpgradeConfirmedEvent;
+use crate::physics::handle_rapier_context_error;
+use crate::trail::Trail;
+use crate::weapons::weapon_upgrade::{WeaponBaseStats, WeaponUpgradeChange};
+use crate::weapons::{
+    Area, Attack, Damage, FiredBy, LiveAttackStats, Orbits, SourceWeapon, WeaponArea,
+    WeaponCooldown, WeaponMeta, WeaponType,
+};
+use bevy::prelude::*;
+use bevy_prototype_lyon::draw::Fill;
+use bevy_prototype_lyon::entity::ShapeBundle;
+use bevy_prototype_lyon::geometry::GeometryBuilder;
+use bevy_prototype_lyon::shapes;
+use bevy_rapier2d::prelude::*;
+
+/// Familiars that circle the player for as long as the weapon is owned.
+/// The weapon's area is their orbit radius and its cooldown how often they bite.
+#[derive(Component, Clone)]
+pub struct Orbitals {
+    pub count: u32,
+    pub base_speed: f32,  // Radians per second
+    pub speed_bonus: i32, // Percent, positive numbers spin faster
+}
+
+impl Orbitals {
+    pub fn speed(&self) -> f32 {
+        self.base_speed * (100 + self.speed_bonus) as f32 / 100.0
+    }
+}
+
+#[derive(Component)]
+pub struct Familiar;
+
+const FAMILIAR_SIZE: f32 = 10.0;
+const FAMILIAR_COLOR: Color = Color::srgba(0.6, 1.0, 0.8, 0.9);
+
+pub fn spawn_orbitals(commands: &mut Commands, player_entity: Entity, base: &WeaponBaseStats) {
+    info!("Spawning orbitals for player: {:?}", player_entity);
+    commands.entity(player_entity).with_children(|parent| {
+        parent.spawn((
+            WeaponMeta {
+                weapon_type: WeaponType::Orbitals,
+                level: 1,
+            },
+            base.cooldown(),
+            base.damage(),
+            base.area(),
+            Orbitals {
+                count: 2,
+                base_speed: 2.5,
+                speed_bonus: 0,
+            },
+            // Familiars live as long as the weapon, so they have to follow its upgrades
+            LiveAttackStats,
+        ));
+    });
+}
+
+fn spawn_familiar(
+    commands: &mut Commands,
+    player_entity: Entity,
+    weapon_entity: Entity,
+    radius: f32,
+    orbits: Orbits,
+) {
+    commands.entity(player_entity).with_children(|parent| {
+        parent.spawn((
+            Attack,
+            Familiar,
+            SourceWeapon(weapon_entity),
+            FiredBy(WeaponType::Orbitals),
+            // Both resolved from the weapon on the next frame
+            Damage { amount: 0 },
+            Area { radius },
+            orbits,
+            ShapeBundle {
+                path: GeometryBuilder::build_as(&shapes::Circle {
+                    radius: FAMILIAR_SIZE,
+                    center: Vec2::ZERO,
+                }),
+                transform: Transform::from_xyz(0.0, 0.0, 0.5),
+                ..default()
+            },
+            Fill::color(FAMILIAR_COLOR),
+            Trail::new(FAMILIAR_COLOR.with_alpha(0.4), FAMILIAR_SIZE, 0.2),
+            Sensor,
+            Collider::ball(FAMILIAR_SIZE),
+            ActiveEvents::COLLISION_EVENTS,
+            CollisionGroups::new(Group::GROUP_3, Group::GROUP_2),
+        ));
+    });
+}
+
+/// Respawns a weapon's familiars whenever their count or speed changes, evenly spaced around the player
+pub fn sync_familiars(
+    mut commands: Commands,
+    weapon_query: Query<(Entity, &Parent, &Orbitals, &WeaponArea), Changed<Orbitals>>,
+    familiar_query: Query<(Entity, &SourceWeapon), With<Familiar>>,
+) {
+    for (weapon_entity, parent, orbitals, weapon_area) in weapon_query.iter() {
+        for (familiar_entity, source) in familiar_query.iter() {
+            if source.0 == weapon_entity {
+                commands.entity(familiar_entity).despawn_recursive();
+            }
+        }
+
+        let angle_step = std::f32::consts::TAU / orbitals.count.max(1) as f32;
+        for i in 0..orbitals.count {
+            spawn_familiar(
+                &mut commands,
+                parent.get(),
+                weapon_entity,
+                weapon_area.base_radius,
+                Orbits::circular(
+                    weapon_area.base_radius,
+                    orbitals.speed(),
+                    angle_step * i as f32,
+                ),
+            );
+        }
+    }
+}
+
+// Keeps the orbit in step with the live `Area`, the same way magick circles resize
+pub fn resize_familiar_orbits(
+    mut familiar_query: Query<(&Area, &mut Orbits), (With<Familiar>, Changed<Area>)>,
+) {
+    for (area, mut orbits) in familiar_query.iter_mut() {
+        orbits.radius = area.radius;
+    }
+}
+
+/// Every time the weapon's cooldown comes up, each familiar damages whatever it's touching
+pub fn familiar_contact_damage(
+    weapon_query: Query<&WeaponCooldown, With<Orbitals>>,
+    familiar_query: Query<(Entity, &SourceWeapon, &Damage), With<Familiar>>,
+    context_query: Query<&RapierContext>,
+    enemy_query: Query<Entity, (With<Enemy>, Without<MarkedForDeath>)>,
+    mut damage_events: EventWriter<DamageEvent>,
+) {
+    let rapier_context = context_query
+        .get_single()
+        .unwrap_or_else(|e| handle_rapier_context_error(e));
+
+    for (familiar_entity, source, damage) in familiar_query.iter() {
+        // The cooldown was already ticked by weapon_firing_system this frame
+        let Ok(cooldown) = weapon_query.get(source.0) else {
+            continue;
+        };
+        if !cooldown.timer.just_finished() {
+            continue;
+        }
+
+        for (collider1, collider2, intersecting) in
+            rapier_context.intersection_pairs_with(familiar_entity)
+        {
+            if !intersecting {
+                continue;
+            }
+
+            let enemy_entity = if collider1 == familiar_entity {
+                collider2
+            } else {
+                collider1
+            };
+
+            if enemy_query.contains(enemy_entity) {
+                damage_events.send(DamageEvent {
+                    target: enemy_entity,
+                    amount: damage.amount,
+                    source: Some(familiar_entity),
+                });
+            }
+        }
+    }
+}
+
+pub fn apply_orbitals_weapon_upgrades(
+    mut upgrade_events: EventReader<WeaponUpgradeConfirmedEvent>,
+    mut weapon_query: Query<(&mut Orbitals, &WeaponMeta)>,
+) {
+    for upgrade_event in upgrade_events.read() {
+        for (mut orbitals, meta) in weapon_query.iter_mut() {
+            if meta.weapon_type != upgrade_event.weapon_type {
+                continue;
+            }
+
+            for change in &upgrade_event.upgrade_spec.changes {
+                match change {
+                    WeaponUpgradeChange::ExtraOrbitals(count) => {
+                        orbitals.count += *count;
+                        info!(
+                            "Orbitals now has {} familiars at level {}",
+                            orbitals.count, meta.level
+                        );
+                    }
+                    WeaponUpgradeChange::RotationSpeed(value) => {
+                        orbitals.speed_bonus += *value;
+                    }
+                    _ => (),
+                }
+            }
+        }
+    }
+}
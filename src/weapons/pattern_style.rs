@@ -0,0 +1,219 @@
+use crate::death::MarkedForDeath;
+use crate::performance::EffectParticle;
+use crate::settings::GameSettings;
+use crate::weapons::magick_circle::PatternType;
+use crate::weapons::Area;
+use bevy::prelude::*;
+use bevy_prototype_lyon::draw::Fill;
+use bevy_prototype_lyon::entity::{Path, ShapeBundle};
+use bevy_prototype_lyon::geometry::GeometryBuilder;
+use bevy_prototype_lyon::prelude::RectangleOrigin;
+use bevy_prototype_lyon::shapes;
+
+/// Outline the sigils orbiting a circle are drawn with
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SigilShape {
+    Square,
+    Triangle,
+    Diamond,
+    Hexagon,
+    Circle,
+}
+
+impl SigilShape {
+    pub fn build(&self, size: f32) -> Path {
+        let half = size * 0.5;
+        match self {
+            SigilShape::Square => GeometryBuilder::build_as(&shapes::Rectangle {
+                extents: Vec2::splat(size),
+                origin: RectangleOrigin::Center,
+                ..default()
+            }),
+            SigilShape::Triangle => GeometryBuilder::build_as(&shapes::RegularPolygon {
+                sides: 3,
+                feature: shapes::RegularPolygonFeature::Radius(half),
+                ..default()
+            }),
+            SigilShape::Diamond => GeometryBuilder::build_as(&shapes::RegularPolygon {
+                sides: 4,
+                feature: shapes::RegularPolygonFeature::Radius(half),
+                ..default()
+            }),
+            SigilShape::Hexagon => GeometryBuilder::build_as(&shapes::RegularPolygon {
+                sides: 6,
+                feature: shapes::RegularPolygonFeature::Radius(half),
+                ..default()
+            }),
+            SigilShape::Circle => GeometryBuilder::build_as(&shapes::Circle {
+                radius: half,
+                center: Vec2::ZERO,
+            }),
+        }
+    }
+}
+
+/// How a circle of one pattern looks and sounds, from spawning to expiring
+#[derive(Debug, Clone, Copy)]
+pub struct PatternStyle {
+    pub fill: Color,
+    pub sigil_color: Color,
+    pub sigil_shape: SigilShape,
+    pub spin: f32, // Radians per second the sigils circle at, negative turns clockwise
+    pub burst_color: Color, // Ring flashed out when the circle appears and when it fades
+    pub spawn_sound: Option<&'static str>, // Asset path
+    pub expire_sound: Option<&'static str>,
+}
+
+impl PatternType {
+    pub fn style(&self) -> PatternStyle {
+        // No circle sounds recorded yet, so only the visuals are filled in
+        match self {
+            PatternType::Protection => PatternStyle {
+                fill: Color::srgba(1.0, 0.9, 0.5, 0.3),
+                sigil_color: Color::srgba(1.0, 0.95, 0.7, 0.8),
+                sigil_shape: SigilShape::Hexagon,
+                spin: 0.6,
+                burst_color: Color::srgb(1.0, 0.9, 0.5),
+                spawn_sound: None,
+                expire_sound: None,
+            },
+            PatternType::Binding => PatternStyle {
+                fill: Color::srgba(0.4, 1.0, 0.6, 0.3),
+                sigil_color: Color::srgba(0.6, 1.0, 0.75, 0.8),
+                sigil_shape: SigilShape::Diamond,
+                spin: -0.5,
+                burst_color: Color::srgb(0.4, 1.0, 0.6),
+                spawn_sound: None,
+                expire_sound: None,
+            },
+            PatternType::Banishment => PatternStyle {
+                fill: Color::srgba(0.5, 0.5, 1.0, 0.3),
+                sigil_color: Color::srgba(0.7, 0.7, 1.0, 0.8),
+                sigil_shape: SigilShape::Square,
+                spin: 1.0,
+                burst_color: Color::srgb(0.6, 0.6, 1.0),
+                spawn_sound: None,
+                expire_sound: None,
+            },
+            PatternType::Invocation => PatternStyle {
+                fill: Color::srgba(1.0, 0.45, 0.35, 0.3),
+                sigil_color: Color::srgba(1.0, 0.6, 0.5, 0.8),
+                sigil_shape: SigilShape::Triangle,
+                spin: -1.6,
+                burst_color: Color::srgb(1.0, 0.45, 0.35),
+                spawn_sound: None,
+                expire_sound: None,
+            },
+            // Matches the zones it leaves behind
+            PatternType::Manifestation => PatternStyle {
+                fill: Color::srgba(0.8, 0.4, 1.0, 0.3),
+                sigil_color: Color::srgba(0.9, 0.6, 1.0, 0.8),
+                sigil_shape: SigilShape::Circle,
+                spin: 0.4,
+                burst_color: Color::srgb(0.8, 0.4, 1.0),
+                spawn_sound: None,
+                expire_sound: None,
+            },
+        }
+    }
+}
+
+/// Ring that flashes outwards from a circle as it appears or fades
+#[derive(Component)]
+pub struct PatternBurst {
+    pub timer: Timer,
+    pub color: Color,
+}
+
+const BURST_LIFETIME: f32 = 0.35;
+const BURST_START_ALPHA: f32 = 0.6;
+const BURST_GROWTH: f32 = 0.5; // Extra scale reached by the end of its lifetime
+
+fn spawn_pattern_burst(commands: &mut Commands, position: Vec3, radius: f32, color: Color) {
+    commands.spawn((
+        PatternBurst {
+            timer: Timer::from_seconds(BURST_LIFETIME, TimerMode::Once),
+            color,
+        },
+        EffectParticle,
+        ShapeBundle {
+            path: GeometryBuilder::build_as(&shapes::Circle {
+                radius,
+                center: Vec2::ZERO,
+            }),
+            // Under the circle it came from
+            transform: Transform::from_translation(position.truncate().extend(position.z - 0.2)),
+            ..default()
+        },
+        Fill::color(color.with_alpha(BURST_START_ALPHA)),
+    ));
+}
+
+fn play_pattern_sound(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    settings: &GameSettings,
+    sound: Option<&str>,
+) {
+    if let Some(sound) = sound {
+        commands.spawn((
+            AudioPlayer::new(asset_server.load(sound.to_string())),
+            settings.sfx_playback(),
+        ));
+    }
+}
+
+pub fn play_pattern_spawn_effects(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    settings: Res<GameSettings>,
+    circle_query: Query<(&PatternType, &Transform, &Area), Added<PatternType>>,
+) {
+    for (pattern, transform, area) in circle_query.iter() {
+        let style = pattern.style();
+        spawn_pattern_burst(
+            &mut commands,
+            transform.translation,
+            area.radius,
+            style.burst_color,
+        );
+        play_pattern_sound(&mut commands, &asset_server, &settings, style.spawn_sound);
+    }
+}
+
+pub fn play_pattern_expire_effects(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    settings: Res<GameSettings>,
+    circle_query: Query<(&PatternType, &Transform, &Area), Added<MarkedForDeath>>,
+) {
+    for (pattern, transform, area) in circle_query.iter() {
+        let style = pattern.style();
+        spawn_pattern_burst(
+            &mut commands,
+            transform.translation,
+            area.radius,
+            style.burst_color,
+        );
+        play_pattern_sound(&mut commands, &asset_server, &settings, style.expire_sound);
+    }
+}
+
+/// Grows and fades bursts out, despawning them once they're gone
+pub fn fade_pattern_bursts(
+    mut commands: Commands,
+    time: Res<Time<Virtual>>,
+    mut burst_query: Query<(Entity, &mut PatternBurst, &mut Fill, &mut Transform)>,
+) {
+    for (entity, mut burst, mut fill, mut transform) in burst_query.iter_mut() {
+        burst.timer.tick(time.delta());
+        if burst.timer.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let progress = burst.timer.fraction();
+        fill.color = burst.color.with_alpha(BURST_START_ALPHA * (1.0 - progress));
+        transform.scale = Vec3::splat(1.0 + progress * BURST_GROWTH);
+    }
+}
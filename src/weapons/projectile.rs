@@ -0,0 +1,196 @@
+use crate::combat::DamageEvent;
+use crate::components::Enemy;
+use crate::death::MarkedForDeath;
+use crate::game_time::PausableTimer;
+use crate::physics::handle_rapier_context_error;
+use crate::resources::GameTextures;
+use crate::spatial_grid::EnemySpatialGrid;
+use crate::weapons::{can_hit, Attack, Damage, Lifetime, PiercingAttack};
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+/// Attack that flies on its own velocity and is used up when it hits an enemy
+#[derive(Component)]
+pub struct Projectile;
+
+/// Steers a projectile towards the nearest enemy.
+/// Put it on a weapon to have every projectile it fires home in.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Homing {
+    pub turn_rate: f32, // Radians per second
+    pub max_speed: f32,
+}
+
+/// Remaining ricochets: on hit the projectile redirects to another nearby enemy instead of being spent.
+/// Like `Homing`, weapons carry it and hand it to every projectile they fire.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Bounces(pub u32);
+
+/// Enemies a projectile passes through before it's spent or ricochets.
+/// Weapons carry it and give every projectile they fire a fresh `PiercingAttack` from it.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Pierce(pub u32);
+
+// The enemy a bounced projectile just left, so it can't hit it again while still overlapping
+#[derive(Component)]
+pub struct LastHit(pub Entity);
+
+// How far a homing projectile looks for something to chase
+const HOMING_RANGE: f32 = 300.0;
+// How far a ricochet can reach for its next target
+const BOUNCE_RANGE: f32 = 200.0;
+
+/// Helper function to spawn a projectile attack
+pub fn spawn_projectile(
+    commands: &mut Commands,
+    textures: &GameTextures,
+    sprite_index: usize,
+    origin: Vec3,
+    velocity: Vec2,
+    damage: i32,
+    radius: f32,
+) -> Entity {
+    commands
+        .spawn((
+            Attack,
+            Projectile,
+            Lifetime {
+                timer: PausableTimer::from_seconds(3.0, TimerMode::Once),
+            },
+            Damage { amount: damage },
+            Sprite::from_atlas_image(
+                textures.projectiles.clone(),
+                TextureAtlas {
+                    layout: textures.projectiles_layout.clone(),
+                    index: sprite_index,
+                },
+            ),
+            Transform::from_translation(origin)
+                .with_rotation(Quat::from_rotation_z(velocity.to_angle())),
+            RigidBody::KinematicVelocityBased,
+            Velocity::linear(velocity),
+            Sensor,
+            Collider::ball(radius),
+            ActiveEvents::COLLISION_EVENTS,
+            CollisionGroups::new(Group::GROUP_3, Group::GROUP_2),
+        ))
+        .id()
+}
+
+/// Turns homing projectiles towards the closest enemy, capped by their turn rate
+pub fn steer_homing_projectiles(
+    time: Res<Time<Virtual>>,
+    grid: Res<EnemySpatialGrid>,
+    mut query: Query<(&Homing, &mut Velocity, &mut Transform), With<Projectile>>,
+) {
+    for (homing, mut velocity, mut transform) in query.iter_mut() {
+        let position = transform.translation.truncate();
+        let Some((_, target)) = grid.nearest(position, HOMING_RANGE, |_| false) else {
+            continue;
+        };
+
+        let current = velocity.linvel;
+        let desired = target - position;
+        if current == Vec2::ZERO || desired == Vec2::ZERO {
+            continue;
+        }
+
+        let max_turn = homing.turn_rate * time.delta_secs();
+        let turn = current.angle_to(desired).clamp(-max_turn, max_turn);
+        let speed = current.length().min(homing.max_speed);
+
+        velocity.linvel = Vec2::from_angle(turn).rotate(current).normalize() * speed;
+        transform.rotation = Quat::from_rotation_z(velocity.linvel.to_angle());
+    }
+}
+
+/// Projectile collision response: damage the first enemy touched, then either ricochet
+/// towards a new target or the projectile is spent
+pub fn projectile_hit_system(
+    mut commands: Commands,
+    grid: Res<EnemySpatialGrid>,
+    mut projectile_query: Query<
+        (
+            Entity,
+            &Damage,
+            &Transform,
+            &mut Velocity,
+            Option<&mut Bounces>,
+            Option<&LastHit>,
+            Option<&mut PiercingAttack>,
+        ),
+        (With<Projectile>, Without<MarkedForDeath>),
+    >,
+    enemy_query: Query<Entity, (With<Enemy>, Without<MarkedForDeath>)>,
+    context_query: Query<&RapierContext>,
+    mut damage_events: EventWriter<DamageEvent>,
+) {
+    let rapier_context = context_query
+        .get_single()
+        .unwrap_or_else(|e| handle_rapier_context_error(e));
+
+    for (projectile_entity, damage, transform, mut velocity, bounces, last_hit, mut piercing) in
+        projectile_query.iter_mut()
+    {
+        let last_hit = last_hit.map(|last_hit| last_hit.0);
+
+        let hit = rapier_context
+            .intersection_pairs_with(projectile_entity)
+            .filter(|(_, _, intersecting)| *intersecting)
+            .map(|(collider1, collider2, _)| {
+                if collider1 == projectile_entity {
+                    collider2
+                } else {
+                    collider1
+                }
+            })
+            .find(|entity| {
+                enemy_query.contains(*entity)
+                    && Some(*entity) != last_hit
+                    && piercing
+                        .as_ref()
+                        .is_none_or(|piercing| !piercing.spent && !piercing.hit.contains(entity))
+            });
+
+        let Some(enemy_entity) = hit else {
+            continue;
+        };
+        if !can_hit(piercing.as_deref_mut(), enemy_entity) {
+            continue;
+        }
+
+        damage_events.send(DamageEvent {
+            target: enemy_entity,
+            amount: damage.amount,
+            source: Some(projectile_entity),
+        });
+
+        // Piercing projectiles keep flying until they've gone through enough enemies
+        if piercing.as_ref().is_some_and(|piercing| !piercing.spent) {
+            continue;
+        }
+
+        if let Some(mut bounces) = bounces.filter(|bounces| bounces.0 > 0) {
+            // Target re-selection: anything nearby except the enemy we just hit
+            let position = transform.translation.truncate();
+            let next_target = grid.nearest(position, BOUNCE_RANGE, |entity| entity == enemy_entity);
+
+            if let Some((_, target_position)) = next_target {
+                bounces.0 -= 1;
+                // Out of pierce, so from here on each hit uses up a ricochet instead.
+                // Enemies it already went through stay off limits.
+                if let Some(piercing) = piercing.as_mut() {
+                    piercing.spent = false;
+                }
+                let speed = velocity.linvel.length();
+                velocity.linvel = (target_position - position).normalize_or_zero() * speed;
+                commands
+                    .entity(projectile_entity)
+                    .insert(LastHit(enemy_entity));
+                continue;
+            }
+        }
+
+        commands.entity(projectile_entity).insert(MarkedForDeath);
+    }
+}
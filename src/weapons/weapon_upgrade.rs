@@ -1,10 +1,56 @@
+use crate::game_time::PausableTimer;
 use crate::menu::WeaponUpgradeConfirmedEvent;
-use crate::weapons::magick_circle::PatternType;
-use crate::weapons::{WeaponArea, WeaponCooldown, WeaponDamage, WeaponMeta, WeaponType};
+use crate::number_format::{abbreviate, percent_points};
+use crate::types::{EquipmentType, Rarity};
+use crate::weapons::arcane_bolt::apply_arcane_bolt_weapon_upgrades;
+use crate::weapons::arcane_storm::apply_arcane_storm_weapon_upgrades;
+use crate::weapons::boomerang::apply_boomerang_weapon_upgrades;
+use crate::weapons::lance::apply_lance_weapon_upgrades;
+use crate::weapons::magick_circle::{apply_magick_circle_weapon_upgrades, PatternType};
+use crate::weapons::orbitals::apply_orbitals_weapon_upgrades;
+use crate::weapons::projectile::{Bounces, Homing, Pierce};
+use crate::weapons::{
+    Amount, Knockback, WeaponArea, WeaponCooldown, WeaponDamage, WeaponDuration, WeaponMeta,
+    WeaponType,
+};
 use bevy::prelude::*;
-use bevy::utils::HashMap;
+use bevy::utils::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use strum::IntoEnumIterator;
 
-#[derive(Debug, Clone, PartialEq)]
+/// Applies confirmed upgrades to the weapons they target.
+/// Kept apart from the rest of the weapon systems so it can run without physics or a player.
+pub struct WeaponUpgradePlugin;
+
+impl Plugin for WeaponUpgradePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WeaponUpgradeConfig>()
+            .add_event::<WeaponUpgradeConfirmedEvent>()
+            .add_systems(
+                Update,
+                (
+                    update_weapon_level,
+                    (
+                        apply_common_weapon_upgrades,
+                        apply_magick_circle_weapon_upgrades,
+                        apply_projectile_weapon_upgrades,
+                        apply_arcane_bolt_weapon_upgrades,
+                        apply_orbitals_weapon_upgrades,
+                        apply_lance_weapon_upgrades,
+                        apply_boomerang_weapon_upgrades,
+                        apply_arcane_storm_weapon_upgrades,
+                    )
+                        .after(update_weapon_level),
+                ),
+            );
+    }
+}
+
+/// Level reached once a weapon's whole progression table has been applied
+pub const MAX_WEAPON_LEVEL: u32 = 8;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum WeaponUpgradeChange {
     Damage(i32),
     Area(i32),
@@ -14,33 +60,165 @@ pub enum WeaponUpgradeChange {
         pattern: PatternType,
         // offset_angle: f32,
     },
+    Homing {
+        turn_rate: f32,
+        max_speed: f32,
+    },
+    Bounces(u32),
+    SplitOnExpire {
+        count: u32,
+    },
+    ExtraProjectiles(u32),
+    ExtraOrbitals(u32),
+    RotationSpeed(i32),
+    Pierce(u32),
+    Knockback(f32),
+    Amount(u32),
+    ReverseOrbit,
+    CounterRing {
+        sigils: u32,
+    },
+    EllipticalOrbit {
+        stretch: f32, // Multiplies the orbit's width, its height stays put
+    },
 }
 
 impl std::fmt::Display for WeaponUpgradeChange {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
-            WeaponUpgradeChange::Damage(damage) => write!(f, "Increase damage by {}", damage),
-            WeaponUpgradeChange::Area(area) => write!(f, "Increase area by {}", area),
+            WeaponUpgradeChange::Damage(damage) => {
+                write!(f, "Increase damage by {}", percent_points(*damage))
+            }
+            WeaponUpgradeChange::Area(area) => {
+                write!(f, "Increase area by {}", percent_points(*area))
+            }
+            // Cooldown bonuses are negative when they shorten it
             WeaponUpgradeChange::Cooldown(cooldown) => {
-                write!(f, "Decrease cooldown by {}", cooldown)
+                write!(f, "Decrease cooldown by {}", percent_points(-*cooldown))
             }
             WeaponUpgradeChange::Duration(duration) => {
-                write!(f, "Increase duration by {}", duration)
+                write!(f, "Increase duration by {}", percent_points(*duration))
             }
             WeaponUpgradeChange::AddCircle { pattern, .. } => {
                 write!(f, "Add a {} Magick Circle", pattern)
             }
+            WeaponUpgradeChange::Homing { .. } => write!(f, "Projectiles seek out enemies"),
+            WeaponUpgradeChange::Bounces(bounces) => {
+                write!(f, "Projectiles ricochet {} more times", bounces)
+            }
+            WeaponUpgradeChange::SplitOnExpire { count } => {
+                write!(f, "Expiring circles split into {} more", count)
+            }
+            WeaponUpgradeChange::ExtraProjectiles(count) => {
+                write!(f, "Fire {} more projectiles", count)
+            }
+            WeaponUpgradeChange::ExtraOrbitals(count) => {
+                write!(f, "Summon {} more familiars", count)
+            }
+            WeaponUpgradeChange::RotationSpeed(value) => {
+                write!(f, "Familiars orbit {} faster", percent_points(*value))
+            }
+            WeaponUpgradeChange::Pierce(count) => {
+                write!(f, "Pierce through {} more enemies", count)
+            }
+            WeaponUpgradeChange::Knockback(force) => {
+                write!(
+                    f,
+                    "Knock enemies back with {} more force",
+                    abbreviate(*force as f64)
+                )
+            }
+            WeaponUpgradeChange::Amount(count) => {
+                write!(f, "Fire {} more attacks each time", count)
+            }
+            WeaponUpgradeChange::ReverseOrbit => write!(f, "Sigils orbit the other way"),
+            WeaponUpgradeChange::CounterRing { sigils } => {
+                write!(f, "Add {} sigils to a counter-rotating inner ring", sigils)
+            }
+            WeaponUpgradeChange::EllipticalOrbit { stretch } => {
+                write!(
+                    f,
+                    "Stretch sigil orbits {}x wider",
+                    abbreviate(*stretch as f64)
+                )
+            }
         }
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+// Written as a plain list of changes in weapons.ron
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct WeaponUpgradeSpec {
     pub changes: Vec<WeaponUpgradeChange>,
 }
 
-#[derive(Debug, Clone)]
+impl std::fmt::Display for WeaponUpgradeSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let changes: Vec<String> = self.changes.iter().map(ToString::to_string).collect();
+        write!(f, "{}", changes.join(", "))
+    }
+}
+
+impl WeaponUpgradeSpec {
+    // Extra attacks multiply everything else, so they're harder to come by
+    pub fn rarity(&self) -> Rarity {
+        if self
+            .changes
+            .iter()
+            .any(|change| matches!(change, WeaponUpgradeChange::Amount(_)))
+        {
+            Rarity::Rare
+        } else {
+            Rarity::Common
+        }
+    }
+}
+
+/// Stats a weapon starts with at level 1, before any upgrades or player bonuses
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WeaponBaseStats {
+    pub cooldown: f32, // Seconds
+    pub damage: i32,
+    pub area: f32,
+    #[serde(default)]
+    pub duration: Option<f32>, // Seconds, only for weapons whose attacks expire
+}
+
+impl WeaponBaseStats {
+    pub fn cooldown(&self) -> WeaponCooldown {
+        WeaponCooldown {
+            timer: PausableTimer::from_seconds(self.cooldown, TimerMode::Repeating),
+            base_duration: self.cooldown,
+            cooldown_bonus: 0,
+        }
+    }
+
+    pub fn damage(&self) -> WeaponDamage {
+        WeaponDamage {
+            base_amount: self.damage,
+            damage_bonus: 0,
+        }
+    }
+
+    pub fn area(&self) -> WeaponArea {
+        WeaponArea {
+            base_radius: self.area,
+            area_bonus: 0,
+        }
+    }
+
+    pub fn duration(&self) -> Option<WeaponDuration> {
+        self.duration.map(|base_duration| WeaponDuration {
+            base_duration,
+            duration_bonus: 0,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WeaponUpgradeData {
+    pub base: WeaponBaseStats,
     pub progression: Vec<WeaponUpgradeSpec>,
     pub limit_breaks: Vec<WeaponUpgradeSpec>,
 }
@@ -65,13 +243,13 @@ pub fn apply_common_weapon_upgrades(
         &mut WeaponDamage,
         &mut WeaponArea,
         &mut WeaponCooldown,
-        // Option<&mut WeaponDuration>,  // if you have such a component
+        Option<&mut WeaponDuration>,
         &WeaponMeta,
     )>,
 ) {
     for upgrade_event in upgrade_events.read() {
         // We already have the final `upgrade_spec` in `upgrade_event`
-        for (mut damage, mut area, mut cooldown, /*duration, */ meta) in weapon_query.iter_mut() {
+        for (mut damage, mut area, mut cooldown, mut duration, meta) in weapon_query.iter_mut() {
             if meta.weapon_type == upgrade_event.weapon_type {
                 for change in &upgrade_event.upgrade_spec.changes {
                     match &change {
@@ -84,11 +262,11 @@ pub fn apply_common_weapon_upgrades(
                         WeaponUpgradeChange::Cooldown(value) => {
                             cooldown.cooldown_bonus += *value;
                         }
-                        WeaponUpgradeChange::Duration(_) => {
-                            // If you have a separate "duration" component:
-                            // if let Some(mut dur) = duration {
-                            //     dur.duration_bonus += *value;
-                            // }
+                        WeaponUpgradeChange::Duration(value) => {
+                            // Only weapons whose attacks expire have a duration
+                            if let Some(duration) = duration.as_mut() {
+                                duration.duration_bonus += *value;
+                            }
                         }
                         // We'll ignore specialized changes (e.g. AddCircle) here.
                         // A separate "magick circle upgrades" system can handle that.
@@ -102,108 +280,173 @@ pub fn apply_common_weapon_upgrades(
     }
 }
 
-#[derive(Resource)]
+// Projectile and hit modifiers live on the weapon so every attack it fires afterwards inherits them
+pub fn apply_projectile_weapon_upgrades(
+    mut commands: Commands,
+    mut upgrade_events: EventReader<WeaponUpgradeConfirmedEvent>,
+    mut weapon_query: Query<(
+        Entity,
+        &WeaponMeta,
+        Option<&mut Bounces>,
+        Option<&mut Knockback>,
+        Option<&mut Amount>,
+        Option<&mut Pierce>,
+    )>,
+) {
+    for upgrade_event in upgrade_events.read() {
+        for (weapon_entity, meta, mut bounces, mut knockback, mut amount, mut pierce) in
+            weapon_query.iter_mut()
+        {
+            if meta.weapon_type != upgrade_event.weapon_type {
+                continue;
+            }
+
+            for change in &upgrade_event.upgrade_spec.changes {
+                match change {
+                    WeaponUpgradeChange::Homing {
+                        turn_rate,
+                        max_speed,
+                    } => {
+                        info!(
+                            "Adding homing to {} at level {}",
+                            meta.weapon_type, meta.level
+                        );
+                        commands.entity(weapon_entity).insert(Homing {
+                            turn_rate: *turn_rate,
+                            max_speed: *max_speed,
+                        });
+                    }
+                    WeaponUpgradeChange::Bounces(value) => {
+                        // Stack onto existing bounces rather than replacing them
+                        match bounces.as_mut() {
+                            Some(bounces) => bounces.0 += *value,
+                            None => {
+                                commands.entity(weapon_entity).insert(Bounces(*value));
+                            }
+                        }
+                    }
+                    WeaponUpgradeChange::Knockback(force) => match knockback.as_mut() {
+                        Some(knockback) => knockback.force += *force,
+                        None => {
+                            commands
+                                .entity(weapon_entity)
+                                .insert(Knockback { force: *force });
+                        }
+                    },
+                    WeaponUpgradeChange::Amount(count) => match amount.as_mut() {
+                        Some(amount) => amount.0 += *count,
+                        None => {
+                            commands.entity(weapon_entity).insert(Amount(*count));
+                        }
+                    },
+                    // Lances count their pierce on `Lance` itself
+                    WeaponUpgradeChange::Pierce(count) if meta.weapon_type != WeaponType::Lance => {
+                        match pierce.as_mut() {
+                            Some(pierce) => pierce.0 += *count,
+                            None => {
+                                commands.entity(weapon_entity).insert(Pierce(*count));
+                            }
+                        }
+                    }
+                    _ => (),
+                }
+            }
+        }
+    }
+}
+
+/// A maxed `base` weapon turns into `evolved` once the player carries the `catalyst`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WeaponEvolution {
+    pub base: WeaponType,
+    pub catalyst: EquipmentType,
+    pub evolved: WeaponType,
+}
+
+/// Base stats, upgrade tables and evolutions for every weapon, loaded from `WEAPONS_PATH`
+#[derive(Resource, Debug, Serialize, Deserialize)]
 pub struct WeaponUpgradeConfig {
     pub data: HashMap<WeaponType, WeaponUpgradeData>,
+    pub evolutions: Vec<WeaponEvolution>,
+    // Kept in step with the codex, nothing is locked until it says so
+    #[serde(skip)]
+    pub locked_patterns: HashSet<PatternType>,
 }
 
+const WEAPONS_PATH: &str = "assets/weapons.ron";
+// Compiled in as well, so a missing or broken file still leaves every weapon playable
+const BUILTIN_WEAPONS: &str = include_str!("../../assets/weapons.ron");
+
 impl Default for WeaponUpgradeConfig {
     fn default() -> Self {
-        Self::new()
+        Self::load()
     }
 }
 
 impl WeaponUpgradeConfig {
-    pub fn new() -> Self {
-        let mut data = HashMap::new();
-
-        // For MagickCircle, fill in all the level-based UpgradeSpecs
-        let magick_circle_data = WeaponUpgradeData {
-            progression: vec![
-                // Level 2: Initial power boost
-                WeaponUpgradeSpec {
-                    changes: vec![WeaponUpgradeChange::Damage(2), WeaponUpgradeChange::Area(1)],
-                },
-                // Level 3: First additional circle
-                WeaponUpgradeSpec {
-                    changes: vec![WeaponUpgradeChange::AddCircle {
-                        pattern: PatternType::Banishment,
-                        // offset_angle: std::f32::consts::PI,
-                    }],
-                },
-                // Level 4: Second circle + minor boost
-                WeaponUpgradeSpec {
-                    changes: vec![
-                        WeaponUpgradeChange::AddCircle {
-                            pattern: PatternType::Banishment,
-                            // offset_angle: std::f32::consts::PI * 0.5,
-                        },
-                        WeaponUpgradeChange::Damage(1),
-                        WeaponUpgradeChange::Area(1),
-                    ],
-                },
-                // Level 5: Third circle
-                WeaponUpgradeSpec {
-                    changes: vec![WeaponUpgradeChange::AddCircle {
-                        pattern: PatternType::Banishment,
-                        // offset_angle: std::f32::consts::PI * 1.5,
-                    }],
-                },
-                // Level 6: Significant power boost + fourth circle
-                WeaponUpgradeSpec {
-                    changes: vec![
-                        WeaponUpgradeChange::AddCircle {
-                            pattern: PatternType::Banishment,
-                            // offset_angle: std::f32::consts::PI * 2.0,
-                        },
-                        WeaponUpgradeChange::Damage(2),
-                        WeaponUpgradeChange::Area(1),
-                    ],
-                },
-                // Level 7: Fifth circle + minor boost
-                WeaponUpgradeSpec {
-                    changes: vec![
-                        WeaponUpgradeChange::AddCircle {
-                            pattern: PatternType::Banishment,
-                            // offset_angle: std::f32::consts::PI * 2.5,
-                        },
-                        WeaponUpgradeChange::Damage(1),
-                        WeaponUpgradeChange::Area(1),
-                    ],
-                },
-                // Level 8: Final circle + major power spike
-                WeaponUpgradeSpec {
-                    changes: vec![
-                        WeaponUpgradeChange::AddCircle {
-                            pattern: PatternType::Banishment,
-                            // offset_angle: std::f32::consts::PI * 3.0,
-                        },
-                        WeaponUpgradeChange::Damage(3),
-                        WeaponUpgradeChange::Area(2),
-                    ],
-                },
-            ],
-            limit_breaks: vec![
-                WeaponUpgradeSpec {
-                    changes: vec![WeaponUpgradeChange::Damage(2)],
-                },
-                WeaponUpgradeSpec {
-                    changes: vec![WeaponUpgradeChange::Area(2)],
-                },
-                WeaponUpgradeSpec {
-                    changes: vec![WeaponUpgradeChange::Duration(2)],
-                },
-                WeaponUpgradeSpec {
-                    changes: vec![WeaponUpgradeChange::Cooldown(-2)],
-                },
-            ],
+    /// Reads `WEAPONS_PATH`, falling back to the copy shipped with the build
+    pub fn load() -> Self {
+        let Ok(contents) = fs::read_to_string(WEAPONS_PATH) else {
+            warn!("No {} found, using built-in weapons", WEAPONS_PATH);
+            return Self::builtin();
         };
 
-        // Insert into the map
-        data.insert(WeaponType::MagickCircle, magick_circle_data);
+        match ron::from_str::<Self>(&contents) {
+            Ok(config) => match config.validate() {
+                Ok(()) => config,
+                Err(e) => {
+                    warn!(
+                        "{} is incomplete, using built-in weapons: {}",
+                        WEAPONS_PATH, e
+                    );
+                    Self::builtin()
+                }
+            },
+            Err(e) => {
+                warn!(
+                    "Failed to parse {}, using built-in weapons: {}",
+                    WEAPONS_PATH, e
+                );
+                Self::builtin()
+            }
+        }
+    }
+
+    /// Every weapon needs upgrade data, and limit breaks to offer once it is maxed
+    pub fn validate(&self) -> Result<(), String> {
+        for weapon in WeaponType::iter() {
+            match self.data.get(&weapon) {
+                None => return Err(format!("{} has no upgrade data", weapon)),
+                Some(data) if data.limit_breaks.is_empty() => {
+                    return Err(format!("{} has no limit breaks", weapon))
+                }
+                Some(_) => (),
+            }
+        }
+        Ok(())
+    }
+
+    pub fn builtin() -> Self {
+        ron::from_str(BUILTIN_WEAPONS).expect("Built-in weapons.ron should always parse")
+    }
 
-        // Potentially do the same for other weapon types...
-        Self { data }
+    pub fn base_stats(&self, weapon: WeaponType) -> WeaponBaseStats {
+        self.data
+            .get(&weapon)
+            .unwrap_or_else(|| panic!("Unable to find weapon {} in weapon upgrade config", weapon))
+            .base
+    }
+
+    pub fn evolution_from(&self, base: WeaponType) -> Option<&WeaponEvolution> {
+        self.evolutions
+            .iter()
+            .find(|evolution| evolution.base == base)
+    }
+
+    pub fn evolution_into(&self, evolved: WeaponType) -> Option<&WeaponEvolution> {
+        self.evolutions
+            .iter()
+            .find(|evolution| evolution.evolved == evolved)
     }
 
     pub fn get_next_upgrades(&self, weapon: WeaponType, level: u32) -> Vec<WeaponUpgradeSpec> {
@@ -212,10 +455,29 @@ impl WeaponUpgradeConfig {
             .get(&weapon)
             .unwrap_or_else(|| panic!("Unable to find weapon {} in weapon upgrade config", weapon));
 
-        if level < weapon_upgrade_data.progression.len() as u32 {
-            vec![weapon_upgrade_data.progression[level as usize].clone()]
-        } else {
-            weapon_upgrade_data.limit_breaks.to_vec()
+        // progression[0] takes a weapon from level 1 to 2
+        match weapon_upgrade_data
+            .progression
+            .get(level.saturating_sub(1) as usize)
+        {
+            Some(spec) => vec![self.gate_patterns(spec.clone())],
+            None => weapon_upgrade_data
+                .limit_breaks
+                .iter()
+                .map(|spec| self.gate_patterns(spec.clone()))
+                .collect(),
+        }
+    }
+
+    // Circles of a pattern that isn't unlocked yet come in as Banishment instead
+    fn gate_patterns(&self, mut spec: WeaponUpgradeSpec) -> WeaponUpgradeSpec {
+        for change in &mut spec.changes {
+            if let WeaponUpgradeChange::AddCircle { pattern } = change {
+                if self.locked_patterns.contains(pattern) {
+                    *pattern = PatternType::Banishment;
+                }
+            }
         }
+        spec
     }
 }
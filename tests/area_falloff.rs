@@ -0,0 +1,31 @@
+use survivors_prototype::weapons::magick_circle::PatternType;
+use survivors_prototype::weapons::{AreaFalloff, DamageFalloff};
+
+#[test]
+fn falloff_runs_from_full_damage_at_the_center_to_the_edge_share() {
+    for falloff in [
+        DamageFalloff::Linear { edge: 0.5 },
+        DamageFalloff::Smooth { edge: 0.5 },
+    ] {
+        assert_eq!(falloff.factor(0.0, 100.0), 1.0);
+        assert_eq!(falloff.factor(100.0, 100.0), 0.5);
+        // Enemies overlapping the edge from outside don't drop any further
+        assert_eq!(falloff.factor(112.0, 100.0), 0.5);
+    }
+
+    // Smooth holds on to more damage halfway out
+    let halfway = |falloff: DamageFalloff| falloff.factor(50.0, 100.0);
+    assert_eq!(halfway(DamageFalloff::Linear { edge: 0.5 }), 0.75);
+    assert!(halfway(DamageFalloff::Smooth { edge: 0.5 }) > 0.75);
+}
+
+#[test]
+fn only_patterns_with_falloff_lose_damage() {
+    let falloff = AreaFalloff::default();
+
+    assert_eq!(falloff.apply(PatternType::Banishment, 10, 0.0, 50.0), 10);
+    assert!(falloff.apply(PatternType::Banishment, 10, 50.0, 50.0) < 10);
+    assert_eq!(falloff.apply(PatternType::Binding, 10, 50.0, 50.0), 10);
+    // A hit at the edge always does something
+    assert_eq!(falloff.apply(PatternType::Banishment, 1, 50.0, 50.0), 1);
+}
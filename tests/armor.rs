@@ -0,0 +1,40 @@
+use bevy::prelude::*;
+use survivors_prototype::combat::{handle_damage, DamageEvent};
+use survivors_prototype::components::{Armor, Health};
+
+#[test]
+fn armor_reduces_hits_but_never_below_one() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_event::<DamageEvent>()
+        .add_systems(Update, handle_damage);
+
+    let target = app
+        .world_mut()
+        .spawn((
+            Health {
+                current: 100,
+                maximum: 100,
+            },
+            Armor(3),
+        ))
+        .id();
+    let hit = |amount| DamageEvent {
+        target,
+        amount,
+        source: None,
+    };
+
+    app.world_mut().send_event(hit(10));
+    app.update();
+    assert_eq!(app.world().get::<Health>(target).unwrap().current, 93);
+
+    app.world_mut().send_event(hit(2));
+    app.update();
+    assert_eq!(app.world().get::<Health>(target).unwrap().current, 92);
+
+    // Healing goes through untouched
+    app.world_mut().send_event(hit(-5));
+    app.update();
+    assert_eq!(app.world().get::<Health>(target).unwrap().current, 97);
+}
@@ -0,0 +1,54 @@
+use survivors_prototype::balance::BalanceOverride;
+use survivors_prototype::definitions::Definitions;
+use survivors_prototype::weapons::WeaponType;
+
+fn parse(contents: &str) -> BalanceOverride {
+    ron::from_str(contents).expect("override parses")
+}
+
+#[test]
+fn only_the_given_values_change() {
+    let mut definitions = Definitions::builtin();
+    let imp = definitions.enemies["imp"].clone();
+
+    let applied = parse(r#"(enemies: {"imp": (speed: Some(12.5), health: Some(99))})"#)
+        .apply(&mut definitions);
+
+    let tuned = &definitions.enemies["imp"];
+    assert_eq!(applied, 2);
+    assert_eq!(tuned.speed, 12.5);
+    assert_eq!(tuned.health, 99);
+    assert_eq!(tuned.experience_value, imp.experience_value);
+    assert_eq!(tuned.cost, imp.cost);
+}
+
+#[test]
+fn weapons_and_the_active_stage_can_be_tuned() {
+    let mut definitions = Definitions::builtin();
+
+    let applied = parse(
+        "(weapons: {MagickCircle: (damage: Some(40))}, director: (max_enemies: Some(7), kill_goal: Some(50)))",
+    )
+    .apply(&mut definitions);
+
+    assert_eq!(applied, 3);
+    let circle = definitions
+        .weapons
+        .values()
+        .find(|definition| definition.weapon == WeaponType::MagickCircle)
+        .expect("the override adds a definition when there isn't one");
+    assert_eq!(circle.damage, Some(40));
+    assert_eq!(circle.cooldown, None);
+
+    let stage = definitions.stage().expect("default stage exists");
+    assert_eq!(stage.max_enemies, 7);
+    assert_eq!(stage.kill_goal, Some(50));
+}
+
+#[test]
+fn unknown_enemies_are_skipped() {
+    let mut definitions = Definitions::builtin();
+    let applied = parse(r#"(enemies: {"nobody": (speed: Some(1.0))})"#).apply(&mut definitions);
+    assert_eq!(applied, 0);
+    assert!(!definitions.enemies.contains_key("nobody"));
+}
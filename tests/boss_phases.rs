@@ -0,0 +1,21 @@
+use survivors_prototype::boss::BossPhase;
+
+#[test]
+fn bosses_move_through_phases_as_they_lose_health() {
+    let phases: Vec<_> = [1.0, 0.7, 0.5, 0.34, 0.2, 0.0]
+        .into_iter()
+        .map(BossPhase::for_health)
+        .collect();
+
+    assert_eq!(
+        phases,
+        [
+            BossPhase::Hunt,
+            BossPhase::Hunt,
+            BossPhase::Barrage,
+            BossPhase::Barrage,
+            BossPhase::Frenzy,
+            BossPhase::Frenzy,
+        ]
+    );
+}
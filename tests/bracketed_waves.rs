@@ -0,0 +1,77 @@
+use survivors_prototype::definitions::Definitions;
+use survivors_prototype::waves::{BracketedWaves, DirectorStrategy, WaveDirector};
+
+const BRACKETED: BracketedWaves = BracketedWaves {
+    intermission: 5.0,
+    gold_reward: 10,
+};
+
+// Spends everything the director can afford, returns what it cost
+fn spend_all(director: &mut WaveDirector, definitions: &Definitions) -> u32 {
+    let mut spent = 0;
+    while let Some(definition) = director.spend(definitions, 0.5) {
+        spent += definition.cost;
+    }
+    spent
+}
+
+#[test]
+fn bracketed_waves_wait_to_be_cleared() {
+    let definitions = Definitions::builtin();
+    let stage = definitions.stage().expect("default stage exists");
+    let mut director = WaveDirector::new(stage.waves.clone())
+        .with_strategy(DirectorStrategy::Bracketed(BRACKETED));
+    let first = stage.waves[0].clone();
+
+    // Well past the wave's duration, it still hasn't moved on
+    let mut spent = 0;
+    for _ in 0..(first.duration as usize * 20) {
+        assert!(!director.advance(0.1));
+        spent += spend_all(&mut director, &definitions);
+    }
+    assert_eq!(director.number, 0);
+    assert!(director.fully_spawned());
+    assert!(spent <= first.budget);
+    assert!(spent + definitions.enemies["imp"].cost > first.budget);
+
+    // Enemies still standing keep the wave going
+    assert_eq!(director.clear(true), None);
+    assert_eq!(director.clear(false), Some(BRACKETED));
+    assert_eq!(director.intermission_left(), Some(5.0));
+    // Only pays out once
+    assert_eq!(director.clear(false), None);
+
+    // No spawns during the intermission
+    for _ in 0..49 {
+        assert!(!director.advance(0.1));
+        assert_eq!(spend_all(&mut director, &definitions), 0);
+    }
+    assert!(director.advance(0.2));
+    assert_eq!(director.number, 1);
+    assert_eq!(director.intermission_left(), None);
+    assert!(!director.fully_spawned());
+}
+
+#[test]
+fn continuous_waves_never_wait_to_be_cleared() {
+    let definitions = Definitions::builtin();
+    let stage = definitions.stage().expect("default stage exists");
+    assert_eq!(stage.strategy, DirectorStrategy::Continuous);
+    let mut director = WaveDirector::new(stage.waves.clone());
+
+    director.advance(stage.waves[0].duration * 0.99);
+    spend_all(&mut director, &definitions);
+    assert_eq!(director.clear(false), None);
+    assert_eq!(director.intermission_left(), None);
+}
+
+#[test]
+fn gauntlet_stage_plays_the_default_waves_bracketed() {
+    let definitions = Definitions::builtin();
+    let default = &definitions.stages["default"];
+    let gauntlet = &definitions.stages["gauntlet"];
+
+    assert_eq!(gauntlet.waves.len(), default.waves.len());
+    assert!(matches!(gauntlet.strategy, DirectorStrategy::Bracketed(_)));
+    assert!(gauntlet.bosses.is_empty() && gauntlet.surges.is_empty());
+}
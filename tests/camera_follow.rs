@@ -0,0 +1,47 @@
+use bevy::prelude::Vec2;
+use survivors_prototype::camera::CameraFollow;
+
+#[test]
+fn camera_eases_towards_the_player_without_overshooting() {
+    let follow = CameraFollow::default();
+    let player = Vec2::new(100.0, 0.0);
+
+    let mut camera = Vec2::ZERO;
+    let mut last_gap = player.distance(camera);
+    for _ in 0..60 {
+        camera = follow.approach(camera, follow.target(camera, player), 1.0 / 60.0);
+        let gap = player.distance(camera);
+        assert!(gap < last_gap);
+        assert!(camera.x <= player.x);
+        last_gap = gap;
+    }
+    assert!(last_gap < 1.0);
+}
+
+#[test]
+fn no_smoothing_sticks_to_the_player() {
+    let mut follow = CameraFollow::default();
+    follow.smoothing = 0.0;
+    let player = Vec2::new(30.0, -40.0);
+
+    assert_eq!(follow.approach(Vec2::ZERO, player, 1.0 / 60.0), player);
+}
+
+#[test]
+fn deadzone_only_follows_once_the_player_leaves_it() {
+    let mut follow = CameraFollow::default();
+    follow.deadzone = Some(Vec2::new(50.0, 30.0));
+    let camera = Vec2::ZERO;
+
+    // Inside the box the camera stays where it is
+    assert_eq!(follow.target(camera, Vec2::new(40.0, -20.0)), camera);
+    // Outside, it heads just far enough to put the player on the edge
+    assert_eq!(
+        follow.target(camera, Vec2::new(80.0, 10.0)),
+        Vec2::new(30.0, 0.0)
+    );
+    assert_eq!(
+        follow.target(camera, Vec2::new(-60.0, -50.0)),
+        Vec2::new(-10.0, -20.0)
+    );
+}
@@ -0,0 +1,14 @@
+use survivors_prototype::character_select::character_choices;
+use survivors_prototype::components::Character;
+use survivors_prototype::weapons::WeaponType;
+
+#[test]
+fn choices_cover_base_weapons_only() {
+    let choices = character_choices();
+
+    assert!(choices.contains(&(Character::Magus, WeaponType::MagickCircle)));
+    assert!(choices.contains(&(Character::Magus, WeaponType::Boomerang)));
+    assert!(choices
+        .iter()
+        .all(|(_, weapon_type)| !weapon_type.is_evolution()));
+}
@@ -0,0 +1,329 @@
+//! Shared helpers for tests that drive the game through a stripped-down `App`
+
+// Each test crate only uses some of these
+#![allow(dead_code)]
+
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use survivors_prototype::components::{Armor, Equipment, Health, Luck, Player};
+use survivors_prototype::controls::KeyBindings;
+use survivors_prototype::experience::{check_level_up, Experience};
+use survivors_prototype::gameplay_events::GameplayEvent;
+use survivors_prototype::menu::{
+    GenericUpgradeConfirmedEvent, MenuPlugin, WeaponUpgradeConfirmedEvent,
+};
+use survivors_prototype::player_handle::PlayerHandlePlugin;
+use survivors_prototype::resources::{GameRng, GameState, RunModifiers};
+use survivors_prototype::upgrade::{
+    handle_generic_upgrade, heal_on_level_up, GenericUpgrade, LevelUpHealing, UpgradePool,
+};
+use survivors_prototype::weapons::arcane_bolt::ArcaneBolt;
+use survivors_prototype::weapons::boomerang::Boomerang;
+use survivors_prototype::weapons::lance::Lance;
+use survivors_prototype::weapons::magick_circle::MagickCircle;
+use survivors_prototype::weapons::orbitals::Orbitals;
+use survivors_prototype::weapons::projectile::{Bounces, Homing, Pierce};
+use survivors_prototype::weapons::weapon_upgrade::{
+    WeaponUpgradeChange, WeaponUpgradeConfig, WeaponUpgradePlugin, WeaponUpgradeSpec,
+};
+use survivors_prototype::weapons::{
+    spawn_weapon, Amount, Knockback, WeaponArea, WeaponCooldown, WeaponDamage, WeaponDuration,
+    WeaponMeta, WeaponType,
+};
+
+/// Just the upgrade systems on `MinimalPlugins`, no rendering or physics
+pub fn upgrade_test_app() -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(WeaponUpgradePlugin);
+    app
+}
+
+/// Upgrade systems plus the level-up check and menu, starting mid-run in `Playing`
+pub fn level_up_test_app() -> App {
+    let mut app = upgrade_test_app();
+    app.add_plugins(StatesPlugin)
+        .add_plugins(MenuPlugin)
+        .add_plugins(PlayerHandlePlugin)
+        .insert_state(GameState::Playing)
+        .init_resource::<ButtonInput<KeyCode>>()
+        .init_resource::<KeyBindings>()
+        .init_resource::<UpgradePool>()
+        .init_resource::<RunModifiers>()
+        .insert_resource(GameRng::from_seed(0))
+        .init_resource::<LevelUpHealing>()
+        .add_event::<GenericUpgradeConfirmedEvent>()
+        .add_event::<GameplayEvent>()
+        .add_systems(
+            Update,
+            (
+                check_level_up.run_if(in_state(GameState::Playing)),
+                (handle_generic_upgrade, heal_on_level_up).chain(),
+            ),
+        );
+    app
+}
+
+/// A bare player carrying one level 1 copy of each given weapon
+pub fn spawn_player_with_weapons(app: &mut App, weapons: &[WeaponType]) -> Entity {
+    let world = app.world_mut();
+    let player = world
+        .spawn((
+            Player {
+                speed: 150.0,
+                magnet_strength: 100.0,
+                magnet_speed: 200.0,
+            },
+            Experience {
+                current: 0,
+                level: 1,
+            },
+            Luck(0),
+            Armor::default(),
+            Equipment::default(),
+            Health {
+                current: 100,
+                maximum: 100,
+            },
+        ))
+        .id();
+
+    world.resource_scope(|world, config: Mut<WeaponUpgradeConfig>| {
+        let mut commands = world.commands();
+        for weapon_type in weapons {
+            spawn_weapon(&mut commands, player, *weapon_type, &config);
+        }
+    });
+    world.flush();
+
+    player
+}
+
+/// Confirms an upgrade the way the level-up menu does and lets the systems apply it
+pub fn confirm_upgrade(app: &mut App, weapon_type: WeaponType, upgrade_spec: WeaponUpgradeSpec) {
+    app.world_mut().send_event(WeaponUpgradeConfirmedEvent {
+        weapon_type,
+        upgrade_spec,
+    });
+    app.update();
+}
+
+/// Confirms a generic level-up choice and lets the systems apply it
+pub fn confirm_generic_upgrade(app: &mut App, generic_upgrade_type: GenericUpgrade) {
+    app.world_mut().send_event(GenericUpgradeConfirmedEvent {
+        generic_upgrade_type,
+    });
+    app.update();
+}
+
+/// What a weapon should look like after a series of upgrades, built up alongside the real thing
+#[derive(Debug, Default)]
+pub struct ExpectedWeapon {
+    pub level: u32,
+    pub damage_bonus: i32,
+    pub area_bonus: i32,
+    pub cooldown_bonus: i32,
+    pub duration_bonus: i32,
+    pub added_circles: usize,
+    pub split_count: u32,
+    pub orbit_reversed: bool,
+    pub counter_sigils: u32,
+    pub bounces: u32,
+    pub homing: bool,
+    pub extra_projectiles: u32,
+    pub extra_orbitals: u32,
+    pub rotation_bonus: i32,
+    pub extra_pierce: u32,
+    pub knockback: f32,
+    pub amount: u32,
+}
+
+impl ExpectedWeapon {
+    pub fn new() -> Self {
+        Self {
+            level: 1,
+            ..default()
+        }
+    }
+
+    pub fn apply(&mut self, spec: &WeaponUpgradeSpec) {
+        self.level += 1;
+        for change in &spec.changes {
+            match change {
+                WeaponUpgradeChange::Damage(value) => self.damage_bonus += value,
+                WeaponUpgradeChange::Area(value) => self.area_bonus += value,
+                WeaponUpgradeChange::Cooldown(value) => self.cooldown_bonus += value,
+                WeaponUpgradeChange::Duration(value) => self.duration_bonus += value,
+                WeaponUpgradeChange::AddCircle { .. } => self.added_circles += 1,
+                WeaponUpgradeChange::SplitOnExpire { count } => self.split_count += count,
+                WeaponUpgradeChange::Bounces(value) => self.bounces += value,
+                WeaponUpgradeChange::Homing { .. } => self.homing = true,
+                WeaponUpgradeChange::ExtraProjectiles(count) => self.extra_projectiles += count,
+                WeaponUpgradeChange::ExtraOrbitals(count) => self.extra_orbitals += count,
+                WeaponUpgradeChange::RotationSpeed(value) => self.rotation_bonus += value,
+                WeaponUpgradeChange::Pierce(count) => self.extra_pierce += count,
+                WeaponUpgradeChange::Knockback(force) => self.knockback += force,
+                WeaponUpgradeChange::Amount(count) => self.amount += count,
+                WeaponUpgradeChange::ReverseOrbit => self.orbit_reversed = !self.orbit_reversed,
+                WeaponUpgradeChange::CounterRing { sigils } => self.counter_sigils += sigils,
+                WeaponUpgradeChange::EllipticalOrbit { .. } => (),
+            }
+        }
+    }
+
+    /// Panics with `context` if the weapon's components don't match
+    pub fn assert_matches(&self, app: &mut App, weapon_type: WeaponType, context: &str) {
+        let world = app.world_mut();
+        let mut query = world.query::<(
+            &WeaponMeta,
+            &WeaponDamage,
+            &WeaponArea,
+            &WeaponCooldown,
+            Option<&WeaponDuration>,
+            Option<&MagickCircle>,
+            Option<&ArcaneBolt>,
+            Option<&Orbitals>,
+            Option<&Lance>,
+            Option<&Boomerang>,
+            Option<&Bounces>,
+            Option<&Pierce>,
+            Option<&Knockback>,
+            Option<&Amount>,
+            Has<Homing>,
+        )>();
+
+        let (
+            meta,
+            damage,
+            area,
+            cooldown,
+            duration,
+            circle,
+            bolt,
+            orbitals,
+            lance,
+            boomerang,
+            bounces,
+            pierce,
+            knockback,
+            amount,
+            homing,
+        ) = query
+            .iter(world)
+            .find(|(meta, ..)| meta.weapon_type == weapon_type)
+            .unwrap_or_else(|| panic!("{}: no {} weapon found", context, weapon_type));
+
+        assert_eq!(meta.level, self.level, "{}: level", context);
+        assert_eq!(
+            damage.damage_bonus, self.damage_bonus,
+            "{}: damage",
+            context
+        );
+        assert_eq!(area.area_bonus, self.area_bonus, "{}: area", context);
+        assert_eq!(
+            cooldown.cooldown_bonus, self.cooldown_bonus,
+            "{}: cooldown",
+            context
+        );
+        if let Some(duration) = duration {
+            assert_eq!(
+                duration.duration_bonus, self.duration_bonus,
+                "{}: duration",
+                context
+            );
+        }
+        assert_eq!(
+            bounces.map_or(0, |bounces| bounces.0),
+            self.bounces,
+            "{}: bounces",
+            context
+        );
+        assert_eq!(
+            knockback.map_or(0.0, |knockback| knockback.force),
+            self.knockback,
+            "{}: knockback",
+            context
+        );
+        assert_eq!(
+            amount.map_or(0, |amount| amount.0),
+            self.amount,
+            "{}: amount",
+            context
+        );
+        // Some weapons come with homing built in
+        assert!(homing || !self.homing, "{}: homing", context);
+
+        if let Some(circle) = circle {
+            assert_eq!(
+                circle.patterns.len(),
+                1 + self.added_circles,
+                "{}: circles",
+                context
+            );
+            assert_eq!(
+                circle.split_on_expire.map_or(0, |split| split.count),
+                self.split_count,
+                "{}: split",
+                context
+            );
+            assert_eq!(
+                circle.sigils.reversed, self.orbit_reversed,
+                "{}: orbit direction",
+                context
+            );
+            assert_eq!(
+                circle.sigils.counter_count, self.counter_sigils,
+                "{}: counter ring",
+                context
+            );
+        }
+
+        if let Some(bolt) = bolt {
+            assert_eq!(
+                bolt.projectiles,
+                1 + self.extra_projectiles,
+                "{}: projectiles",
+                context
+            );
+        }
+
+        if let Some(orbitals) = orbitals {
+            assert_eq!(
+                orbitals.count,
+                2 + self.extra_orbitals,
+                "{}: familiars",
+                context
+            );
+            assert_eq!(
+                orbitals.speed_bonus, self.rotation_bonus,
+                "{}: rotation",
+                context
+            );
+        }
+
+        if let Some(boomerang) = boomerang {
+            assert_eq!(
+                boomerang.count,
+                1 + self.extra_projectiles,
+                "{}: blades",
+                context
+            );
+        }
+
+        if let Some(lance) = lance {
+            assert_eq!(
+                lance.pierce_count,
+                2 + self.extra_pierce,
+                "{}: pierce",
+                context
+            );
+        } else {
+            assert_eq!(
+                pierce.map_or(0, |pierce| pierce.0),
+                self.extra_pierce,
+                "{}: pierce",
+                context
+            );
+        }
+    }
+}
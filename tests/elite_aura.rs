@@ -0,0 +1,27 @@
+use survivors_prototype::components::Health;
+use survivors_prototype::elite::aura_radius;
+
+#[test]
+fn aura_pulls_in_as_the_elite_is_hurt() {
+    let full = aura_radius(&Health {
+        current: 200,
+        maximum: 200,
+    });
+    let hurt = aura_radius(&Health {
+        current: 100,
+        maximum: 200,
+    });
+    let nearly_dead = aura_radius(&Health {
+        current: 1,
+        maximum: 200,
+    });
+    let overkilled = aura_radius(&Health {
+        current: -50,
+        maximum: 200,
+    });
+
+    assert!(full > hurt && hurt > nearly_dead);
+    // Something is always left to see until it dies
+    assert!(overkilled > 0.0);
+    assert!(nearly_dead >= overkilled);
+}
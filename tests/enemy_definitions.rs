@@ -0,0 +1,105 @@
+use bevy::prelude::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use survivors_prototype::components::EnemyKind;
+use survivors_prototype::definitions::{Definitions, SurgeFormation};
+use survivors_prototype::difficulty::DifficultyCurve;
+use survivors_prototype::systems::surge_positions;
+use survivors_prototype::waves::WaveDirector;
+
+#[test]
+fn only_first_wave_enemies_spawn_early_in_a_run() {
+    let definitions = Definitions::builtin();
+    let stage = definitions.stage().expect("default stage exists");
+    let mut director = WaveDirector::new(stage.waves.clone());
+
+    director.advance(10.0);
+    for i in 0..20 {
+        director.points = 100.0;
+        let definition = director
+            .spend(&definitions, i as f32 / 20.0)
+            .expect("the first wave can afford an enemy");
+        assert_eq!(definition.kind, EnemyKind::Imp);
+    }
+}
+
+#[test]
+fn every_wave_enemy_can_spawn() {
+    let definitions = Definitions::builtin();
+    let stage = definitions.stage().expect("default stage exists");
+
+    for wave in &stage.waves {
+        for entry in &wave.enemies {
+            let definition = definitions
+                .enemies
+                .get(&entry.enemy)
+                .unwrap_or_else(|| panic!("unknown wave enemy {}", entry.enemy));
+            assert!(entry.weight > 0.0, "{} can never be picked", entry.enemy);
+            assert!(
+                definition.cost <= wave.budget,
+                "{} costs more than its wave's whole budget",
+                entry.enemy
+            );
+        }
+    }
+}
+
+#[test]
+fn waves_spend_their_budget_and_move_on() {
+    let definitions = Definitions::builtin();
+    let stage = definitions.stage().expect("default stage exists");
+    let mut director = WaveDirector::new(stage.waves.clone());
+    let first = stage.waves[0].clone();
+
+    let mut spent = 0;
+    let mut started_next = false;
+    // One step past the end, so rounding in the clock can't leave the wave unfinished
+    let steps = (first.duration / 0.1).round() as usize + 1;
+    for _ in 0..steps {
+        started_next |= director.advance(0.1);
+        while let Some(definition) = director.spend(&definitions, 0.5) {
+            spent += definition.cost;
+        }
+    }
+
+    assert!(started_next, "the first wave never ended");
+    assert_eq!(director.number, 1);
+    // Whatever was left unspent when the wave ended is less than one more imp
+    assert!(spent + definitions.enemies["imp"].cost > first.budget);
+    assert!(spent <= first.budget);
+}
+
+#[test]
+fn surges_spawn_the_whole_crowd_around_the_player() {
+    let center = Vec2::new(100.0, -50.0);
+    let mut rng = StdRng::seed_from_u64(7);
+
+    for formation in [SurgeFormation::Ring, SurgeFormation::Wall] {
+        let positions = surge_positions(formation, 12, center, &mut rng);
+        assert_eq!(positions.len(), 12, "{:?} lost enemies", formation);
+        assert!(
+            positions
+                .iter()
+                .all(|position| position.distance(center) > 300.0),
+            "{:?} spawned enemies on top of the player",
+            formation
+        );
+    }
+}
+
+#[test]
+fn difficulty_curves_start_flat_and_ramp_up() {
+    let breakpoints = DifficultyCurve::Breakpoints(vec![(60.0, 2.0), (120.0, 3.0)]);
+    assert_eq!(breakpoints.multiplier(0.0), 1.0);
+    assert_eq!(breakpoints.multiplier(30.0), 1.5);
+    assert_eq!(breakpoints.multiplier(90.0), 2.5);
+    assert_eq!(breakpoints.multiplier(600.0), 3.0);
+
+    let linear = DifficultyCurve::Linear { per_minute: 0.5 };
+    assert_eq!(linear.multiplier(0.0), 1.0);
+    assert_eq!(linear.multiplier(120.0), 2.0);
+
+    let exponential = DifficultyCurve::Exponential { per_minute: 1.0 };
+    assert_eq!(exponential.multiplier(0.0), 1.0);
+    assert_eq!(exponential.multiplier(180.0), 8.0);
+}
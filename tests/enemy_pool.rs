@@ -0,0 +1,53 @@
+use bevy::prelude::*;
+use survivors_prototype::components::{Enemy, EnemyBehavior, EnemyKind, Health};
+use survivors_prototype::death::{cleanup_marked_entities, MarkedForDespawn};
+use survivors_prototype::enemy_pool::{EnemyPool, EnemyPoolPlugin, Pooled};
+use survivors_prototype::game_time::PausableTimer;
+use survivors_prototype::status::Chilled;
+
+#[test]
+fn dead_enemies_are_parked_and_everything_else_despawns() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(EnemyPoolPlugin)
+        .add_systems(Update, cleanup_marked_entities);
+
+    let enemy = app
+        .world_mut()
+        .spawn((
+            Enemy {
+                kind: EnemyKind::Imp,
+                speed: 100.0,
+                experience_value: 50,
+                contact_damage: 1,
+                behavior: EnemyBehavior::Chase,
+            },
+            Health {
+                current: 0,
+                maximum: 20,
+            },
+            Chilled {
+                slow: 0.5,
+                remaining: PausableTimer::from_seconds(2.0, TimerMode::Once),
+            },
+            Transform::from_xyz(10.0, 20.0, 0.0),
+            MarkedForDespawn,
+        ))
+        .id();
+    let orb = app
+        .world_mut()
+        .spawn((Transform::default(), MarkedForDespawn))
+        .id();
+
+    app.update();
+
+    assert!(app.world().get_entity(orb).is_err());
+    let parked = app.world().entity(enemy);
+    assert!(parked.contains::<Pooled>());
+    // Nothing from its last life comes along to the next one
+    assert!(!parked.contains::<Enemy>());
+    assert!(!parked.contains::<Health>());
+    assert!(!parked.contains::<Chilled>());
+    assert!(!parked.contains::<MarkedForDespawn>());
+    assert_eq!(app.world().resource::<EnemyPool>().idle(), 1);
+}
@@ -0,0 +1,98 @@
+use bevy::prelude::*;
+use survivors_prototype::components::{
+    AreaMultiplier, CooldownReduction, DamageMultiplier, Equipment, Health, Player,
+};
+use survivors_prototype::equipment::{apply_equipment_stats, equipment_definition};
+use survivors_prototype::menu::GenericUpgradeConfirmedEvent;
+use survivors_prototype::types::EquipmentType;
+use survivors_prototype::upgrade::{
+    handle_equipment_upgrade, GenericUpgrade, UpgradePool, UpgradeType,
+};
+
+fn equipment_app() -> (App, Entity) {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_event::<GenericUpgradeConfirmedEvent>()
+        .add_systems(
+            Update,
+            (handle_equipment_upgrade, apply_equipment_stats).chain(),
+        );
+
+    let player = app
+        .world_mut()
+        .spawn((
+            Player {
+                speed: 100.0,
+                magnet_strength: 50.0,
+                magnet_speed: 1.0,
+            },
+            Health {
+                current: 100,
+                maximum: 100,
+            },
+            Equipment::default(),
+            CooldownReduction::default(),
+            DamageMultiplier::default(),
+            AreaMultiplier::default(),
+        ))
+        .id();
+    (app, player)
+}
+
+fn equip(app: &mut App, equipment_type: EquipmentType) {
+    app.world_mut().send_event(GenericUpgradeConfirmedEvent {
+        generic_upgrade_type: GenericUpgrade::Equipment(equipment_type),
+    });
+    app.update();
+}
+
+#[test]
+fn levelling_items_recomputes_player_stats() {
+    let (mut app, player) = equipment_app();
+
+    equip(&mut app, EquipmentType::Gloves);
+    equip(&mut app, EquipmentType::Gloves);
+    equip(&mut app, EquipmentType::Armor);
+    equip(&mut app, EquipmentType::Boots);
+
+    let world = app.world();
+    assert_eq!(
+        world
+            .get::<Equipment>(player)
+            .unwrap()
+            .level(EquipmentType::Gloves),
+        2
+    );
+    assert!((world.get::<AreaMultiplier>(player).unwrap().factor - 1.2).abs() < 0.001);
+    assert!((world.get::<Player>(player).unwrap().speed - 110.0).abs() < 0.001);
+    assert_eq!(world.get::<Health>(player).unwrap().maximum, 115);
+
+    // Bonuses from elsewhere survive the next recompute
+    app.world_mut()
+        .get_mut::<DamageMultiplier>(player)
+        .unwrap()
+        .factor += 0.5;
+    equip(&mut app, EquipmentType::Amulet);
+    let damage = app.world().get::<DamageMultiplier>(player).unwrap().factor;
+    assert!((damage - 1.6).abs() < 0.001);
+}
+
+#[test]
+fn maxed_items_stop_being_offered() {
+    let pool = UpgradePool::new();
+    let mut equipment = Equipment::default();
+    let offers_ring = |equipment: &Equipment| {
+        pool.generate_equipment_choices(equipment)
+            .iter()
+            .any(|choice| {
+                choice.upgrade_type
+                    == UpgradeType::Generic(GenericUpgrade::Equipment(EquipmentType::Ring))
+            })
+    };
+
+    assert!(offers_ring(&equipment));
+    for _ in 0..equipment_definition(EquipmentType::Ring).max_level {
+        equipment.add(EquipmentType::Ring);
+    }
+    assert!(!offers_ring(&equipment));
+}
@@ -0,0 +1,27 @@
+use survivors_prototype::difficulty::{DifficultyScaling, ExperienceScale};
+use survivors_prototype::resources::{Mutator, RunModifiers};
+
+#[test]
+fn drops_scale_and_round_but_never_vanish() {
+    assert_eq!(ExperienceScale::default().apply(66), 66);
+    assert_eq!(ExperienceScale(1.5).apply(66), 99);
+    assert_eq!(ExperienceScale(0.1).apply(2), 1);
+}
+
+#[test]
+fn curses_stack_onto_the_difficulty_curve() {
+    let run_modifiers = RunModifiers {
+        mutators: vec![
+            Mutator::ChoiceTimer(10.0),
+            Mutator::Curse(0.5),
+            Mutator::Curse(0.25),
+        ],
+    };
+    assert_eq!(RunModifiers::default().curse_multiplier(), 1.0);
+    assert_eq!(run_modifiers.curse_multiplier(), 1.75);
+
+    // Experience grows over the run even without a curse
+    let scaling = DifficultyScaling::default();
+    assert_eq!(scaling.experience.multiplier(0.0), 1.0);
+    assert!(scaling.experience.multiplier(600.0) > 1.0);
+}
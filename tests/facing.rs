@@ -0,0 +1,21 @@
+use bevy::prelude::*;
+use survivors_prototype::components::Facing;
+
+#[test]
+fn vertical_movement_keeps_the_last_side() {
+    let mut facing = Facing::default();
+    assert!(!facing.is_left());
+
+    facing.face(Vec2::NEG_X);
+    assert!(facing.is_left());
+
+    // Straight up and down still aim that way, the sprite doesn't flip back
+    facing.face(Vec2::Y);
+    assert!(facing.is_left());
+    assert_eq!(facing.direction, Vec2::Y);
+    facing.face(Vec2::NEG_Y);
+    assert!(facing.is_left());
+
+    facing.face(Vec2::new(1.0, 1.0).normalize());
+    assert!(!facing.is_left());
+}
@@ -0,0 +1,48 @@
+use survivors_prototype::settings::{DisplayMode, GameSettings, SettingKind};
+
+#[test]
+fn volume_steps_up_and_wraps_to_silent() {
+    let mut settings = GameSettings {
+        master_volume: 0.6,
+        ..Default::default()
+    };
+
+    settings.cycle(SettingKind::MasterVolume);
+    assert!((settings.master_volume - 0.8).abs() < 1e-4);
+    settings.cycle(SettingKind::MasterVolume);
+    settings.cycle(SettingKind::MasterVolume);
+    assert_eq!(settings.master_volume, 0.0);
+    assert_eq!(settings.label(SettingKind::MasterVolume), "Master: 0%");
+}
+
+#[test]
+fn toggles_and_display_mode_cycle() {
+    let mut settings = GameSettings::default();
+
+    settings.cycle(SettingKind::ScreenShake);
+    assert!(!settings.screen_shake);
+    assert_eq!(
+        settings.label(SettingKind::ScreenShake),
+        "Screen Shake: Off"
+    );
+
+    settings.cycle(SettingKind::DisplayMode);
+    assert_eq!(settings.display_mode, DisplayMode::Borderless);
+}
+
+#[test]
+fn settings_round_trip_and_fill_in_missing_fields() {
+    let settings = GameSettings {
+        sfx_volume: 0.4,
+        vsync: false,
+        display_mode: DisplayMode::Fullscreen,
+        ..Default::default()
+    };
+    let saved = ron::to_string(&settings).unwrap();
+    assert_eq!(ron::from_str::<GameSettings>(&saved).unwrap(), settings);
+
+    // Files from before a setting existed still load
+    let older: GameSettings = ron::from_str("(master_volume: 0.5)").unwrap();
+    assert_eq!(older.master_volume, 0.5);
+    assert!(older.damage_numbers);
+}
@@ -0,0 +1,44 @@
+use bevy::prelude::*;
+use survivors_prototype::ui::HudPreview;
+
+#[test]
+fn preview_cycles_through_every_shape_and_back_off() {
+    let mut preview = HudPreview::default();
+    let mut seen = Vec::new();
+    loop {
+        preview.aspect = preview.next();
+        match preview.aspect {
+            Some(aspect) => seen.push(aspect),
+            None => break,
+        }
+    }
+
+    assert_eq!(seen.len(), 4);
+    assert!(seen.windows(2).all(|pair| pair[0] < pair[1]));
+}
+
+#[test]
+fn previewed_screen_is_letterboxed_in_the_window() {
+    let window = Vec2::new(1920.0, 1080.0);
+
+    let off = HudPreview::default().screen_rect(window);
+    assert_eq!(off.size(), window);
+
+    // Narrower than the window: full height, bars at the sides
+    let square = HudPreview {
+        aspect: Some(4.0 / 3.0),
+    }
+    .screen_rect(window);
+    assert_eq!(square.height(), 1080.0);
+    assert_eq!(square.width(), 1440.0);
+    assert_eq!(square.min.x, 240.0);
+
+    // Wider than the window: full width, bars top and bottom
+    let ultrawide = HudPreview {
+        aspect: Some(32.0 / 9.0),
+    }
+    .screen_rect(window);
+    assert_eq!(ultrawide.width(), 1920.0);
+    assert_eq!(ultrawide.height(), 540.0);
+    assert_eq!(ultrawide.min.y, 270.0);
+}
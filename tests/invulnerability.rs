@@ -0,0 +1,46 @@
+use bevy::prelude::*;
+use survivors_prototype::combat::{handle_damage, DamageEvent, Invulnerable};
+use survivors_prototype::components::{Health, Player};
+
+#[test]
+fn big_hits_grant_invulnerability_and_small_ones_dont() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_event::<DamageEvent>()
+        .add_systems(Update, handle_damage);
+
+    let player = app
+        .world_mut()
+        .spawn((
+            Player {
+                speed: 200.0,
+                magnet_strength: 50.0,
+                magnet_speed: 1.0,
+            },
+            Health {
+                current: 100,
+                maximum: 100,
+            },
+        ))
+        .id();
+    let hit = |amount| DamageEvent {
+        target: player,
+        amount,
+        source: None,
+    };
+
+    app.world_mut().send_event(hit(5));
+    app.update();
+    assert!(!app.world().entity(player).contains::<Invulnerable>());
+
+    // The second hit lands in the same frame, after invulnerability was granted
+    app.world_mut().send_event(hit(30));
+    app.world_mut().send_event(hit(30));
+    app.update();
+    assert!(app.world().entity(player).contains::<Invulnerable>());
+    assert_eq!(app.world().get::<Health>(player).unwrap().current, 65);
+
+    app.world_mut().send_event(hit(5));
+    app.update();
+    assert_eq!(app.world().get::<Health>(player).unwrap().current, 65);
+}
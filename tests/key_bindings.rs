@@ -0,0 +1,71 @@
+use bevy::prelude::KeyCode;
+use strum::IntoEnumIterator;
+use survivors_prototype::controls::{controls_label, key_name, Action, KeyBindings, Rebinding};
+
+#[test]
+fn every_action_has_a_key_by_default() {
+    let bindings = KeyBindings::default();
+    for action in Action::iter() {
+        assert!(!bindings.keys(action).is_empty(), "{} has no keys", action);
+    }
+    assert_eq!(bindings.keys(Action::MoveUp), vec![KeyCode::KeyW]);
+    assert_eq!(bindings.primary(Action::Confirm), KeyCode::Enter);
+}
+
+#[test]
+fn rebinding_replaces_only_that_action() {
+    let mut bindings = KeyBindings::default();
+    bindings.bind(Action::MoveUp, KeyCode::ArrowUp);
+
+    assert_eq!(bindings.keys(Action::MoveUp), vec![KeyCode::ArrowUp]);
+    assert_eq!(bindings.keys(Action::MoveDown), vec![KeyCode::KeyS]);
+    assert_eq!(
+        controls_label(&bindings, &Rebinding::default(), Action::MoveUp),
+        "Move Up: Up"
+    );
+    assert_eq!(
+        controls_label(&bindings, &Rebinding(Some(Action::MoveUp)), Action::MoveUp),
+        "Move Up: ..."
+    );
+}
+
+#[test]
+fn binding_a_taken_key_swaps_the_two_actions() {
+    let mut bindings = KeyBindings::default();
+    bindings.bind(Action::MoveUp, KeyCode::KeyE);
+
+    assert_eq!(bindings.keys(Action::MoveUp), vec![KeyCode::KeyE]);
+    assert_eq!(bindings.keys(Action::Interact), vec![KeyCode::KeyW]);
+    // Menu keys are read on their own, so W still moves through menus too
+    assert_eq!(
+        bindings.keys(Action::MenuUp),
+        vec![KeyCode::ArrowUp, KeyCode::KeyW]
+    );
+
+    // Pause also backs out of menus, so it trades keys with menu actions too
+    bindings.bind(Action::Pause, KeyCode::Enter);
+    assert_eq!(bindings.keys(Action::Pause), vec![KeyCode::Enter]);
+    assert_eq!(bindings.keys(Action::Confirm), vec![KeyCode::Escape]);
+}
+
+#[test]
+fn bindings_survive_a_save_round_trip() {
+    let mut bindings = KeyBindings::default();
+    bindings.bind(Action::Interact, KeyCode::KeyF);
+
+    let saved = ron::to_string(&bindings).unwrap();
+    let loaded: KeyBindings = ron::from_str(&saved).unwrap();
+    assert_eq!(loaded, bindings);
+
+    // A file from before any rebinding leaves every default in place
+    let empty: KeyBindings = ron::from_str("()").unwrap();
+    assert_eq!(empty, KeyBindings::default());
+}
+
+#[test]
+fn key_names_drop_the_prefix() {
+    assert_eq!(key_name(KeyCode::KeyW), "W");
+    assert_eq!(key_name(KeyCode::Digit3), "3");
+    assert_eq!(key_name(KeyCode::ArrowLeft), "Left");
+    assert_eq!(key_name(KeyCode::Escape), "Escape");
+}
@@ -0,0 +1,67 @@
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+use survivors_prototype::components::{Health, Player};
+use survivors_prototype::death::{death_system, KilledBy};
+use survivors_prototype::difficulty::ExperienceScale;
+use survivors_prototype::events::EntityDeathEvent;
+use survivors_prototype::kill_cam::{KillCam, KillCamPlugin};
+use survivors_prototype::resources::{GameState, GameStats};
+
+fn state(app: &App) -> GameState {
+    app.world().resource::<State<GameState>>().get().clone()
+}
+
+#[test]
+fn player_death_plays_the_kill_cam_before_game_over() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin))
+        .insert_state(GameState::Playing)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )))
+        .init_resource::<GameStats>()
+        .init_resource::<ExperienceScale>()
+        .add_event::<EntityDeathEvent>()
+        .add_plugins(KillCamPlugin)
+        .add_systems(Update, death_system.run_if(in_state(GameState::Playing)));
+
+    let killer = app.world_mut().spawn(Transform::default()).id();
+    let player = app
+        .world_mut()
+        .spawn((
+            Player {
+                speed: 200.0,
+                magnet_strength: 50.0,
+                magnet_speed: 1.0,
+            },
+            Health {
+                current: 0,
+                maximum: 100,
+            },
+            Transform::default(),
+            KilledBy(Some(killer)),
+        ))
+        .id();
+
+    app.update();
+    app.world_mut().despawn(player);
+    app.update();
+    let kill_cam = app.world().resource::<KillCam>();
+    assert!(kill_cam.is_playing());
+    assert_eq!(kill_cam.killer(), Some(killer));
+    assert_eq!(state(&app), GameState::Playing);
+    assert!(app.world().resource::<Time<Virtual>>().relative_speed() < 1.0);
+
+    // Well past the kill cam in real time
+    for _ in 0..20 {
+        app.update();
+    }
+    assert_eq!(state(&app), GameState::GameOver);
+    assert!(!app.world().resource::<KillCam>().is_playing());
+    assert_eq!(
+        app.world().resource::<Time<Virtual>>().relative_speed(),
+        1.0
+    );
+}
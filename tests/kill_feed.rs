@@ -0,0 +1,38 @@
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use survivors_prototype::events::EntityDeathEvent;
+use survivors_prototype::interaction::InteractEvent;
+use survivors_prototype::kill_feed::{KillFeed, KillFeedEntry, KillFeedPlugin, Notable};
+use survivors_prototype::menu::WeaponUpgradeConfirmedEvent;
+use survivors_prototype::resources::GameState;
+
+#[test]
+fn a_burst_of_notable_deaths_stays_within_the_cap() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin, KillFeedPlugin))
+        .insert_state(GameState::Playing)
+        .add_event::<EntityDeathEvent>()
+        .add_event::<WeaponUpgradeConfirmedEvent>()
+        .add_event::<InteractEvent>();
+    app.update();
+
+    for i in 0..8 {
+        let elite = app.world_mut().spawn(Notable(format!("Elite {i}"))).id();
+        app.world_mut().send_event(EntityDeathEvent {
+            entity: elite,
+            position: Vec2::ZERO,
+            exp_value: None,
+            elite: true,
+        });
+    }
+    app.update();
+
+    let world = app.world_mut();
+    let rows = world
+        .query_filtered::<&Children, With<KillFeed>>()
+        .single(world)
+        .len();
+    let entries = world.query::<&KillFeedEntry>().iter(world).count();
+    assert_eq!(rows, 5);
+    assert_eq!(entries, 5);
+}
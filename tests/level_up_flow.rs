@@ -0,0 +1,215 @@
+mod common;
+
+use bevy::prelude::*;
+use common::{
+    confirm_generic_upgrade, confirm_upgrade, level_up_test_app, spawn_player_with_weapons,
+    ExpectedWeapon,
+};
+use survivors_prototype::components::{Armor, Health, Player};
+use survivors_prototype::experience::Experience;
+use survivors_prototype::menu::{FocusOrder, MenuAction, MenuActionComponent, MenuItem, MenuRoot};
+use survivors_prototype::resources::GameState;
+use survivors_prototype::upgrade::{GenericUpgrade, UpgradeType};
+use survivors_prototype::weapons::weapon_upgrade::{WeaponUpgradeConfig, WeaponUpgradeSpec};
+use survivors_prototype::weapons::WeaponType;
+
+fn state(app: &App) -> GameState {
+    app.world().resource::<State<GameState>>().get().clone()
+}
+
+fn menu_count(app: &mut App) -> usize {
+    let world = app.world_mut();
+    world.query::<&MenuRoot>().iter(world).count() + world.query::<&MenuItem>().iter(world).count()
+}
+
+#[test]
+fn level_up_without_a_player_goes_back_to_playing() {
+    let mut app = level_up_test_app();
+    app.update();
+
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::LevelUp);
+    app.update();
+    app.update();
+
+    assert_eq!(state(&app), GameState::Playing);
+    assert_eq!(menu_count(&mut app), 0);
+}
+
+#[test]
+fn level_up_cycle_applies_choice_and_returns_to_playing() {
+    let mut app = level_up_test_app();
+    let player = spawn_player_with_weapons(&mut app, &[WeaponType::MagickCircle]);
+    app.update();
+    assert_eq!(state(&app), GameState::Playing);
+
+    // Exactly enough XP for level 2
+    app.world_mut()
+        .get_mut::<Experience>(player)
+        .expect("player has experience")
+        .current = 100;
+
+    // One frame to notice the level up, one for the transition to spawn the menu
+    app.update();
+    app.update();
+    assert_eq!(state(&app), GameState::LevelUp);
+
+    let experience = app.world().get::<Experience>(player).unwrap();
+    assert_eq!(experience.level, 2);
+    assert_eq!(experience.current, 0);
+
+    // Pick the Magick Circle upgrade as if it were clicked
+    let world = app.world_mut();
+    let mut items = world.query::<(Entity, &MenuActionComponent, &mut Interaction)>();
+    let (chosen, spec) = items
+        .iter(world)
+        .find_map(|(entity, action, _)| match &action.action {
+            MenuAction::SelectUpgrade(choice) => match &choice.upgrade_type {
+                UpgradeType::Weapon(WeaponType::MagickCircle, spec) => Some((entity, spec.clone())),
+                _ => None,
+            },
+            _ => None,
+        })
+        .expect("level up menu offers the owned weapon's next level");
+    *items.get_mut(world, chosen).unwrap().2 = Interaction::Pressed;
+
+    // Confirm, then transition back
+    app.update();
+    app.update();
+    assert_eq!(state(&app), GameState::Playing);
+    assert_eq!(menu_count(&mut app), 0, "level up menu should be despawned");
+
+    let mut expected = ExpectedWeapon::new();
+    expected.apply(&spec);
+    expected.assert_matches(&mut app, WeaponType::MagickCircle, "after level up");
+
+    // Nothing left over should trigger a second level up
+    app.update();
+    assert_eq!(state(&app), GameState::Playing);
+}
+
+#[test]
+fn keyboard_focus_moves_through_choices_in_order() {
+    let mut app = level_up_test_app();
+    let player = spawn_player_with_weapons(&mut app, &[WeaponType::MagickCircle]);
+    app.update();
+    app.world_mut()
+        .get_mut::<Experience>(player)
+        .expect("player has experience")
+        .current = 100;
+    app.update();
+    app.update();
+    assert_eq!(state(&app), GameState::LevelUp);
+
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(KeyCode::ArrowDown);
+    app.update();
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .reset_all();
+    app.update();
+
+    let world = app.world_mut();
+    let mut items: Vec<_> = world
+        .query::<(&FocusOrder, &MenuItem, &Outline)>()
+        .iter(world)
+        .map(|(order, item, outline)| (*order, item.selected, outline.color))
+        .collect();
+    items.sort_by_key(|(order, _, _)| *order);
+
+    let focused: Vec<_> = items.iter().map(|(_, selected, _)| *selected).collect();
+    assert_eq!(
+        focused,
+        [false, true, false],
+        "focus should move to the second choice"
+    );
+    assert!(items
+        .iter()
+        .all(|(_, selected, color)| *selected == (*color != Color::NONE)));
+}
+
+fn set_health(app: &mut App, player: Entity, current: i32) {
+    app.world_mut()
+        .get_mut::<Health>(player)
+        .expect("player has health")
+        .current = current;
+}
+
+fn health(app: &App, player: Entity) -> (i32, i32) {
+    let health = app.world().get::<Health>(player).unwrap();
+    (health.current, health.maximum)
+}
+
+fn first_magick_circle_level(app: &App) -> WeaponUpgradeSpec {
+    app.world().resource::<WeaponUpgradeConfig>().data[&WeaponType::MagickCircle].progression[0]
+        .clone()
+}
+
+#[test]
+fn confirming_a_level_up_heals_a_share_of_max_health() {
+    let mut app = level_up_test_app();
+    let player = spawn_player_with_weapons(&mut app, &[WeaponType::MagickCircle]);
+    set_health(&mut app, player, 50);
+
+    let spec = first_magick_circle_level(&app);
+    confirm_upgrade(&mut app, WeaponType::MagickCircle, spec);
+
+    // 10% of 100
+    assert_eq!(health(&app, player), (60, 100));
+}
+
+#[test]
+fn level_up_healing_never_overheals() {
+    let mut app = level_up_test_app();
+    let player = spawn_player_with_weapons(&mut app, &[WeaponType::MagickCircle]);
+    set_health(&mut app, player, 95);
+
+    let spec = first_magick_circle_level(&app);
+    confirm_upgrade(&mut app, WeaponType::MagickCircle, spec);
+    assert_eq!(health(&app, player), (100, 100));
+
+    confirm_generic_upgrade(&mut app, GenericUpgrade::HealthPickup(20));
+    assert_eq!(health(&app, player), (100, 100));
+}
+
+#[test]
+fn max_health_upgrade_heals_the_gained_amount() {
+    let mut app = level_up_test_app();
+    let player = spawn_player_with_weapons(&mut app, &[]);
+    set_health(&mut app, player, 50);
+
+    // +20 max and +20 current, then the level-up heal scales off the new maximum
+    confirm_generic_upgrade(&mut app, GenericUpgrade::MaxHealth(20));
+    assert_eq!(health(&app, player), (82, 120));
+}
+
+#[test]
+fn max_health_upgrade_at_full_health_stays_full() {
+    let mut app = level_up_test_app();
+    let player = spawn_player_with_weapons(&mut app, &[]);
+
+    confirm_generic_upgrade(&mut app, GenericUpgrade::MaxHealth(20));
+    assert_eq!(health(&app, player), (120, 120));
+}
+
+#[test]
+fn defense_upgrades_stack_onto_armor() {
+    let mut app = level_up_test_app();
+    let player = spawn_player_with_weapons(&mut app, &[]);
+
+    confirm_generic_upgrade(&mut app, GenericUpgrade::Defense(1));
+    confirm_generic_upgrade(&mut app, GenericUpgrade::Defense(2));
+    assert_eq!(app.world().get::<Armor>(player).unwrap().0, 3);
+}
+
+#[test]
+fn speed_upgrade_scales_movement_speed() {
+    let mut app = level_up_test_app();
+    let player = spawn_player_with_weapons(&mut app, &[]);
+
+    confirm_generic_upgrade(&mut app, GenericUpgrade::Speed(0.1));
+    let speed = app.world().get::<Player>(player).unwrap().speed;
+    assert!((speed - 165.0).abs() < 0.001);
+}
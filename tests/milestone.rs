@@ -0,0 +1,27 @@
+use survivors_prototype::milestone::{MilestoneProgress, MILESTONE_TIMES};
+use survivors_prototype::upgrade::{UpgradePool, UpgradeType};
+
+#[test]
+fn milestones_come_due_in_order() {
+    let mut progress = MilestoneProgress::default();
+    assert_eq!(progress.due(119.0), None);
+    assert_eq!(progress.due(125.0), Some(MILESTONE_TIMES[0]));
+
+    progress.reached = 1;
+    assert_eq!(progress.last_reached(), Some(120.0));
+    // Running late still grants the next mark, one at a time
+    assert_eq!(progress.due(400.0), Some(360.0));
+
+    progress.reached = MILESTONE_TIMES.len();
+    assert_eq!(progress.due(10_000.0), None);
+}
+
+#[test]
+fn milestone_choices_are_stats_only() {
+    let choices = UpgradePool::new().generate_milestone_choices();
+
+    assert!(!choices.is_empty());
+    assert!(choices
+        .iter()
+        .all(|choice| matches!(choice.upgrade_type, UpgradeType::Generic(_))));
+}
@@ -0,0 +1,31 @@
+use survivors_prototype::number_format::{abbreviate, percent, percent_points};
+
+#[test]
+fn small_numbers_are_left_alone() {
+    assert_eq!(abbreviate(0.0), "0");
+    assert_eq!(abbreviate(17.0), "17");
+    assert_eq!(abbreviate(123.4), "123.4");
+    assert_eq!(abbreviate(-42.0), "-42");
+}
+
+#[test]
+fn large_numbers_are_abbreviated() {
+    assert_eq!(abbreviate(1_000.0), "1k");
+    assert_eq!(abbreviate(1_234.0), "1.2k");
+    assert_eq!(abbreviate(3_400_000.0), "3.4M");
+    assert_eq!(abbreviate(5_600_000_000.0), "5.6B");
+    assert_eq!(abbreviate(-2_500.0), "-2.5k");
+}
+
+#[test]
+fn rounding_up_moves_to_the_next_suffix() {
+    assert_eq!(abbreviate(999.96), "1k");
+    assert_eq!(abbreviate(999_960.0), "1M");
+}
+
+#[test]
+fn percentages_keep_one_decimal() {
+    assert_eq!(percent(0.125), "12.5%");
+    assert_eq!(percent(0.1), "10.0%");
+    assert_eq!(percent_points(-15), "-15.0%");
+}
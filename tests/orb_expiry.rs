@@ -0,0 +1,74 @@
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+use survivors_prototype::components::{Facing, Player};
+use survivors_prototype::death::MarkedForDespawn;
+use survivors_prototype::experience::{
+    consolidate_expired_orbs, spawn_experience_orb, ConsolidatedGem, ExperienceOrb, OrbExpiry,
+};
+use survivors_prototype::player_handle::PlayerHandlePlugin;
+
+fn expiry_test_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, PlayerHandlePlugin))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            250,
+        )))
+        .insert_resource(OrbExpiry {
+            lifetime: Some(1.0),
+            gem_distance: 100.0,
+        })
+        .add_systems(Update, consolidate_expired_orbs);
+    app.world_mut().spawn((
+        Player {
+            speed: 200.0,
+            magnet_strength: 50.0,
+            magnet_speed: 1.0,
+        },
+        Transform::default(),
+        Facing::new(Vec2::Y),
+    ));
+    app
+}
+
+fn gems(app: &mut App) -> Vec<(u32, Vec3)> {
+    app.world_mut()
+        .query_filtered::<(&ExperienceOrb, &Transform), With<ConsolidatedGem>>()
+        .iter(app.world())
+        .map(|(orb, transform)| (orb.value, transform.translation))
+        .collect()
+}
+
+#[test]
+fn expired_orbs_end_up_in_one_gem_ahead_of_the_player() {
+    let mut app = expiry_test_app();
+    let mut commands = app.world_mut().commands();
+    let old_orbs: Vec<Entity> = (0..3)
+        .map(|i| spawn_experience_orb(&mut commands, Vec2::new(-500.0, i as f32 * 40.0), 5))
+        .collect();
+    app.world_mut().flush();
+
+    // Not old enough yet
+    app.update();
+    assert!(gems(&mut app).is_empty());
+
+    for _ in 0..6 {
+        app.update();
+    }
+    for orb in old_orbs {
+        assert!(app.world().entity(orb).contains::<MarkedForDespawn>());
+    }
+    let gems = gems(&mut app);
+    assert_eq!(gems.len(), 1);
+    assert_eq!(gems[0].0, 15, "no experience should be lost");
+    assert_eq!(gems[0].1.truncate(), Vec2::new(0.0, 100.0));
+
+    // Later orbs top up the same gem instead of making another
+    let mut commands = app.world_mut().commands();
+    spawn_experience_orb(&mut commands, Vec2::new(300.0, 0.0), 7);
+    app.world_mut().flush();
+    for _ in 0..6 {
+        app.update();
+    }
+    assert_eq!(self::gems(&mut app), vec![(22, Vec3::new(0.0, 100.0, 0.0))]);
+}
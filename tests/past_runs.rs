@@ -0,0 +1,64 @@
+use survivors_prototype::components::Character;
+use survivors_prototype::past_runs::{format_date, PastRuns, RunRecord, RunResult, MAX_PAST_RUNS};
+use survivors_prototype::save::SaveData;
+use survivors_prototype::weapons::WeaponType;
+
+fn record(seed: u64) -> RunRecord {
+    RunRecord {
+        finished_at: 1_700_000_000,
+        seed,
+        character: Character::default(),
+        starting_weapon: WeaponType::ArcaneBolt,
+        time: 312.5,
+        kills: 1_480,
+        result: RunResult::Defeat,
+    }
+}
+
+#[test]
+fn past_runs_keep_the_newest_fifty() {
+    let mut past_runs = PastRuns::default();
+    for seed in 0..60 {
+        past_runs.record(record(seed));
+    }
+
+    assert_eq!(past_runs.runs.len(), MAX_PAST_RUNS);
+    assert_eq!(past_runs.runs[0].seed, 59);
+    assert_eq!(past_runs.runs.last().unwrap().seed, 10);
+
+    // Every run shows up on exactly one page
+    let listed: usize = (0..past_runs.pages())
+        .map(|page| past_runs.page(page).len())
+        .sum();
+    assert_eq!(listed, MAX_PAST_RUNS);
+    assert!(past_runs.page(past_runs.pages()).is_empty());
+}
+
+#[test]
+fn dates_read_as_the_utc_calendar_day() {
+    assert_eq!(format_date(0), "1970-01-01");
+    assert_eq!(format_date(1_700_000_000), "2023-11-14");
+    assert_eq!(format_date(951_782_400), "2000-02-29");
+    assert_eq!(format_date(1_709_251_199), "2024-02-29");
+}
+
+#[test]
+fn records_list_what_the_run_was_and_how_to_replay_it() {
+    let text = record(42).to_string();
+    assert!(text.starts_with("2023-11-14"));
+    assert!(text.contains("05:12"));
+    assert!(text.contains("1.5k kills"));
+    assert!(text.contains("Seed 42"));
+}
+
+#[test]
+fn saves_from_before_past_runs_still_load() {
+    let save: SaveData = ron::from_str("(loadouts: [])").unwrap();
+    assert!(save.past_runs.runs.is_empty());
+
+    let mut save = SaveData::default();
+    save.past_runs.record(record(7));
+    let contents = ron::to_string(&save).unwrap();
+    let loaded: SaveData = ron::from_str(&contents).unwrap();
+    assert_eq!(loaded.past_runs, save.past_runs);
+}
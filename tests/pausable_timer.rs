@@ -0,0 +1,78 @@
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+use survivors_prototype::game_time::PausableTimer;
+
+#[derive(Resource)]
+struct TestTimer(PausableTimer);
+
+fn tick_test_timer(time: Res<Time<Virtual>>, mut timer: ResMut<TestTimer>) {
+    timer.0.tick(&time);
+}
+
+// Every update is a quarter second of real time
+fn timer_app(timer: PausableTimer) -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            250,
+        )))
+        .insert_resource(TestTimer(timer))
+        .add_systems(Update, tick_test_timer);
+    // The first update only starts the clock
+    app.update();
+    app
+}
+
+fn timer(app: &App) -> &PausableTimer {
+    &app.world().resource::<TestTimer>().0
+}
+
+#[test]
+fn timers_stand_still_through_a_pause() {
+    let mut app = timer_app(PausableTimer::from_seconds(1.0, TimerMode::Once));
+
+    app.update();
+    app.update();
+    assert_eq!(timer(&app).elapsed_secs(), 0.5);
+
+    // However long the menu stays open, nothing runs down behind it
+    app.world_mut().resource_mut::<Time<Virtual>>().pause();
+    for _ in 0..20 {
+        app.update();
+    }
+    assert_eq!(timer(&app).elapsed_secs(), 0.5);
+    assert!(!timer(&app).finished());
+
+    app.world_mut().resource_mut::<Time<Virtual>>().unpause();
+    app.update();
+    assert!(!timer(&app).finished());
+    app.update();
+    assert!(timer(&app).just_finished());
+}
+
+#[test]
+fn timers_follow_slowed_down_time() {
+    let mut app = timer_app(PausableTimer::from_seconds(1.0, TimerMode::Repeating));
+    app.world_mut()
+        .resource_mut::<Time<Virtual>>()
+        .set_relative_speed(0.5);
+
+    for _ in 0..7 {
+        app.update();
+        assert!(!timer(&app).just_finished());
+    }
+    app.update();
+    assert!(timer(&app).just_finished());
+}
+
+#[test]
+fn a_head_start_carries_over() {
+    let mut head_start = PausableTimer::from_seconds(2.0, TimerMode::Repeating);
+    head_start.set_elapsed(Duration::from_millis(1750));
+    let mut app = timer_app(head_start);
+
+    app.update();
+    assert!(timer(&app).just_finished());
+    assert_eq!(timer(&app).times_finished_this_tick(), 1);
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use bevy::prelude::*;
+use common::{confirm_upgrade, spawn_player_with_weapons, upgrade_test_app};
+use survivors_prototype::death::MarkedForDeath;
+use survivors_prototype::weapons::projectile::Pierce;
+use survivors_prototype::weapons::weapon_upgrade::{WeaponUpgradeChange, WeaponUpgradeSpec};
+use survivors_prototype::weapons::{
+    can_hit, expire_spent_piercing_attacks, PiercingAttack, WeaponType,
+};
+
+#[test]
+fn piercing_attacks_hit_each_enemy_once_until_spent() {
+    let mut world = World::new();
+    let first = world.spawn_empty().id();
+    let second = world.spawn_empty().id();
+    let third = world.spawn_empty().id();
+
+    let mut piercing = PiercingAttack::new(1);
+    assert!(piercing.try_hit(first));
+    assert_eq!(piercing.pierce_count, 0);
+    // Already went through this one
+    assert!(!piercing.try_hit(first));
+    assert!(!piercing.spent);
+
+    // The last enemy it can reach still takes the hit
+    assert!(piercing.try_hit(second));
+    assert!(piercing.spent);
+    assert!(!piercing.try_hit(third));
+
+    // Attacks without pierce hit whatever they touch
+    assert!(can_hit(None, third));
+}
+
+#[test]
+fn spent_attacks_are_marked_for_death() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_systems(Update, expire_spent_piercing_attacks);
+
+    let target = app.world_mut().spawn_empty().id();
+    let attack = app.world_mut().spawn(PiercingAttack::new(0)).id();
+
+    app.update();
+    assert!(!app.world().entity(attack).contains::<MarkedForDeath>());
+
+    let mut piercing = app.world_mut().get_mut::<PiercingAttack>(attack).unwrap();
+    assert!(can_hit(Some(&mut *piercing), target));
+
+    app.update();
+    assert!(app.world().entity(attack).contains::<MarkedForDeath>());
+}
+
+#[test]
+fn pierce_upgrades_go_on_the_weapon_except_for_lances() {
+    let mut app = upgrade_test_app();
+    spawn_player_with_weapons(&mut app, &[WeaponType::ArcaneBolt, WeaponType::Lance]);
+
+    let pierce = WeaponUpgradeSpec {
+        changes: vec![WeaponUpgradeChange::Pierce(1)],
+    };
+    confirm_upgrade(&mut app, WeaponType::ArcaneBolt, pierce.clone());
+    confirm_upgrade(&mut app, WeaponType::ArcaneBolt, pierce.clone());
+    confirm_upgrade(&mut app, WeaponType::Lance, pierce);
+
+    let pierces: Vec<_> = app
+        .world_mut()
+        .query::<&Pierce>()
+        .iter(app.world())
+        .copied()
+        .collect();
+    assert_eq!(pierces, vec![Pierce(2)]);
+}
@@ -0,0 +1,27 @@
+use bevy::prelude::*;
+use survivors_prototype::components::Player;
+use survivors_prototype::player_handle::{PlayerHandle, PlayerHandlePlugin};
+
+#[test]
+fn handle_follows_the_player_in_and_out() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, PlayerHandlePlugin));
+
+    app.update();
+    assert_eq!(app.world().resource::<PlayerHandle>().get(), None);
+
+    let player = app
+        .world_mut()
+        .spawn(Player {
+            speed: 200.0,
+            magnet_strength: 50.0,
+            magnet_speed: 1.0,
+        })
+        .id();
+    app.update();
+    assert_eq!(app.world().resource::<PlayerHandle>().get(), Some(player));
+
+    app.world_mut().despawn(player);
+    app.update();
+    assert_eq!(app.world().resource::<PlayerHandle>().get(), None);
+}
@@ -0,0 +1,53 @@
+use bevy::prelude::*;
+use survivors_prototype::menu::{GenericUpgradeConfirmedEvent, WeaponUpgradeConfirmedEvent};
+use survivors_prototype::run_history::{record_upgrades, RunHistory, UpgradeRecord};
+use survivors_prototype::upgrade::{GenericUpgrade, UpgradeType};
+use survivors_prototype::weapons::weapon_upgrade::{WeaponUpgradeChange, WeaponUpgradeSpec};
+use survivors_prototype::weapons::WeaponType;
+
+#[test]
+fn confirmed_upgrades_are_recorded_in_order() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .init_resource::<RunHistory>()
+        .add_event::<WeaponUpgradeConfirmedEvent>()
+        .add_event::<GenericUpgradeConfirmedEvent>()
+        .add_systems(Update, record_upgrades);
+
+    let spec = WeaponUpgradeSpec {
+        changes: vec![WeaponUpgradeChange::Damage(10)],
+    };
+    app.world_mut().send_event(WeaponUpgradeConfirmedEvent {
+        weapon_type: WeaponType::MagickCircle,
+        upgrade_spec: spec.clone(),
+    });
+    app.update();
+    app.world_mut().send_event(GenericUpgradeConfirmedEvent {
+        generic_upgrade_type: GenericUpgrade::Defense(1),
+    });
+    app.update();
+
+    let upgrades: Vec<UpgradeType> = app
+        .world()
+        .resource::<RunHistory>()
+        .upgrades
+        .iter()
+        .map(|record| record.upgrade.clone())
+        .collect();
+    assert_eq!(
+        upgrades,
+        vec![
+            UpgradeType::Weapon(WeaponType::MagickCircle, spec),
+            UpgradeType::Generic(GenericUpgrade::Defense(1)),
+        ]
+    );
+}
+
+#[test]
+fn records_read_with_their_run_time() {
+    let record = UpgradeRecord {
+        elapsed: 125.7,
+        upgrade: UpgradeType::Generic(GenericUpgrade::Defense(1)),
+    };
+    assert_eq!(record.to_string(), "02:05  Stoneskin Rune");
+}
@@ -0,0 +1,115 @@
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use survivors_prototype::controls::KeyBindings;
+use survivors_prototype::definitions::StageWall;
+use survivors_prototype::menu::{MenuAction, MenuActionComponent, MenuItem};
+use survivors_prototype::resources::{GameState, GameStats};
+use survivors_prototype::run_history::RunHistory;
+use survivors_prototype::run_reset::{reset_run, RestartRunEvent, RunResetPlugin};
+use survivors_prototype::telemetry::RunTotals;
+use survivors_prototype::upgrade::{GenericUpgrade, UpgradeType};
+
+#[test]
+fn reset_clears_the_run_but_keeps_the_stage() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .insert_resource(GameStats {
+            enemies_killed: 120,
+            bosses_killed: 1,
+            time_elapsed: 300.0,
+        })
+        .insert_resource(RunTotals {
+            damage_dealt: 5000,
+            ..default()
+        })
+        .init_resource::<RunHistory>();
+    app.world_mut()
+        .resource_mut::<RunHistory>()
+        .record(10.0, UpgradeType::Generic(GenericUpgrade::Defense(1)));
+
+    let world = app.world_mut();
+    let camera = world.spawn((Camera2d, Transform::default())).id();
+    let wall = world.spawn((StageWall, Transform::default())).id();
+    let interface = world.spawn(Node::default()).id();
+    let orb = world.spawn(Transform::from_xyz(40.0, 0.0, 0.0)).id();
+    let enemy = world.spawn(Transform::default()).id();
+    let hit_effect = world.spawn(Transform::default()).id();
+    world.entity_mut(enemy).add_child(hit_effect);
+
+    reset_run(world);
+
+    for kept in [camera, wall, interface] {
+        assert!(world.get_entity(kept).is_ok());
+    }
+    for gone in [orb, enemy, hit_effect] {
+        assert!(world.get_entity(gone).is_err());
+    }
+
+    let stats = world.resource::<GameStats>();
+    assert_eq!(stats.enemies_killed, 0);
+    assert_eq!(stats.bosses_killed, 0);
+    assert_eq!(world.resource::<RunTotals>().damage_dealt, 0);
+    assert!(world.resource::<RunHistory>().upgrades.is_empty());
+}
+
+#[test]
+fn restart_event_starts_a_new_run() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin, RunResetPlugin))
+        .init_state::<GameState>()
+        .init_resource::<ButtonInput<KeyCode>>()
+        .init_resource::<KeyBindings>()
+        .insert_resource(GameStats {
+            enemies_killed: 40,
+            bosses_killed: 0,
+            time_elapsed: 90.0,
+        });
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::GameOver);
+    app.update();
+
+    app.world_mut().send_event(RestartRunEvent);
+    app.update();
+    app.update();
+
+    assert_eq!(
+        *app.world().resource::<State<GameState>>().get(),
+        GameState::Playing
+    );
+    assert_eq!(app.world().resource::<GameStats>().enemies_killed, 0);
+}
+
+#[test]
+fn main_menu_button_ends_the_run() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin, RunResetPlugin))
+        .init_state::<GameState>()
+        .init_resource::<ButtonInput<KeyCode>>()
+        .init_resource::<KeyBindings>()
+        .insert_resource(GameStats {
+            enemies_killed: 40,
+            bosses_killed: 0,
+            time_elapsed: 90.0,
+        });
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::Paused);
+    app.update();
+
+    app.world_mut().spawn((
+        MenuItem { selected: false },
+        MenuActionComponent {
+            action: MenuAction::ExitToMainMenu,
+        },
+        Interaction::Pressed,
+    ));
+    app.update();
+    app.update();
+
+    assert_eq!(
+        *app.world().resource::<State<GameState>>().get(),
+        GameState::MainMenu
+    );
+    assert_eq!(app.world().resource::<GameStats>().enemies_killed, 0);
+}
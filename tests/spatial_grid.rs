@@ -0,0 +1,45 @@
+use bevy::prelude::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use survivors_prototype::spatial_grid::EnemySpatialGrid;
+
+#[test]
+fn radius_queries_match_checking_every_enemy() {
+    let mut rng = StdRng::seed_from_u64(3);
+    let mut grid = EnemySpatialGrid::default();
+    let enemies: Vec<(Entity, Vec2)> = (0..500)
+        .map(|i| {
+            let position = Vec2::new(rng.gen_range(-600.0..600.0), rng.gen_range(-600.0..600.0));
+            (Entity::from_raw(i), position)
+        })
+        .collect();
+    for (entity, position) in &enemies {
+        grid.insert(*entity, *position);
+    }
+
+    // Radii below and above the cell size, centers off the cell corners
+    for (center, radius) in [
+        (Vec2::ZERO, 30.0),
+        (Vec2::new(13.0, -201.0), 96.0),
+        (Vec2::new(-450.0, 320.5), 250.0),
+    ] {
+        let mut found: Vec<Entity> = grid
+            .query_radius(center, radius)
+            .map(|(entity, _)| entity)
+            .collect();
+        let mut expected: Vec<Entity> = enemies
+            .iter()
+            .filter(|(_, position)| position.distance(center) <= radius)
+            .map(|(entity, _)| *entity)
+            .collect();
+        found.sort();
+        expected.sort();
+
+        assert!(!expected.is_empty(), "the sample should hit something");
+        assert_eq!(
+            found, expected,
+            "circle at {} with radius {}",
+            center, radius
+        );
+    }
+}
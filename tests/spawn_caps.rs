@@ -0,0 +1,34 @@
+use survivors_prototype::director::{EnemyArchetype, SpawnCaps};
+
+#[test]
+fn caps_fill_up_within_a_frame_and_free_up_on_recount() {
+    let mut caps = SpawnCaps::default();
+    let limit = caps.limits[&EnemyArchetype::Elite];
+
+    for _ in 0..limit {
+        assert!(caps.has_room(EnemyArchetype::Elite));
+        caps.record_spawn(EnemyArchetype::Elite);
+    }
+    assert!(!caps.has_room(EnemyArchetype::Elite));
+    assert!(
+        caps.has_room(EnemyArchetype::Boss),
+        "archetypes are capped separately"
+    );
+
+    // One of them died, the next count sees the gap
+    caps.set_alive(EnemyArchetype::Elite, limit - 1);
+    assert!(caps.has_room(EnemyArchetype::Elite));
+}
+
+#[test]
+fn stages_can_override_or_lift_caps() {
+    let caps: SpawnCaps = ron::from_str("(limits: {Elite: 1})").expect("caps parse");
+    assert_eq!(caps.limits.get(&EnemyArchetype::Elite), Some(&1));
+
+    // Left out of the stage's list, so bosses aren't limited at all
+    let mut caps = caps;
+    for _ in 0..10 {
+        caps.record_spawn(EnemyArchetype::Boss);
+    }
+    assert!(caps.has_room(EnemyArchetype::Boss));
+}
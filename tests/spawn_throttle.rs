@@ -0,0 +1,30 @@
+use survivors_prototype::director::SpawnThrottle;
+
+const SLOW_FRAME: f32 = 0.05;
+const FAST_FRAME: f32 = 1.0 / 60.0;
+
+#[test]
+fn throttle_needs_sustained_slow_frames_and_recovers() {
+    let mut throttle = SpawnThrottle::default();
+
+    // A second of hitches broken up by one good frame isn't enough
+    for _ in 0..20 {
+        assert!(!throttle.record_frame(SLOW_FRAME));
+    }
+    throttle.record_frame(FAST_FRAME);
+    assert!(!throttle.active);
+
+    let slow_frames = (1..200)
+        .find(|_| throttle.record_frame(SLOW_FRAME))
+        .expect("throttle never engaged");
+    assert!(slow_frames as f32 * SLOW_FRAME >= throttle.engage_after - SLOW_FRAME);
+    assert!(throttle.active);
+    assert!(throttle.spawn_rate() < 1.0);
+
+    let fast_frames = (1..1000)
+        .find(|_| throttle.record_frame(FAST_FRAME))
+        .expect("throttle never released");
+    assert!(fast_frames as f32 * FAST_FRAME >= throttle.release_after - FAST_FRAME);
+    assert!(!throttle.active);
+    assert_eq!(throttle.spawn_rate(), 1.0);
+}
@@ -0,0 +1,129 @@
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use survivors_prototype::combat::DamageEvent;
+use survivors_prototype::components::{Enemy, EnemyBehavior, EnemyKind};
+use survivors_prototype::materials::{StatusTint, Tint};
+use survivors_prototype::resources::GameState;
+use survivors_prototype::status::{
+    movement_multiplier, ApplyStatusEvent, Bound, Chilled, Feared, Poisoned, StatusEffect,
+    StatusEffectPlugin,
+};
+use survivors_prototype::status_icons::{status_icon_text, MAX_STATUS_ICONS};
+
+fn status_test_app() -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(StatesPlugin)
+        .add_plugins(StatusEffectPlugin)
+        .insert_state(GameState::Playing)
+        .add_event::<DamageEvent>();
+    app
+}
+
+fn spawn_enemy(app: &mut App) -> Entity {
+    app.world_mut()
+        .spawn(Enemy {
+            kind: EnemyKind::Imp,
+            speed: 100.0,
+            experience_value: 50,
+            contact_damage: 1,
+            behavior: EnemyBehavior::Chase,
+        })
+        .id()
+}
+
+fn apply(app: &mut App, target: Entity, effect: StatusEffect) {
+    app.world_mut().send_event(ApplyStatusEvent {
+        target,
+        effect,
+        source: None,
+    });
+    app.update();
+}
+
+const POISON: StatusEffect = StatusEffect::Poison {
+    damage: 2,
+    duration: 3.0,
+    max_stacks: 2,
+};
+
+#[test]
+fn poison_stacks_up_to_its_cap() {
+    let mut app = status_test_app();
+    let enemy = spawn_enemy(&mut app);
+    app.update();
+
+    apply(&mut app, enemy, POISON);
+    assert_eq!(app.world().get::<Poisoned>(enemy).unwrap().stacks, 1);
+
+    apply(&mut app, enemy, POISON);
+    apply(&mut app, enemy, POISON);
+    assert_eq!(app.world().get::<Poisoned>(enemy).unwrap().stacks, 2);
+}
+
+#[test]
+fn binding_holds_enemies_and_tints_them() {
+    let mut app = status_test_app();
+    let enemy = spawn_enemy(&mut app);
+    app.update();
+
+    apply(
+        &mut app,
+        enemy,
+        StatusEffect::Bind {
+            strength: 1.0,
+            duration: 1.0,
+        },
+    );
+    // Tints are synced after the status lands
+    app.update();
+
+    let bound = app.world().get::<Bound>(enemy).expect("enemy is bound");
+    assert_eq!(movement_multiplier(Some(bound), None), 0.0);
+    assert_eq!(
+        app.world().get::<Tint>(enemy).map(|tint| tint.0),
+        Some(Tint::status(StatusTint::Bound).0)
+    );
+}
+
+#[test]
+fn reapplying_a_chill_keeps_the_stronger_slow() {
+    let mut app = status_test_app();
+    let enemy = spawn_enemy(&mut app);
+    app.update();
+
+    let chill = |slow| StatusEffect::Chill {
+        slow,
+        duration: 2.0,
+    };
+    apply(&mut app, enemy, chill(0.5));
+    apply(&mut app, enemy, chill(0.25));
+
+    let chilled = app.world().get::<Chilled>(enemy).expect("enemy is chilled");
+    assert_eq!(chilled.slow, 0.5);
+    assert_eq!(movement_multiplier(None, Some(chilled)), 0.5);
+}
+
+#[test]
+fn fear_wears_off() {
+    let mut app = status_test_app();
+    let enemy = spawn_enemy(&mut app);
+    app.update();
+
+    apply(&mut app, enemy, StatusEffect::Fear { duration: 0.0 });
+    assert!(app.world().get::<Feared>(enemy).is_some());
+
+    // A zero duration runs out on the next tick
+    app.update();
+    assert!(app.world().get::<Feared>(enemy).is_none());
+}
+
+#[test]
+fn status_icons_are_capped_with_a_count_of_the_rest() {
+    assert_eq!(status_icon_text((false, false, false, false, false)), "");
+    assert_eq!(status_icon_text((true, false, false, false, true)), "🔗🔥");
+
+    let all = status_icon_text((true, true, true, true, true));
+    assert!(all.ends_with(&format!("+{}", 5 - MAX_STATUS_ICONS)));
+    assert!(all.starts_with("🔗"));
+}
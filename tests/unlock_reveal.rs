@@ -0,0 +1,53 @@
+use strum::IntoEnumIterator;
+use survivors_prototype::codex::{Codex, CodexSection};
+use survivors_prototype::components::EnemyKind;
+use survivors_prototype::telemetry::MasteryTier;
+use survivors_prototype::unlock_reveal::{newly_unlocked, Unlock};
+use survivors_prototype::weapons::magick_circle::PatternType;
+use survivors_prototype::weapons::WeaponType;
+
+#[test]
+fn an_unchanged_codex_reveals_nothing() {
+    let mut codex = Codex::default();
+    codex.weapons_maxed.insert(WeaponType::MagickCircle);
+    codex
+        .weapon_mastery
+        .insert(WeaponType::MagickCircle, MasteryTier::Silver);
+
+    assert!(newly_unlocked(&codex, &codex.clone()).is_empty());
+}
+
+#[test]
+fn only_what_the_run_added_is_revealed() {
+    let mut before = Codex::default();
+    before
+        .weapon_mastery
+        .insert(WeaponType::MagickCircle, MasteryTier::Bronze);
+
+    let mut after = before.clone();
+    after.patterns_unlocked.insert(PatternType::Binding);
+    after
+        .weapon_mastery
+        .insert(WeaponType::MagickCircle, MasteryTier::Gold);
+    after.weapons_maxed.insert(WeaponType::MagickCircle);
+
+    assert_eq!(
+        newly_unlocked(&before, &after),
+        vec![
+            Unlock::Pattern(PatternType::Binding),
+            Unlock::Mastery(WeaponType::MagickCircle, MasteryTier::Gold),
+            Unlock::WeaponMaxed(WeaponType::MagickCircle),
+        ]
+    );
+}
+
+#[test]
+fn finishing_a_section_leads_the_reveal() {
+    let before = Codex::default();
+    let mut after = Codex::default();
+    after.enemies_defeated.extend(EnemyKind::iter());
+
+    let unlocks = newly_unlocked(&before, &after);
+    assert_eq!(unlocks[0], Unlock::Section(CodexSection::Bestiary));
+    assert_eq!(unlocks.len(), 1 + EnemyKind::iter().count());
+}
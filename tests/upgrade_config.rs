@@ -0,0 +1,263 @@
+mod common;
+
+use bevy::prelude::App;
+use common::{confirm_upgrade, spawn_player_with_weapons, upgrade_test_app};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use strum::IntoEnumIterator;
+use survivors_prototype::components::{AreaMultiplier, CooldownReduction, DamageMultiplier};
+use survivors_prototype::weapons::magick_circle::PatternType;
+use survivors_prototype::weapons::weapon_upgrade::{
+    WeaponUpgradeChange, WeaponUpgradeConfig, MAX_WEAPON_LEVEL,
+};
+use survivors_prototype::weapons::{
+    effective_cooldown, effective_damage, effective_radius, WeaponArea, WeaponCooldown,
+    WeaponDamage, WeaponMeta, WeaponType,
+};
+
+// Random limit break sequences tried per weapon, and how many picks each one makes
+const SEQUENCES: u64 = 32;
+const PICKS_PER_SEQUENCE: usize = 40;
+
+/// Stats the weapon would fire with for a player without any passive bonuses
+fn assert_stats_valid(app: &mut App, weapon_type: WeaponType, context: &str) {
+    let world = app.world_mut();
+    let mut query = world.query::<(&WeaponMeta, &WeaponCooldown, &WeaponDamage, &WeaponArea)>();
+    let (_, cooldown, damage, area) = query
+        .iter(world)
+        .find(|(meta, ..)| meta.weapon_type == weapon_type)
+        .unwrap_or_else(|| panic!("{}: no {} weapon spawned", context, weapon_type));
+
+    let cooldown = effective_cooldown(cooldown, &CooldownReduction { percent: 0.0 });
+    let damage = effective_damage(damage, &DamageMultiplier { factor: 1.0 });
+    let radius = effective_radius(area, &AreaMultiplier { factor: 1.0 });
+
+    assert!(cooldown > 0.0, "{}: cooldown is {}", context, cooldown);
+    assert!(damage > 0, "{}: damage is {}", context, damage);
+    assert!(radius > 0.0, "{}: radius is {}", context, radius);
+}
+
+#[test]
+fn every_weapon_has_a_full_progression() {
+    let config = WeaponUpgradeConfig::builtin();
+
+    // Evolutions skip straight to max level
+    for weapon_type in WeaponType::iter().filter(|weapon| !weapon.is_evolution()) {
+        let data = config
+            .data
+            .get(&weapon_type)
+            .unwrap_or_else(|| panic!("{} has no upgrade data", weapon_type));
+
+        assert_eq!(
+            data.progression.len() as u32,
+            MAX_WEAPON_LEVEL - 1,
+            "{} needs one progression step for each of levels 2..={}",
+            weapon_type,
+            MAX_WEAPON_LEVEL
+        );
+
+        for (i, spec) in data.progression.iter().enumerate() {
+            assert!(
+                !spec.changes.is_empty(),
+                "{} level {} upgrade changes nothing",
+                weapon_type,
+                i + 2
+            );
+        }
+
+        // The menu offers each step in turn, then limit breaks once the weapon is maxed
+        for level in 1..MAX_WEAPON_LEVEL {
+            assert_eq!(
+                config.get_next_upgrades(weapon_type, level),
+                vec![data.progression[level as usize - 1].clone()],
+                "{} at level {} is offered the wrong upgrade",
+                weapon_type,
+                level
+            );
+        }
+        assert_eq!(
+            config.get_next_upgrades(weapon_type, MAX_WEAPON_LEVEL),
+            data.limit_breaks,
+            "{} at max level should be offered its limit breaks",
+            weapon_type
+        );
+    }
+}
+
+#[test]
+fn every_weapon_has_limit_breaks() {
+    let config = WeaponUpgradeConfig::builtin();
+
+    for weapon_type in WeaponType::iter() {
+        let limit_breaks = &config.data[&weapon_type].limit_breaks;
+        assert!(
+            !limit_breaks.is_empty(),
+            "{} has no limit breaks",
+            weapon_type
+        );
+
+        for (i, spec) in limit_breaks.iter().enumerate() {
+            assert!(
+                !spec.changes.is_empty(),
+                "{} limit break {} changes nothing",
+                weapon_type,
+                i
+            );
+        }
+    }
+}
+
+#[test]
+fn incomplete_configs_fail_validation() {
+    assert_eq!(WeaponUpgradeConfig::builtin().validate(), Ok(()));
+
+    let mut missing = WeaponUpgradeConfig::builtin();
+    missing.data.remove(&WeaponType::Lance);
+    assert!(missing.validate().is_err());
+
+    let mut no_limit_breaks = WeaponUpgradeConfig::builtin();
+    no_limit_breaks
+        .data
+        .get_mut(&WeaponType::ArcaneStorm)
+        .unwrap()
+        .limit_breaks
+        .clear();
+    assert!(no_limit_breaks.validate().is_err());
+}
+
+#[test]
+fn progression_keeps_stats_valid() {
+    for weapon_type in WeaponType::iter() {
+        let mut app = upgrade_test_app();
+        spawn_player_with_weapons(&mut app, &[weapon_type]);
+        assert_stats_valid(&mut app, weapon_type, &format!("{} at spawn", weapon_type));
+
+        let progression = app.world().resource::<WeaponUpgradeConfig>().data[&weapon_type]
+            .progression
+            .clone();
+
+        for (i, spec) in progression.into_iter().enumerate() {
+            confirm_upgrade(&mut app, weapon_type, spec);
+            assert_stats_valid(
+                &mut app,
+                weapon_type,
+                &format!("{} level {}", weapon_type, i + 2),
+            );
+        }
+    }
+}
+
+#[test]
+fn random_limit_breaks_keep_stats_valid() {
+    for weapon_type in WeaponType::iter() {
+        for seed in 0..SEQUENCES {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut app = upgrade_test_app();
+            spawn_player_with_weapons(&mut app, &[weapon_type]);
+
+            let data = app.world().resource::<WeaponUpgradeConfig>().data[&weapon_type].clone();
+            for spec in data.progression {
+                confirm_upgrade(&mut app, weapon_type, spec);
+            }
+
+            for pick in 0..PICKS_PER_SEQUENCE {
+                let spec = data.limit_breaks[rng.gen_range(0..data.limit_breaks.len())].clone();
+                confirm_upgrade(&mut app, weapon_type, spec);
+                assert_stats_valid(
+                    &mut app,
+                    weapon_type,
+                    &format!("{} seed {} pick {}", weapon_type, seed, pick),
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn evolutions_link_a_base_weapon_to_an_evolved_one() {
+    let config = WeaponUpgradeConfig::builtin();
+
+    for weapon_type in WeaponType::iter().filter(|weapon| weapon.is_evolution()) {
+        let evolution = config
+            .evolution_into(weapon_type)
+            .unwrap_or_else(|| panic!("{} can't be evolved into", weapon_type));
+        assert!(
+            !evolution.base.is_evolution(),
+            "{} evolves from another evolution",
+            weapon_type
+        );
+        assert!(
+            config.data[&weapon_type].progression.is_empty(),
+            "{} starts at max level and shouldn't have a progression",
+            weapon_type
+        );
+    }
+
+    for evolution in &config.evolutions {
+        assert!(
+            evolution.evolved.is_evolution(),
+            "{} evolves into {}, which isn't marked as an evolution",
+            evolution.base,
+            evolution.evolved
+        );
+    }
+}
+
+#[test]
+fn weapons_spawn_with_their_defined_base_stats() {
+    let mut app = upgrade_test_app();
+    let weapons: Vec<_> = WeaponType::iter().collect();
+    spawn_player_with_weapons(&mut app, &weapons);
+
+    let world = app.world_mut();
+    let mut query = world.query::<(&WeaponMeta, &WeaponCooldown, &WeaponDamage, &WeaponArea)>();
+    let spawned: Vec<_> = query
+        .iter(world)
+        .map(|(meta, cooldown, damage, area)| {
+            (
+                meta.weapon_type,
+                cooldown.base_duration,
+                damage.base_amount,
+                area.base_radius,
+            )
+        })
+        .collect();
+    let config = world.resource::<WeaponUpgradeConfig>();
+
+    assert_eq!(spawned.len(), weapons.len());
+    for (weapon_type, cooldown, damage, radius) in spawned {
+        let base = config.base_stats(weapon_type);
+        assert_eq!(cooldown, base.cooldown, "{} cooldown", weapon_type);
+        assert_eq!(damage, base.damage, "{} damage", weapon_type);
+        assert_eq!(radius, base.area, "{} area", weapon_type);
+    }
+}
+
+#[test]
+fn locked_patterns_are_offered_as_banishment() {
+    let mut config = WeaponUpgradeConfig::builtin();
+    config.locked_patterns = PatternType::iter()
+        .filter(|pattern| *pattern != PatternType::Banishment)
+        .collect();
+
+    let offered_patterns: Vec<PatternType> = (1..MAX_WEAPON_LEVEL)
+        .flat_map(|level| config.get_next_upgrades(WeaponType::MagickCircle, level))
+        .flat_map(|spec| spec.changes)
+        .filter_map(|change| match change {
+            WeaponUpgradeChange::AddCircle { pattern } => Some(pattern),
+            _ => None,
+        })
+        .collect();
+    let progression_circles = config.data[&WeaponType::MagickCircle]
+        .progression
+        .iter()
+        .flat_map(|spec| &spec.changes)
+        .filter(|change| matches!(change, WeaponUpgradeChange::AddCircle { .. }))
+        .count();
+
+    // Still a circle at every step that had one, just not of a locked pattern
+    assert_eq!(offered_patterns.len(), progression_circles);
+    assert!(offered_patterns
+        .iter()
+        .all(|pattern| *pattern == PatternType::Banishment));
+}
@@ -0,0 +1,283 @@
+mod common;
+
+use bevy::prelude::*;
+use common::{confirm_upgrade, spawn_player_with_weapons, upgrade_test_app, ExpectedWeapon};
+use strum::IntoEnumIterator;
+use survivors_prototype::components::{Equipment, Luck};
+use survivors_prototype::player_handle::PlayerHandlePlugin;
+use survivors_prototype::resources::GameRng;
+use survivors_prototype::types::EquipmentType;
+use survivors_prototype::upgrade::{GenericUpgrade, UpgradePool, UpgradeType};
+use survivors_prototype::weapons::magick_circle::{
+    spawn_magick_circle_attack, MagickCircle, PatternType,
+};
+use survivors_prototype::weapons::weapon_upgrade::{
+    WeaponUpgradeChange, WeaponUpgradeConfig, WeaponUpgradeSpec, MAX_WEAPON_LEVEL,
+};
+use survivors_prototype::weapons::{
+    evolve_weapons, handle_new_weapons, unlock_new_weapons, AddWeaponEvent, Orbits, Sigil,
+    SourceWeapon, WeaponMeta, WeaponType,
+};
+
+#[test]
+fn every_progression_level_applies_to_its_weapon() {
+    for weapon_type in WeaponType::iter().filter(|weapon| !weapon.is_evolution()) {
+        let mut app = upgrade_test_app();
+        spawn_player_with_weapons(&mut app, &[weapon_type]);
+
+        let progression = app.world().resource::<WeaponUpgradeConfig>().data[&weapon_type]
+            .progression
+            .clone();
+
+        let mut expected = ExpectedWeapon::new();
+        expected.assert_matches(&mut app, weapon_type, &format!("{} at spawn", weapon_type));
+
+        for spec in progression {
+            expected.apply(&spec);
+            confirm_upgrade(&mut app, weapon_type, spec);
+            expected.assert_matches(
+                &mut app,
+                weapon_type,
+                &format!("{} level {}", weapon_type, expected.level),
+            );
+        }
+
+        assert_eq!(
+            expected.level, MAX_WEAPON_LEVEL,
+            "{} progression should end at the max level",
+            weapon_type
+        );
+    }
+}
+
+#[test]
+fn limit_breaks_stack_past_max_level() {
+    for weapon_type in WeaponType::iter().filter(|weapon| !weapon.is_evolution()) {
+        let mut app = upgrade_test_app();
+        spawn_player_with_weapons(&mut app, &[weapon_type]);
+
+        let data = app.world().resource::<WeaponUpgradeConfig>().data[&weapon_type].clone();
+
+        let mut expected = ExpectedWeapon::new();
+        for spec in data.progression {
+            expected.apply(&spec);
+            confirm_upgrade(&mut app, weapon_type, spec);
+        }
+
+        // Every limit break twice, so repeat picks have to stack
+        for spec in data.limit_breaks.iter().chain(&data.limit_breaks) {
+            expected.apply(spec);
+            confirm_upgrade(&mut app, weapon_type, spec.clone());
+            expected.assert_matches(
+                &mut app,
+                weapon_type,
+                &format!("{} limit break at level {}", weapon_type, expected.level),
+            );
+        }
+    }
+}
+
+#[test]
+fn orbit_limit_breaks_reshape_sigils_already_out() {
+    let mut app = upgrade_test_app();
+    spawn_player_with_weapons(&mut app, &[WeaponType::MagickCircle]);
+
+    let (weapon, circle) = app
+        .world_mut()
+        .query::<(Entity, &MagickCircle)>()
+        .single(app.world());
+    let sigils = circle.sigils;
+    let mut commands = app.world_mut().commands();
+    let circle = spawn_magick_circle_attack(
+        &mut commands,
+        Vec3::ZERO,
+        10,
+        64.0,
+        3.0,
+        PatternType::Banishment,
+        sigils,
+        None,
+    );
+    commands.entity(circle).insert(SourceWeapon(weapon));
+    app.world_mut().flush();
+
+    confirm_upgrade(
+        &mut app,
+        WeaponType::MagickCircle,
+        WeaponUpgradeSpec {
+            changes: vec![
+                WeaponUpgradeChange::ReverseOrbit,
+                WeaponUpgradeChange::CounterRing { sigils: 3 },
+                WeaponUpgradeChange::EllipticalOrbit { stretch: 1.5 },
+            ],
+        },
+    );
+
+    let orbits: Vec<(bool, bool, Vec2)> = app
+        .world_mut()
+        .query::<(&Sigil, &Orbits)>()
+        .iter(app.world())
+        .map(|(sigil, orbits)| (sigil.counter, orbits.reversed, orbits.axes))
+        .collect();
+    let outer: Vec<_> = orbits.iter().filter(|(counter, ..)| !counter).collect();
+    let inner: Vec<_> = orbits.iter().filter(|(counter, ..)| *counter).collect();
+
+    assert_eq!(outer.len(), sigils.count as usize);
+    assert_eq!(inner.len(), 3, "the circle should gain an inner ring");
+    assert!(outer.iter().all(|(_, reversed, _)| *reversed));
+    assert!(
+        inner.iter().all(|(_, reversed, _)| !reversed),
+        "the inner ring turns against the outer one"
+    );
+    assert!(orbits.iter().all(|(_, _, axes)| axes.x > axes.y));
+}
+
+#[test]
+fn upgrades_only_touch_the_targeted_weapon() {
+    let mut app = upgrade_test_app();
+    let weapons: Vec<_> = WeaponType::iter()
+        .filter(|weapon| !weapon.is_evolution())
+        .collect();
+    spawn_player_with_weapons(&mut app, &weapons);
+
+    let config = app.world().resource::<WeaponUpgradeConfig>();
+    let target = weapons[0];
+    let spec = config.get_next_upgrades(target, 0)[0].clone();
+
+    let mut expected_target = ExpectedWeapon::new();
+    expected_target.apply(&spec);
+    confirm_upgrade(&mut app, target, spec);
+
+    expected_target.assert_matches(&mut app, target, "upgraded weapon");
+    for other in weapons.into_iter().skip(1) {
+        ExpectedWeapon::new().assert_matches(&mut app, other, "untouched weapon");
+    }
+}
+
+fn owned_weapons(app: &mut App) -> Vec<(WeaponType, u32)> {
+    let world = app.world_mut();
+    world
+        .query::<&WeaponMeta>()
+        .iter(world)
+        .map(|meta| (meta.weapon_type, meta.level))
+        .collect()
+}
+
+#[test]
+fn maxed_weapon_with_its_catalyst_evolves() {
+    let mut app = upgrade_test_app();
+    app.add_plugins(PlayerHandlePlugin)
+        .add_event::<AddWeaponEvent>()
+        .add_systems(
+            Update,
+            (unlock_new_weapons, evolve_weapons, handle_new_weapons).chain(),
+        );
+    let player = spawn_player_with_weapons(&mut app, &[WeaponType::ArcaneBolt]);
+
+    let progression = app.world().resource::<WeaponUpgradeConfig>().data[&WeaponType::ArcaneBolt]
+        .progression
+        .clone();
+    for spec in progression {
+        confirm_upgrade(&mut app, WeaponType::ArcaneBolt, spec);
+    }
+
+    let offered = |app: &mut App| {
+        let world = app.world_mut();
+        let weapons: Vec<WeaponMeta> = world
+            .query::<&WeaponMeta>()
+            .iter(world)
+            .map(|meta| WeaponMeta {
+                weapon_type: meta.weapon_type,
+                level: meta.level,
+            })
+            .collect();
+        let weapons: Vec<&WeaponMeta> = weapons.iter().collect();
+        let equipment = world.get::<Equipment>(player).unwrap();
+        UpgradePool::generate_evolutions(
+            world.resource::<WeaponUpgradeConfig>(),
+            &weapons,
+            equipment,
+        )
+    };
+
+    // Maxed, but without the catalyst
+    assert!(offered(&mut app).is_empty());
+
+    let catalyst = app
+        .world()
+        .resource::<WeaponUpgradeConfig>()
+        .evolution_from(WeaponType::ArcaneBolt)
+        .expect("Arcane Bolt has an evolution")
+        .catalyst;
+    assert_eq!(catalyst, EquipmentType::Ring);
+    app.world_mut()
+        .get_mut::<Equipment>(player)
+        .unwrap()
+        .add(catalyst);
+
+    let choices = offered(&mut app);
+    assert_eq!(choices.len(), 1);
+    let UpgradeType::Weapon(evolved, spec) = choices[0].upgrade_type.clone() else {
+        panic!("evolution should be offered as a weapon");
+    };
+    assert_eq!(evolved, WeaponType::ArcaneStorm);
+
+    // Confirm, then one more frame for the spawn and despawn commands to land
+    confirm_upgrade(&mut app, evolved, spec);
+    app.update();
+
+    let weapons = owned_weapons(&mut app);
+    assert_eq!(weapons.len(), 1, "the base weapon should be replaced");
+    let (weapon_type, level) = weapons[0];
+    assert_eq!(weapon_type, WeaponType::ArcaneStorm);
+    assert!(level >= MAX_WEAPON_LEVEL, "evolutions start maxed");
+    assert!(
+        offered(&mut app).is_empty(),
+        "an evolved weapon shouldn't be offered again"
+    );
+}
+
+#[test]
+fn same_seed_offers_the_same_choices() {
+    let pool = UpgradePool::new();
+    let config = WeaponUpgradeConfig::builtin();
+    let bolt = WeaponMeta {
+        weapon_type: WeaponType::ArcaneBolt,
+        level: 2,
+    };
+    let circle = WeaponMeta {
+        weapon_type: WeaponType::MagickCircle,
+        level: 1,
+    };
+
+    let roll = |weapons: &[&WeaponMeta], seed| {
+        let mut rng = GameRng::from_seed(seed);
+        (0..5)
+            .flat_map(|_| {
+                pool.generate_choices(&config, &Luck(10), weapons, &Equipment::default(), &mut rng)
+            })
+            .map(|choice| choice.description)
+            .collect::<Vec<_>>()
+    };
+
+    // Weapons come out of a query, so the order they arrive in shouldn't matter
+    assert_eq!(roll(&[&bolt, &circle], 7), roll(&[&circle, &bolt], 7));
+}
+
+#[test]
+fn stat_choices_only_offer_stats_that_do_something() {
+    let pool = UpgradePool::new();
+    let mut rng = GameRng::from_seed(3);
+
+    for _ in 0..50 {
+        let choice = pool.generate_stat_choice(&mut rng).unwrap();
+        assert!(matches!(
+            choice.upgrade_type,
+            UpgradeType::Generic(
+                GenericUpgrade::MaxHealth(_)
+                    | GenericUpgrade::Speed(_)
+                    | GenericUpgrade::Defense(_)
+            )
+        ));
+    }
+}
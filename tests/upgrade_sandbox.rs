@@ -0,0 +1,69 @@
+mod common;
+
+use common::{spawn_player_with_weapons, upgrade_test_app};
+use survivors_prototype::devtools::{
+    revert_weapon, sandbox_specs, snapshot_weapon, UpgradeSandbox,
+};
+use survivors_prototype::menu::WeaponUpgradeConfirmedEvent;
+use survivors_prototype::weapons::projectile::Bounces;
+use survivors_prototype::weapons::weapon_upgrade::{
+    WeaponUpgradeChange, WeaponUpgradeConfig, WeaponUpgradeSpec,
+};
+use survivors_prototype::weapons::{WeaponDamage, WeaponMeta, WeaponType};
+
+#[test]
+fn sandbox_lists_every_upgrade_in_the_tables() {
+    let config = WeaponUpgradeConfig::builtin();
+    let data = &config.data[&WeaponType::ArcaneBolt];
+
+    let specs = sandbox_specs(&config, WeaponType::ArcaneBolt);
+    assert_eq!(
+        specs.len(),
+        data.progression.len() + data.limit_breaks.len()
+    );
+    assert_eq!(specs[0].0, "Lv 2");
+    assert_eq!(specs[0].1, data.progression[0]);
+}
+
+#[test]
+fn reverting_undoes_every_preview_since_the_first() {
+    let mut app = upgrade_test_app();
+    app.init_resource::<UpgradeSandbox>();
+    spawn_player_with_weapons(&mut app, &[WeaponType::ArcaneBolt]);
+
+    let world = app.world_mut();
+    let weapon = world
+        .query::<(bevy::prelude::Entity, &WeaponMeta)>()
+        .single(world)
+        .0;
+    let before = world.get::<WeaponDamage>(weapon).unwrap().damage_bonus;
+
+    let spec = WeaponUpgradeSpec {
+        changes: vec![
+            WeaponUpgradeChange::Damage(50),
+            WeaponUpgradeChange::Bounces(2),
+        ],
+    };
+    for _ in 0..2 {
+        snapshot_weapon(app.world_mut(), weapon);
+        app.world_mut().send_event(WeaponUpgradeConfirmedEvent {
+            weapon_type: WeaponType::ArcaneBolt,
+            upgrade_spec: spec.clone(),
+        });
+        app.update();
+    }
+
+    let world = app.world_mut();
+    assert_eq!(world.get::<WeaponMeta>(weapon).unwrap().level, 3);
+    assert_eq!(world.get::<Bounces>(weapon).unwrap().0, 4);
+    assert!(world.resource::<UpgradeSandbox>().is_previewing(weapon));
+
+    assert!(revert_weapon(world, weapon));
+    assert_eq!(world.get::<WeaponMeta>(weapon).unwrap().level, 1);
+    assert_eq!(
+        world.get::<WeaponDamage>(weapon).unwrap().damage_bonus,
+        before
+    );
+    assert!(world.get::<Bounces>(weapon).is_none());
+    assert!(!revert_weapon(world, weapon));
+}
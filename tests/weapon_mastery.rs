@@ -0,0 +1,27 @@
+use survivors_prototype::telemetry::{MasteryTier, WeaponTally};
+
+fn tally(kills: u32) -> WeaponTally {
+    WeaponTally {
+        kills,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn kill_milestones_pick_the_highest_tier_reached() {
+    assert_eq!(tally(0).mastery(), None);
+    assert_eq!(tally(49).mastery(), None);
+    assert_eq!(tally(50).mastery(), Some(MasteryTier::Bronze));
+    assert_eq!(tally(249).mastery(), Some(MasteryTier::Bronze));
+    assert_eq!(tally(250).mastery(), Some(MasteryTier::Silver));
+    assert_eq!(tally(5000).mastery(), Some(MasteryTier::Gold));
+}
+
+#[test]
+fn tiers_rank_in_milestone_order() {
+    assert!(MasteryTier::Bronze < MasteryTier::Silver);
+    assert!(MasteryTier::Silver < MasteryTier::Gold);
+    assert!(MasteryTier::ALL
+        .windows(2)
+        .all(|pair| pair[0].kills() < pair[1].kills()));
+}